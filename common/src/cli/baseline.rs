@@ -0,0 +1,74 @@
+use crate::report::{Baseline, KnownFinding};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Baseline")]
+pub struct BaselineArguments {
+    /// A baseline file, only reporting findings which are not part of it, or suppressed by it
+    #[arg(long, value_name = "FILE", value_parser = load_baseline)]
+    pub baseline: Option<Baseline>,
+
+    /// Write the findings of this run as a baseline to this file (keeping the suppressions of the current baseline)
+    #[arg(long, value_name = "FILE")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Also report findings of the baseline which are no longer present
+    #[arg(long, requires = "baseline")]
+    pub report_fixed: bool,
+}
+
+fn load_baseline(path: &str) -> Result<Baseline, String> {
+    Baseline::load(path).map_err(|err| format!("{err:#}"))
+}
+
+impl BaselineArguments {
+    /// Write the new baseline, if requested, and apply the current one, if present.
+    ///
+    /// Returns the findings of the baseline which are no longer present, if they should be
+    /// reported.
+    pub fn process<K: Clone + Ord, T>(
+        &self,
+        findings: &mut BTreeMap<K, Vec<T>>,
+        documents: &BTreeSet<K>,
+        url: impl Fn(&K) -> String,
+        known: impl Fn(&T) -> KnownFinding,
+    ) -> anyhow::Result<BTreeMap<K, Vec<KnownFinding>>> {
+        let today = chrono::Utc::now().date_naive();
+
+        if let Some(path) = &self.write_baseline {
+            self.baseline
+                .clone()
+                .unwrap_or_default()
+                .update(findings, &url, &known, today)
+                .store(path)?;
+        }
+
+        let Some(baseline) = &self.baseline else {
+            return Ok(Default::default());
+        };
+
+        let outcome = baseline.apply(findings, documents, &url, known, today);
+
+        for suppression in &outcome.expired {
+            log::warn!(
+                "Suppression expired ({}): {}",
+                suppression.expires.unwrap_or_default(),
+                suppression.justification
+            );
+        }
+        log::info!(
+            "Baseline: {} known, {} suppressed, {} fixed",
+            outcome.known,
+            outcome.suppressed,
+            outcome.fixed.values().map(Vec::len).sum::<usize>()
+        );
+
+        Ok(match self.report_fixed {
+            true => outcome.fixed,
+            false => Default::default(),
+        })
+    }
+}
@@ -1,7 +1,11 @@
 //! Command line helpers
+pub mod baseline;
 pub mod client;
+pub mod report;
 pub mod runner;
 
+#[cfg(feature = "openpgp")]
+pub mod trust;
 #[cfg(feature = "openpgp")]
 pub mod validation;
 
@@ -0,0 +1,105 @@
+//! Report command helpers
+
+use crate::report::{self, ReportDiff, RunReport};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+/// The format of a report
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// HTML, for humans
+    Html,
+    /// JSON, for machines (see the `JSON_REPORT_SCHEMA` of the `csaf-walker` or `sbom-walker` crate)
+    Json,
+    /// SARIF 2.1.0, for code scanning tools
+    Sarif,
+    /// JUnit XML, for CI systems
+    Junit,
+}
+
+impl ReportFormat {
+    /// The file extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Json => "json",
+            Self::Sarif => "sarif",
+            Self::Junit => "xml",
+        }
+    }
+}
+
+/// Compare the JSON reports of two runs, listing regressed, fixed, and changed documents.
+#[derive(clap::Args, Debug)]
+pub struct Diff {
+    /// The JSON report of the old run
+    old: PathBuf,
+
+    /// The JSON report of the new run
+    new: PathBuf,
+
+    /// Path of the output file [default: report-diff.<extension of the format>]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// The format of the result
+    #[arg(long, value_enum, default_value_t = DiffFormat::Html)]
+    format: DiffFormat,
+
+    /// Create a self-contained HTML report, not loading any external resources
+    #[arg(long)]
+    self_contained: bool,
+}
+
+/// The format of the result
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// HTML, for humans
+    Html,
+    /// JSON, for machines
+    Json,
+}
+
+fn load<R: RunReport>(path: &Path) -> anyhow::Result<R> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open report: {}", path.display()))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| format!("Failed to parse report: {}", path.display()))
+}
+
+impl Diff {
+    /// Compare two reports of the type `R`, using the title for the HTML result.
+    pub async fn run<R: RunReport>(self, title: &str) -> anyhow::Result<()> {
+        let old = load::<R>(&self.old)?.run_findings();
+        let new = load::<R>(&self.new)?.run_findings();
+
+        let diff = ReportDiff::new(&old, &new);
+
+        log::info!(
+            "{} regressed, {} fixed, {} changed, {} removed documents",
+            diff.regressed.len(),
+            diff.fixed.len(),
+            diff.changed.len(),
+            diff.removed.len()
+        );
+
+        let output = self.output.unwrap_or_else(|| match self.format {
+            DiffFormat::Html => "report-diff.html".into(),
+            DiffFormat::Json => "report-diff.json".into(),
+        });
+        let mut out = std::fs::File::create(&output)
+            .with_context(|| format!("Failed to create output: {}", output.display()))?;
+
+        match self.format {
+            DiffFormat::Html => report::render(
+                &mut out,
+                title,
+                &diff,
+                &report::report_options(self.self_contained),
+            )?,
+            DiffFormat::Json => serde_json::to_writer_pretty(&mut out, &diff)?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    cli::CommandDefaults,
+    validate::trust::{KeyTrust, TrustMode, TrustStore},
+};
+use bytes::Bytes;
+use sequoia_openpgp::{Fingerprint, policy::StandardPolicy};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, clap::Parser)]
+#[command(next_help_heading = "Trust store")]
+pub struct TrustArguments {
+    /// Path to a local trust store, containing pinned keys per provider.
+    #[arg(long, env = "WALKER_TRUST_STORE")]
+    pub trust_store: Option<PathBuf>,
+
+    /// How to combine pinned keys with keys announced by the provider metadata.
+    ///
+    /// Pinned keys are only used with the modes 'augment' and 'pinned'.
+    #[arg(long, value_enum, default_value_t, requires = "trust_store")]
+    pub trust_mode: TrustMode,
+
+    /// Name of the provider in the trust store and the policy configuration. Defaults to the domain of the source.
+    #[arg(long)]
+    pub trust_provider: Option<String>,
+}
+
+impl From<TrustArguments> for Option<KeyTrust> {
+    fn from(value: TrustArguments) -> Self {
+        let store = value.trust_store?;
+
+        if value.trust_mode == TrustMode::Metadata {
+            log::warn!(
+                "The trust store ({}) is not used with the trust mode 'metadata', use '--trust-mode augment' or '--trust-mode pinned' to use pinned keys",
+                store.display()
+            );
+            return None;
+        }
+
+        Some(
+            KeyTrust::new(TrustStore::new(store))
+                .mode(value.trust_mode)
+                .provider(value.trust_provider),
+        )
+    }
+}
+
+/// Manage pinned provider keys in the local trust store.
+#[derive(Debug, clap::Args)]
+pub struct Keys {
+    /// Path to the local trust store.
+    #[arg(long, env = "WALKER_TRUST_STORE")]
+    trust_store: PathBuf,
+
+    #[command(subcommand)]
+    command: KeysCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum KeysCommand {
+    /// List pinned keys.
+    List {
+        /// Only list keys of this provider.
+        provider: Option<String>,
+    },
+    /// Import (pin) keys of a provider from a file.
+    Import {
+        /// The provider name, e.g. its domain.
+        provider: String,
+        /// The file containing the key(s).
+        file: PathBuf,
+        /// Require the key to have this fingerprint.
+        #[arg(long)]
+        fingerprint: Option<String>,
+    },
+    /// Remove a pinned key of a provider.
+    Remove {
+        /// The provider name, e.g. its domain.
+        provider: String,
+        /// The fingerprint of the key to remove.
+        fingerprint: Fingerprint,
+    },
+}
+
+impl CommandDefaults for Keys {
+    fn progress(&self) -> bool {
+        false
+    }
+}
+
+impl Keys {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let store = TrustStore::new(self.trust_store);
+
+        match self.command {
+            KeysCommand::List { provider } => {
+                for (name, certs) in store.list().await? {
+                    if provider.as_ref().is_some_and(|provider| provider != &name) {
+                        continue;
+                    }
+
+                    println!("{name}:");
+                    for cert in certs {
+                        println!("  {}", cert.fingerprint().to_hex());
+                        for user_id in cert.userids() {
+                            println!("    {}", user_id.userid());
+                        }
+                        if let Some(expiration) = cert
+                            .with_policy(&StandardPolicy::new(), None)
+                            .ok()
+                            .and_then(|cert| cert.primary_key().key_expiration_time())
+                        {
+                            println!(
+                                "    Expires: {}",
+                                humantime::format_rfc3339_seconds(expiration)
+                            );
+                        }
+                    }
+                }
+            }
+            KeysCommand::Import {
+                provider,
+                file,
+                fingerprint,
+            } => {
+                let data = Bytes::from(tokio::fs::read(&file).await?);
+                for fingerprint in store
+                    .import(&provider, data, fingerprint.as_deref())
+                    .await?
+                {
+                    println!("Pinned key for '{provider}': {}", fingerprint.to_hex());
+                }
+            }
+            KeysCommand::Remove {
+                provider,
+                fingerprint,
+            } => {
+                if store.remove(&provider, &fingerprint).await? {
+                    println!("Removed key for '{provider}': {}", fingerprint.to_hex());
+                } else {
+                    anyhow::bail!(
+                        "No pinned key for '{provider}' with fingerprint: {}",
+                        fingerprint.to_hex()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,17 @@
-use crate::validate::ValidationOptions;
+use crate::{
+    cli::trust::TrustArguments,
+    utils::url::provider_name,
+    validate::{
+        ValidationOptions,
+        policy::{
+            AlgorithmPolicy, PolicyConfig, PolicyConfigFile, parse_asymmetric_policy,
+            parse_hash_policy,
+        },
+        trust::KeyTrust,
+    },
+};
 use flexible_time::timestamp::StartTimestamp;
+use sequoia_openpgp::{Fingerprint, policy::AsymmetricAlgorithm, types::HashAlgorithm};
 use std::time::SystemTime;
 use time::{Date, Month, UtcOffset};
 
@@ -13,6 +25,77 @@ pub struct ValidationArguments {
     /// Enable OpenPGP v3 signatures. Conflicts with 'policy_date'.
     #[arg(short = '3', long = "v3-signatures", conflicts_with = "policy_date")]
     v3_signatures: bool,
+
+    /// Require documents to have at least one digest (SHA-256 or SHA-512).
+    #[arg(long)]
+    require_digest: bool,
+
+    /// Require documents to have a SHA-512 digest.
+    #[arg(long)]
+    require_sha512: bool,
+
+    /// Require documents to have a signature.
+    #[arg(long)]
+    require_signature: bool,
+
+    /// Only accept signatures from certificates with this fingerprint. May be repeated.
+    ///
+    /// Documents without a signature are only rejected when combined with '--require-signature'.
+    #[arg(long = "allowed-signer", value_name = "FINGERPRINT")]
+    allowed_signers: Vec<Fingerprint>,
+
+    /// Adjust the OpenPGP policy for a hash algorithm. May be repeated.
+    ///
+    /// The policy can be 'accept', 'reject', or a cutoff date after which signatures are rejected (e.g. 'SHA1=accept', 'SHA224=2023-01-01').
+    #[arg(long, value_name = "ALGORITHM=POLICY", value_parser = parse_hash_policy)]
+    hash_policy: Vec<(HashAlgorithm, AlgorithmPolicy)>,
+
+    /// Adjust the OpenPGP policy for a public key algorithm. May be repeated.
+    ///
+    /// The policy can be 'accept', 'reject', or a cutoff date after which keys are rejected (e.g. 'RSA1024=accept', 'DSA2048=reject').
+    #[arg(long, value_name = "ALGORITHM=POLICY", value_parser = parse_asymmetric_policy)]
+    key_policy: Vec<(AsymmetricAlgorithm, AlgorithmPolicy)>,
+
+    /// Reject RSA keys smaller than this size (in bits).
+    #[arg(long, value_name = "BITS")]
+    min_rsa_bits: Option<u32>,
+
+    /// A JSON file, adjusting the OpenPGP policy, with optional overrides per provider.
+    ///
+    /// Other policy arguments are applied on top of the defaults of the file.
+    #[arg(long, value_name = "FILE", value_parser = load_policy_config)]
+    policy_config: Option<PolicyConfigFile>,
+
+    #[command(flatten)]
+    trust: TrustArguments,
+}
+
+impl ValidationArguments {
+    /// The trust store configuration for loading provider keys, if any.
+    ///
+    /// Unless provided explicitly, the name of the provider is derived from the `source` (see
+    /// [`provider_name`]).
+    pub fn key_trust(&self, source: &str) -> Option<KeyTrust> {
+        Option::<KeyTrust>::from(self.trust.clone())
+            .map(|trust| trust.provider(self.provider(source)))
+    }
+
+    /// The validation options, using the policy overrides of the provider of the `source`.
+    pub fn options(self, source: &str) -> ValidationOptions {
+        let provider = self.provider(source);
+        ValidationOptions::from(self).provider(provider)
+    }
+
+    /// The name of the provider, unless provided explicitly, derived from the source.
+    ///
+    /// This selects the provider in the trust store, as well as the per-provider sections of
+    /// configuration files.
+    pub fn provider(&self, source: &str) -> Option<String> {
+        self.trust
+            .trust_provider
+            .clone()
+            .or_else(|| provider_name(source))
+    }
 }
 
 impl From<ValidationArguments> for ValidationOptions {
@@ -30,6 +113,27 @@ impl From<ValidationArguments> for ValidationOptions {
 
         log::debug!("Policy date: {validation_date:?}");
 
-        Self { validation_date }
+        let config = value.policy_config.unwrap_or_default();
+        let mut policy = config.default;
+        policy.merge(&PolicyConfig {
+            hash_algorithms: value.hash_policy,
+            asymmetric_algorithms: value.key_policy,
+            min_rsa_bits: value.min_rsa_bits,
+        });
+
+        Self {
+            validation_date,
+            require_digest: value.require_digest,
+            require_sha512: value.require_sha512,
+            require_signature: value.require_signature,
+            allowed_signers: value.allowed_signers,
+            policy,
+            provider_policies: config.providers,
+            provider: None,
+        }
     }
 }
+
+fn load_policy_config(path: &str) -> Result<PolicyConfigFile, String> {
+    PolicyConfigFile::load(path).map_err(|err| format!("{err:#}"))
+}
@@ -0,0 +1,371 @@
+use anyhow::{Context, bail};
+use chrono::NaiveDate;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+/// The version of the baseline format
+pub const BASELINE_VERSION: u32 = 1;
+
+/// A baseline of known findings, and suppressions of accepted findings
+///
+/// The findings are recorded from a previous run, the suppressions are maintained manually.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// Known findings, by URL of the document
+    #[serde(default)]
+    pub findings: BTreeMap<String, BTreeSet<KnownFinding>>,
+    /// Accepted findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<Suppression>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            findings: Default::default(),
+            suppressions: vec![],
+        }
+    }
+}
+
+/// A finding, as recorded in a baseline
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct KnownFinding {
+    /// The ID of the check
+    pub rule: String,
+    pub message: String,
+    /// The JSON pointer to the offending location of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Findings of the baseline which are no longer found in a document
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FixedFindings {
+    /// The URL of the document
+    pub document: String,
+    pub findings: Vec<KnownFinding>,
+}
+
+/// The suppression of findings, by document and check
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Suppression {
+    /// The URL of the document, a trailing `*` matches any suffix. Matches all documents if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document: Option<String>,
+    /// The ID of the check, a trailing `*` matches any suffix. Matches all checks if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check: Option<String>,
+    /// The reason for accepting the findings
+    pub justification: String,
+    /// The last day the suppression is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+fn matches(pattern: Option<&str>, value: &str) -> bool {
+    match pattern {
+        None => true,
+        Some(pattern) => match pattern.strip_suffix('*') {
+            Some(prefix) => value.starts_with(prefix),
+            None => pattern == value,
+        },
+    }
+}
+
+impl Suppression {
+    pub fn matches(&self, document: &str, check: &str) -> bool {
+        matches(self.document.as_deref(), document) && matches(self.check.as_deref(), check)
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| today > expires)
+    }
+}
+
+/// The outcome of applying a baseline to the findings of a run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BaselineOutcome<K> {
+    /// Number of findings removed, as they are part of the baseline
+    pub known: usize,
+    /// Number of findings removed by a suppression
+    pub suppressed: usize,
+    /// Findings of the baseline, no longer found in processed documents
+    pub fixed: BTreeMap<K, Vec<KnownFinding>>,
+    /// Suppressions which are no longer active
+    pub expired: Vec<Suppression>,
+}
+
+impl<K> Default for BaselineOutcome<K> {
+    fn default() -> Self {
+        Self {
+            known: 0,
+            suppressed: 0,
+            fixed: Default::default(),
+            expired: vec![],
+        }
+    }
+}
+
+impl Baseline {
+    /// Load a baseline from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open baseline: {}", path.display()))?;
+        let baseline: Self = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse baseline: {}", path.display()))?;
+        baseline
+            .check_version()
+            .with_context(|| format!("Unsupported baseline: {}", path.display()))?;
+        Ok(baseline)
+    }
+
+    /// Ensure the baseline uses a supported version of the format.
+    fn check_version(&self) -> anyhow::Result<()> {
+        if self.version != BASELINE_VERSION {
+            bail!(
+                "Version {} of the baseline format is not supported (expected: {BASELINE_VERSION})",
+                self.version
+            );
+        }
+        Ok(())
+    }
+
+    /// Store the baseline as a JSON file.
+    pub fn store(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create baseline: {}", path.display()))?;
+        serde_json::to_writer_pretty(std::io::BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write baseline: {}", path.display()))
+    }
+
+    /// Get the active suppression of a check for a document, if any.
+    pub fn suppression(
+        &self,
+        document: &str,
+        check: &str,
+        today: NaiveDate,
+    ) -> Option<&Suppression> {
+        self.suppressions.iter().find(|suppression| {
+            !suppression.is_expired(today) && suppression.matches(document, check)
+        })
+    }
+
+    /// Check if a finding is part of the baseline.
+    pub fn is_known(&self, document: &str, finding: &KnownFinding) -> bool {
+        self.findings
+            .get(document)
+            .is_some_and(|findings| findings.contains(finding))
+    }
+
+    /// Create a new baseline from the findings of a run, keeping the suppressions of this baseline.
+    ///
+    /// Findings covered by an active suppression are not recorded, so that they show up again once
+    /// the suppression expires.
+    pub fn update<K, T>(
+        &self,
+        findings: &BTreeMap<K, Vec<T>>,
+        url: impl Fn(&K) -> String,
+        known: impl Fn(&T) -> KnownFinding,
+        today: NaiveDate,
+    ) -> Self {
+        let mut result = BTreeMap::<String, BTreeSet<KnownFinding>>::new();
+
+        for (key, findings) in findings {
+            let document = url(key);
+            let recorded = findings
+                .iter()
+                .map(&known)
+                .filter(|finding| self.suppression(&document, &finding.rule, today).is_none())
+                .collect::<BTreeSet<_>>();
+
+            if !recorded.is_empty() {
+                result.entry(document).or_default().extend(recorded);
+            }
+        }
+
+        Self {
+            version: BASELINE_VERSION,
+            findings: result,
+            suppressions: self.suppressions.clone(),
+        }
+    }
+
+    /// Remove all known and suppressed findings.
+    ///
+    /// Findings of the baseline which are no longer found are reported as fixed, only considering
+    /// documents which were processed (listed in `documents`, or having findings).
+    pub fn apply<K: Clone + Ord, T>(
+        &self,
+        findings: &mut BTreeMap<K, Vec<T>>,
+        documents: &BTreeSet<K>,
+        url: impl Fn(&K) -> String,
+        known: impl Fn(&T) -> KnownFinding,
+        today: NaiveDate,
+    ) -> BaselineOutcome<K> {
+        let mut outcome = BaselineOutcome::default();
+        let mut current = BTreeMap::<K, (String, BTreeSet<KnownFinding>)>::new();
+
+        for (key, findings) in findings.iter_mut() {
+            let document = url(key);
+            let mut seen = BTreeSet::new();
+
+            findings.retain(|finding| {
+                let finding = known(finding);
+                let keep = if self.suppression(&document, &finding.rule, today).is_some() {
+                    outcome.suppressed += 1;
+                    false
+                } else if self.is_known(&document, &finding) {
+                    outcome.known += 1;
+                    false
+                } else {
+                    true
+                };
+                seen.insert(finding);
+                keep
+            });
+
+            current.insert(key.clone(), (document, seen));
+        }
+        findings.retain(|_, findings| !findings.is_empty());
+
+        for key in documents {
+            current
+                .entry(key.clone())
+                .or_insert_with(|| (url(key), BTreeSet::new()));
+        }
+
+        for (key, (document, seen)) in current {
+            let fixed = self
+                .findings
+                .get(&document)
+                .into_iter()
+                .flatten()
+                .filter(|finding| !seen.contains(*finding))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !fixed.is_empty() {
+                outcome.fixed.insert(key, fixed);
+            }
+        }
+
+        outcome.expired = self
+            .suppressions
+            .iter()
+            .filter(|suppression| suppression.is_expired(today))
+            .cloned()
+            .collect();
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn finding(rule: &str, message: &str) -> KnownFinding {
+        KnownFinding {
+            rule: rule.to_string(),
+            message: message.to_string(),
+            path: None,
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date must parse")
+    }
+
+    #[test]
+    fn apply() {
+        let baseline: Baseline = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "findings": {
+                "a.json": [
+                    { "rule": "6.1.1", "message": "Missing product" },
+                    { "rule": "6.1.2", "message": "Fixed" },
+                ],
+                "gone.json": [
+                    { "rule": "6.1.1", "message": "Not processed" },
+                ],
+            },
+            "suppressions": [
+                { "document": "b.*", "check": "6.2.*", "justification": "Accepted", "expires": "2024-12-31" },
+                { "check": "6.3.1", "justification": "Expired", "expires": "2024-01-01" },
+            ],
+        }))
+        .expect("baseline must parse");
+        assert!(baseline.check_version().is_ok());
+
+        let mut findings = BTreeMap::from_iter([
+            (
+                "a.json".to_string(),
+                vec![finding("6.1.1", "Missing product"), finding("6.1.1", "New")],
+            ),
+            (
+                "b.json".to_string(),
+                vec![finding("6.2.1", "Suppressed"), finding("6.3.1", "Expired")],
+            ),
+        ]);
+
+        let update = baseline.update(&findings, Clone::clone, Clone::clone, date("2024-06-01"));
+        assert_eq!(update.suppressions, baseline.suppressions);
+        assert_eq!(
+            update.findings["b.json"],
+            BTreeSet::from_iter([finding("6.3.1", "Expired")])
+        );
+
+        let outcome = baseline.apply(
+            &mut findings,
+            &BTreeSet::from_iter(["c.json".to_string()]),
+            Clone::clone,
+            Clone::clone,
+            date("2024-06-01"),
+        );
+
+        assert_eq!(
+            findings,
+            BTreeMap::from_iter([
+                ("a.json".to_string(), vec![finding("6.1.1", "New")]),
+                ("b.json".to_string(), vec![finding("6.3.1", "Expired")]),
+            ])
+        );
+        assert_eq!(outcome.known, 1);
+        assert_eq!(outcome.suppressed, 1);
+        assert_eq!(
+            outcome.fixed,
+            BTreeMap::from_iter([("a.json".to_string(), vec![finding("6.1.2", "Fixed")])])
+        );
+        assert_eq!(outcome.expired.len(), 1);
+
+        // the suppression is active until (including) its expiration date
+        assert!(
+            baseline
+                .suppression("b.json", "6.2.1", date("2024-12-31"))
+                .is_some()
+        );
+        assert!(
+            baseline
+                .suppression("b.json", "6.2.1", date("2025-01-01"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn version() {
+        let baseline = Baseline {
+            version: BASELINE_VERSION + 1,
+            ..Default::default()
+        };
+        assert!(baseline.check_version().is_err());
+        assert!(Baseline::default().check_version().is_ok());
+    }
+}
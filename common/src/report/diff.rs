@@ -0,0 +1,293 @@
+use crate::report::{FilterAttributes, KnownFinding};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+
+/// The findings of a run, by document
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunFindings {
+    /// Findings, by URL of the document
+    pub findings: BTreeMap<String, BTreeSet<KnownFinding>>,
+    /// All documents processed by the run, if known
+    pub documents: Option<BTreeSet<String>>,
+}
+
+/// A report of a run, which can be compared with the report of another run
+pub trait RunReport: serde::de::DeserializeOwned {
+    /// The findings of the run
+    fn run_findings(&self) -> RunFindings;
+}
+
+/// The difference between the findings of two runs
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReportDiff {
+    /// Documents with findings, which had none before
+    pub regressed: Vec<DocumentDiff>,
+    /// Documents without findings, which had some before
+    pub fixed: Vec<DocumentDiff>,
+    /// Documents with findings in both runs, but different ones
+    pub changed: Vec<DocumentDiff>,
+    /// Documents with findings before, which were no longer processed
+    pub removed: Vec<String>,
+}
+
+/// The change of findings of a document
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentDiff {
+    /// The URL of the document
+    pub document: String,
+    /// Findings only present in the new run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<KnownFinding>,
+    /// Findings only present in the old run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<KnownFinding>,
+}
+
+impl ReportDiff {
+    /// Compare the findings of an old and a new run.
+    pub fn new(old: &RunFindings, new: &RunFindings) -> Self {
+        let mut result = Self::default();
+        let none = BTreeSet::new();
+
+        let documents = old
+            .findings
+            .keys()
+            .chain(new.findings.keys())
+            .collect::<BTreeSet<_>>();
+
+        for document in documents {
+            let before = old.findings.get(document).unwrap_or(&none);
+            let after = new.findings.get(document).unwrap_or(&none);
+
+            if before == after {
+                continue;
+            }
+
+            let diff = DocumentDiff {
+                document: document.clone(),
+                added: after.difference(before).cloned().collect(),
+                removed: before.difference(after).cloned().collect(),
+            };
+
+            if before.is_empty() {
+                result.regressed.push(diff);
+            } else if !after.is_empty() {
+                result.changed.push(diff);
+            } else if new
+                .documents
+                .as_ref()
+                .is_none_or(|documents| documents.contains(document))
+            {
+                result.fixed.push(diff);
+            } else {
+                result.removed.push(diff.document);
+            }
+        }
+
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regressed.is_empty()
+            && self.fixed.is_empty()
+            && self.changed.is_empty()
+            && self.removed.is_empty()
+    }
+
+    fn render_documents(
+        f: &mut Formatter<'_>,
+        title: &str,
+        documents: &[DocumentDiff],
+    ) -> std::fmt::Result {
+        if documents.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            f,
+            r#"<h2>{title} <span class="badge bg-secondary">{count}</span></h2>"#,
+            count = documents.len()
+        )?;
+        writeln!(f, r#"<table class="table">"#)?;
+        writeln!(
+            f,
+            r#"<thead><tr><th scope="col">Document</th><th scope="col">Findings</th></tr></thead>"#
+        )?;
+        writeln!(f, "<tbody>")?;
+
+        for document in documents {
+            let url = html_escape::encode_quoted_attribute(&document.document);
+            let label = html_escape::encode_text(&document.document);
+            let filter = document
+                .added
+                .iter()
+                .chain(&document.removed)
+                .fold(FilterAttributes::new(), |filter, finding| {
+                    filter.check(&finding.rule)
+                });
+            writeln!(
+                f,
+                r#"<tr{filter}><td><a href="{url}" target="_blank">{label}</a></td><td><ul>"#
+            )?;
+
+            for (sign, class, finding) in document
+                .added
+                .iter()
+                .map(|finding| ("+", "text-danger", finding))
+                .chain(
+                    document
+                        .removed
+                        .iter()
+                        .map(|finding| ("-", "text-success", finding)),
+                )
+            {
+                write!(
+                    f,
+                    r#"<li class="{class}">{sign} <code>{rule}</code> {message}"#,
+                    rule = html_escape::encode_text(&finding.rule),
+                    message = html_escape::encode_text(&finding.message),
+                )?;
+                if let Some(path) = &finding.path {
+                    write!(f, " <code>{}</code>", html_escape::encode_text(path))?;
+                }
+                writeln!(f, "</li>")?;
+            }
+
+            writeln!(f, "</ul></td></tr>")?;
+        }
+
+        writeln!(f, "</tbody></table>")
+    }
+}
+
+/// Render the difference as HTML fragment.
+impl Display for ReportDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, r#"<h2>Summary</h2>"#)?;
+        writeln!(f, r#"<dl class="row">"#)?;
+        for (label, count) in [
+            ("Regressed", self.regressed.len()),
+            ("Fixed", self.fixed.len()),
+            ("Changed", self.changed.len()),
+            ("Removed", self.removed.len()),
+        ] {
+            writeln!(
+                f,
+                r#"<dt class="col-sm-2">{label}</dt><dd class="col-sm-10">{count}</dd>"#
+            )?;
+        }
+        writeln!(f, r#"</dl>"#)?;
+
+        Self::render_documents(f, "Regressed documents", &self.regressed)?;
+        Self::render_documents(f, "Changed documents", &self.changed)?;
+        Self::render_documents(f, "Fixed documents", &self.fixed)?;
+
+        if !self.removed.is_empty() {
+            writeln!(
+                f,
+                r#"<h2>Removed documents <span class="badge bg-secondary">{count}</span></h2>"#,
+                count = self.removed.len()
+            )?;
+            writeln!(f, "<ul>")?;
+            for document in &self.removed {
+                writeln!(
+                    f,
+                    "<li{filter}>{document}</li>",
+                    filter = FilterAttributes::new(),
+                    document = html_escape::encode_text(document)
+                )?;
+            }
+            writeln!(f, "</ul>")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn finding(rule: &str) -> KnownFinding {
+        KnownFinding {
+            rule: rule.to_string(),
+            message: format!("Failed {rule}"),
+            path: None,
+        }
+    }
+
+    fn run<const N: usize>(
+        findings: [(&str, Vec<KnownFinding>); N],
+        documents: Option<&[&str]>,
+    ) -> RunFindings {
+        RunFindings {
+            findings: findings
+                .into_iter()
+                .map(|(document, findings)| (document.to_string(), findings.into_iter().collect()))
+                .collect(),
+            documents: documents
+                .map(|documents| documents.iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let old = run(
+            [
+                ("a.json", vec![finding("6.1.1")]),
+                ("b.json", vec![finding("6.1.1"), finding("6.1.2")]),
+                ("c.json", vec![finding("6.1.1")]),
+                ("d.json", vec![finding("6.1.1")]),
+            ],
+            None,
+        );
+        let new = run(
+            [
+                ("b.json", vec![finding("6.1.2"), finding("6.1.3")]),
+                ("c.json", vec![finding("6.1.1")]),
+                ("e.json", vec![finding("6.1.1")]),
+            ],
+            Some(&["a.json", "b.json", "c.json", "e.json"]),
+        );
+
+        let diff = ReportDiff::new(&old, &new);
+
+        assert_eq!(
+            diff.regressed,
+            vec![DocumentDiff {
+                document: "e.json".to_string(),
+                added: vec![finding("6.1.1")],
+                removed: vec![],
+            }]
+        );
+        assert_eq!(
+            diff.changed,
+            vec![DocumentDiff {
+                document: "b.json".to_string(),
+                added: vec![finding("6.1.3")],
+                removed: vec![finding("6.1.1")],
+            }]
+        );
+        assert_eq!(
+            diff.fixed,
+            vec![DocumentDiff {
+                document: "a.json".to_string(),
+                added: vec![],
+                removed: vec![finding("6.1.1")],
+            }]
+        );
+        assert_eq!(diff.removed, vec!["d.json".to_string()]);
+
+        assert!(ReportDiff::new(&new, &new).is_empty());
+
+        let html = diff.to_string();
+        assert!(
+            html.contains(
+                r#"<tr data-filter data-checks="[&quot;6.1.1&quot;,&quot;6.1.3&quot;]">"#
+            )
+        );
+        assert!(html.contains("<li data-filter>d.json</li>"));
+    }
+}
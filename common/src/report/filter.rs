@@ -0,0 +1,84 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Formatter},
+};
+
+/// Attributes of an HTML element, allowing the report script to search and filter it.
+///
+/// Renders as a list of attributes, starting with a space, to be placed inside the element's start tag.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilterAttributes {
+    pub distribution: Option<String>,
+    pub checks: BTreeSet<String>,
+    pub severities: BTreeSet<String>,
+}
+
+impl FilterAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distribution(mut self, distribution: impl Into<String>) -> Self {
+        self.distribution = Some(distribution.into());
+        self
+    }
+
+    pub fn check(mut self, check: impl Into<String>) -> Self {
+        self.checks.insert(check.into());
+        self
+    }
+
+    pub fn severity(mut self, severity: impl Into<String>) -> Self {
+        self.severities.insert(severity.into());
+        self
+    }
+
+    fn attribute<'a>(
+        f: &mut Formatter<'_>,
+        name: &str,
+        values: impl IntoIterator<Item = &'a String>,
+    ) -> std::fmt::Result {
+        let values = values.into_iter().collect::<Vec<_>>();
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let json = serde_json::to_string(&values).map_err(|_| std::fmt::Error)?;
+        write!(
+            f,
+            r#" data-{name}="{json}""#,
+            json = html_escape::encode_double_quoted_attribute(&json)
+        )
+    }
+}
+
+impl Display for FilterAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(" data-filter")?;
+        Self::attribute(f, "distribution", &self.distribution)?;
+        Self::attribute(f, "checks", &self.checks)?;
+        Self::attribute(f, "severities", &self.severities)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn attributes() {
+        assert_eq!(FilterAttributes::new().to_string(), " data-filter");
+
+        let attributes = FilterAttributes::new()
+            .distribution("https://example.com/\"csaf\"/")
+            .check("6.1.2")
+            .check("6.1.1")
+            .check("6.1.2")
+            .severity("Warning");
+
+        assert_eq!(
+            attributes.to_string(),
+            r#" data-filter data-distribution="[&quot;https://example.com/\&quot;csaf\&quot;/&quot;]" data-checks="[&quot;6.1.1&quot;,&quot;6.1.2&quot;]" data-severities="[&quot;Warning&quot;]""#
+        );
+    }
+}
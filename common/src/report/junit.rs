@@ -0,0 +1,220 @@
+//! A minimal writer for the JUnit XML format, as understood by most CI systems.
+
+use std::io::Write;
+
+/// The root element, a set of test suites
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestSuites {
+    pub name: String,
+    pub suites: Vec<TestSuite>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestCase {
+    pub name: String,
+    pub classname: String,
+    /// The test case could not be run
+    pub error: Option<Failure>,
+    pub failures: Vec<Failure>,
+    /// Additional output, not failing the test case
+    pub system_out: Option<String>,
+}
+
+impl TestCase {
+    pub fn new(classname: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            classname: classname.into(),
+            ..Default::default()
+        }
+    }
+
+    fn is_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    fn is_failure(&self) -> bool {
+        !self.is_error() && !self.failures.is_empty()
+    }
+}
+
+/// A failure, or error, of a test case
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Failure {
+    pub r#type: String,
+    pub message: String,
+    pub text: String,
+}
+
+impl Failure {
+    pub fn new(
+        r#type: impl Into<String>,
+        message: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self {
+            r#type: r#type.into(),
+            message: message.into(),
+            text: text.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+}
+
+impl Counts {
+    fn of<'a>(cases: impl IntoIterator<Item = &'a TestCase>) -> Self {
+        cases.into_iter().fold(Self::default(), |mut counts, case| {
+            counts.tests += 1;
+            counts.failures += case.is_failure() as usize;
+            counts.errors += case.is_error() as usize;
+            counts
+        })
+    }
+}
+
+/// Strip characters which are not allowed in XML 1.0 documents
+fn valid(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| matches!(c, '\t' | '\n' | '\r') || *c >= ' ')
+        .collect()
+}
+
+fn attr(value: &str) -> String {
+    html_escape::encode_double_quoted_attribute(&valid(value)).into_owned()
+}
+
+fn text(value: &str) -> String {
+    html_escape::encode_text(&valid(value)).into_owned()
+}
+
+impl TestSuites {
+    /// Write the test suites as JUnit XML
+    pub fn write(&self, mut out: impl Write) -> std::io::Result<()> {
+        let counts = Counts::of(self.suites.iter().flat_map(|suite| &suite.cases));
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<testsuites name="{name}" tests="{tests}" failures="{failures}" errors="{errors}">"#,
+            name = attr(&self.name),
+            tests = counts.tests,
+            failures = counts.failures,
+            errors = counts.errors,
+        )?;
+
+        for suite in &self.suites {
+            let counts = Counts::of(&suite.cases);
+            writeln!(
+                out,
+                r#"  <testsuite name="{name}" tests="{tests}" failures="{failures}" errors="{errors}">"#,
+                name = attr(&suite.name),
+                tests = counts.tests,
+                failures = counts.failures,
+                errors = counts.errors,
+            )?;
+
+            for case in &suite.cases {
+                write!(
+                    out,
+                    r#"    <testcase name="{name}" classname="{classname}""#,
+                    name = attr(&case.name),
+                    classname = attr(&case.classname),
+                )?;
+
+                if case.error.is_none() && case.failures.is_empty() && case.system_out.is_none() {
+                    writeln!(out, "/>")?;
+                    continue;
+                }
+                writeln!(out, ">")?;
+
+                for (element, failure) in case
+                    .error
+                    .iter()
+                    .map(|error| ("error", error))
+                    .chain(case.failures.iter().map(|failure| ("failure", failure)))
+                {
+                    writeln!(
+                        out,
+                        r#"      <{element} type="{type}" message="{message}">{text}</{element}>"#,
+                        r#type = attr(&failure.r#type),
+                        message = attr(&failure.message),
+                        text = text(&failure.text),
+                    )?;
+                }
+
+                if let Some(system_out) = &case.system_out {
+                    writeln!(out, "      <system-out>{}</system-out>", text(system_out))?;
+                }
+
+                writeln!(out, "    </testcase>")?;
+            }
+
+            writeln!(out, "  </testsuite>")?;
+        }
+
+        writeln!(out, "</testsuites>")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let suites = TestSuites {
+            name: "walker".to_string(),
+            suites: vec![TestSuite {
+                name: "https://example.com/".to_string(),
+                cases: vec![
+                    TestCase::new("https://example.com/", "a.json"),
+                    TestCase {
+                        error: Some(Failure::new("error", "Failed <to> parse", "")),
+                        ..TestCase::new("https://example.com/", "b.json")
+                    },
+                    TestCase {
+                        failures: vec![Failure::new("6.1.1", "1 finding", "error: \"a\" & b\u{1}")],
+                        system_out: Some("info".to_string()),
+                        ..TestCase::new("https://example.com/", "c.json")
+                    },
+                ],
+            }],
+        };
+
+        let mut out = vec![];
+        suites.write(&mut out).expect("must write");
+
+        assert_eq!(
+            String::from_utf8(out).expect("must be UTF-8"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="walker" tests="3" failures="1" errors="1">
+  <testsuite name="https://example.com/" tests="3" failures="1" errors="1">
+    <testcase name="a.json" classname="https://example.com/"/>
+    <testcase name="b.json" classname="https://example.com/">
+      <error type="error" message="Failed &lt;to&gt; parse"></error>
+    </testcase>
+    <testcase name="c.json" classname="https://example.com/">
+      <failure type="6.1.1" message="1 finding">error: "a" &amp; b</failure>
+      <system-out>info</system-out>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
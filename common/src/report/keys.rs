@@ -0,0 +1,156 @@
+use super::signers::Date;
+use crate::locale::Formatted;
+use std::fmt::{Display, Formatter};
+use time::OffsetDateTime;
+
+/// Changes of the provider keys, compared to the keys known from a previous run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyChanges {
+    /// No keys were known before, all current keys have been recorded
+    pub first_use: bool,
+    /// Keys which were not known before
+    pub added: Vec<KeyInfo>,
+    /// Keys which were known before, but are no longer provided
+    pub removed: Vec<KeyInfo>,
+    /// Keys which have been revoked since the previous run
+    pub revoked: Vec<KeyInfo>,
+    /// Keys which are expired
+    pub expired: Vec<KeyInfo>,
+    /// Keys which will expire soon
+    pub expiring: Vec<KeyInfo>,
+}
+
+/// Information about a key
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyInfo {
+    /// The fingerprint of the certificate
+    pub fingerprint: String,
+    /// User IDs of the certificate
+    pub user_ids: Vec<String>,
+    /// The expiration of the key
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expiration: Option<OffsetDateTime>,
+}
+
+impl KeyChanges {
+    /// Check if the keys changed unexpectedly (added, removed, or revoked keys).
+    ///
+    /// Recording the keys for the first time is not considered a change.
+    pub fn is_changed(&self) -> bool {
+        !self.first_use
+            && (!self.added.is_empty() || !self.removed.is_empty() || !self.revoked.is_empty())
+    }
+
+    /// Check if there is anything to report.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.revoked.is_empty()
+            && self.expired.is_empty()
+            && self.expiring.is_empty()
+    }
+
+    /// Log the changes.
+    pub fn log(&self) {
+        if self.first_use {
+            for key in &self.added {
+                log::info!("Recording key: {}", key.fingerprint);
+            }
+        } else {
+            for key in &self.added {
+                log::warn!("Key added: {}", key.fingerprint);
+            }
+        }
+        for key in &self.removed {
+            log::warn!("Key removed: {}", key.fingerprint);
+        }
+        for key in &self.revoked {
+            log::warn!("Key revoked: {}", key.fingerprint);
+        }
+        for key in &self.expired {
+            log::warn!("Key expired: {}", key.fingerprint);
+        }
+        for key in &self.expiring {
+            log::warn!(
+                "Key expiring soon: {} ({})",
+                key.fingerprint,
+                Date(key.expiration)
+            );
+        }
+    }
+
+    fn render_section(f: &mut Formatter<'_>, title: &str, keys: &[KeyInfo]) -> std::fmt::Result {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            f,
+            r#"<h3>{title} <span class="badge text-bg-light rounded-pill">{}</span></h3>"#,
+            Formatted(keys.len())
+        )?;
+        writeln!(
+            f,
+            r#"
+    <table class="table">
+        <thead>
+            <tr>
+                <th scope="col">Certificate</th>
+                <th scope="col">User IDs</th>
+                <th scope="col">Expiration</th>
+            </tr>
+        </thead>
+
+        <tbody>
+"#
+        )?;
+
+        for key in keys {
+            writeln!(
+                f,
+                r#"<tr><td><code>{fingerprint}</code></td><td>"#,
+                fingerprint = html_escape::encode_text(&key.fingerprint),
+            )?;
+            for user_id in &key.user_ids {
+                writeln!(
+                    f,
+                    "{user_id}<br>",
+                    user_id = html_escape::encode_text(user_id)
+                )?;
+            }
+            writeln!(
+                f,
+                "</td><td>{expiration}</td></tr>",
+                expiration = Date(key.expiration)
+            )?;
+        }
+
+        writeln!(f, "</tbody></table>")
+    }
+}
+
+impl Display for KeyChanges {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "<h2>Provider keys</h2>")?;
+
+        if self.first_use {
+            writeln!(
+                f,
+                "<p>Keys have been recorded for the first time, changes will be reported on subsequent runs.</p>"
+            )?;
+        } else if self.is_empty() {
+            writeln!(f, "<p>No changes.</p>")?;
+            return Ok(());
+        }
+
+        if !self.first_use {
+            Self::render_section(f, "Added", &self.added)?;
+        }
+        Self::render_section(f, "Removed", &self.removed)?;
+        Self::render_section(f, "Revoked", &self.revoked)?;
+        Self::render_section(f, "Expired", &self.expired)?;
+        Self::render_section(f, "Expiring soon", &self.expiring)?;
+
+        Ok(())
+    }
+}
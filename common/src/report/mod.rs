@@ -1,10 +1,24 @@
 //! Common functionality for creating the reports
 
+mod baseline;
+mod diff;
+mod filter;
+pub mod junit;
+mod keys;
+pub mod sarif;
+mod signers;
 mod stats;
 mod summary;
+mod trends;
 
+pub use baseline::*;
+pub use diff::*;
+pub use filter::*;
+pub use keys::*;
+pub use signers::*;
 pub use stats::*;
 pub use summary::*;
+pub use trends::*;
 
 use std::fmt::Display;
 use std::io::Write;
@@ -16,12 +30,27 @@ const BOOTSTRAP_CSS_SRI: &str =
 const BOOTSTRAP_JS_SRI: &str =
     "sha384-YvpcrYf0tY3lHB60NNkmXc5s9fDVZLESaAA55NDzOxhy9GkcIdslK1eN7N6jIeHz";
 
+/// The stylesheet used instead of Bootstrap, for self-contained reports
+const INLINE_CSS: &str = include_str!("report.css");
+/// The script providing search and filtering of reports
+const REPORT_JS: &str = include_str!("report.js");
+
 /// Options for rendering reports.
 #[derive(Clone, Debug, Default)]
 pub struct ReportOptions {
     pub bootstrap: Bootstrap,
 }
 
+/// The options for rendering HTML reports, inlining all resources when `self_contained` is set
+pub fn report_options(self_contained: bool) -> ReportOptions {
+    ReportOptions {
+        bootstrap: match self_contained {
+            true => Bootstrap::SelfContained,
+            false => Bootstrap::Default,
+        },
+    }
+}
+
 /// Options for the imported bootstrap resources.
 #[derive(Clone, Default, Debug)]
 pub enum Bootstrap {
@@ -41,22 +70,28 @@ pub enum Bootstrap {
         /// An optional SRI value for the JS resource
         js_integrity: Option<String>,
     },
+    /// Don't load any external resources, inlining a minimal stylesheet instead
+    ///
+    /// This creates self-contained reports, which can be viewed without network access.
+    SelfContained,
 }
 
 impl Bootstrap {
-    pub fn css_location(&self) -> String {
+    /// The location of the stylesheet, `None` for self-contained reports (inlining a stylesheet)
+    pub fn css_location(&self) -> Option<String> {
         match self {
-            Self::Default => format!(
+            Self::Default => Some(format!(
                 "https://cdn.jsdelivr.net/npm/bootstrap@{BOOTSTRAP_VERSION}/dist/css/bootstrap.min.css"
-            ),
+            )),
             Self::Custom {
                 location,
                 js_location,
                 ..
-            } => match js_location {
+            } => Some(match js_location {
                 Some(_) => location.clone(),
                 None => format!("{location}/css/bootstrap.min.css"),
-            },
+            }),
+            Self::SelfContained => None,
         }
     }
 
@@ -64,22 +99,25 @@ impl Bootstrap {
         match self {
             Self::Default => Some(BOOTSTRAP_CSS_SRI.into()),
             Self::Custom { css_integrity, .. } => css_integrity.clone(),
+            Self::SelfContained => None,
         }
     }
 
-    pub fn js_location(&self) -> String {
+    /// The location of the script, `None` if no script is loaded
+    pub fn js_location(&self) -> Option<String> {
         match self {
-            Self::Default => format!(
+            Self::Default => Some(format!(
                 "https://cdn.jsdelivr.net/npm/bootstrap@{BOOTSTRAP_VERSION}/dist/js/bootstrap.bundle.min.js"
-            ),
+            )),
             Self::Custom {
                 location,
                 js_location,
                 ..
-            } => match js_location {
+            } => Some(match js_location {
                 Some(js_location) => js_location.clone(),
                 None => format!("{location}/js/bootstrap.bundle.min.js"),
-            },
+            }),
+            Self::SelfContained => None,
         }
     }
 
@@ -87,16 +125,45 @@ impl Bootstrap {
         match self {
             Self::Default => Some(BOOTSTRAP_JS_SRI.into()),
             Self::Custom { js_integrity, .. } => js_integrity.clone(),
+            Self::SelfContained => None,
         }
     }
 }
 
+fn integrity(sri: Option<String>) -> String {
+    sri.map(|sri| {
+        format!(
+            r#" integrity="{sri}""#,
+            sri = html_escape::encode_quoted_attribute(&sri)
+        )
+    })
+    .unwrap_or_default()
+}
+
 pub fn render(
     mut write: impl Write,
     title: impl Display,
     report: impl Display,
     options: &ReportOptions,
 ) -> anyhow::Result<()> {
+    let css = match options.bootstrap.css_location() {
+        Some(css) => format!(
+            r#"<link href="{css}" rel="stylesheet"{css_integrity} crossorigin="anonymous">"#,
+            css = html_escape::encode_quoted_attribute(&css),
+            css_integrity = integrity(options.bootstrap.css_integrity()),
+        ),
+        None => format!("<style>\n{INLINE_CSS}    </style>"),
+    };
+
+    let js = match options.bootstrap.js_location() {
+        Some(js) => format!(
+            r#"<script src="{js}"{js_integrity} crossorigin="anonymous"></script>"#,
+            js = html_escape::encode_quoted_attribute(&js),
+            js_integrity = integrity(options.bootstrap.js_integrity()),
+        ),
+        None => String::new(),
+    };
+
     writeln!(
         write,
         r#"<!doctype html>
@@ -105,7 +172,7 @@ pub fn render(
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
     <title>{title}</title>
-    <link href="{css}" rel="stylesheet" {css_integrity} crossorigin="anonymous">
+    {css}
   </head>
   <body>
     <div class="container-fluid">
@@ -114,7 +181,9 @@ pub fn render(
       </h1>
       {report}
     </div>
-    <script src="{js}" {js_integrity} crossorigin="anonymous"></script>
+    {js}
+    <script>
+{REPORT_JS}    </script>
   </body>
 </html>
 "#,
@@ -124,26 +193,36 @@ pub fn render(
                 "[year]-[month padding:zero]-[day padding:zero] [hour repr:24]:[minute padding:zero]:[second padding:zero] [offset_hour sign:mandatory]:[offset_minute]"
             ))
             .unwrap_or_else(|_| "Unknown".to_string()),
-        css = html_escape::encode_quoted_attribute(&options.bootstrap.css_location()),
-        js = html_escape::encode_quoted_attribute(&options.bootstrap.js_location()),
-        css_integrity = options
-            .bootstrap
-            .css_integrity()
-            .map(|sri| format!(
-                r#"integrity="{sri}""#,
-                sri = html_escape::encode_quoted_attribute(&sri)
-            ))
-            .unwrap_or_default(),
-        js_integrity = options
-            .bootstrap
-            .js_integrity()
-            .map(|sri| format!(
-                r#"integrity="{sri}""#,
-                sri = html_escape::encode_quoted_attribute(&sri)
-            ))
-            .unwrap_or_default(),
         title = html_escape::encode_text(&title.to_string()),
     )?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render_to_string(options: &ReportOptions) -> String {
+        let mut out = Vec::new();
+        render(&mut out, "Report", "<p>content</p>", options).expect("must render");
+        String::from_utf8(out).expect("must be UTF-8")
+    }
+
+    #[test]
+    fn self_contained() {
+        let html = render_to_string(&Default::default());
+        assert!(html.contains("cdn.jsdelivr.net"));
+        assert!(html.contains("data-filter"));
+
+        let html = render_to_string(&ReportOptions {
+            bootstrap: Bootstrap::SelfContained,
+        });
+        assert!(!html.contains("cdn.jsdelivr.net"));
+        assert!(!html.contains("<link "));
+        assert!(!html.contains("<script src="));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("data-filter"));
+        assert!(html.contains("<p>content</p>"));
+    }
+}
@@ -0,0 +1,116 @@
+// Client side search and filtering of the report, without any dependencies.
+//
+// Elements which can be filtered carry a `data-filter` attribute, plus JSON arrays of values in `data-checks`,
+// `data-severities`, and `data-distribution`. Individual findings carry `data-check` and `data-severity`.
+(function () {
+    "use strict";
+
+    const rows = Array.from(document.querySelectorAll("[data-filter]"));
+    if (rows.length === 0) {
+        return;
+    }
+
+    const valuesOf = (row, name) => {
+        try {
+            return JSON.parse(row.dataset[name] || "[]");
+        } catch (e) {
+            return [];
+        }
+    };
+
+    const form = document.createElement("form");
+    form.className = "report-filter row g-2 mb-3";
+    form.setAttribute("role", "search");
+    form.addEventListener("submit", (event) => event.preventDefault());
+
+    const column = (element) => {
+        const div = document.createElement("div");
+        div.className = "col-auto";
+        div.appendChild(element);
+        form.appendChild(div);
+        return element;
+    };
+
+    const search = column(document.createElement("input"));
+    search.type = "search";
+    search.className = "form-control";
+    search.placeholder = "Search";
+    search.setAttribute("aria-label", "Search");
+
+    const select = (name, label) => {
+        const values = new Set();
+        rows.forEach((row) => valuesOf(row, name).forEach((value) => values.add(value)));
+        if (values.size === 0) {
+            return null;
+        }
+
+        const element = column(document.createElement("select"));
+        element.className = "form-select";
+        element.setAttribute("aria-label", label);
+        element.add(new Option(label, ""));
+        Array.from(values).sort().forEach((value) => element.add(new Option(value, value)));
+        return element;
+    };
+
+    const filters = [
+        ["checks", select("checks", "All checks")],
+        ["severities", select("severities", "All severities")],
+        ["distribution", select("distribution", "All distributions")],
+    ].filter(([, element]) => element !== null);
+
+    const toggle = (label, open) => {
+        const button = column(document.createElement("button"));
+        button.type = "button";
+        button.className = "btn btn-outline-secondary";
+        button.textContent = label;
+        button.addEventListener("click", () => {
+            document.querySelectorAll("details").forEach((details) => (details.open = open));
+        });
+    };
+    if (document.querySelector("details") !== null) {
+        toggle("Expand all", true);
+        toggle("Collapse all", false);
+    }
+
+    const status = column(document.createElement("span"));
+    status.className = "form-text";
+
+    const apply = () => {
+        const text = search.value.trim().toLowerCase();
+        const selected = Object.fromEntries(filters.map(([name, element]) => [name, element.value]));
+        let visible = 0;
+
+        rows.forEach((row) => {
+            let show = text === "" || row.textContent.toLowerCase().includes(text);
+            filters.forEach(([name]) => {
+                if (selected[name] !== "" && !valuesOf(row, name).includes(selected[name])) {
+                    show = false;
+                }
+            });
+
+            row.querySelectorAll("[data-check]").forEach((finding) => {
+                finding.hidden =
+                    (selected.checks && finding.dataset.check !== selected.checks) ||
+                    (selected.severities && finding.dataset.severity !== selected.severities);
+            });
+
+            row.hidden = !show;
+            if (show) {
+                visible += 1;
+            }
+        });
+
+        status.textContent = visible + " of " + rows.length + " entries";
+    };
+
+    search.addEventListener("input", apply);
+    filters.forEach(([, element]) => element.addEventListener("change", apply));
+    apply();
+
+    const heading = document.querySelector("h1");
+    if (heading !== null) {
+        heading.after(form);
+    } else {
+        document.body.prepend(form);
+    }
+})();
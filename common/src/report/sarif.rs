@@ -0,0 +1,211 @@
+//! A minimal model of the SARIF 2.1.0 format, as far as it is used by the reports.
+
+use std::collections::BTreeMap;
+
+/// The version of the SARIF format
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// The schema of the SARIF format
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A SARIF log
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Sarif {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+impl Sarif {
+    pub fn new(run: Run) -> Self {
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: vec![run],
+        }
+    }
+}
+
+/// A single run of a tool
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+impl Run {
+    pub fn new(driver: ToolComponent) -> Self {
+        Self {
+            tool: Tool { driver },
+            results: vec![],
+        }
+    }
+
+    /// Add a result, registering its rule with the driver, if it is not yet known.
+    pub fn add_result(&mut self, mut result: SarifResult) {
+        let rules = &mut self.tool.driver.rules;
+        let index = match rules.iter().position(|rule| rule.id == result.rule_id) {
+            Some(index) => index,
+            None => {
+                rules.push(ReportingDescriptor {
+                    id: result.rule_id.clone(),
+                    short_description: None,
+                });
+                rules.len() - 1
+            }
+        };
+
+        result.rule_index = Some(index);
+        self.results.push(result);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+impl ToolComponent {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn information_uri(mut self, information_uri: impl Into<String>) -> Self {
+        self.information_uri = Some(information_uri.into());
+        self
+    }
+
+    /// Register a rule upfront, providing a description.
+    pub fn rule(mut self, id: impl Into<String>, description: impl Into<String>) -> Self {
+        self.rules.push(ReportingDescriptor {
+            id: id.into(),
+            short_description: Some(Message::new(description)),
+        });
+        self
+    }
+}
+
+/// A rule
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<Message>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+/// The result of a rule, for an artifact
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    /// The index of the rule in the driver, set when adding the result to a [`Run`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+    pub level: Level,
+    pub message: Message,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+}
+
+impl SarifResult {
+    pub fn new(rule_id: impl Into<String>, level: Level, message: impl Into<String>) -> Self {
+        Self {
+            rule_id: rule_id.into(),
+            rule_index: None,
+            level,
+            message: Message::new(message),
+            locations: vec![],
+            properties: Default::default(),
+        }
+    }
+
+    /// Add the location of the artifact, and an optional JSON pointer into it.
+    pub fn location(mut self, uri: impl Into<String>, pointer: Option<String>) -> Self {
+        self.locations.push(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri: uri.into() },
+            },
+            logical_locations: pointer
+                .into_iter()
+                .map(|fully_qualified_name| LogicalLocation {
+                    fully_qualified_name,
+                })
+                .collect(),
+        });
+        self
+    }
+
+    pub fn property(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(name.into(), value.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Message {
+    pub text: String,
+}
+
+impl Message {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalLocation {
+    /// The JSON pointer to the location in the artifact
+    pub fully_qualified_name: String,
+}
@@ -0,0 +1,271 @@
+use crate::locale::Formatted;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+use time::{OffsetDateTime, macros::format_description};
+
+/// The signers of documents, seen during a run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Signers {
+    /// Signers, by fingerprint of the certificate
+    pub signers: BTreeMap<String, Signer>,
+    /// Number of documents without a signature
+    pub unsigned: usize,
+    /// Number of documents with a signature only valid under a relaxed policy
+    pub relaxed: usize,
+}
+
+/// Information about a certificate which signed documents.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Signer {
+    /// User IDs of the certificate
+    pub user_ids: Vec<String>,
+    /// Number of documents signed
+    pub documents: usize,
+    /// Fingerprints of the (sub)keys which created signatures
+    pub keys: BTreeSet<String>,
+    /// Public key and hash algorithms used for signing
+    pub algorithms: BTreeSet<String>,
+    /// The creation time of the oldest signature
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub first_signature: Option<OffsetDateTime>,
+    /// The creation time of the newest signature
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub last_signature: Option<OffsetDateTime>,
+    /// The expiration of the signing key
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub key_expiration: Option<OffsetDateTime>,
+}
+
+#[cfg(feature = "openpgp")]
+impl Signers {
+    /// Record the signature of a document, `None` if the document was not signed.
+    pub fn record(&mut self, signature: Option<&crate::validate::openpgp::SignatureVerification>) {
+        let Some(signature) = signature else {
+            self.unsigned += 1;
+            return;
+        };
+
+        let signer = self
+            .signers
+            .entry(signature.fingerprint.to_hex())
+            .or_default();
+
+        signer.documents += 1;
+        if signature.relaxed_policy {
+            self.relaxed += 1;
+        }
+        signer.user_ids.clone_from(&signature.user_ids);
+        signer.keys.insert(signature.key_fingerprint.to_hex());
+        signer.algorithms.insert(format!(
+            "{}/{}",
+            signature.public_key_algorithm, signature.hash_algorithm
+        ));
+
+        if let Some(created) = signature.creation_time.map(OffsetDateTime::from) {
+            if signer.first_signature.is_none_or(|first| created < first) {
+                signer.first_signature = Some(created);
+            }
+            if signer.last_signature.is_none_or(|last| created > last) {
+                signer.last_signature = Some(created);
+            }
+        }
+
+        signer.key_expiration = signature.key_expiration.map(OffsetDateTime::from);
+    }
+}
+
+impl Signers {
+    /// Number of signed documents
+    pub fn signed(&self) -> usize {
+        self.signers.values().map(|signer| signer.documents).sum()
+    }
+}
+
+pub(super) struct Date(pub(super) Option<OffsetDateTime>);
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0.and_then(|date| {
+            date.format(&format_description!("[year]-[month]-[day]"))
+                .ok()
+        }) {
+            Some(date) => f.write_str(&date),
+            None => f.write_str("—"),
+        }
+    }
+}
+
+impl Display for Signers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"<h2>Signatures <span class="badge text-bg-light rounded-pill">{}</span></h2>"#,
+            Formatted(self.signers.len())
+        )?;
+        writeln!(
+            f,
+            "<p>{signed} signed document(s), {unsigned} unsigned document(s)</p>",
+            signed = Formatted(self.signed()),
+            unsigned = Formatted(self.unsigned),
+        )?;
+        if self.relaxed > 0 {
+            writeln!(
+                f,
+                r#"<p class="text-warning-emphasis">{relaxed} document(s) only have a valid signature under a relaxed OpenPGP policy</p>"#,
+                relaxed = Formatted(self.relaxed),
+            )?;
+        }
+
+        if self.signers.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            f,
+            r#"
+    <table class="table">
+        <thead>
+            <tr>
+                <th scope="col">Certificate</th>
+                <th scope="col">User IDs</th>
+                <th scope="col" class="text-right">Documents</th>
+                <th scope="col">Algorithms</th>
+                <th scope="col">First signature</th>
+                <th scope="col">Last signature</th>
+                <th scope="col">Key expiration</th>
+            </tr>
+        </thead>
+
+        <tbody>
+"#
+        )?;
+
+        for (fingerprint, signer) in &self.signers {
+            writeln!(
+                f,
+                r#"
+            <tr>
+                <td><code>{fingerprint}</code>"#,
+                fingerprint = html_escape::encode_text(fingerprint),
+            )?;
+            for key in signer.keys.iter().filter(|key| *key != fingerprint) {
+                writeln!(
+                    f,
+                    r#"<br><small class="text-body-secondary">Key: <code>{key}</code></small>"#,
+                    key = html_escape::encode_text(key),
+                )?;
+            }
+            writeln!(f, "</td><td>")?;
+            for user_id in &signer.user_ids {
+                writeln!(
+                    f,
+                    "{user_id}<br>",
+                    user_id = html_escape::encode_text(user_id)
+                )?;
+            }
+            writeln!(
+                f,
+                r#"</td>
+                <td class="text-right">{documents}</td>
+                <td>{algorithms}</td>
+                <td>{first}</td>
+                <td>{last}</td>
+                <td>{expiration}</td>
+            </tr>
+"#,
+                documents = Formatted(signer.documents),
+                algorithms = html_escape::encode_text(
+                    &signer
+                        .algorithms
+                        .iter()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                first = Date(signer.first_signature),
+                last = Date(signer.last_signature),
+                expiration = Date(signer.key_expiration),
+            )?;
+        }
+
+        writeln!(f, "</tbody></table>")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "openpgp"))]
+mod test {
+    use super::*;
+    use crate::validate::openpgp::SignatureVerification;
+    use sequoia_openpgp::{
+        Fingerprint,
+        types::{HashAlgorithm, PublicKeyAlgorithm},
+    };
+    use std::time::{Duration, SystemTime};
+
+    fn verification(cert: u8, key: u8, day: u64, relaxed_policy: bool) -> SignatureVerification {
+        SignatureVerification {
+            fingerprint: Fingerprint::from_bytes(4, &[cert; 20]).expect("must be valid"),
+            key_fingerprint: Fingerprint::from_bytes(4, &[key; 20]).expect("must be valid"),
+            user_ids: vec!["<signer@example.com>".to_string()],
+            creation_time: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(day * 86400)),
+            hash_algorithm: HashAlgorithm::SHA256,
+            public_key_algorithm: PublicKeyAlgorithm::EdDSA,
+            key_expiration: None,
+            relaxed_policy,
+        }
+    }
+
+    #[test]
+    fn record() {
+        let mut signers = Signers::default();
+
+        signers.record(None);
+        signers.record(Some(&verification(0xAA, 0xA1, 2, false)));
+        signers.record(Some(&verification(0xAA, 0xA2, 1, true)));
+        signers.record(Some(&verification(0xAA, 0xA1, 3, false)));
+        signers.record(Some(&verification(0xBB, 0xBB, 1, false)));
+
+        assert_eq!(signers.unsigned, 1);
+        assert_eq!(signers.relaxed, 1);
+        assert_eq!(signers.signed(), 4);
+        assert_eq!(signers.signers.len(), 2);
+
+        let signer = &signers.signers[&"AA".repeat(20)];
+        assert_eq!(signer.documents, 3);
+        assert_eq!(
+            signer.keys,
+            BTreeSet::from(["A1".repeat(20), "A2".repeat(20)])
+        );
+        assert_eq!(
+            signer.algorithms,
+            BTreeSet::from(["EdDSA/SHA256".to_string()])
+        );
+        assert_eq!(
+            signer.first_signature,
+            Some(OffsetDateTime::UNIX_EPOCH + time::Duration::days(1))
+        );
+        assert_eq!(
+            signer.last_signature,
+            Some(OffsetDateTime::UNIX_EPOCH + time::Duration::days(3))
+        );
+    }
+
+    #[test]
+    fn render() {
+        let mut signers = Signers::default();
+        signers.record(None);
+        signers.record(Some(&verification(0xAA, 0xA1, 1, true)));
+
+        let html = signers.to_string();
+        assert!(html.contains("1 signed document(s), 1 unsigned document(s)"));
+        assert!(html.contains("1 document(s) only have a valid signature under a relaxed"));
+        assert!(html.contains(&format!("<code>{}</code>", "AA".repeat(20))));
+        assert!(html.contains(&format!("Key: <code>{}</code>", "A1".repeat(20))));
+        assert!(html.contains("&lt;signer@example.com&gt;"));
+        assert!(html.contains("<td>1970-01-02</td>"));
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::ErrorKind;
 use std::path::Path;
@@ -49,6 +50,9 @@ pub struct Record {
     pub warnings: usize,
     /// The total number of warnings
     pub total_warnings: usize,
+    /// The number of findings, by check
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<String, usize>,
 }
 
 pub fn record(path: impl AsRef<Path>, record: Record) -> Result<(), Error> {
@@ -90,6 +94,8 @@ pub struct Statistics {
     pub total_errors: usize,
     pub warnings: usize,
     pub total_warnings: usize,
+    /// The number of findings, by check, if available
+    pub checks: BTreeMap<String, usize>,
 }
 
 /// Update the stats file with a new record, having the timestamp of `now`.
@@ -101,6 +107,7 @@ pub fn record_now(stats_file: Option<&Path>, stats: Statistics) -> Result<(), Er
             total_errors,
             warnings,
             total_warnings,
+            checks,
         } = stats;
 
         record(
@@ -112,6 +119,7 @@ pub fn record_now(stats_file: Option<&Path>, stats: Statistics) -> Result<(), Er
                 total_errors,
                 warnings,
                 total_warnings,
+                checks,
             },
         )?;
     }
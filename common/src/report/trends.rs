@@ -0,0 +1,338 @@
+use crate::report::{Record, ReportStatistics};
+use std::fmt::{Display, Formatter};
+use time::{OffsetDateTime, macros::format_description};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 240.0;
+const LEFT: f64 = 60.0;
+const RIGHT: f64 = 20.0;
+const TOP: f64 = 20.0;
+const BOTTOM: f64 = 30.0;
+
+const AXIS_COLOR: &str = "#adb5bd";
+const COLORS: [&str; 8] = [
+    "#0d6efd", "#dc3545", "#ffc107", "#198754", "#6f42c1", "#fd7e14", "#20c997", "#6c757d",
+];
+
+/// The maximum number of checks shown in the breakdown
+const MAX_CHECKS: usize = COLORS.len();
+
+/// Trend charts of the runs recorded in the statistics, rendered as inline SVG.
+///
+/// Renders nothing unless there are at least two runs.
+pub struct Trends<'a>(pub &'a ReportStatistics);
+
+/// A named series, and the function extracting its values from a record
+type Extract<'a> = (&'a str, &'a dyn Fn(&Record) -> f64);
+
+struct Series {
+    name: String,
+    values: Vec<f64>,
+}
+
+struct Chart<'a> {
+    title: &'a str,
+    /// Values are percentages
+    percent: bool,
+    timestamps: Vec<OffsetDateTime>,
+    series: Vec<Series>,
+}
+
+fn format_date(timestamp: OffsetDateTime) -> String {
+    timestamp
+        .format(&format_description!(
+            "[year]-[month padding:zero]-[day padding:zero]"
+        ))
+        .unwrap_or_else(|_| "Unknown".to_string())
+}
+
+/// Round up to a value with a single significant digit
+fn nice_max(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powi(value.log10().floor() as i32);
+    (value / magnitude).ceil() * magnitude
+}
+
+fn rate(value: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => value as f64 * 100.0 / total as f64,
+    }
+}
+
+impl Chart<'_> {
+    fn format_value(&self, value: f64) -> String {
+        match self.percent {
+            true => format!("{value:.1}%"),
+            false => format!("{value:.0}"),
+        }
+    }
+
+    fn x(&self, index: usize) -> f64 {
+        let width = WIDTH - LEFT - RIGHT;
+        let first = self.timestamps[0].unix_timestamp();
+        let last = self.timestamps[self.timestamps.len() - 1].unix_timestamp();
+
+        if last > first {
+            let time = self.timestamps[index].unix_timestamp();
+            LEFT + width * (time - first) as f64 / (last - first) as f64
+        } else {
+            LEFT + width * index as f64 / (self.timestamps.len() - 1).max(1) as f64
+        }
+    }
+
+    fn y(&self, value: f64, max: f64) -> f64 {
+        HEIGHT - BOTTOM - (HEIGHT - TOP - BOTTOM) * value / max
+    }
+}
+
+impl Display for Chart<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let max = nice_max(
+            self.series
+                .iter()
+                .flat_map(|series| &series.values)
+                .copied()
+                .fold(0.0, f64::max),
+        );
+
+        writeln!(f, "<h3>{}</h3>", html_escape::encode_text(self.title))?;
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {WIDTH} {HEIGHT}" width="100%" role="img" aria-label="{title}" style="max-width: {WIDTH}px;">"#,
+            title = html_escape::encode_quoted_attribute(self.title),
+        )?;
+
+        // grid and axis
+
+        for step in [0.0, 0.5, 1.0] {
+            let y = self.y(max * step, max);
+            writeln!(
+                f,
+                r#"<line x1="{LEFT}" y1="{y}" x2="{x2}" y2="{y}" stroke="{AXIS_COLOR}" stroke-width="{width}"/>"#,
+                x2 = WIDTH - RIGHT,
+                width = if step == 0.0 { 1.0 } else { 0.5 },
+            )?;
+            writeln!(
+                f,
+                r#"<text x="{x}" y="{y}" text-anchor="end" dominant-baseline="middle" font-size="12">{label}</text>"#,
+                x = LEFT - 6.0,
+                label = self.format_value(max * step),
+            )?;
+        }
+
+        let last = self.timestamps.len() - 1;
+        for (index, anchor) in [(0, "start"), (last, "end")] {
+            writeln!(
+                f,
+                r#"<text x="{x}" y="{y}" text-anchor="{anchor}" font-size="12">{label}</text>"#,
+                x = self.x(index),
+                y = HEIGHT - BOTTOM / 3.0,
+                label = format_date(self.timestamps[index]),
+            )?;
+        }
+
+        // series
+
+        for (series, color) in self.series.iter().zip(COLORS.iter().cycle()) {
+            let points = series
+                .values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| format!("{:.1},{:.1}", self.x(index), self.y(*value, max)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                f,
+                r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="2"/>"#
+            )?;
+
+            for (index, value) in series.values.iter().enumerate() {
+                writeln!(
+                    f,
+                    r#"<circle cx="{x:.1}" cy="{y:.1}" r="3" fill="{color}"><title>{name}: {value} ({date})</title></circle>"#,
+                    x = self.x(index),
+                    y = self.y(*value, max),
+                    name = html_escape::encode_text(&series.name),
+                    value = self.format_value(*value),
+                    date = format_date(self.timestamps[index]),
+                )?;
+            }
+        }
+
+        writeln!(f, "</svg>")?;
+
+        // legend
+
+        writeln!(f, r#"<p class="small">"#)?;
+        for (series, color) in self.series.iter().zip(COLORS.iter().cycle()) {
+            writeln!(
+                f,
+                r#"<span class="me-3 text-nowrap"><span style="display: inline-block; width: 0.8em; height: 0.8em; background-color: {color};"></span> {name}</span>"#,
+                name = html_escape::encode_text(&series.name),
+            )?;
+        }
+        writeln!(f, "</p>")?;
+
+        Ok(())
+    }
+}
+
+impl Trends<'_> {
+    fn chart<'a>(
+        title: &'a str,
+        percent: bool,
+        entries: &[Record],
+        series: &[Extract],
+    ) -> Chart<'a> {
+        Chart {
+            title,
+            percent,
+            timestamps: entries.iter().map(|entry| entry.timestamp).collect(),
+            series: series
+                .iter()
+                .map(|(name, value)| Series {
+                    name: name.to_string(),
+                    values: entries.iter().map(value).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The chart of findings by check, starting with the first run recording them
+    fn checks(&self) -> Option<Chart<'static>> {
+        let entries = &self.0.entries;
+        let first = entries.iter().position(|entry| !entry.checks.is_empty())?;
+        let entries = &entries[first..];
+        if entries.len() < 2 {
+            return None;
+        }
+
+        // the checks with the most findings in the latest run
+        let mut checks = entries[entries.len() - 1].checks.iter().collect::<Vec<_>>();
+        checks.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        Some(Chart {
+            title: "Findings by check",
+            percent: false,
+            timestamps: entries.iter().map(|entry| entry.timestamp).collect(),
+            series: checks
+                .into_iter()
+                .take(MAX_CHECKS)
+                .map(|(check, _)| Series {
+                    name: check.clone(),
+                    values: entries
+                        .iter()
+                        .map(|entry| entry.checks.get(check).copied().unwrap_or_default() as f64)
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Display for Trends<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entries = &self.0.entries;
+        if entries.len() < 2 {
+            return Ok(());
+        }
+
+        writeln!(f, "<h2>Trends</h2>")?;
+
+        Self::chart(
+            "Documents",
+            false,
+            entries,
+            &[
+                ("Total", &|entry| entry.total as f64),
+                ("With errors", &|entry| entry.errors as f64),
+                ("With warnings", &|entry| entry.warnings as f64),
+            ],
+        )
+        .fmt(f)?;
+
+        Self::chart(
+            "Errors and warnings",
+            false,
+            entries,
+            &[
+                ("Errors", &|entry| entry.total_errors as f64),
+                ("Warnings", &|entry| entry.total_warnings as f64),
+            ],
+        )
+        .fmt(f)?;
+
+        Self::chart(
+            "Rate of documents with errors or warnings",
+            true,
+            entries,
+            &[
+                ("Errors", &|entry| rate(entry.errors, entry.total)),
+                ("Warnings", &|entry| rate(entry.warnings, entry.total)),
+            ],
+        )
+        .fmt(f)?;
+
+        if let Some(checks) = self.checks() {
+            checks.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+    use time::Duration;
+
+    fn record(day: i64, errors: usize, checks: &[(&str, usize)]) -> Record {
+        Record {
+            timestamp: OffsetDateTime::UNIX_EPOCH + Duration::days(day),
+            total: 10,
+            errors,
+            total_errors: errors * 2,
+            warnings: 0,
+            total_warnings: 0,
+            checks: checks
+                .iter()
+                .map(|(check, count)| (check.to_string(), *count))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn trends() {
+        let mut statistics = ReportStatistics {
+            last_run: OffsetDateTime::UNIX_EPOCH,
+            entries: vec![record(0, 5, &[])],
+        };
+        assert_eq!(Trends(&statistics).to_string(), "");
+
+        statistics.entries.push(record(1, 4, &[("6.1.1", 2)]));
+        let html = Trends(&statistics).to_string();
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.contains("<title>With errors: 5 (1970-01-01)</title>"));
+        assert!(html.contains("<title>Errors: 40.0% (1970-01-02)</title>"));
+
+        statistics
+            .entries
+            .push(record(3, 2, &[("6.1.1", 1), ("<script>", 3)]));
+        let html = Trends(&statistics).to_string();
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn nice() {
+        assert_eq!(nice_max(0.0), 1.0);
+        assert_eq!(nice_max(7.0), 7.0);
+        assert_eq!(nice_max(42.0), 50.0);
+        assert_eq!(nice_max(1234.0), 2000.0);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::USER_AGENT;
 use anyhow::bail;
-use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain};
 use aws_sdk_s3::{
     Client,
     config::{AppName, Credentials},
@@ -213,10 +213,10 @@ impl Source {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct S3 {
-    region: String,
-    credentials: Option<(String, String)>,
-    bucket: String,
-    key: Option<String>,
+    pub(crate) region: String,
+    pub(crate) credentials: Option<(String, String)>,
+    pub(crate) bucket: String,
+    pub(crate) key: Option<String>,
 }
 
 impl TryFrom<&str> for S3 {
@@ -259,20 +259,24 @@ impl TryFrom<&str> for S3 {
 
 impl S3 {
     pub async fn client(&self) -> anyhow::Result<Client> {
+        Ok(Client::new(&self.config().await?))
+    }
+
+    /// The shared AWS configuration, using the region and credentials of the URL
+    pub(crate) async fn config(&self) -> anyhow::Result<SdkConfig> {
         let region_provider = RegionProviderChain::first_try(Region::new(self.region.clone()));
 
         let mut shared_config = aws_config::defaults(BehaviorVersion::v2025_01_17())
             .region(region_provider)
-            .app_name(AppName::new(USER_AGENT)?);
+            // app names must not contain a "/"
+            .app_name(AppName::new(USER_AGENT.replace('/', "-"))?);
 
         if let Some((key_id, access_key)) = &self.credentials {
             let credentials = Credentials::new(key_id, access_key, None, None, "config");
             shared_config = shared_config.credentials_provider(credentials);
         }
 
-        let shared_config = shared_config.load().await;
-
-        Ok(Client::new(&shared_config))
+        Ok(shared_config.load().await)
     }
 }
 
@@ -1,46 +1,63 @@
-use crate::retrieve::{RetrievalMetadata, RetrievedDigest};
+use super::{Document, StoreBackend, StoreError};
 use anyhow::Context;
-use sha2::{Sha256, Sha512};
-use std::path::Path;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
-#[cfg(target_os = "macos")]
-pub const ATTR_ETAG: &str = "etag";
-#[cfg(target_os = "linux")]
-pub const ATTR_ETAG: &str = "user.etag";
-
-#[derive(Debug, thiserror::Error)]
-pub enum StoreError {
-    #[error("{0:#}")]
-    Io(anyhow::Error),
-    #[error("Failed to construct filename from URL: {0}")]
-    Filename(String),
-    #[error("Serialize key error: {0:#}")]
-    SerializeKey(anyhow::Error),
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use super::ATTR_ETAG;
+
+/// Stores documents in the local filesystem.
+///
+/// Additional metadata (like the etag) is stored using extended attributes.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    /// the output base
+    pub base: PathBuf,
 }
 
-pub struct Document<'a> {
-    /// The data to store
-    pub data: &'a [u8],
-    /// An optional SHA256 digest
-    pub sha256: &'a Option<RetrievedDigest<Sha256>>,
-    /// An optional SHA512 digest
-    pub sha512: &'a Option<RetrievedDigest<Sha512>>,
-    /// An optional signature
-    pub signature: &'a Option<String>,
+impl FileStore {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self { base: base.into() }
+    }
+
+    /// The location of a path in the filesystem
+    pub fn path(&self, path: &str) -> PathBuf {
+        self.base.join(path)
+    }
+}
 
-    /// Last change date
-    pub changed: SystemTime,
+impl StoreBackend for FileStore {
+    async fn create_dir(&self, path: &str) -> Result<(), StoreError> {
+        let path = self.path(path);
 
-    /// Metadata from the retrieval process
-    pub metadata: &'a RetrievalMetadata,
+        fs::create_dir_all(&path)
+            .await
+            .with_context(|| format!("Failed to create directory: {}", path.display()))
+            .map_err(StoreError::Io)
+    }
 
-    pub no_timestamps: bool,
-    #[cfg(any(target_os = "linux", target_os = "macos"))]
-    pub no_xattrs: bool,
+    async fn write(&self, path: &str, data: &[u8]) -> Result<(), StoreError> {
+        let file = self.path(path);
+
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create parent directory: {}", parent.display()))
+                .map_err(StoreError::Io)?;
+        }
+
+        fs::write(&file, data)
+            .await
+            .with_context(|| format!("Failed to write file: {}", file.display()))
+            .map_err(StoreError::Io)
+    }
+
+    async fn store_document(&self, path: &str, document: Document<'_>) -> Result<(), StoreError> {
+        store_document(&self.path(path), document).await
+    }
 }
 
+/// Store a document in the filesystem, including its digests, signature, and metadata.
 pub async fn store_document(file: &Path, document: Document<'_>) -> Result<(), StoreError> {
     log::debug!("Writing {}", file.display());
 
@@ -79,14 +96,7 @@ pub async fn store_document(file: &Path, document: Document<'_>) -> Result<(), S
     }
 
     if !document.no_timestamps {
-        // We use the retrieval metadata timestamp as file timestamp. If that's not available, then
-        // we use the change entry timestamp.
-        let mtime = document
-            .metadata
-            .last_modification
-            .map(SystemTime::from)
-            .unwrap_or_else(|| document.changed)
-            .into();
+        let mtime = document.timestamp().into();
         filetime::set_file_mtime(file, mtime)
             .with_context(|| {
                 format!(
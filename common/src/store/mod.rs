@@ -0,0 +1,173 @@
+//! Storing documents, using different backends
+
+mod fs;
+mod s3;
+
+pub use fs::*;
+pub use s3::*;
+
+use crate::retrieve::{RetrievalMetadata, RetrievedDigest};
+use sha2::{Sha256, Sha512};
+use std::time::SystemTime;
+
+#[cfg(target_os = "macos")]
+pub const ATTR_ETAG: &str = "etag";
+#[cfg(target_os = "linux")]
+pub const ATTR_ETAG: &str = "user.etag";
+
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error("{0:#}")]
+    Io(anyhow::Error),
+    #[error("Failed to construct filename from URL: {0}")]
+    Filename(String),
+    #[error("Serialize key error: {0:#}")]
+    SerializeKey(anyhow::Error),
+    #[error("{0:#}")]
+    Backend(anyhow::Error),
+}
+
+pub struct Document<'a> {
+    /// The data to store
+    pub data: &'a [u8],
+    /// An optional SHA256 digest
+    pub sha256: &'a Option<RetrievedDigest<Sha256>>,
+    /// An optional SHA512 digest
+    pub sha512: &'a Option<RetrievedDigest<Sha512>>,
+    /// An optional signature
+    pub signature: &'a Option<String>,
+
+    /// Last change date
+    pub changed: SystemTime,
+
+    /// Metadata from the retrieval process
+    pub metadata: &'a RetrievalMetadata,
+
+    pub no_timestamps: bool,
+    /// Don't store metadata using extended attributes
+    ///
+    /// This is ignored by backends which don't use extended attributes, like S3.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub no_xattrs: bool,
+}
+
+impl Document<'_> {
+    /// The timestamp of the document.
+    ///
+    /// We use the retrieval metadata timestamp. If that's not available, then we use the change
+    /// entry timestamp.
+    pub fn timestamp(&self) -> SystemTime {
+        self.metadata
+            .last_modification
+            .map(SystemTime::from)
+            .unwrap_or(self.changed)
+    }
+}
+
+/// A backend for storing documents, along with their metadata.
+///
+/// Paths are relative to the root of the store, using `/` as a separator. Digests and signatures
+/// are stored next to the document, using the extensions `.sha256`, `.sha512`, and `.asc`.
+pub trait StoreBackend {
+    /// Create a directory, including its parents.
+    ///
+    /// Backends without the concept of directories may ignore this.
+    fn create_dir(&self, path: &str) -> impl Future<Output = Result<(), StoreError>>;
+
+    /// Write a file, creating its parent directories if necessary.
+    fn write(&self, path: &str, data: &[u8]) -> impl Future<Output = Result<(), StoreError>>;
+
+    /// Store a document, including its digests, signature, and metadata.
+    fn store_document(
+        &self,
+        path: &str,
+        document: Document<'_>,
+    ) -> impl Future<Output = Result<(), StoreError>>;
+}
+
+/// A common store backend, dispatching to the known implementations.
+#[derive(Clone, Debug)]
+pub enum DispatchStore {
+    File(FileStore),
+    S3(S3Store),
+}
+
+impl From<FileStore> for DispatchStore {
+    fn from(value: FileStore) -> Self {
+        Self::File(value)
+    }
+}
+
+impl From<S3Store> for DispatchStore {
+    fn from(value: S3Store) -> Self {
+        Self::S3(value)
+    }
+}
+
+impl StoreBackend for DispatchStore {
+    async fn create_dir(&self, path: &str) -> Result<(), StoreError> {
+        match self {
+            Self::File(store) => store.create_dir(path).await,
+            Self::S3(store) => store.create_dir(path).await,
+        }
+    }
+
+    async fn write(&self, path: &str, data: &[u8]) -> Result<(), StoreError> {
+        match self {
+            Self::File(store) => store.write(path, data).await,
+            Self::S3(store) => store.write(path, data).await,
+        }
+    }
+
+    async fn store_document(&self, path: &str, document: Document<'_>) -> Result<(), StoreError> {
+        match self {
+            Self::File(store) => store.store_document(path, document).await,
+            Self::S3(store) => store.store_document(path, document).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn dispatch_file() {
+        let base = std::env::temp_dir().join(format!("walker-dispatch-{}", std::process::id()));
+        let store = DispatchStore::from(FileStore::new(&base));
+
+        store
+            .store_document(
+                "dist/2023/cve.json",
+                Document {
+                    data: b"{}",
+                    sha256: &None,
+                    sha512: &None,
+                    signature: &Some("signature".to_string()),
+                    changed: SystemTime::UNIX_EPOCH,
+                    metadata: &RetrievalMetadata {
+                        last_modification: None,
+                        etag: None,
+                    },
+                    no_timestamps: true,
+                    #[cfg(any(target_os = "linux", target_os = "macos"))]
+                    no_xattrs: true,
+                },
+            )
+            .await
+            .expect("must store");
+        store
+            .write("dist/changes.csv", b"2023/cve.json")
+            .await
+            .expect("must write");
+
+        let document = std::fs::read(base.join("dist/2023/cve.json"));
+        let signature = std::fs::read(base.join("dist/2023/cve.json.asc"));
+        let changes = std::fs::read(base.join("dist/changes.csv"));
+        let _ = std::fs::remove_dir_all(&base);
+
+        assert_eq!(document.expect("must read document"), b"{}");
+        assert_eq!(signature.expect("must read signature"), b"signature");
+        assert_eq!(changes.expect("must read changes"), b"2023/cve.json");
+    }
+}
@@ -0,0 +1,323 @@
+use super::{Document, StoreBackend, StoreError};
+use crate::scoop::S3;
+use anyhow::Context;
+use aws_sdk_s3::{Client, primitives::ByteStream};
+use std::collections::HashMap;
+
+/// The object metadata key of the etag
+pub const META_ETAG: &str = "etag";
+/// The object metadata key of the last modification timestamp (RFC 3339)
+pub const META_LAST_MODIFIED: &str = "last-modified";
+
+/// Options for connecting to an S3 compatible object store.
+#[derive(Clone, Debug, Default)]
+pub struct S3Options {
+    /// A custom endpoint, like `http://localhost:9000` for a local MinIO instance
+    pub endpoint: Option<String>,
+    /// Address buckets by path (`<endpoint>/<bucket>`) instead of by virtual host, as required by MinIO
+    pub force_path_style: bool,
+}
+
+impl S3Options {
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    pub fn force_path_style(mut self, force_path_style: bool) -> Self {
+        self.force_path_style = force_path_style;
+        self
+    }
+}
+
+/// Stores documents in an S3 compatible object store.
+///
+/// Additional metadata (like the etag) is stored as object metadata. As extended attributes are
+/// not used, [`Document::no_xattrs`] is ignored.
+#[derive(Clone, Debug)]
+pub struct S3Store {
+    client: Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3Store {
+    pub fn new(client: Client, bucket: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix: String::new(),
+        }
+    }
+
+    /// Create a store from a URL like `s3://[<key>:<secret>@]<region>/<bucket>[/<prefix>]`.
+    pub async fn from_url(url: &str, options: S3Options) -> anyhow::Result<Self> {
+        let s3 = S3::try_from(url)?;
+
+        let mut config = aws_sdk_s3::config::Builder::from(&s3.config().await?)
+            .force_path_style(options.force_path_style);
+        config.set_endpoint_url(options.endpoint);
+
+        Ok(Self::new(Client::from_conf(config.build()), s3.bucket)
+            .prefix(s3.key.unwrap_or_default()))
+    }
+
+    /// Set a prefix for all object keys.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().trim_matches('/').to_string();
+        self
+    }
+
+    /// The object key of a path
+    pub fn key(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        match self.prefix.is_empty() {
+            true => path.to_string(),
+            false => format!("{}/{path}", self.prefix),
+        }
+    }
+
+    async fn put(
+        &self,
+        path: &str,
+        data: impl Into<ByteStream>,
+        metadata: HashMap<String, String>,
+    ) -> Result<(), StoreError> {
+        let key = self.key(path);
+        log::debug!("Writing s3://{}/{key}", self.bucket);
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .set_metadata((!metadata.is_empty()).then_some(metadata))
+            .body(data.into())
+            .send()
+            .await
+            .with_context(|| format!("Failed to write object: s3://{}/{key}", self.bucket))
+            .map_err(StoreError::Backend)?;
+
+        Ok(())
+    }
+}
+
+impl StoreBackend for S3Store {
+    async fn create_dir(&self, _path: &str) -> Result<(), StoreError> {
+        // object stores don't have directories
+        Ok(())
+    }
+
+    async fn write(&self, path: &str, data: &[u8]) -> Result<(), StoreError> {
+        self.put(path, data.to_vec(), Default::default()).await
+    }
+
+    async fn store_document(&self, path: &str, document: Document<'_>) -> Result<(), StoreError> {
+        let mut metadata = HashMap::new();
+        if !document.no_timestamps {
+            metadata.insert(
+                META_LAST_MODIFIED.to_string(),
+                humantime::format_rfc3339(document.timestamp()).to_string(),
+            );
+        }
+        if let Some(etag) = &document.metadata.etag {
+            metadata.insert(META_ETAG.to_string(), etag.clone());
+        }
+
+        self.put(path, document.data.to_vec(), metadata).await?;
+
+        if let Some(sha256) = &document.sha256 {
+            self.write(&format!("{path}.sha256"), sha256.expected.as_bytes())
+                .await?;
+        }
+        if let Some(sha512) = &document.sha512 {
+            self.write(&format!("{path}.sha512"), sha512.expected.as_bytes())
+                .await?;
+        }
+        if let Some(signature) = &document.signature {
+            self.write(&format!("{path}.asc"), signature.as_bytes())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::retrieve::{RetrievalMetadata, RetrievedDigest};
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region, RequestChecksumCalculation};
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime},
+    };
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    #[derive(Debug)]
+    struct Request {
+        method: String,
+        path: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    /// A minimal stand-in for an S3 server, accepting all requests and recording them
+    async fn stand_in() -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("must bind");
+        let endpoint = format!(
+            "http://{}",
+            listener.local_addr().expect("must have address")
+        );
+        let requests: Arc<Mutex<Vec<Request>>> = Default::default();
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    loop {
+                        let mut line = String::new();
+                        if stream.read_line(&mut line).await.unwrap_or_default() == 0 {
+                            return;
+                        }
+                        let mut parts = line.split_whitespace();
+                        let method = parts.next().unwrap_or_default().to_string();
+                        let path = parts.next().unwrap_or_default().to_string();
+
+                        let mut headers = HashMap::new();
+                        loop {
+                            let mut line = String::new();
+                            stream.read_line(&mut line).await.expect("must read header");
+                            match line.trim_end().split_once(':') {
+                                Some((name, value)) => {
+                                    headers.insert(name.to_lowercase(), value.trim().to_string());
+                                }
+                                None => break,
+                            }
+                        }
+
+                        let length = headers
+                            .get("content-length")
+                            .and_then(|length| length.parse().ok())
+                            .unwrap_or(0);
+                        let mut body = vec![0; length];
+                        stream.read_exact(&mut body).await.expect("must read body");
+
+                        recorded.lock().expect("must lock").push(Request {
+                            method,
+                            path,
+                            headers,
+                            body,
+                        });
+
+                        stream
+                            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                            .await
+                            .expect("must write response");
+                    }
+                });
+            }
+        });
+
+        (endpoint, requests)
+    }
+
+    #[tokio::test]
+    async fn store_document() {
+        let (endpoint, requests) = stand_in().await;
+
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("key", "secret", None, None, "test"))
+            .endpoint_url(endpoint)
+            .force_path_style(true)
+            .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+            .build();
+        let store = S3Store::new(Client::from_conf(config), "bucket").prefix("/csaf/");
+
+        let metadata = RetrievalMetadata {
+            last_modification: None,
+            etag: Some(r#""1234""#.to_string()),
+        };
+        let sha256 = Some(RetrievedDigest {
+            expected: "abcd".to_string(),
+            actual: Default::default(),
+        });
+
+        store
+            .store_document(
+                "dist/2023/cve.json",
+                Document {
+                    data: b"{}",
+                    sha256: &sha256,
+                    sha512: &None,
+                    signature: &Some("signature".to_string()),
+                    changed: SystemTime::UNIX_EPOCH + Duration::from_secs(86400),
+                    metadata: &metadata,
+                    no_timestamps: false,
+                    #[cfg(any(target_os = "linux", target_os = "macos"))]
+                    no_xattrs: false,
+                },
+            )
+            .await
+            .expect("must store");
+
+        let requests = requests.lock().expect("must lock");
+        let requests = requests
+            .iter()
+            .map(|request| (request.path.as_str(), request))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(requests.len(), 3);
+
+        let document = requests["/bucket/csaf/dist/2023/cve.json?x-id=PutObject"];
+        assert_eq!(document.method, "PUT");
+        assert_eq!(document.body, b"{}");
+        assert_eq!(document.headers["x-amz-meta-etag"], r#""1234""#);
+        assert_eq!(
+            document.headers["x-amz-meta-last-modified"],
+            "1970-01-02T00:00:00Z"
+        );
+
+        let digest = requests["/bucket/csaf/dist/2023/cve.json.sha256?x-id=PutObject"];
+        assert_eq!(digest.body, b"abcd");
+        assert!(!digest.headers.contains_key("x-amz-meta-etag"));
+
+        let signature = requests["/bucket/csaf/dist/2023/cve.json.asc?x-id=PutObject"];
+        assert_eq!(signature.body, b"signature");
+    }
+
+    #[tokio::test]
+    async fn from_url() {
+        let (endpoint, requests) = stand_in().await;
+
+        let store = S3Store::from_url(
+            "s3://key:secret@us-east-1/bucket/csaf",
+            S3Options::default()
+                .endpoint(endpoint)
+                .force_path_style(true),
+        )
+        .await
+        .expect("must create store");
+
+        store
+            .write("dist/changes.csv", b"")
+            .await
+            .expect("must write");
+
+        let requests = requests.lock().expect("must lock");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(
+            requests[0].path,
+            "/bucket/csaf/dist/changes.csv?x-id=PutObject"
+        );
+    }
+}
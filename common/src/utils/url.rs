@@ -45,3 +45,39 @@ where
         }
     }
 }
+
+/// Get the name of the provider from the source provided by the user.
+///
+/// This is the host of a URL, or a bare domain (like `redhat.com`). Local sources (like `file:`
+/// URLs or paths) don't have a provider name.
+///
+/// The name is used as the key of the provider in the trust store, as well as in the per-provider
+/// sections of configuration files.
+pub fn provider_name(source: &str) -> Option<String> {
+    match Url::parse(source) {
+        Ok(url) => url.host_str().map(ToString::to_string),
+        Err(_) if !source.is_empty() && !source.contains(['/', '\\']) => {
+            Some(source.to_lowercase())
+        }
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn provider_names() {
+        assert_eq!(provider_name("redhat.com").as_deref(), Some("redhat.com"));
+        assert_eq!(provider_name("RedHat.com").as_deref(), Some("redhat.com"));
+        assert_eq!(
+            provider_name("https://security.access.redhat.com/data/csaf/v2/provider-metadata.json")
+                .as_deref(),
+            Some("security.access.redhat.com")
+        );
+        assert_eq!(provider_name("file:/data/csaf"), None);
+        assert_eq!(provider_name("data/csaf"), None);
+        assert_eq!(provider_name(""), None);
+    }
+}
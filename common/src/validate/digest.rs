@@ -10,3 +10,32 @@ pub fn validate_digest<D: Digest>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sha2::Sha256;
+
+    fn digest(expected: &str, data: &[u8]) -> Option<RetrievedDigest<Sha256>> {
+        Some(RetrievedDigest {
+            expected: expected.to_string(),
+            actual: Sha256::digest(data),
+        })
+    }
+
+    #[test]
+    fn validate() {
+        // "foo"
+        let expected = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+
+        assert_eq!(validate_digest::<Sha256>(&None), Ok(()));
+        assert_eq!(validate_digest(&digest(expected, b"foo")), Ok(()));
+        assert_eq!(
+            validate_digest(&digest(expected, b"bar")),
+            Err((
+                expected.to_string(),
+                "fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9".to_string()
+            ))
+        );
+    }
+}
@@ -2,7 +2,9 @@ use crate::{
     retrieve::{RetrievalError, RetrievedDocument},
     source::Source,
     utils::url::Urlify,
+    validate::RequiredDigest,
 };
+use sequoia_openpgp::Fingerprint;
 use std::fmt::{Debug, Display, Formatter};
 use url::Url;
 
@@ -25,6 +27,18 @@ where
         error: anyhow::Error,
         retrieved: S::Retrieved,
     },
+    /// A digest required by the policy is missing
+    MissingDigest {
+        required: RequiredDigest,
+        retrieved: S::Retrieved,
+    },
+    /// A signature is required by the policy, but missing
+    MissingSignature { retrieved: S::Retrieved },
+    /// The document was signed by a certificate which is not allowed by the policy
+    UntrustedSigner {
+        signer: Fingerprint,
+        retrieved: S::Retrieved,
+    },
 }
 
 impl<S> Urlify for ValidationError<S>
@@ -36,6 +50,9 @@ where
             Self::Retrieval(err) => err.url(),
             Self::DigestMismatch { retrieved, .. } => retrieved.url(),
             Self::Signature { retrieved, .. } => retrieved.url(),
+            Self::MissingDigest { retrieved, .. } => retrieved.url(),
+            Self::MissingSignature { retrieved } => retrieved.url(),
+            Self::UntrustedSigner { retrieved, .. } => retrieved.url(),
         }
     }
 }
@@ -59,6 +76,23 @@ where
             Self::Signature { error, retrieved } => {
                 write!(f, "Invalid signature: {error} ({})", retrieved.url())
             }
+            Self::MissingDigest {
+                required,
+                retrieved,
+            } => write!(
+                f,
+                "Missing digest - required: {required} ({})",
+                retrieved.url()
+            ),
+            Self::MissingSignature { retrieved } => {
+                write!(f, "Missing signature ({})", retrieved.url())
+            }
+            Self::UntrustedSigner { signer, retrieved } => write!(
+                f,
+                "Untrusted signer - fingerprint: {} ({})",
+                signer.to_hex(),
+                retrieved.url()
+            ),
         }
     }
 }
@@ -4,8 +4,20 @@ pub use error::*;
 
 pub mod digest;
 pub mod openpgp;
+pub mod policy;
+pub mod rotation;
 pub mod source;
+pub mod trust;
 
+#[doc(hidden)]
+#[cfg(any(test, feature = "_test"))]
+pub mod testing;
+
+use policy::PolicyConfig;
+use sequoia_openpgp::Fingerprint;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 use std::time::SystemTime;
 
 #[non_exhaustive]
@@ -13,6 +25,20 @@ use std::time::SystemTime;
 pub struct ValidationOptions {
     /// time for policy checks
     pub validation_date: Option<SystemTime>,
+    /// require at least one digest (SHA-256 or SHA-512)
+    pub require_digest: bool,
+    /// require a SHA-512 digest
+    pub require_sha512: bool,
+    /// require a signature
+    pub require_signature: bool,
+    /// fingerprints of certificates which are allowed to sign documents, empty means all
+    pub allowed_signers: Vec<Fingerprint>,
+    /// adjustments to the standard OpenPGP policy
+    pub policy: PolicyConfig,
+    /// adjustments to the OpenPGP policy, by provider, applied on top of `policy`
+    pub provider_policies: BTreeMap<String, PolicyConfig>,
+    /// the name of the provider, selecting the adjustments of `provider_policies`
+    pub provider: Option<String>,
 }
 
 impl ValidationOptions {
@@ -24,4 +50,181 @@ impl ValidationOptions {
         self.validation_date = validation_date.into();
         self
     }
+
+    pub fn require_digest(mut self, require_digest: bool) -> Self {
+        self.require_digest = require_digest;
+        self
+    }
+
+    pub fn require_sha512(mut self, require_sha512: bool) -> Self {
+        self.require_sha512 = require_sha512;
+        self
+    }
+
+    pub fn require_signature(mut self, require_signature: bool) -> Self {
+        self.require_signature = require_signature;
+        self
+    }
+
+    pub fn allowed_signers<I>(mut self, allowed_signers: I) -> Self
+    where
+        I: IntoIterator<Item = Fingerprint>,
+    {
+        self.allowed_signers = allowed_signers.into_iter().collect();
+        self
+    }
+
+    pub fn policy(mut self, policy: PolicyConfig) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn provider_policies(mut self, provider_policies: BTreeMap<String, PolicyConfig>) -> Self {
+        self.provider_policies = provider_policies;
+        self
+    }
+
+    /// Set the name of the provider, see [`crate::utils::url::provider_name`].
+    pub fn provider(mut self, provider: impl Into<Option<String>>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
+    /// Get the effective options, applying the policy overrides of the provider (if any).
+    pub fn effective(&self) -> Cow<'_, Self> {
+        match self
+            .provider
+            .as_ref()
+            .and_then(|provider| self.provider_policies.get(provider))
+        {
+            Some(overrides) => {
+                let mut options = self.clone();
+                options.policy.merge(overrides);
+                Cow::Owned(options)
+            }
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// Check if the OpenPGP policy deviates from the standard policy.
+    pub fn is_custom_policy(&self) -> bool {
+        self.validation_date.is_some() || !self.policy.is_empty()
+    }
+
+    /// Check if the presence of digests satisfies the policy.
+    pub fn check_digests(&self, sha256: bool, sha512: bool) -> Result<(), RequiredDigest> {
+        if self.require_sha512 && !sha512 {
+            return Err(RequiredDigest::Sha512);
+        }
+        if self.require_digest && !(sha256 || sha512) {
+            return Err(RequiredDigest::Any);
+        }
+
+        Ok(())
+    }
+
+    /// Check if the signer is allowed by the policy.
+    pub fn is_allowed_signer(&self, signer: &Fingerprint) -> bool {
+        self.allowed_signers.is_empty() || self.allowed_signers.contains(signer)
+    }
+}
+
+/// The digest required by the validation policy
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RequiredDigest {
+    /// Any digest (SHA-256 or SHA-512)
+    Any,
+    /// A SHA-512 digest
+    Sha512,
+}
+
+impl Display for RequiredDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => f.write_str("SHA-256 or SHA-512"),
+            Self::Sha512 => f.write_str("SHA-512"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn digests_not_required() {
+        let options = ValidationOptions::new();
+        assert_eq!(options.check_digests(false, false), Ok(()));
+        assert_eq!(options.check_digests(true, false), Ok(()));
+        assert_eq!(options.check_digests(false, true), Ok(()));
+    }
+
+    #[test]
+    fn require_digest() {
+        let options = ValidationOptions::new().require_digest(true);
+        assert_eq!(
+            options.check_digests(false, false),
+            Err(RequiredDigest::Any)
+        );
+        assert_eq!(options.check_digests(true, false), Ok(()));
+        assert_eq!(options.check_digests(false, true), Ok(()));
+        assert_eq!(options.check_digests(true, true), Ok(()));
+    }
+
+    #[test]
+    fn require_sha512() {
+        let options = ValidationOptions::new().require_sha512(true);
+        assert_eq!(
+            options.check_digests(false, false),
+            Err(RequiredDigest::Sha512)
+        );
+        assert_eq!(
+            options.check_digests(true, false),
+            Err(RequiredDigest::Sha512)
+        );
+        assert_eq!(options.check_digests(false, true), Ok(()));
+
+        // the more specific requirement wins
+        let options = options.require_digest(true);
+        assert_eq!(
+            options.check_digests(false, false),
+            Err(RequiredDigest::Sha512)
+        );
+    }
+
+    #[test]
+    fn allowed_signers() {
+        let a = Fingerprint::from_bytes(4, &[0xAA; 20]).expect("must be a valid fingerprint");
+        let b = Fingerprint::from_bytes(4, &[0xBB; 20]).expect("must be a valid fingerprint");
+
+        let options = ValidationOptions::new();
+        assert!(options.is_allowed_signer(&a));
+        assert!(options.is_allowed_signer(&b));
+
+        let options = options.allowed_signers([a.clone()]);
+        assert!(options.is_allowed_signer(&a));
+        assert!(!options.is_allowed_signer(&b));
+    }
+
+    #[test]
+    fn effective() {
+        let overrides = PolicyConfig {
+            min_rsa_bits: Some(3072),
+            ..Default::default()
+        };
+        let options = ValidationOptions::new()
+            .policy(PolicyConfig {
+                min_rsa_bits: Some(2048),
+                ..Default::default()
+            })
+            .provider_policies([("example.com".to_string(), overrides)].into());
+
+        assert_eq!(options.effective().policy.min_rsa_bits, Some(2048));
+
+        let options = options.provider("other.com".to_string());
+        assert_eq!(options.effective().policy.min_rsa_bits, Some(2048));
+
+        let options = options.provider("example.com".to_string());
+        assert_eq!(options.effective().policy.min_rsa_bits, Some(3072));
+    }
 }
@@ -3,20 +3,42 @@ use crate::utils::openpgp::PublicKey;
 use crate::validate::ValidationOptions;
 use anyhow::bail;
 use sequoia_openpgp::{
-    Cert, KeyHandle, Packet,
-    cert::prelude::ValidErasedKeyAmalgamation,
+    Cert, Fingerprint, KeyHandle, Packet,
+    cert::{amalgamation::ValidAmalgamation, prelude::ValidErasedKeyAmalgamation},
     packet::{Signature, key::PublicParts},
     parse::{
         Parse,
         stream::{DetachedVerifierBuilder, MessageLayer, MessageStructure, VerificationHelper},
     },
     policy::{HashAlgoSecurity, Policy, StandardPolicy},
-    types::{AEADAlgorithm, SymmetricAlgorithm},
+    types::{AEADAlgorithm, HashAlgorithm, PublicKeyAlgorithm, SymmetricAlgorithm},
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, time::SystemTime};
+
+/// The outcome of a successful signature verification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureVerification {
+    /// The fingerprint of the certificate which signed the document
+    pub fingerprint: Fingerprint,
+    /// The fingerprint of the (sub)key which created the signature
+    pub key_fingerprint: Fingerprint,
+    /// The (valid) user IDs of the certificate
+    pub user_ids: Vec<String>,
+    /// The creation time of the signature
+    pub creation_time: Option<SystemTime>,
+    /// The hash algorithm used by the signature
+    pub hash_algorithm: HashAlgorithm,
+    /// The public key algorithm used by the signature
+    pub public_key_algorithm: PublicKeyAlgorithm,
+    /// The expiration time of the signing key
+    pub key_expiration: Option<SystemTime>,
+    /// The signature is only valid under the configured (relaxed) policy, not the standard policy
+    pub relaxed_policy: bool,
+}
 
 struct Helper<'a> {
     keys: &'a [PublicKey],
+    verification: Option<SignatureVerification>,
 }
 
 impl VerificationHelper for Helper<'_> {
@@ -32,7 +54,24 @@ impl VerificationHelper for Helper<'_> {
 
             match (i, layer) {
                 (0, MessageLayer::SignatureGroup { results }) => match results.into_iter().next() {
-                    Some(Ok(_)) => good = true,
+                    Some(Ok(checksum)) => {
+                        let ka = checksum.ka;
+                        self.verification = Some(SignatureVerification {
+                            fingerprint: ka.cert().fingerprint(),
+                            key_fingerprint: ka.key().fingerprint(),
+                            user_ids: ka
+                                .valid_cert()
+                                .userids()
+                                .map(|uid| String::from_utf8_lossy(uid.userid().value()).into())
+                                .collect(),
+                            creation_time: checksum.sig.signature_creation_time(),
+                            hash_algorithm: checksum.sig.hash_algo(),
+                            public_key_algorithm: checksum.sig.pk_algo(),
+                            key_expiration: ka.key_expiration_time(),
+                            relaxed_policy: false,
+                        });
+                        good = true;
+                    }
                     Some(Err(err)) => {
                         return Err(sequoia_openpgp::Error::from(err).into());
                     }
@@ -85,25 +124,130 @@ impl Policy for LoggingPolicy<'_> {
     }
 }
 
+/// Validate a detached signature, returning information about the signer.
+///
+/// If the options deviate from the standard policy, the signature is also checked against the
+/// standard policy, flagging signatures which are only valid under the relaxed policy.
 pub fn validate_signature(
     options: &ValidationOptions,
     keys: &[PublicKey],
     signature: &str,
     data: impl AsRef<[u8]>,
-) -> Result<(), anyhow::Error> {
+) -> Result<SignatureVerification, anyhow::Error> {
+    let data = data.as_ref();
+
     // TODO: we could move this into the context and re-use
-    let policy = match options.validation_date {
+    let mut policy = match options.validation_date {
         Some(time) => StandardPolicy::at(time),
         None => StandardPolicy::new(),
     };
-    let policy = LoggingPolicy(policy);
+    options.policy.apply(&mut policy);
+
+    let mut verification = verify(&LoggingPolicy(policy), keys, signature, data)?;
+
+    if options.is_custom_policy() && verify(&StandardPolicy::new(), keys, signature, data).is_err()
+    {
+        log::debug!("Signature is only valid under relaxed policy");
+        verification.relaxed_policy = true;
+    }
+
+    Ok(verification)
+}
+
+fn verify(
+    policy: &dyn Policy,
+    keys: &[PublicKey],
+    signature: &str,
+    data: &[u8],
+) -> Result<SignatureVerification, anyhow::Error> {
     let mut verifier = DetachedVerifierBuilder::from_bytes(&signature)?.with_policy(
-        &policy,
+        policy,
         None,
-        Helper { keys },
+        Helper {
+            keys,
+            verification: None,
+        },
     )?;
 
     verifier.verify_bytes(data)?;
 
-    Ok(())
+    match verifier.into_helper().verification {
+        Some(verification) => Ok(verification),
+        None => bail!("Signature verification failed"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::validate::{
+        policy::{AlgorithmPolicy, PolicyConfig},
+        testing,
+    };
+
+    const DATA: &[u8] = b"{}";
+
+    fn cert() -> Cert {
+        testing::cert("Signer <signer@example.com>", None)
+    }
+
+    fn sign(cert: &Cert, hash: HashAlgorithm) -> String {
+        testing::sign(cert, hash, DATA)
+    }
+
+    fn keys(cert: &Cert) -> Vec<PublicKey> {
+        vec![PublicKey {
+            certs: vec![cert.clone()],
+            raw: Default::default(),
+        }]
+    }
+
+    #[test]
+    fn verification() {
+        let cert = cert();
+        let signature = sign(&cert, HashAlgorithm::SHA256);
+
+        let verification = validate_signature(&Default::default(), &keys(&cert), &signature, DATA)
+            .expect("must be valid");
+
+        assert_eq!(verification.fingerprint, cert.fingerprint());
+        assert_ne!(verification.key_fingerprint, cert.fingerprint());
+        assert!(
+            cert.keys()
+                .any(|key| key.key().fingerprint() == verification.key_fingerprint)
+        );
+        assert_eq!(
+            verification.user_ids,
+            vec!["Signer <signer@example.com>".to_string()]
+        );
+        assert!(verification.creation_time.is_some());
+        assert_eq!(verification.hash_algorithm, HashAlgorithm::SHA256);
+        assert_eq!(
+            verification.public_key_algorithm,
+            cert.primary_key().key().pk_algo()
+        );
+        assert!(!verification.relaxed_policy);
+
+        // data doesn't match
+        assert!(validate_signature(&Default::default(), &keys(&cert), &signature, b"[]").is_err());
+        // unknown key
+        assert!(validate_signature(&Default::default(), &[], &signature, DATA).is_err());
+    }
+
+    #[test]
+    fn relaxed_policy() {
+        let cert = cert();
+        let signature = sign(&cert, HashAlgorithm::SHA1);
+
+        assert!(validate_signature(&Default::default(), &keys(&cert), &signature, DATA).is_err());
+
+        let options = ValidationOptions::new().policy(PolicyConfig {
+            hash_algorithms: vec![(HashAlgorithm::SHA1, AlgorithmPolicy::Accept)],
+            ..Default::default()
+        });
+        let verification = validate_signature(&options, &keys(&cert), &signature, DATA)
+            .expect("must be valid under the relaxed policy");
+        assert_eq!(verification.hash_algorithm, HashAlgorithm::SHA1);
+        assert!(verification.relaxed_policy);
+    }
 }
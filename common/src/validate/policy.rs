@@ -0,0 +1,359 @@
+//! Configurable OpenPGP policy.
+//!
+//! Allows to deviate from the [`StandardPolicy`], e.g. to accept SHA-1 signatures of legacy
+//! providers, or to require stronger keys.
+
+use anyhow::Context;
+use sequoia_openpgp::{
+    policy::{AsymmetricAlgorithm, StandardPolicy},
+    types::HashAlgorithm,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
+    time::SystemTime,
+};
+
+/// How the policy treats an algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlgorithmPolicy {
+    /// Accept the algorithm
+    Accept,
+    /// Reject the algorithm
+    Reject,
+    /// Reject the algorithm for signatures created after the cutoff date
+    RejectAfter(SystemTime),
+}
+
+impl FromStr for AlgorithmPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "accept" => Ok(Self::Accept),
+            "reject" => Ok(Self::Reject),
+            // a plain date, e.g. 2023-01-31
+            date if date.len() == 10 => humantime::parse_rfc3339_weak(&format!("{date}T00:00:00"))
+                .map(Self::RejectAfter)
+                .map_err(|err| format!("invalid cutoff date '{date}': {err}")),
+            date => humantime::parse_rfc3339_weak(date)
+                .map(Self::RejectAfter)
+                .map_err(|err| {
+                    format!("expected 'accept', 'reject', or a cutoff date, found '{date}': {err}")
+                }),
+        }
+    }
+}
+
+impl Display for AlgorithmPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Accept => f.write_str("accept"),
+            Self::Reject => f.write_str("reject"),
+            Self::RejectAfter(time) => write!(f, "{}", humantime::format_rfc3339_seconds(*time)),
+        }
+    }
+}
+
+impl Serialize for AlgorithmPolicy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AlgorithmPolicy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Adjustments to the [`StandardPolicy`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyConfig {
+    /// Policy for hash algorithms, by name (e.g. `SHA1`), later entries win
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "by_name")]
+    pub hash_algorithms: Vec<(HashAlgorithm, AlgorithmPolicy)>,
+
+    /// Policy for public key algorithms, by name (e.g. `RSA1024`, `DSA2048`, `NistP256`), later entries win
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "by_name")]
+    pub asymmetric_algorithms: Vec<(AsymmetricAlgorithm, AlgorithmPolicy)>,
+
+    /// Reject RSA keys smaller than this size (in bits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rsa_bits: Option<u32>,
+}
+
+impl PolicyConfig {
+    /// Check if this configuration keeps the standard policy unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.hash_algorithms.is_empty()
+            && self.asymmetric_algorithms.is_empty()
+            && self.min_rsa_bits.is_none()
+    }
+
+    /// Merge another configuration on top of this one, entries of the other configuration win.
+    ///
+    /// A minimum RSA key size of the other configuration drops the entries of this configuration
+    /// for the RSA key sizes it rejects, as explicit entries would otherwise take precedence.
+    pub fn merge(&mut self, other: &PolicyConfig) {
+        if let Some(min) = other.min_rsa_bits {
+            self.asymmetric_algorithms
+                .retain(|(algo, _)| rsa_bits(*algo).is_none_or(|bits| bits >= min));
+            self.min_rsa_bits = Some(min);
+        }
+        self.hash_algorithms.extend(&other.hash_algorithms);
+        self.asymmetric_algorithms
+            .extend(&other.asymmetric_algorithms);
+    }
+
+    /// Apply the configuration to a policy.
+    ///
+    /// Explicit entries for public key algorithms take precedence over the minimum RSA key size.
+    pub fn apply(&self, policy: &mut StandardPolicy) {
+        if let Some(min) = self.min_rsa_bits {
+            for algo in AsymmetricAlgorithm::variants() {
+                if rsa_bits(algo).is_some_and(|bits| bits < min) {
+                    policy.reject_asymmetric_algo(algo);
+                }
+            }
+        }
+
+        for (algo, config) in &self.asymmetric_algorithms {
+            match config {
+                AlgorithmPolicy::Accept => policy.accept_asymmetric_algo(*algo),
+                AlgorithmPolicy::Reject => policy.reject_asymmetric_algo(*algo),
+                AlgorithmPolicy::RejectAfter(time) => {
+                    policy.reject_asymmetric_algo_at(*algo, *time)
+                }
+            }
+        }
+
+        for (algo, config) in &self.hash_algorithms {
+            match config {
+                AlgorithmPolicy::Accept => policy.accept_hash(*algo),
+                AlgorithmPolicy::Reject => policy.reject_hash(*algo),
+                AlgorithmPolicy::RejectAfter(time) => policy.reject_hash_at(*algo, *time),
+            }
+        }
+    }
+}
+
+/// The key size of an RSA algorithm.
+fn rsa_bits(algo: AsymmetricAlgorithm) -> Option<u32> {
+    match algo {
+        AsymmetricAlgorithm::RSA1024 => Some(1024),
+        AsymmetricAlgorithm::RSA2048 => Some(2048),
+        AsymmetricAlgorithm::RSA3072 => Some(3072),
+        AsymmetricAlgorithm::RSA4096 => Some(4096),
+        _ => None,
+    }
+}
+
+/// A policy configuration file, with overrides for individual providers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyConfigFile {
+    /// The default configuration
+    #[serde(flatten)]
+    pub default: PolicyConfig,
+
+    /// Overrides, by provider (domain)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, PolicyConfig>,
+}
+
+impl PolicyConfigFile {
+    /// Load a configuration from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open policy file: {}", path.display()))?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse policy file: {}", path.display()))
+    }
+}
+
+/// Algorithms referenced by name.
+trait Named: Sized {
+    fn name(&self) -> String;
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+impl Named for HashAlgorithm {
+    fn name(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        name.parse().ok()
+    }
+}
+
+impl Named for AsymmetricAlgorithm {
+    fn name(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        AsymmetricAlgorithm::variants().find(|algo| algo.to_string().eq_ignore_ascii_case(name))
+    }
+}
+
+fn parse_entry<K: Named>(value: &str, kind: &str) -> Result<(K, AlgorithmPolicy), String> {
+    let (name, policy) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected <ALGORITHM>=<POLICY>, found: {value}"))?;
+    let algo = K::from_name(name).ok_or_else(|| format!("unknown {kind} algorithm: {name}"))?;
+    Ok((algo, policy.parse()?))
+}
+
+/// Parse a hash algorithm policy, in the form of `<ALGORITHM>=<POLICY>` (e.g. `SHA1=accept`).
+pub fn parse_hash_policy(value: &str) -> Result<(HashAlgorithm, AlgorithmPolicy), String> {
+    parse_entry(value, "hash")
+}
+
+/// Parse a public key algorithm policy, in the form of `<ALGORITHM>=<POLICY>` (e.g. `RSA1024=accept`).
+pub fn parse_asymmetric_policy(
+    value: &str,
+) -> Result<(AsymmetricAlgorithm, AlgorithmPolicy), String> {
+    parse_entry(value, "public key")
+}
+
+mod by_name {
+    use super::{AlgorithmPolicy, Named};
+    use serde::{Deserialize, Deserializer, Serializer, de, ser::SerializeMap};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<K, S>(value: &[(K, AlgorithmPolicy)], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Named,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(value.len()))?;
+        for (k, v) in value {
+            map.serialize_entry(&k.name(), v)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, K, D>(deserializer: D) -> Result<Vec<(K, AlgorithmPolicy)>, D::Error>
+    where
+        K: Named,
+        D: Deserializer<'de>,
+    {
+        BTreeMap::<String, AlgorithmPolicy>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(k, v)| match K::from_name(&k) {
+                Some(k) => Ok((k, v)),
+                None => Err(de::Error::custom(format!("unknown algorithm: {k}"))),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_config() {
+        let config: PolicyConfigFile = serde_json::from_value(json!({
+            "hashAlgorithms": {
+                "SHA1": "accept",
+                "SHA224": "2020-01-01",
+            },
+            "minRsaBits": 3072,
+            "providers": {
+                "example.com": {
+                    "asymmetricAlgorithms": { "rsa1024": "accept" },
+                }
+            }
+        }))
+        .expect("must parse");
+
+        assert_eq!(
+            config.default.hash_algorithms[0],
+            (HashAlgorithm::SHA1, AlgorithmPolicy::Accept)
+        );
+        assert!(matches!(
+            config.default.hash_algorithms[1],
+            (HashAlgorithm::SHA224, AlgorithmPolicy::RejectAfter(_))
+        ));
+        assert_eq!(
+            config.providers["example.com"].asymmetric_algorithms,
+            vec![(AsymmetricAlgorithm::RSA1024, AlgorithmPolicy::Accept)]
+        );
+    }
+
+    #[test]
+    fn reject_unknown() {
+        assert!(
+            serde_json::from_value::<PolicyConfig>(json!({"hashAlgorithms": {"FOO": "accept"}}))
+                .is_err()
+        );
+        assert!(
+            serde_json::from_value::<PolicyConfig>(json!({"hashAlgorithms": {"SHA1": "maybe"}}))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn merge_min_rsa_bits() {
+        let mut config = PolicyConfig {
+            asymmetric_algorithms: vec![
+                (AsymmetricAlgorithm::RSA1024, AlgorithmPolicy::Accept),
+                (AsymmetricAlgorithm::DSA1024, AlgorithmPolicy::Accept),
+            ],
+            ..Default::default()
+        };
+        config.merge(&PolicyConfig {
+            min_rsa_bits: Some(2048),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            config.asymmetric_algorithms,
+            vec![(AsymmetricAlgorithm::DSA1024, AlgorithmPolicy::Accept)]
+        );
+
+        let mut policy = StandardPolicy::new();
+        config.apply(&mut policy);
+        assert!(
+            policy
+                .asymmetric_algo_cutoff(AsymmetricAlgorithm::RSA1024)
+                .is_some()
+        );
+        assert!(
+            policy
+                .asymmetric_algo_cutoff(AsymmetricAlgorithm::RSA2048)
+                .is_none()
+        );
+
+        // an explicit entry of the override still wins over its own minimum
+        config.merge(&PolicyConfig {
+            asymmetric_algorithms: vec![(AsymmetricAlgorithm::RSA1024, AlgorithmPolicy::Accept)],
+            min_rsa_bits: Some(3072),
+            ..Default::default()
+        });
+
+        let mut policy = StandardPolicy::new();
+        config.apply(&mut policy);
+        assert!(
+            policy
+                .asymmetric_algo_cutoff(AsymmetricAlgorithm::RSA1024)
+                .is_none()
+        );
+        assert!(
+            policy
+                .asymmetric_algo_cutoff(AsymmetricAlgorithm::RSA2048)
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Detecting changes of provider keys (trust on first use).
+//!
+//! The keys seen during a run are recorded in a state file. On subsequent runs, the current keys
+//! are compared to the recorded ones.
+
+use crate::{
+    report::{KeyChanges, KeyInfo},
+    utils::openpgp::PublicKey,
+};
+use sequoia_openpgp::{
+    Cert, cert::prelude::ValidCert, policy::StandardPolicy, types::RevocationStatus,
+};
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+use time::OffsetDateTime;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to access known keys ({path}): {err}")]
+    Io { path: PathBuf, err: std::io::Error },
+    #[error("Failed to parse known keys ({path}): {err}")]
+    Parse {
+        path: PathBuf,
+        err: serde_json::Error,
+    },
+    #[error(
+        "Provider keys changed unexpectedly - added: {added}, removed: {removed}, revoked: {revoked}"
+    )]
+    Changed {
+        added: usize,
+        removed: usize,
+        revoked: usize,
+    },
+}
+
+/// The keys recorded during a previous run.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct KnownKeys {
+    /// Known keys, by fingerprint of the certificate
+    #[serde(default)]
+    pub keys: BTreeMap<String, KnownKey>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownKey {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub first_seen: OffsetDateTime,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expiration: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub revoked: bool,
+}
+
+impl From<(&String, &KnownKey)> for KeyInfo {
+    fn from((fingerprint, key): (&String, &KnownKey)) -> Self {
+        KeyInfo {
+            fingerprint: fingerprint.clone(),
+            user_ids: key.user_ids.clone(),
+            expiration: key.expiration,
+        }
+    }
+}
+
+/// Evaluate keys, tracking changes between runs.
+#[derive(Clone, Debug)]
+pub struct KeyTracking {
+    /// The file the known keys are stored in
+    pub path: PathBuf,
+    /// Report keys which expire within this period
+    pub expiration_warning: Duration,
+    /// Fail when keys changed unexpectedly, without recording the current keys
+    pub fail_on_change: bool,
+}
+
+impl KeyTracking {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            expiration_warning: Duration::from_secs(30 * 24 * 60 * 60),
+            fail_on_change: false,
+        }
+    }
+
+    pub fn expiration_warning(mut self, expiration_warning: Duration) -> Self {
+        self.expiration_warning = expiration_warning;
+        self
+    }
+
+    pub fn fail_on_change(mut self, fail_on_change: bool) -> Self {
+        self.fail_on_change = fail_on_change;
+        self
+    }
+
+    async fn load(&self) -> Result<Option<KnownKeys>, Error> {
+        let data = match tokio::fs::read(&self.path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::Io {
+                    path: self.path.clone(),
+                    err,
+                });
+            }
+        };
+
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|err| Error::Parse {
+                path: self.path.clone(),
+                err,
+            })
+    }
+
+    async fn store(&self, known: &KnownKeys) -> Result<(), Error> {
+        let io = |err| Error::Io {
+            path: self.path.clone(),
+            err,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io)?;
+        }
+
+        let data = serde_json::to_vec_pretty(known).map_err(|err| Error::Parse {
+            path: self.path.clone(),
+            err,
+        })?;
+        tokio::fs::write(&self.path, data).await.map_err(io)
+    }
+
+    /// Compare the current keys with the known keys, and record the current keys.
+    ///
+    /// If configured to fail on changes, the known keys will not be updated in case of a change.
+    pub async fn track(&self, keys: &[PublicKey]) -> Result<KeyChanges, Error> {
+        let now = SystemTime::now();
+        let previous = self.load().await?;
+
+        let (changes, current) = evaluate(
+            previous.as_ref(),
+            keys.iter().flat_map(|key| &key.certs),
+            now,
+            self.expiration_warning,
+        );
+
+        changes.log();
+
+        if self.fail_on_change && changes.is_changed() {
+            return Err(Error::Changed {
+                added: changes.added.len(),
+                removed: changes.removed.len(),
+                revoked: changes.revoked.len(),
+            });
+        }
+
+        self.store(&current).await?;
+
+        Ok(changes)
+    }
+}
+
+/// Evaluate the current certificates against the known keys.
+///
+/// Returns the changes, as well as the new state of known keys.
+pub fn evaluate<'a>(
+    previous: Option<&KnownKeys>,
+    certs: impl IntoIterator<Item = &'a Cert>,
+    now: SystemTime,
+    expiration_warning: Duration,
+) -> (KeyChanges, KnownKeys) {
+    let policy = StandardPolicy::new();
+
+    let mut changes = KeyChanges {
+        first_use: previous.is_none(),
+        ..Default::default()
+    };
+    let mut current = KnownKeys::default();
+
+    for cert in certs {
+        let fingerprint = cert.fingerprint().to_hex();
+        let valid = cert.with_policy(&policy, now).ok();
+
+        let user_ids = cert
+            .userids()
+            .map(|uid| String::from_utf8_lossy(uid.userid().value()).to_string())
+            .collect::<Vec<_>>();
+        let expiration = valid
+            .as_ref()
+            .and_then(|valid| valid.primary_key().key_expiration_time());
+        let revoked = matches!(
+            cert.revocation_status(&policy, now),
+            RevocationStatus::Revoked(_)
+        );
+
+        let known = previous.and_then(|previous| previous.keys.get(&fingerprint));
+
+        let key = KnownKey {
+            user_ids,
+            first_seen: known
+                .map(|known| known.first_seen)
+                .unwrap_or_else(|| now.into()),
+            expiration: expiration.map(OffsetDateTime::from),
+            revoked,
+        };
+        let info = KeyInfo::from((&fingerprint, &key));
+
+        if known.is_none() {
+            changes.added.push(info.clone());
+        }
+        if revoked && !known.is_some_and(|known| known.revoked) {
+            changes.revoked.push(info.clone());
+        }
+
+        if !revoked {
+            match &valid {
+                Some(valid) if is_expired(valid, now) => changes.expired.push(info),
+                Some(_)
+                    if expiration.is_some_and(|expiration| {
+                        expiration <= now + expiration_warning && expiration > now
+                    }) =>
+                {
+                    changes.expiring.push(info)
+                }
+                _ => {}
+            }
+        }
+
+        current.keys.insert(fingerprint, key);
+    }
+
+    if let Some(previous) = previous {
+        changes.removed = previous
+            .keys
+            .iter()
+            .filter(|(fingerprint, _)| !current.keys.contains_key(*fingerprint))
+            .map(KeyInfo::from)
+            .collect();
+    }
+
+    (changes, current)
+}
+
+fn is_expired(cert: &ValidCert, now: SystemTime) -> bool {
+    cert.alive().is_err()
+        && cert
+            .primary_key()
+            .key_expiration_time()
+            .is_some_and(|expiration| expiration <= now)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::validate::testing::cert;
+
+    #[test]
+    fn first_use_then_rotate() {
+        let now = SystemTime::now();
+        let warning = Duration::from_secs(30 * 24 * 60 * 60);
+
+        let a = cert("a@example.com", None);
+        let b = cert("b@example.com", Some(Duration::from_secs(7 * 24 * 60 * 60)));
+
+        let (changes, known) = evaluate(None, [&a], now, warning);
+        assert!(changes.first_use);
+        assert!(!changes.is_changed());
+        assert_eq!(changes.added.len(), 1);
+
+        let (changes, _) = evaluate(Some(&known), [&a], now, warning);
+        assert!(changes.is_empty());
+
+        let (changes, _) = evaluate(Some(&known), [&b], now, warning);
+        assert!(changes.is_changed());
+        assert_eq!(changes.added[0].fingerprint, b.fingerprint().to_hex());
+        assert_eq!(changes.removed[0].fingerprint, a.fingerprint().to_hex());
+        assert_eq!(changes.expiring[0].fingerprint, b.fingerprint().to_hex());
+    }
+}
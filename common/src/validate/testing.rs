@@ -0,0 +1,58 @@
+//! Fixtures for testing the validation of signatures
+//!
+//! Only available for tests, using the `_test` feature.
+
+use sequoia_openpgp::{
+    Cert,
+    armor::Kind,
+    cert::CertBuilder,
+    policy::StandardPolicy,
+    serialize::stream::{Armorer, Message, Signer},
+    types::HashAlgorithm,
+};
+use std::{
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
+/// Generate a certificate, valid since a minute ago, for the provided period
+pub fn cert(user_id: &str, validity: Option<Duration>) -> Cert {
+    CertBuilder::general_purpose(Some(user_id))
+        .set_creation_time(SystemTime::now() - Duration::from_secs(60))
+        .set_validity_period(validity)
+        .generate()
+        .expect("must generate cert")
+        .0
+}
+
+/// Create an ASCII armored, detached signature of the data
+pub fn sign(cert: &Cert, hash: HashAlgorithm, data: &[u8]) -> String {
+    let keypair = cert
+        .keys()
+        .with_policy(&StandardPolicy::new(), None)
+        .secret()
+        .for_signing()
+        .next()
+        .expect("must have a signing key")
+        .key()
+        .clone()
+        .into_keypair()
+        .expect("must have a secret key");
+
+    let mut sink = vec![];
+    let message = Armorer::new(Message::new(&mut sink))
+        .kind(Kind::Signature)
+        .build()
+        .expect("must build armorer");
+    let mut signer = Signer::new(message, keypair)
+        .expect("must create signer")
+        .hash_algo(hash)
+        .expect("must set hash algorithm")
+        .detached()
+        .build()
+        .expect("must build signer");
+    signer.write_all(data).expect("must sign");
+    signer.finalize().expect("must finalize");
+
+    String::from_utf8(sink).expect("must be ASCII armored")
+}
@@ -0,0 +1,267 @@
+//! A local trust store of pinned provider keys.
+//!
+//! The store is a directory, containing one directory per provider (by default, the domain of the
+//! provider), which contains the pinned certificates as armored files named by their fingerprint:
+//!
+//! ```text
+//! <store>/<provider>/<FINGERPRINT>.asc
+//! ```
+
+use crate::utils::{self, openpgp::PublicKey};
+use bytes::Bytes;
+use sequoia_openpgp::{Cert, Fingerprint, armor::Kind, serialize::Serialize};
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    OpenPgp(#[from] utils::openpgp::Error),
+    #[error("Invalid provider name: {0}")]
+    InvalidProvider(String),
+    #[error("Unable to determine the provider name, it must be provided explicitly")]
+    MissingProvider,
+    #[error("No pinned keys for provider: {0}")]
+    NoPinnedKeys(String),
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(value: anyhow::Error) -> Self {
+        Self::OpenPgp(value.into())
+    }
+}
+
+/// How keys from the trust store and the provider metadata are combined.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TrustMode {
+    /// Only use keys announced by the provider metadata
+    #[default]
+    Metadata,
+    /// Use keys announced by the provider metadata, in addition to pinned keys
+    Augment,
+    /// Only use pinned keys, ignoring keys announced by the provider metadata
+    Pinned,
+}
+
+/// A local store of pinned keys, by provider.
+#[derive(Clone, Debug)]
+pub struct TrustStore {
+    base: PathBuf,
+}
+
+impl TrustStore {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self { base: base.into() }
+    }
+
+    /// The base directory of the store
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    fn provider_dir(&self, provider: &str) -> Result<PathBuf, Error> {
+        if provider.is_empty()
+            || provider == "."
+            || provider == ".."
+            || provider.contains(['/', '\\'])
+        {
+            return Err(Error::InvalidProvider(provider.to_string()));
+        }
+
+        Ok(self.base.join(provider))
+    }
+
+    /// Load all pinned keys of a provider.
+    ///
+    /// If the provider has no pinned keys, an empty list is returned.
+    pub async fn load(&self, provider: &str) -> Result<Vec<PublicKey>, Error> {
+        let dir = self.provider_dir(provider)?;
+
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut files = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("asc") {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut keys = Vec::with_capacity(files.len());
+        for path in files {
+            let data = Bytes::from(tokio::fs::read(&path).await?);
+            // the file name is the fingerprint, which the content must match
+            let fingerprint = path.file_stem().and_then(|stem| stem.to_str());
+            log::debug!("Loading pinned key: {}", path.display());
+            keys.push(utils::openpgp::validate_keys(data, fingerprint)?);
+        }
+
+        Ok(keys)
+    }
+
+    /// List all providers and their pinned certificates.
+    pub async fn list(&self) -> Result<BTreeMap<String, Vec<Cert>>, Error> {
+        let mut result = BTreeMap::new();
+
+        let mut entries = match tokio::fs::read_dir(&self.base).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(result),
+            Err(err) => return Err(err.into()),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let Some(provider) = entry.file_name().to_str().map(ToString::to_string) else {
+                continue;
+            };
+
+            let certs = self
+                .load(&provider)
+                .await?
+                .into_iter()
+                .flat_map(|key| key.certs)
+                .collect();
+            result.insert(provider, certs);
+        }
+
+        Ok(result)
+    }
+
+    /// Import certificates for a provider, returning the fingerprints of the imported certificates.
+    ///
+    /// If a fingerprint is provided, all certificates must match it.
+    pub async fn import(
+        &self,
+        provider: &str,
+        data: Bytes,
+        fingerprint: Option<&str>,
+    ) -> Result<Vec<Fingerprint>, Error> {
+        let dir = self.provider_dir(provider)?;
+
+        let key = utils::openpgp::validate_keys(data, fingerprint)?;
+
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let mut result = Vec::with_capacity(key.certs.len());
+        for cert in key.certs {
+            let fingerprint = cert.fingerprint();
+
+            let mut out = vec![];
+            {
+                let mut writer = sequoia_openpgp::armor::Writer::new(&mut out, Kind::PublicKey)?;
+                cert.serialize(&mut writer)?;
+                writer.finalize()?;
+            }
+
+            tokio::fs::write(dir.join(format!("{}.asc", fingerprint.to_hex())), out).await?;
+            result.push(fingerprint);
+        }
+
+        Ok(result)
+    }
+
+    /// Remove a pinned certificate of a provider, returning `false` if it wasn't present.
+    pub async fn remove(&self, provider: &str, fingerprint: &Fingerprint) -> Result<bool, Error> {
+        let path = self
+            .provider_dir(provider)?
+            .join(format!("{}.asc", fingerprint.to_hex()));
+
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Configuration of how to use a [`TrustStore`] when loading provider keys.
+#[derive(Clone, Debug)]
+pub struct KeyTrust {
+    /// The trust store
+    pub store: TrustStore,
+    /// How to combine pinned keys with the provider metadata keys
+    pub mode: TrustMode,
+    /// The name of the provider in the store, e.g. derived from the source using
+    /// [`provider_name`](crate::utils::url::provider_name)
+    pub provider: Option<String>,
+}
+
+impl KeyTrust {
+    pub fn new(store: TrustStore) -> Self {
+        Self {
+            store,
+            mode: Default::default(),
+            provider: None,
+        }
+    }
+
+    pub fn mode(mut self, mode: TrustMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn provider(mut self, provider: impl Into<Option<String>>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
+    /// Whether keys from the provider metadata should be loaded
+    pub fn use_metadata_keys(&self) -> bool {
+        self.mode != TrustMode::Pinned
+    }
+
+    /// Load the pinned keys, according to the mode.
+    ///
+    /// The provider name must be set. When using the [`TrustMode::Pinned`] mode, having no pinned
+    /// keys is considered an error.
+    pub async fn pinned_keys(&self) -> Result<Vec<PublicKey>, Error> {
+        if self.mode == TrustMode::Metadata {
+            return Ok(vec![]);
+        }
+
+        let provider = self.provider.as_deref().ok_or(Error::MissingProvider)?;
+
+        let keys = self.store.load(provider).await?;
+
+        log::info!(
+            "Loaded {} pinned key{} for provider '{provider}'",
+            keys.len(),
+            if keys.len() != 1 { "s" } else { "" }
+        );
+
+        if keys.is_empty() && self.mode == TrustMode::Pinned {
+            return Err(Error::NoPinnedKeys(provider.to_string()));
+        }
+
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn provider_names() {
+        let store = TrustStore::new("/store");
+
+        assert!(store.provider_dir("redhat.com").is_ok());
+        assert!(store.provider_dir("").is_err());
+        assert!(store.provider_dir("..").is_err());
+        assert!(store.provider_dir("../etc").is_err());
+        assert!(store.provider_dir("foo\\bar").is_err());
+    }
+}
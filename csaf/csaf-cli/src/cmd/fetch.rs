@@ -17,7 +17,6 @@ use walker_common::{
     },
     progress::Progress,
     since::Since,
-    validate::ValidationOptions,
 };
 
 /// Discover, retrieve, validate, and print documents.
@@ -86,7 +85,8 @@ fn show(output: &str, pretty: bool, doc: ValidatedAdvisory) -> anyhow::Result<()
 
 impl Fetch {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let trust = self.validation.key_trust(&self.discover.source);
+        let options = self.validation.options(&self.discover.source);
 
         let since = Since::new(
             self.skip.since,
@@ -111,7 +111,7 @@ impl Fetch {
             self.runner,
             async |source| {
                 let validation = ValidationVisitor::new(show).with_options(options);
-                Ok(RetrievingVisitor::new(source.clone(), validation))
+                Ok(RetrievingVisitor::new(source.clone(), validation).with_trust(trust))
             },
         )
         .await?;
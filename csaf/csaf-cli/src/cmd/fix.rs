@@ -0,0 +1,152 @@
+use anyhow::Context;
+use csaf_walker::fix::{self, Fixer};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use walker_common::cli::CommandDefaults;
+
+/// Apply safe repairs to a CSAF document
+#[derive(clap::Args, Debug)]
+pub struct Fix {
+    /// The document to fix
+    file: PathBuf,
+
+    /// Write the fixed document to this file [default: the name of the document, with the
+    /// extension `.fixed.json`]
+    #[arg(short, long, conflicts_with = "in_place")]
+    output: Option<PathBuf>,
+
+    /// Replace the original document. Its digests and signature (`.sha256`, `.sha512`, `.asc`)
+    /// will no longer match, and must be refreshed.
+    #[arg(long)]
+    in_place: bool,
+
+    /// Write the log of changes to this file (as JSON)
+    #[arg(long, value_name = "FILE")]
+    changes: Option<PathBuf>,
+
+    /// Only list the changes, without writing any file
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Fixes to apply (can be repeated) [default: all]
+    #[arg(long = "fix", value_enum)]
+    fixes: Vec<FixKind>,
+}
+
+impl CommandDefaults for Fix {}
+
+impl Fix {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let data = std::fs::read(&self.file)
+            .with_context(|| format!("Failed to read document: {}", self.file.display()))?;
+        let mut json: Value = serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to parse document: {}", self.file.display()))?;
+
+        let mut fixer = Fixer::new();
+        if !self.fixes.is_empty() {
+            fixer = fixer.only(self.fixes.iter().copied().map(Into::into));
+        }
+
+        let changes = match self.dry_run {
+            true => fixer.dry_run(&json),
+            false => fixer.fix(&mut json),
+        };
+
+        for change in &changes {
+            match change.applied {
+                true => println!("{change}"),
+                false => println!("{change} [needs manual review]"),
+            }
+        }
+
+        let (applied, proposed) =
+            changes
+                .iter()
+                .fold((0, 0), |(applied, proposed), change| match change.applied {
+                    true => (applied + 1, proposed),
+                    false => (applied, proposed + 1),
+                });
+
+        if self.dry_run {
+            println!("{applied} change(s) proposed, {proposed} requiring manual review");
+            return Ok(());
+        }
+
+        if let Some(path) = &self.changes {
+            std::fs::write(path, serde_json::to_vec_pretty(&changes)?)
+                .with_context(|| format!("Failed to write changes: {}", path.display()))?;
+        }
+
+        let output = match (&self.output, self.in_place) {
+            (Some(output), _) => output.clone(),
+            (None, true) => self.file.clone(),
+            (None, false) => fixed_name(&self.file),
+        };
+        // keep the original document untouched, if there is nothing to fix
+        if applied > 0 || !self.in_place {
+            std::fs::write(&output, serde_json::to_vec_pretty(&json)?)
+                .with_context(|| format!("Failed to write document: {}", output.display()))?;
+        }
+
+        println!("{applied} change(s) applied, {proposed} requiring manual review");
+
+        if self.in_place && applied > 0 {
+            for sidecar in stale_sidecars(&self.file) {
+                log::warn!(
+                    "{} no longer matches the fixed document, it must be refreshed",
+                    sidecar.display()
+                );
+            }
+        } else if applied > 0 {
+            println!("Fixed document written to: {}", output.display());
+        }
+
+        Ok(())
+    }
+}
+
+/// The default name of the fixed document, next to the original one
+fn fixed_name(file: &Path) -> PathBuf {
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    file.with_file_name(format!("{stem}.fixed.json"))
+}
+
+/// Digests and signature of the document, which are invalidated by changing it
+fn stale_sidecars(file: &Path) -> Vec<PathBuf> {
+    ["sha256", "sha512", "asc"]
+        .into_iter()
+        .map(|extension| {
+            let mut name = file.as_os_str().to_owned();
+            name.push(".");
+            name.push(extension);
+            PathBuf::from(name)
+        })
+        .filter(|sidecar| sidecar.exists())
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum FixKind {
+    /// trim whitespace around identifiers
+    Whitespace,
+    /// remove references to undefined product (group) IDs
+    DanglingReferences,
+    /// sort lists of product (group) IDs, removing duplicates
+    ProductIds,
+    /// set the tracking version to the number of the newest revision
+    TrackingVersion,
+}
+
+impl From<FixKind> for fix::Fix {
+    fn from(value: FixKind) -> Self {
+        match value {
+            FixKind::Whitespace => Self::Whitespace,
+            FixKind::DanglingReferences => Self::DanglingReferences,
+            FixKind::ProductIds => Self::ProductIds,
+            FixKind::TrackingVersion => Self::TrackingVersion,
+        }
+    }
+}
@@ -1,11 +1,25 @@
 use anyhow::Context;
-use csaf_walker::visitors::{filter::FilterConfig, store::StoreVisitor};
+use csaf_walker::{
+    verification::{
+        check::{
+            Profile,
+            rules::{RuleCheck, RuleSet},
+        },
+        config::CheckConfigFile,
+    },
+    visitors::{filter::FilterConfig, store::StoreVisitor},
+};
 use flexible_time::timestamp::StartTimestamp;
 use std::path::PathBuf;
+use walker_common::{
+    store::{DispatchStore, FileStore, S3Options, S3Store},
+    validate::rotation::KeyTracking,
+};
 
 pub mod discover;
 pub mod download;
 pub mod fetch;
+pub mod fix;
 pub mod metadata;
 pub mod parse;
 pub mod report;
@@ -63,21 +77,46 @@ pub struct StoreArguments {
     /// Output path, defaults to the local directory.
     #[arg(short, long)]
     pub data: Option<PathBuf>,
-}
 
-impl TryFrom<StoreArguments> for StoreVisitor {
-    type Error = anyhow::Error;
+    /// Store to an S3 compatible object store instead, using a URL like `s3://[<key>:<secret>@]<region>/<bucket>[/<prefix>]`.
+    #[arg(long, value_name = "URL", conflicts_with = "data")]
+    pub s3: Option<String>,
+
+    /// A custom endpoint of the object store, e.g. `http://localhost:9000` for a local MinIO instance.
+    #[arg(long, value_name = "URL", requires = "s3")]
+    pub s3_endpoint: Option<String>,
+
+    /// Address buckets by path instead of by virtual host, as required by MinIO.
+    #[arg(long, requires = "s3")]
+    pub s3_path_style: bool,
+}
 
-    fn try_from(value: StoreArguments) -> Result<Self, Self::Error> {
-        let base = match value.data {
-            Some(base) => base,
-            None => std::env::current_dir().context("Get current working directory")?,
+impl StoreArguments {
+    /// Create the store visitor, storing to the object store if an S3 URL is set, or to the local
+    /// filesystem otherwise.
+    pub async fn into_visitor(self) -> anyhow::Result<StoreVisitor<DispatchStore>> {
+        let backend: DispatchStore = match self.s3 {
+            Some(url) => {
+                let mut options = S3Options::default().force_path_style(self.s3_path_style);
+                options.endpoint = self.s3_endpoint;
+                S3Store::from_url(&url, options)
+                    .await
+                    .context("Create S3 store")?
+                    .into()
+            }
+            None => {
+                let base = match self.data {
+                    Some(base) => base,
+                    None => std::env::current_dir().context("Get current working directory")?,
+                };
+                FileStore::new(base).into()
+            }
         };
 
-        let result = Self::new(base).no_timestamps(value.no_timestamps);
+        let result = StoreVisitor::with_backend(backend).no_timestamps(self.no_timestamps);
 
         #[cfg(any(target_os = "linux", target_os = "macos"))]
-        let result = result.no_xattrs(value.no_xattrs);
+        let result = result.no_xattrs(self.no_xattrs);
 
         Ok(result)
     }
@@ -102,6 +141,22 @@ pub struct SkipArguments {
 #[derive(Debug, clap::Parser)]
 #[command(next_help_heading = "Checks")]
 pub struct VerificationArguments {
+    /// The profile of the native CSAF tests [default: mandatory]
+    #[arg(long, value_enum)]
+    pub check_profile: Option<CheckProfile>,
+
+    /// A JSON file, enabling or disabling checks and overriding their severity (per document category or provider)
+    #[arg(long, value_name = "FILE", value_parser = load_check_config)]
+    pub check_config: Option<CheckConfigFile>,
+
+    /// JSON, YAML, or TOML files with custom rules, using JSONPath expressions (can be repeated)
+    #[arg(long, value_name = "FILE", value_parser = load_rules)]
+    pub rules: Vec<RuleSet>,
+
+    /// Skip the checks across documents (like duplicate tracking IDs or conflicting product status)
+    #[arg(long)]
+    pub skip_corpus_checks: bool,
+
     /// The profile to use for the CSAF validator suite
     #[cfg(feature = "csaf-validator-lib")]
     #[arg(id = "csaf-validator-profile", long, value_enum, default_value_t = ValidatorProfile::Optional)]
@@ -116,6 +171,109 @@ pub struct VerificationArguments {
     #[cfg(feature = "csaf-validator-lib")]
     #[arg(id = "csaf-validator-skip", long)]
     pub skip: Vec<String>,
+
+    /// A CSAF validator bundle to use, instead of the embedded one
+    #[cfg(feature = "csaf-validator-lib")]
+    #[arg(id = "csaf-validator-bundle", long, value_name = "FILE")]
+    pub bundle: Option<PathBuf>,
+
+    /// JavaScript modules with additional tests for the CSAF validator suite (can be repeated)
+    #[cfg(feature = "csaf-validator-lib")]
+    #[arg(id = "csaf-validator-test", long, value_name = "FILE")]
+    pub tests: Vec<PathBuf>,
+
+    /// Number of CSAF validator runtimes, validating documents in parallel (each requiring a considerable amount of memory)
+    #[cfg(feature = "csaf-validator-lib")]
+    #[arg(id = "csaf-validator-runtimes", long, default_value_t = csaf_walker::verification::check::csaf_validator_lib::DEFAULT_RUNTIMES)]
+    pub runtimes: usize,
+
+    /// Replace a CSAF validator runtime after it validated this number of documents
+    #[cfg(feature = "csaf-validator-lib")]
+    #[arg(id = "csaf-validator-recycle-after", long)]
+    pub recycle_after: Option<usize>,
+}
+
+impl VerificationArguments {
+    /// Create the check configuration, the profile argument overriding the default profile of the file.
+    pub fn check_config(&self) -> CheckConfigFile {
+        let mut config = self.check_config.clone().unwrap_or_default();
+        match self.check_profile {
+            Some(profile) => config.default.profile = Some(profile.into()),
+            None => {
+                config.default.profile.get_or_insert(Profile::Mandatory);
+            }
+        }
+        config
+    }
+
+    /// Create the checks of the custom rules, one per rule.
+    pub fn rule_checks(&self) -> anyhow::Result<Vec<RuleCheck>> {
+        let rules = RuleSet {
+            rules: self
+                .rules
+                .iter()
+                .flat_map(|rules| rules.rules.clone())
+                .collect(),
+        };
+
+        rules.compile()
+    }
+
+    /// Create the CSAF validator bundle, including additional tests.
+    #[cfg(feature = "csaf-validator-lib")]
+    pub fn bundle(
+        &self,
+    ) -> anyhow::Result<csaf_walker::verification::check::csaf_validator_lib::Bundle> {
+        use csaf_walker::verification::check::csaf_validator_lib::{Bundle, Module};
+
+        let bundle = match &self.bundle {
+            Some(path) => Bundle::load(path)?,
+            None => Bundle::embedded(),
+        };
+
+        let tests = self
+            .tests
+            .iter()
+            .map(Module::load)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // fail before processing any document
+        let bundle = bundle.extend_tests(tests);
+        bundle.verify()?;
+
+        Ok(bundle)
+    }
+}
+
+fn load_rules(path: &str) -> Result<RuleSet, String> {
+    RuleSet::load(path).map_err(|err| format!("{err:#}"))
+}
+
+fn load_check_config(path: &str) -> Result<CheckConfigFile, String> {
+    CheckConfigFile::load(path).map_err(|err| format!("{err:#}"))
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum CheckProfile {
+    /// schema only
+    Schema,
+    /// schema and mandatory tests
+    Mandatory,
+    /// schema, mandatory, and optional tests
+    Optional,
+    /// schema, mandatory, optional, and informative tests
+    Informative,
+}
+
+impl From<CheckProfile> for Profile {
+    fn from(value: CheckProfile) -> Self {
+        match value {
+            CheckProfile::Schema => Self::Schema,
+            CheckProfile::Mandatory => Self::Mandatory,
+            CheckProfile::Optional => Self::Optional,
+            CheckProfile::Informative => Self::Informative,
+        }
+    }
 }
 
 #[cfg(feature = "csaf-validator-lib")]
@@ -150,3 +308,30 @@ impl From<ValidatorProfile>
         }
     }
 }
+
+#[derive(Debug, clap::Parser)]
+#[command(next_help_heading = "Key tracking")]
+pub struct KeyTrackingArguments {
+    /// File to record the provider keys in, reporting added, removed, revoked, and expiring keys on subsequent runs.
+    #[arg(long)]
+    pub known_keys: Option<PathBuf>,
+
+    /// Report keys which expire within this period.
+    #[arg(long, default_value = "30d")]
+    pub key_expiration_warning: humantime::Duration,
+
+    /// Fail when provider keys were added, removed, or revoked since the previous run.
+    #[arg(long)]
+    pub fail_on_key_change: bool,
+}
+
+impl KeyTrackingArguments {
+    /// Create the key tracking configuration, using the default file if none was provided.
+    pub fn into_tracking(self, default: Option<PathBuf>) -> Option<KeyTracking> {
+        self.known_keys.or(default).map(|path| {
+            KeyTracking::new(path)
+                .expiration_warning(self.key_expiration_warning.into())
+                .fail_on_change(self.fail_on_key_change)
+        })
+    }
+}
@@ -1,22 +1,27 @@
 use crate::{
-    cmd::{DiscoverArguments, FilterArguments, VerificationArguments},
+    cmd::{DiscoverArguments, FilterArguments, KeyTrackingArguments, VerificationArguments},
     common::walk_visitor,
 };
 use csaf_walker::{
     discover::AsDiscovered,
-    report::{DocumentKey, Duplicates, ReportRenderOption, ReportResult, render_to_html},
+    report::{
+        DocumentKey, Duplicates, JsonReport, RULE_DOCUMENT_ERROR, ReportRenderOption, ReportResult,
+        render_to_html, render_to_json, render_to_junit, render_to_sarif,
+    },
     retrieve::RetrievingVisitor,
     source::DispatchSource,
     validation::{ValidatedAdvisory, ValidationError, ValidationVisitor},
     verification::{
         VerificationError, VerifiedAdvisory, VerifyingVisitor,
-        check::{CheckError, init_verifying_visitor},
+        check::{Finding, Profile, Severity, init_profile_checks, init_verifying_visitor},
+        corpus::Corpus,
     },
-    visitors::duplicates::DetectDuplicatesVisitor,
+    visitors::{duplicates::DetectDuplicatesVisitor, keys::KeyTrackingVisitor},
 };
 use reqwest::Url;
 use std::{
-    collections::BTreeMap,
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
     path::PathBuf,
     sync::{
         Arc,
@@ -26,18 +31,25 @@ use std::{
 use tokio::sync::Mutex;
 use walker_common::{
     cli::{
-        CommandDefaults, client::ClientArguments, runner::RunnerArguments,
+        CommandDefaults,
+        baseline::BaselineArguments,
+        client::ClientArguments,
+        report::{Diff, ReportFormat},
+        runner::RunnerArguments,
         validation::ValidationArguments,
     },
     progress::Progress,
-    report::{self, Statistics},
+    report::{self, KeyChanges, ReportOptions, ReportStatistics, Signers, Statistics},
     utils::url::Urlify,
-    validate::ValidationOptions,
 };
 
 /// Analyze (and report) the state of the data.
 #[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Report {
+    #[command(subcommand)]
+    command: Option<ReportCommand>,
+
     #[command(flatten)]
     client: ClientArguments,
 
@@ -45,7 +57,7 @@ pub struct Report {
     runner: RunnerArguments,
 
     #[command(flatten)]
-    discover: DiscoverArguments,
+    discover: Option<DiscoverArguments>,
 
     #[command(flatten)]
     filter: FilterArguments,
@@ -56,18 +68,37 @@ pub struct Report {
     #[command(flatten)]
     verification: VerificationArguments,
 
+    #[command(flatten)]
+    key_tracking: KeyTrackingArguments,
+
+    #[command(flatten)]
+    baseline: BaselineArguments,
+
     #[command(flatten)]
     render: RenderOptions,
 }
 
-impl CommandDefaults for Report {}
+impl CommandDefaults for Report {
+    fn progress(&self) -> bool {
+        self.command.is_none()
+    }
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ReportCommand {
+    Diff(Diff),
+}
 
 #[derive(clap::Args, Debug)]
 #[command(next_help_heading = "Report rendering")]
 pub struct RenderOptions {
-    /// Path of the HTML output file
-    #[arg(long, default_value = "report.html")]
-    pub output: PathBuf,
+    /// Path of the output file [default: report.<extension of the format>]
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+
+    /// The format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Html)]
+    pub format: ReportFormat,
 
     /// Make links relative to this URL.
     #[arg(short = 'B', long)]
@@ -77,38 +108,96 @@ pub struct RenderOptions {
     #[arg(long)]
     pub source_url: Option<Url>,
 
-    /// Statistics file to append to
+    /// Also write the JSON report to this file, e.g. for comparing runs using `report diff`
+    #[arg(long, value_name = "FILE")]
+    json_output: Option<PathBuf>,
+
+    /// Statistics file to append to, the HTML report shows the trends of the recorded runs
     #[arg(long)]
     statistics_file: Option<PathBuf>,
+
+    /// Create a self-contained HTML report, not loading any external resources
+    #[arg(long)]
+    self_contained: bool,
+}
+
+impl RenderOptions {
+    /// The path of the output file
+    pub fn output(&self) -> PathBuf {
+        self.output
+            .clone()
+            .unwrap_or_else(|| format!("report.{}", self.format.extension()).into())
+    }
+
+    /// The options for rendering the HTML report
+    fn report_options(&self) -> ReportOptions {
+        report::report_options(self.self_contained)
+    }
 }
 
 impl Report {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        if let Some(ReportCommand::Diff(diff)) = self.command {
+            return diff.run::<JsonReport>("CSAF Report Diff").await;
+        }
+        // required, unless running a subcommand
+        let Some(discover) = self.discover else {
+            anyhow::bail!("Missing the source to analyze");
+        };
+
+        let trust = self.validation.key_trust(&discover.source);
+        let provider = self.validation.provider(&discover.source);
+        let options = self.validation.options(&discover.source);
 
         let total = Arc::new(AtomicUsize::default());
+        let documents: Arc<Mutex<BTreeSet<DocumentKey>>> = Default::default();
         let duplicates: Arc<Mutex<Duplicates>> = Default::default();
         let errors: Arc<Mutex<BTreeMap<DocumentKey, String>>> = Default::default();
-        let warnings: Arc<Mutex<BTreeMap<DocumentKey, Vec<CheckError>>>> = Default::default();
+        let warnings: Arc<Mutex<BTreeMap<DocumentKey, Vec<Finding>>>> = Default::default();
+        let signers: Arc<Mutex<Signers>> = Default::default();
+        let keys: Arc<Mutex<Option<KeyChanges>>> = Default::default();
+        let corpus: Option<Arc<Mutex<Corpus<DocumentKey>>>> =
+            (!self.verification.skip_corpus_checks).then(Default::default);
+        let check_config = self.verification.check_config();
 
         {
             let total = total.clone();
+            let documents = documents.clone();
             let duplicates = duplicates.clone();
             let errors = errors.clone();
             let warnings = warnings.clone();
+            let signers = signers.clone();
+            let corpus = corpus.clone();
 
             let visitor = move |advisory: Result<
-                VerifiedAdvisory<ValidatedAdvisory, &'static str>,
+                VerifiedAdvisory<ValidatedAdvisory, Cow<'static, str>>,
                 VerificationError<ValidationError<DispatchSource>, ValidatedAdvisory>,
             >| {
                 (*total).fetch_add(1, Ordering::Release);
 
+                let documents = documents.clone();
                 let errors = errors.clone();
                 let warnings = warnings.clone();
+                let signers = signers.clone();
+                let corpus = corpus.clone();
 
                 async move {
                     let adv = match advisory {
                         Ok(adv) => adv,
+                        Err(VerificationError::Invalid {
+                            advisory, findings, ..
+                        }) => {
+                            // report the violations of the schema, like the findings of checks
+                            let name = DocumentKey::for_document(&advisory);
+                            documents.lock().await.insert(name.clone());
+                            warnings
+                                .lock()
+                                .await
+                                .entry(name)
+                                .or_default()
+                                .extend(findings);
+                            return Ok::<_, anyhow::Error>(());
+                        }
                         Err(err) => {
                             let name = match err.as_discovered().relative_base_and_url() {
                                 Some((base, relative)) => DocumentKey {
@@ -126,6 +215,39 @@ impl Report {
                         }
                     };
 
+                    documents
+                        .lock()
+                        .await
+                        .insert(DocumentKey::for_document(&adv));
+
+                    signers.lock().await.record(adv.signature_verification());
+
+                    if let Some(corpus) = corpus {
+                        corpus.lock().await.add(
+                            DocumentKey::for_document(&adv),
+                            adv.url.clone(),
+                            &adv.data,
+                            &adv.csaf,
+                        );
+                    }
+
+                    if adv
+                        .signature_verification()
+                        .is_some_and(|signature| signature.relaxed_policy)
+                    {
+                        warnings
+                            .lock()
+                            .await
+                            .entry(DocumentKey::for_document(&adv))
+                            .or_default()
+                            .push(
+                                Finding::new("Signature is only valid under a relaxed OpenPGP policy")
+                                    .rule("signature-policy")
+                                    .severity(Severity::Warning)
+                                    .hint("Update the signing key or the signature to meet the default policy"),
+                            );
+                    }
+
                     if !adv.failures.is_empty() {
                         let name = DocumentKey::for_document(&adv);
                         warnings
@@ -142,17 +264,31 @@ impl Report {
 
             // content checks
 
-            let visitor = VerifyingVisitor::with_checks(visitor, init_verifying_visitor());
+            // all checks are registered, the configuration selects the ones to run
+            let mut checks = init_verifying_visitor()
+                .into_iter()
+                .chain(init_profile_checks(Profile::Informative))
+                .map(|(id, check)| (Cow::Borrowed(id), check))
+                .collect::<Vec<_>>();
+            for rule in self.verification.rule_checks()? {
+                checks.push((rule.id().to_string().into(), Box::new(rule)));
+            }
+            let visitor = VerifyingVisitor::with_checks(visitor, checks)
+                .with_config(check_config.clone(), provider.clone());
             #[cfg(feature = "csaf-validator-lib")]
             let visitor = {
                 if let Some(profile) = self.verification.profile.into() {
                     let timeout = self.verification.timeout.map(|timeout| timeout.into());
-                    visitor.add(
-                        "csaf_validator_lib",
+                    let check =
                         csaf_walker::verification::check::csaf_validator_lib::CsafValidatorLib::new(
                             profile,
-                        ).timeout(timeout).ignore(self.verification.skip),
-                    )
+                        )
+                        .bundle(self.verification.bundle()?)
+                        .timeout(timeout)
+                        .runtimes(self.verification.runtimes)
+                        .recycle_after(self.verification.recycle_after)
+                        .ignore(&self.verification.skip);
+                    visitor.add("csaf_validator_lib".into(), check)
                 } else {
                     visitor
                 }
@@ -162,14 +298,22 @@ impl Report {
 
             let visitor = ValidationVisitor::new(visitor).with_options(options);
 
+            // track provider keys
+
+            let visitor = KeyTrackingVisitor {
+                visitor,
+                tracking: self.key_tracking.into_tracking(None),
+                changes: keys.clone(),
+            };
+
             walk_visitor(
                 progress,
                 self.client,
-                self.discover,
+                discover,
                 self.filter,
                 self.runner,
                 async move |source| {
-                    let visitor = RetrievingVisitor::new(source.clone(), visitor);
+                    let visitor = RetrievingVisitor::new(source.clone(), visitor).with_trust(trust);
 
                     Ok(DetectDuplicatesVisitor {
                         duplicates,
@@ -180,19 +324,30 @@ impl Report {
             .await?;
         }
 
+        // checks across documents
+
+        if let Some(corpus) = corpus {
+            let findings = corpus
+                .lock()
+                .await
+                .check_with_config(Some(&check_config), provider.as_deref());
+            let mut warnings = warnings.lock().await;
+            for (key, findings) in findings {
+                warnings.entry(key).or_default().extend(findings);
+            }
+        }
+
         let total = (*total).load(Ordering::Acquire);
-        let errors = errors.lock().await;
-        let warnings = warnings.lock().await;
+        let documents = documents.lock().await;
+        let mut errors = errors.lock().await;
+        let mut warnings = warnings.lock().await;
 
-        Self::render(
-            &self.render,
-            &ReportResult {
-                total,
-                duplicates: &*duplicates.lock().await,
-                errors: &errors,
-                warnings: &warnings,
-            },
-        )?;
+        // statistics, before applying the baseline
+
+        let mut checks = BTreeMap::<String, usize>::new();
+        for finding in warnings.values().flatten() {
+            *checks.entry(finding.rule.to_string()).or_default() += 1;
+        }
 
         report::record_now(
             self.render.statistics_file.as_deref(),
@@ -202,25 +357,108 @@ impl Report {
                 total_errors: errors.len(),
                 warnings: warnings.len(),
                 total_warnings: warnings.iter().map(|(_, v)| v.len()).sum(),
+                checks,
             },
         )?;
 
-        Ok(())
-    }
+        // baseline, including the documents which could not be processed (like the JSON report)
+
+        for (key, error) in errors.iter() {
+            warnings
+                .entry(key.clone())
+                .or_default()
+                .push(Finding::new(error.clone()).rule(RULE_DOCUMENT_ERROR));
+        }
+
+        let fixed = self.baseline.process(
+            &mut warnings,
+            &documents,
+            DocumentKey::document_url,
+            |finding: &Finding| finding.into(),
+        )?;
+
+        // split off the remaining document errors again
+        errors.retain(|key, _| {
+            warnings
+                .get(key)
+                .is_some_and(|findings| findings.iter().any(is_document_error))
+        });
+        for findings in warnings.values_mut() {
+            findings.retain(|finding| !is_document_error(finding));
+        }
+        warnings.retain(|_, findings| !findings.is_empty());
+
+        let signers = signers.lock().await;
+        let keys = keys.lock().await;
 
-    fn render(render: &RenderOptions, report: &ReportResult) -> anyhow::Result<()> {
-        let mut out = std::fs::File::create(&render.output)?;
+        // including the current run
+        let statistics = self
+            .render
+            .statistics_file
+            .as_deref()
+            .map(ReportStatistics::load)
+            .transpose()?;
 
-        render_to_html(
-            &mut out,
-            report,
-            ReportRenderOption {
-                output: &render.output,
-                base_url: &render.base_url,
-                source_url: &render.source_url,
+        Self::render(
+            &self.render,
+            &ReportResult {
+                total,
+                documents: &documents,
+                duplicates: &*duplicates.lock().await,
+                errors: &errors,
+                warnings: &warnings,
+                fixed: &fixed,
+                signers: &signers,
+                keys: keys.as_ref(),
             },
+            statistics.as_ref(),
         )?;
 
         Ok(())
     }
+
+    fn render(
+        render: &RenderOptions,
+        report: &ReportResult,
+        statistics: Option<&ReportStatistics>,
+    ) -> anyhow::Result<()> {
+        let output = render.output();
+        let mut out = std::fs::File::create(&output)?;
+
+        if let Some(path) = &render.json_output {
+            render_to_json(
+                &mut std::fs::File::create(path)?,
+                report,
+                render.source_url.as_ref().or(render.base_url.as_ref()),
+            )?;
+        }
+
+        match render.format {
+            ReportFormat::Html => render_to_html(
+                &mut out,
+                report,
+                ReportRenderOption {
+                    output: &output,
+                    base_url: &render.base_url,
+                    source_url: &render.source_url,
+                    statistics,
+                    report_options: &render.report_options(),
+                },
+            )?,
+            ReportFormat::Json => render_to_json(
+                &mut out,
+                report,
+                render.source_url.as_ref().or(render.base_url.as_ref()),
+            )?,
+            ReportFormat::Sarif => render_to_sarif(&mut out, report)?,
+            ReportFormat::Junit => render_to_junit(&mut out, report)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Document errors are passed to the baseline as findings of [`RULE_DOCUMENT_ERROR`]
+fn is_document_error(finding: &Finding) -> bool {
+    finding.rule == RULE_DOCUMENT_ERROR
 }
@@ -13,7 +13,6 @@ use walker_common::{
     },
     progress::Progress,
     since::Since,
-    validate::ValidationOptions,
 };
 use walker_extras::visitors::{SendArguments, SendVisitor};
 
@@ -50,7 +49,8 @@ impl CommandDefaults for Send {}
 
 impl Send {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let trust = self.validation.key_trust(&self.discover.source);
+        let options = self.validation.options(&self.discover.source);
         let send: SendVisitor = self.send.into_visitor().await?;
 
         let since = Since::new(
@@ -77,6 +77,7 @@ impl Send {
                         })
                         .with_options(options)
                     })
+                    .with_trust(trust)
                 })
             },
         )
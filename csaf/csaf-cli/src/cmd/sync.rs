@@ -1,12 +1,15 @@
 use crate::{
-    cmd::{DiscoverArguments, FilterArguments, SkipArguments, StoreArguments},
+    cmd::{
+        DiscoverArguments, FilterArguments, KeyTrackingArguments, SkipArguments, StoreArguments,
+    },
     common::walk_visitor,
 };
 use csaf_walker::{
     discover::DiscoverConfig,
     retrieve::RetrievingVisitor,
+    source::DispatchSource,
     validation::ValidationVisitor,
-    visitors::{skip::SkipExistingVisitor, store::StoreVisitor},
+    visitors::{keys::KeyTrackingVisitor, skip::SkipExistingVisitor, store::DIR_METADATA},
 };
 use walker_common::{
     cli::{
@@ -15,7 +18,7 @@ use walker_common::{
     },
     progress::Progress,
     since::Since,
-    validate::ValidationOptions,
+    store::DispatchStore,
 };
 
 /// Sync only what changed, and validate.
@@ -41,15 +44,27 @@ pub struct Sync {
 
     #[command(flatten)]
     store: StoreArguments,
+
+    #[command(flatten)]
+    key_tracking: KeyTrackingArguments,
 }
 
 impl CommandDefaults for Sync {}
 
 impl Sync {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
-        let store: StoreVisitor = self.store.try_into()?;
-        let base = store.base.clone();
+        let trust = self.validation.key_trust(&self.discover.source);
+        let options = self.validation.options(&self.discover.source);
+        let store = self.store.into_visitor().await?;
+        // existing documents can only be skipped when storing to the local filesystem
+        let base = match &store.backend {
+            DispatchStore::File(backend) => Some(backend.base.clone()),
+            DispatchStore::S3(_) => None,
+        };
+        let tracking = self.key_tracking.into_tracking(
+            base.as_ref()
+                .map(|base| base.join(DIR_METADATA).join("known-keys.json")),
+        );
 
         let since = Since::new(
             self.skip.since,
@@ -60,28 +75,49 @@ impl Sync {
                 .unwrap_or_default(),
         )?;
 
-        walk_visitor(
-            progress,
-            self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.filter,
-            self.runner,
-            async move |source| {
-                let visitor = {
-                    RetrievingVisitor::new(
-                        source,
-                        ValidationVisitor::new(store).with_options(options),
-                    )
-                };
+        let discover = DiscoverConfig::from(self.discover).with_since(since.since);
+        let visitor = |source: DispatchSource| {
+            RetrievingVisitor::new(
+                source,
+                KeyTrackingVisitor {
+                    visitor: ValidationVisitor::new(store).with_options(options),
+                    tracking,
+                    changes: Default::default(),
+                },
+            )
+            .with_trust(trust)
+        };
 
-                Ok(SkipExistingVisitor {
-                    visitor,
-                    output: base,
-                    since: since.since,
-                })
-            },
-        )
-        .await?;
+        match base {
+            Some(output) => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.filter,
+                    self.runner,
+                    async move |source| {
+                        Ok(SkipExistingVisitor {
+                            visitor: visitor(source),
+                            output,
+                            since: since.since,
+                        })
+                    },
+                )
+                .await?
+            }
+            None => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.filter,
+                    self.runner,
+                    async move |source| Ok(visitor(source)),
+                )
+                .await?
+            }
+        }
 
         since.store()?;
 
@@ -11,7 +11,6 @@ use std::future::Future;
 use walker_common::{
     cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
     progress::Progress,
-    validate::ValidationOptions,
 };
 
 pub async fn walk_standard<V, P>(
@@ -28,7 +27,9 @@ where
     V::Error: Send + Sync + 'static,
     P: Progress,
 {
-    let options: ValidationOptions = validation.into();
+    let discover = discover.into();
+    let trust = validation.key_trust(&discover.source);
+    let options = validation.options(&discover.source);
 
     walk_visitor(
         progress,
@@ -40,7 +41,8 @@ where
             Ok(RetrievingVisitor::new(
                 source.clone(),
                 ValidationVisitor::new(visitor).with_options(options),
-            ))
+            )
+            .with_trust(trust))
         },
     )
     .await
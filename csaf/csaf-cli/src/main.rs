@@ -5,12 +5,13 @@ mod common;
 
 use clap::Parser;
 use cmd::{
-    discover::Discover, download::Download, fetch::Fetch, metadata::Metadata, parse::Parse,
-    report::Report, scan::Scan, scoop::Scoop, send::Send, sync::Sync,
+    discover::Discover, download::Download, fetch::Fetch, fix::Fix, metadata::Metadata,
+    parse::Parse, report::Report, scan::Scan, scoop::Scoop, send::Send, sync::Sync,
 };
 use std::{ops::Deref, process::ExitCode};
 use walker_common::{
-    cli::CommandDefaults, cli::log::Logging, progress::Progress, utils::measure::MeasureTime,
+    cli::CommandDefaults, cli::log::Logging, cli::trust::Keys, progress::Progress,
+    utils::measure::MeasureTime,
 };
 
 #[derive(Debug, Parser)]
@@ -36,6 +37,8 @@ enum Command {
     Send(Send),
     Metadata(Metadata),
     Scoop(Scoop),
+    Keys(Keys),
+    Fix(Fix),
 }
 
 impl Deref for Command {
@@ -53,6 +56,8 @@ impl Deref for Command {
             Self::Send(cmd) => cmd,
             Self::Metadata(cmd) => cmd,
             Self::Scoop(cmd) => cmd,
+            Self::Keys(cmd) => cmd,
+            Self::Fix(cmd) => cmd,
         }
     }
 }
@@ -70,6 +75,8 @@ impl Command {
             Self::Send(cmd) => cmd.run(progress).await,
             Self::Metadata(cmd) => cmd.run().await,
             Self::Scoop(cmd) => cmd.run(progress).await,
+            Self::Keys(cmd) => cmd.run().await,
+            Self::Fix(cmd) => cmd.run().await,
         }
     }
 }
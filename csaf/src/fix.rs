@@ -0,0 +1,640 @@
+//! Repairing CSAF documents
+//!
+//! The [`Fixer`] applies safe, well-defined repairs to the JSON representation of a CSAF document,
+//! keeping all information which isn't affected by a fix. Each modification is recorded as a
+//! [`Change`].
+//!
+//! A fix is never applied if it would break the schema (like emptying a list which must not be
+//! empty) or a mandatory test. Such changes are only proposed, and are reported as not applied.
+
+use crate::verification::check::mandatory::Version;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashSet},
+    fmt::{Display, Formatter},
+};
+
+/// A repair which can be applied to a document
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Fix {
+    /// Trim whitespace around identifiers, and report values consisting of whitespace only
+    Whitespace,
+    /// Remove references to product (group) IDs which are not defined
+    DanglingReferences,
+    /// Sort lists of product (group) IDs, removing duplicates
+    ProductIds,
+    /// Set the tracking version to the number of the newest revision
+    TrackingVersion,
+}
+
+impl Fix {
+    /// All fixes, in the order they are applied
+    pub const ALL: [Fix; 4] = [
+        Self::Whitespace,
+        Self::DanglingReferences,
+        Self::ProductIds,
+        Self::TrackingVersion,
+    ];
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Whitespace => f.write_str("whitespace"),
+            Self::DanglingReferences => f.write_str("dangling-references"),
+            Self::ProductIds => f.write_str("product-ids"),
+            Self::TrackingVersion => f.write_str("tracking-version"),
+        }
+    }
+}
+
+/// A modification of a document
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// The fix applying the change
+    pub fix: Fix,
+    /// The JSON pointer of the modified value
+    pub path: String,
+    pub message: String,
+    /// If the change was applied, or only proposed, as it would break the document
+    pub applied: bool,
+}
+
+impl Change {
+    fn new(fix: Fix, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            fix,
+            path: path.into(),
+            message: message.into(),
+            applied: true,
+        }
+    }
+
+    /// A change which must be reviewed manually, and is not applied
+    fn proposed(fix: Fix, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            applied: false,
+            ..Self::new(fix, path, message)
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {} ({})", self.fix, self.message, self.path)
+    }
+}
+
+/// Applies fixes to documents
+#[derive(Clone, Debug)]
+pub struct Fixer {
+    fixes: BTreeSet<Fix>,
+}
+
+impl Default for Fixer {
+    fn default() -> Self {
+        Self {
+            fixes: Fix::ALL.into_iter().collect(),
+        }
+    }
+}
+
+impl Fixer {
+    /// Create a new instance, applying all fixes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only apply the provided fixes
+    pub fn only(mut self, fixes: impl IntoIterator<Item = Fix>) -> Self {
+        self.fixes = fixes.into_iter().collect();
+        self
+    }
+
+    /// Fix the document, returning the applied changes
+    pub fn fix(&self, json: &mut Value) -> Vec<Change> {
+        let mut changes = vec![];
+
+        for fix in &self.fixes {
+            match fix {
+                Fix::Whitespace => trim_whitespace(json, String::new(), None, &mut changes),
+                Fix::DanglingReferences => remove_dangling_references(json, &mut changes),
+                Fix::ProductIds => sort_product_ids(json, &mut changes),
+                Fix::TrackingVersion => fix_tracking_version(json, &mut changes),
+            }
+        }
+
+        changes
+    }
+
+    /// Evaluate the changes fixing the document would apply, without modifying it
+    pub fn dry_run(&self, json: &Value) -> Vec<Change> {
+        self.fix(&mut json.clone())
+    }
+}
+
+/// Fields containing identifiers, or references to them, which are never meant to start or end
+/// with whitespace. Other values (like titles or notes) are kept as they are.
+const IDENTIFIERS: &[&str] = &[
+    "id",
+    "cve",
+    "product_id",
+    "product_ids",
+    "group_id",
+    "group_ids",
+    "products",
+    "product_reference",
+    "relates_to_product_reference",
+];
+
+fn trim_whitespace(value: &mut Value, path: String, key: Option<&str>, changes: &mut Vec<Change>) {
+    match value {
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if trimmed.is_empty() && !s.is_empty() {
+                // an empty string is not allowed (minLength 1), the value must be filled manually
+                changes.push(Change::proposed(
+                    Fix::Whitespace,
+                    path,
+                    format!("Not trimming whitespace-only value {s:?}, it must not be empty"),
+                ));
+            } else if trimmed.len() != s.len() && key.is_some_and(|key| IDENTIFIERS.contains(&key))
+            {
+                changes.push(Change::new(
+                    Fix::Whitespace,
+                    path,
+                    format!("Trimmed whitespace of {s:?}"),
+                ));
+                *s = trimmed.to_string();
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter_mut().enumerate() {
+                trim_whitespace(value, format!("{path}/{i}"), key, changes);
+            }
+        }
+        Value::Object(values) => {
+            for (name, value) in values.iter_mut() {
+                trim_whitespace(
+                    value,
+                    format!("{path}/{}", escape(name)),
+                    Some(name),
+                    changes,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Escape a name for use in a JSON pointer
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Product,
+    Group,
+}
+
+/// What must be kept when removing references from a list, to not break the document
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Keep {
+    /// A required list, with a minimum number of items
+    Items(usize),
+    /// An optional list, the parent must keep at least one property (`minProperties: 1`)
+    AnyProperty,
+    /// An optional list, the parent must keep a reference to a product or group (6.1.29, 6.1.32)
+    AnyReference,
+    /// An optional list, without further constraints
+    Nothing,
+}
+
+/// The JSON pointers to all lists of product or group ID references
+fn id_lists(json: &Value) -> Vec<(String, Kind, Keep)> {
+    let mut result = vec![];
+
+    for (i, _) in json["product_tree"]["product_groups"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        result.push((
+            format!("/product_tree/product_groups/{i}/product_ids"),
+            Kind::Product,
+            Keep::Items(2),
+        ));
+    }
+
+    for (i, vulnerability) in json["vulnerabilities"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let path = format!("/vulnerabilities/{i}");
+
+        for field in vulnerability["product_status"]
+            .as_object()
+            .into_iter()
+            .flat_map(|status| status.keys())
+        {
+            result.push((
+                format!("{path}/product_status/{}", escape(field)),
+                Kind::Product,
+                Keep::AnyProperty,
+            ));
+        }
+
+        for (name, keep) in [
+            ("remediations", Keep::AnyReference),
+            ("threats", Keep::Nothing),
+            ("flags", Keep::AnyReference),
+            ("scores", Keep::Items(1)),
+        ] {
+            for (j, _) in vulnerability[name]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                if name == "scores" {
+                    result.push((format!("{path}/{name}/{j}/products"), Kind::Product, keep));
+                } else {
+                    result.push((
+                        format!("{path}/{name}/{j}/product_ids"),
+                        Kind::Product,
+                        keep,
+                    ));
+                    result.push((format!("{path}/{name}/{j}/group_ids"), Kind::Group, keep));
+                }
+            }
+        }
+    }
+
+    result
+        .into_iter()
+        .filter(|(path, _, _)| json.pointer(path).is_some_and(Value::is_array))
+        .collect()
+}
+
+/// All product IDs defined by the product tree
+fn product_ids(json: &Value) -> HashSet<&str> {
+    fn walk<'a>(branches: &'a Value, result: &mut HashSet<&'a str>) {
+        for branch in branches.as_array().into_iter().flatten() {
+            if let Some(id) = branch["product"]["product_id"].as_str() {
+                result.insert(id);
+            }
+            walk(&branch["branches"], result);
+        }
+    }
+
+    let tree = &json["product_tree"];
+    let mut result = HashSet::new();
+
+    walk(&tree["branches"], &mut result);
+    result.extend(
+        tree["full_product_names"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|product| product["product_id"].as_str()),
+    );
+    result.extend(
+        tree["relationships"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|relationship| relationship["full_product_name"]["product_id"].as_str()),
+    );
+
+    result
+}
+
+/// All group IDs defined by the product tree
+fn group_ids(json: &Value) -> HashSet<&str> {
+    json["product_tree"]["product_groups"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group["group_id"].as_str())
+        .collect()
+}
+
+fn remove_dangling_references(json: &mut Value, changes: &mut Vec<Change>) {
+    let products = product_ids(json)
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<HashSet<_>>();
+    // without any product, there is nothing to reference, and nothing to safely fix
+    if products.is_empty() {
+        return;
+    }
+    let groups = group_ids(json)
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<HashSet<_>>();
+
+    for (path, kind, keep) in id_lists(json) {
+        let defined = match kind {
+            Kind::Product => &products,
+            Kind::Group => &groups,
+        };
+
+        let Some(Value::Array(ids)) = json.pointer(&path) else {
+            continue;
+        };
+
+        let (retained, removed): (Vec<_>, Vec<_>) = ids
+            .iter()
+            .partition(|id| id.as_str().is_none_or(|id| defined.contains(id)));
+        if removed.is_empty() {
+            continue;
+        }
+        let retained = retained.into_iter().cloned().collect::<Vec<_>>();
+        let removed = removed
+            .iter()
+            .filter_map(|id| id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let kind = match kind {
+            Kind::Product => "product",
+            Kind::Group => "group",
+        };
+
+        let Some((parent, name)) = path.rsplit_once('/') else {
+            continue;
+        };
+        // the other properties of the parent, which are kept
+        let siblings = json
+            .pointer(parent)
+            .and_then(Value::as_object)
+            .map(|parent| {
+                parent
+                    .iter()
+                    .filter(|(key, _)| key.as_str() != name)
+                    .map(|(key, _)| key.as_str())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let conflict = match keep {
+            Keep::Items(min) if retained.len() < min => {
+                Some(format!("the list must keep at least {min} ID(s)"))
+            }
+            Keep::AnyProperty if retained.is_empty() && siblings.is_empty() => {
+                Some("the product status must not be empty".to_string())
+            }
+            Keep::AnyReference
+                if retained.is_empty()
+                    && !siblings
+                        .iter()
+                        .any(|sibling| matches!(*sibling, "product_ids" | "group_ids")) =>
+            {
+                Some("it must keep a reference to a product or group".to_string())
+            }
+            _ => None,
+        };
+
+        if let Some(conflict) = conflict {
+            changes.push(Change::proposed(
+                Fix::DanglingReferences,
+                path,
+                format!(
+                    "Not removing references to undefined {kind} IDs, as {conflict}: {removed}"
+                ),
+            ));
+            continue;
+        }
+
+        changes.push(Change::new(
+            Fix::DanglingReferences,
+            path.clone(),
+            format!("Removed references to undefined {kind} IDs: {removed}"),
+        ));
+
+        match json.pointer_mut(parent) {
+            // empty lists are not allowed, drop them entirely
+            Some(Value::Object(parent)) if retained.is_empty() => {
+                parent.remove(name);
+            }
+            Some(Value::Object(parent)) => {
+                parent.insert(name.to_string(), Value::Array(retained));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sort_product_ids(json: &mut Value, changes: &mut Vec<Change>) {
+    for (path, _, _) in id_lists(json) {
+        let Some(Value::Array(ids)) = json.pointer_mut(&path) else {
+            continue;
+        };
+        // only sort plain lists of IDs
+        let Some(original) = ids
+            .iter()
+            .map(|id| id.as_str().map(ToString::to_string))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+
+        let sorted = original
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if sorted == original {
+            continue;
+        }
+
+        let duplicates = original.len() - sorted.len();
+        *ids = sorted.into_iter().map(Value::String).collect();
+        changes.push(Change::new(
+            Fix::ProductIds,
+            path,
+            match duplicates {
+                0 => "Sorted IDs".to_string(),
+                n => format!("Sorted IDs, removing {n} duplicate(s)"),
+            },
+        ));
+    }
+}
+
+fn fix_tracking_version(json: &mut Value, changes: &mut Vec<Change>) {
+    let tracking = &json["document"]["tracking"];
+
+    let newest = tracking["revision_history"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|revision| {
+            let date =
+                DateTime::<FixedOffset>::parse_from_rfc3339(revision["date"].as_str()?).ok()?;
+            let number = revision["number"].as_str()?;
+            Some((date, Version::parse(number), number))
+        })
+        .max_by(|(a, va, _), (b, vb, _)| {
+            a.cmp(b).then_with(|| match (va, vb) {
+                (Some(va), Some(vb)) => va.cmp_precedence(vb).unwrap_or(Ordering::Equal),
+                _ => Ordering::Equal,
+            })
+        })
+        .map(|(_, _, number)| number.to_string());
+
+    let Some(newest) = newest else {
+        return;
+    };
+    if tracking["version"].as_str() == Some(newest.as_str()) {
+        return;
+    }
+
+    changes.push(Change::new(
+        Fix::TrackingVersion,
+        "/document/tracking/version",
+        match tracking["version"].as_str() {
+            Some(version) => format!(
+                "Changed version from {version} to {newest}, the number of the newest revision"
+            ),
+            None => format!("Set version to {newest}, the number of the newest revision"),
+        },
+    ));
+    json["document"]["tracking"]["version"] = Value::String(newest);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> Value {
+        json!({
+            "document": {
+                "title": " Example ",
+                "notes": [{ "category": "summary", "text": "Some text\n" }],
+                "tracking": {
+                    "id": " EX-1 ",
+                    "version": "1",
+                    "revision_history": [
+                        { "date": "2024-01-01T00:00:00Z", "number": "1", "summary": "Initial" },
+                        { "date": "2024-02-01T00:00:00+01:00", "number": "2", "summary": "Update" },
+                    ],
+                },
+            },
+            "product_tree": {
+                "full_product_names": [
+                    { "name": "A", "product_id": " A" },
+                    { "name": "B", "product_id": "B" },
+                ],
+                "product_groups": [
+                    { "group_id": "G", "product_ids": ["B", "A"] },
+                ],
+            },
+            "vulnerabilities": [{
+                "product_status": {
+                    "fixed": ["B", "A", "B"],
+                    "known_affected": ["C"],
+                },
+                "remediations": [{ "product_ids": ["A", "C"], "group_ids": ["G", "H"] }],
+            }],
+        })
+    }
+
+    #[test]
+    fn fix() {
+        let mut json = document();
+
+        let changes = Fixer::new().dry_run(&json);
+        assert_eq!(json, document());
+
+        assert_eq!(changes, Fixer::new().fix(&mut json));
+        assert_eq!(
+            changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                r#"[whitespace] Trimmed whitespace of " EX-1 " (/document/tracking/id)"#,
+                r#"[whitespace] Trimmed whitespace of " A" (/product_tree/full_product_names/0/product_id)"#,
+                "[dangling-references] Removed references to undefined product IDs: C (/vulnerabilities/0/product_status/known_affected)",
+                "[dangling-references] Removed references to undefined product IDs: C (/vulnerabilities/0/remediations/0/product_ids)",
+                "[dangling-references] Removed references to undefined group IDs: H (/vulnerabilities/0/remediations/0/group_ids)",
+                "[product-ids] Sorted IDs (/product_tree/product_groups/0/product_ids)",
+                "[product-ids] Sorted IDs, removing 1 duplicate(s) (/vulnerabilities/0/product_status/fixed)",
+                "[tracking-version] Changed version from 1 to 2, the number of the newest revision (/document/tracking/version)",
+            ]
+        );
+
+        assert_eq!(json["document"]["title"], " Example ");
+        assert_eq!(json["document"]["tracking"]["id"], "EX-1");
+        assert_eq!(
+            json["product_tree"]["full_product_names"][0]["product_id"],
+            "A"
+        );
+        assert_eq!(json["document"]["notes"][0]["text"], "Some text\n");
+        assert_eq!(json["document"]["tracking"]["version"], "2");
+        assert_eq!(
+            json["vulnerabilities"][0]["product_status"],
+            json!({ "fixed": ["A", "B"] })
+        );
+
+        // fixing is idempotent
+        assert_eq!(Fixer::new().fix(&mut json), vec![]);
+
+        let mut json = document();
+        let changes = Fixer::new().only([Fix::TrackingVersion]).fix(&mut json);
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn keep_valid() {
+        let document = json!({
+            "document": { "title": "  " },
+            "product_tree": {
+                "full_product_names": [
+                    { "name": "A", "product_id": "A" },
+                    { "name": "B", "product_id": "B" },
+                ],
+                "product_groups": [
+                    { "group_id": "G", "product_ids": ["A", "X"] },
+                ],
+            },
+            "vulnerabilities": [{
+                "product_status": { "known_affected": ["X"] },
+                "flags": [{ "label": "component_not_present", "product_ids": ["X"] }],
+                "threats": [{ "category": "impact", "details": "None", "product_ids": ["X"] }],
+                "scores": [{ "products": ["X"] }],
+            }],
+        });
+
+        let mut json = document.clone();
+        let changes = Fixer::new().fix(&mut json);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (change.applied, change.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (false, r#"[whitespace] Not trimming whitespace-only value "  ", it must not be empty (/document/title)"#.to_string()),
+                (false, "[dangling-references] Not removing references to undefined product IDs, as the list must keep at least 2 ID(s): X (/product_tree/product_groups/0/product_ids)".to_string()),
+                (false, "[dangling-references] Not removing references to undefined product IDs, as the product status must not be empty: X (/vulnerabilities/0/product_status/known_affected)".to_string()),
+                (true, "[dangling-references] Removed references to undefined product IDs: X (/vulnerabilities/0/threats/0/product_ids)".to_string()),
+                (false, "[dangling-references] Not removing references to undefined product IDs, as it must keep a reference to a product or group: X (/vulnerabilities/0/flags/0/product_ids)".to_string()),
+                (false, "[dangling-references] Not removing references to undefined product IDs, as the list must keep at least 1 ID(s): X (/vulnerabilities/0/scores/0/products)".to_string()),
+            ]
+        );
+
+        let mut expected = document;
+        expected["vulnerabilities"][0]["threats"][0]
+            .as_object_mut()
+            .expect("must be an object")
+            .remove("product_ids");
+        assert_eq!(json, expected);
+    }
+}
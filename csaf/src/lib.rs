@@ -62,11 +62,12 @@ pub mod retrieve;
 pub mod rolie;
 pub mod source;
 pub mod validation;
+pub mod verification;
 pub mod visitors;
 pub mod walker;
 
 #[cfg(feature = "csaf")]
-pub mod verification;
+pub mod fix;
 
 /// re-export common
 pub use walker_common as common;
@@ -3,6 +3,10 @@ use std::path::{Path, PathBuf};
 
 /// create a distribution base directory
 pub fn distribution_base(base: impl AsRef<Path>, url: &str) -> PathBuf {
-    base.as_ref()
-        .join(utf8_percent_encode(url, NON_ALPHANUMERIC).to_string())
+    base.as_ref().join(distribution_name(url))
+}
+
+/// the name of a distribution's base directory
+pub fn distribution_name(url: &str) -> String {
+    utf8_percent_encode(url, NON_ALPHANUMERIC).to_string()
 }
@@ -0,0 +1,12 @@
+use crate::verification::Finding;
+use walker_common::report::KnownFinding;
+
+impl From<&Finding> for KnownFinding {
+    fn from(value: &Finding) -> Self {
+        Self {
+            rule: value.rule.to_string(),
+            message: value.message.to_string(),
+            path: value.path.clone(),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::report::{Finding, RULE_DOCUMENT_ERROR, ReportResult, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use time::OffsetDateTime;
+use url::Url;
+use walker_common::report::{
+    FixedFindings, KeyChanges, KnownFinding, RunFindings, RunReport, Signers,
+};
+
+/// The version of the JSON report format
+///
+/// The version is increased for changes which are not backwards compatible.
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+/// The JSON schema of the JSON report
+pub const JSON_REPORT_SCHEMA: &str = include_str!("report.schema.json");
+
+/// The report, in a machine-readable format
+///
+/// See [`JSON_REPORT_SCHEMA`] for the schema of the serialized form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonReport {
+    /// The version of the format, see [`JSON_REPORT_VERSION`]
+    pub version: u32,
+    /// The time the report was created
+    #[serde(with = "time::serde::rfc3339")]
+    pub generated: OffsetDateTime,
+    /// The source of the documents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Url>,
+    pub summary: ReportSummary,
+    /// Documents which could be processed, with or without findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<String>,
+    /// Number of findings, by check
+    pub checks: BTreeMap<String, CheckSummary>,
+    /// Documents which could not be processed
+    pub errors: Vec<DocumentError>,
+    /// Findings, by document
+    pub warnings: Vec<DocumentFindings>,
+    /// Findings of the baseline which are no longer present, by document
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed: Vec<FixedFindings>,
+    /// Documents listed more than once
+    pub duplicates: Vec<Duplicate>,
+    pub signers: Signers,
+    /// Changes of the provider keys, if tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<KeyChanges>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSummary {
+    /// The total number of documents
+    pub total: usize,
+    /// The number of documents which could not be processed
+    pub errors: usize,
+    /// The number of documents with findings
+    pub warnings: usize,
+    /// The total number of findings
+    pub findings: usize,
+}
+
+/// The findings of a check, by severity
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckSummary {
+    /// The number of documents with findings
+    pub documents: usize,
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentError {
+    /// The URL of the document
+    pub document: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentFindings {
+    /// The URL of the document
+    pub document: String,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Duplicate {
+    /// The URL of the document
+    pub document: String,
+    /// The number of redundant entries
+    pub count: usize,
+}
+
+impl JsonReport {
+    pub fn new(report: &ReportResult, source: Option<&Url>) -> Self {
+        let mut checks = BTreeMap::<String, CheckSummary>::new();
+        for findings in report.warnings.values() {
+            let mut rules = findings
+                .iter()
+                .map(|finding| finding.rule.to_string())
+                .collect::<Vec<_>>();
+            rules.sort_unstable();
+            rules.dedup();
+            for rule in rules {
+                checks.entry(rule).or_default().documents += 1;
+            }
+
+            for finding in findings {
+                let check = checks.entry(finding.rule.to_string()).or_default();
+                match finding.severity.unwrap_or_default() {
+                    Severity::Error => check.error += 1,
+                    Severity::Warning => check.warning += 1,
+                    Severity::Info => check.info += 1,
+                }
+            }
+        }
+
+        Self {
+            version: JSON_REPORT_VERSION,
+            generated: OffsetDateTime::now_utc(),
+            source: source.cloned(),
+            summary: ReportSummary {
+                total: report.total,
+                errors: report.errors.len(),
+                warnings: report.warnings.len(),
+                findings: report.warnings.values().map(Vec::len).sum(),
+            },
+            documents: report
+                .documents
+                .iter()
+                .map(|key| key.document_url())
+                .collect(),
+            checks,
+            errors: report
+                .errors
+                .iter()
+                .map(|(key, message)| DocumentError {
+                    document: key.document_url(),
+                    message: message.clone(),
+                })
+                .collect(),
+            warnings: report
+                .warnings
+                .iter()
+                .map(|(key, findings)| DocumentFindings {
+                    document: key.document_url(),
+                    // the schema requires the severity of each finding
+                    findings: findings
+                        .iter()
+                        .map(|finding| finding.clone().default_severity(Severity::default()))
+                        .collect(),
+                })
+                .collect(),
+            fixed: report
+                .fixed
+                .iter()
+                .map(|(key, findings)| FixedFindings {
+                    document: key.document_url(),
+                    findings: findings.clone(),
+                })
+                .collect(),
+            duplicates: report
+                .duplicates
+                .duplicates
+                .iter()
+                .map(|(key, count)| Duplicate {
+                    document: key.document_url(),
+                    count: *count,
+                })
+                .collect(),
+            signers: report.signers.clone(),
+            keys: report.keys.cloned(),
+        }
+    }
+}
+
+impl RunReport for JsonReport {
+    /// Get the findings by document, reporting documents which could not be processed as a
+    /// finding of [`RULE_DOCUMENT_ERROR`].
+    fn run_findings(&self) -> RunFindings {
+        let mut findings = BTreeMap::<String, BTreeSet<KnownFinding>>::new();
+
+        for error in &self.errors {
+            findings
+                .entry(error.document.clone())
+                .or_default()
+                .insert(KnownFinding {
+                    rule: RULE_DOCUMENT_ERROR.to_string(),
+                    message: error.message.clone(),
+                    path: None,
+                });
+        }
+
+        for warning in &self.warnings {
+            findings
+                .entry(warning.document.clone())
+                .or_default()
+                .extend(warning.findings.iter().map(KnownFinding::from));
+        }
+
+        RunFindings {
+            findings,
+            documents: (!self.documents.is_empty()).then(|| {
+                self.documents
+                    .iter()
+                    .chain(self.errors.iter().map(|error| &error.document))
+                    .cloned()
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Render the report as JSON
+pub fn render_to_json<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+    source: Option<&Url>,
+) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(out, &JsonReport::new(report, source))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::{
+        Duplicates,
+        example::{errors, key, warnings},
+    };
+    use walker_common::report::{KeyInfo, Signer};
+
+    #[test]
+    fn json() {
+        let mut duplicates = Duplicates::default();
+        duplicates.duplicates.insert(key("2023/a.json"), 2);
+        let errors = errors();
+        let warnings = warnings();
+        let fixed = BTreeMap::from_iter([(
+            key("2023/d.json"),
+            vec![KnownFinding {
+                rule: "6.1.1".to_string(),
+                message: "Missing product".to_string(),
+                path: None,
+            }],
+        )]);
+        let signers = Signers {
+            signers: BTreeMap::from_iter([(
+                "ABCD".to_string(),
+                Signer {
+                    documents: 2,
+                    first_signature: Some(OffsetDateTime::UNIX_EPOCH),
+                    ..Default::default()
+                },
+            )]),
+            unsigned: 1,
+            relaxed: 0,
+        };
+        let keys = KeyChanges {
+            expired: vec![KeyInfo {
+                fingerprint: "ABCD".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let report = JsonReport::new(
+            &ReportResult {
+                total: 4,
+                documents: &BTreeSet::from_iter([key("2023/c.json"), key("2023/d.json")]),
+                duplicates: &duplicates,
+                errors: &errors,
+                warnings: &warnings,
+                fixed: &fixed,
+                signers: &signers,
+                keys: Some(&keys),
+            },
+            None,
+        );
+
+        assert_eq!(
+            report.summary,
+            ReportSummary {
+                total: 4,
+                errors: 1,
+                warnings: 1,
+                findings: 3,
+            }
+        );
+        assert_eq!(
+            report.checks["6.1.1"],
+            CheckSummary {
+                documents: 1,
+                error: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            report.errors[0].document,
+            "https://example.com/csaf/2023/b.json"
+        );
+
+        assert_eq!(
+            report.fixed[0].document,
+            "https://example.com/csaf/2023/d.json"
+        );
+
+        let findings = report.run_findings();
+        assert_eq!(findings.documents.map(|documents| documents.len()), Some(3));
+        assert_eq!(
+            findings.findings["https://example.com/csaf/2023/b.json"]
+                .iter()
+                .map(|finding| finding.rule.as_str())
+                .collect::<Vec<_>>(),
+            vec![RULE_DOCUMENT_ERROR]
+        );
+        assert_eq!(
+            findings.findings["https://example.com/csaf/2023/c.json"].len(),
+            3
+        );
+
+        let json = serde_json::to_value(&report).expect("report must serialize");
+        #[cfg(feature = "csaf")]
+        assert_eq!(
+            crate::verification::check::schema::validate_with(JSON_REPORT_SCHEMA, &json),
+            vec![]
+        );
+        assert_eq!(
+            serde_json::from_value::<JsonReport>(json.clone()).expect("report must deserialize"),
+            report
+        );
+
+        #[cfg(feature = "csaf")]
+        {
+            let mut json = json;
+            json["warnings"][0]["findings"][0]["severity"] = "fatal".into();
+            assert_eq!(
+                crate::verification::check::schema::validate_with(JSON_REPORT_SCHEMA, &json).len(),
+                1
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::report::{DocumentKey, Finding, ReportResult, Severity};
+use std::collections::{BTreeMap, BTreeSet};
+use walker_common::report::junit::{Failure, TestCase, TestSuite, TestSuites};
+
+/// Convert the report into JUnit test suites
+///
+/// Every distribution is a test suite, every document a test case. Each check with findings of
+/// severity warning, or above, is a failure of the test case. Documents which could not be processed
+/// are reported as errors.
+pub fn to_junit(report: &ReportResult) -> TestSuites {
+    let documents = report
+        .documents
+        .iter()
+        .chain(report.errors.keys())
+        .chain(report.warnings.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut suites = BTreeMap::<_, Vec<_>>::new();
+    for key in documents {
+        suites
+            .entry(&key.distribution_url)
+            .or_default()
+            .push(test_case(report, key));
+    }
+
+    TestSuites {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        suites: suites
+            .into_iter()
+            .map(|(distribution_url, cases)| TestSuite {
+                name: distribution_url.to_string(),
+                cases,
+            })
+            .collect(),
+    }
+}
+
+fn test_case(report: &ReportResult, key: &DocumentKey) -> TestCase {
+    let mut case = TestCase::new(key.distribution_url.as_str(), &key.url);
+
+    if let Some(error) = report.errors.get(key) {
+        case.error = Some(Failure::new("document-error", error, ""));
+    }
+
+    let mut checks = BTreeMap::<&str, Vec<&Finding>>::new();
+    for finding in report.warnings.get(key).into_iter().flatten() {
+        checks.entry(&finding.rule).or_default().push(finding);
+    }
+
+    let mut output = vec![];
+    for (check, findings) in checks {
+        let text = findings
+            .iter()
+            .map(|finding| {
+                let mut line = format!(
+                    "{}: {}",
+                    finding.severity.unwrap_or_default(),
+                    finding.message
+                );
+                if let Some(path) = &finding.path {
+                    line.push_str(&format!(" ({path})"));
+                }
+                if let Some(hint) = &finding.hint {
+                    line.push_str(&format!("\n  hint: {hint}"));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if findings
+            .iter()
+            .all(|finding| finding.severity == Some(Severity::Info))
+        {
+            output.push(format!("[{check}] {text}"));
+            continue;
+        }
+
+        let message = match findings.len() {
+            1 => findings[0].message.to_string(),
+            n => format!("{n} findings"),
+        };
+        case.failures.push(Failure::new(check, message, text));
+    }
+
+    if !output.is_empty() {
+        case.system_out = Some(output.join("\n"));
+    }
+
+    case
+}
+
+/// Render the report as JUnit XML
+pub fn render_to_junit<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+) -> anyhow::Result<()> {
+    to_junit(report).write(out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::{
+        Duplicates,
+        example::{errors, key, warnings},
+    };
+
+    #[test]
+    fn junit() {
+        let errors = errors();
+        let warnings = warnings();
+
+        let suites = to_junit(&ReportResult {
+            total: 3,
+            documents: &BTreeSet::from_iter([key("2023/a.json"), key("2023/c.json")]),
+            duplicates: &Duplicates::default(),
+            errors: &errors,
+            warnings: &warnings,
+            fixed: &Default::default(),
+            signers: &Default::default(),
+            keys: None,
+        });
+
+        assert_eq!(suites.suites.len(), 1);
+        let cases = &suites.suites[0].cases;
+        assert_eq!(
+            cases
+                .iter()
+                .map(|case| case.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2023/a.json", "2023/b.json", "2023/c.json"]
+        );
+
+        assert_eq!(
+            cases[0],
+            TestCase::new("https://example.com/csaf/", "2023/a.json")
+        );
+        assert_eq!(
+            cases[1].error,
+            Some(Failure::new("document-error", "Failed to parse", ""))
+        );
+        assert_eq!(
+            cases[2].failures,
+            vec![Failure::new(
+                "6.1.1",
+                "2 findings",
+                "error: Missing product (/vulnerabilities/0)\nerror: Missing product"
+            )]
+        );
+        assert_eq!(
+            cases[2].system_out.as_deref(),
+            Some("[6.2.3] info: Missing score\n  hint: Add a score")
+        );
+    }
+}
@@ -1,21 +1,38 @@
 //! Reporting functionality
 
+mod finding;
+mod json;
+mod junit;
 mod render;
+mod sarif;
 
+pub use crate::verification::{Finding, Severity};
+pub use json::*;
+pub use junit::*;
 pub use render::*;
+pub use sarif::*;
 
 use crate::discover::DiscoveredAdvisory;
-use std::borrow::Cow;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use url::Url;
-use walker_common::utils::url::Urlify;
+use walker_common::{
+    report::{KeyChanges, KnownFinding, Signers},
+    utils::url::Urlify,
+};
 
 #[derive(Clone, Debug)]
 pub struct ReportResult<'d> {
     pub total: usize,
+    /// Documents which could be processed, with or without findings
+    pub documents: &'d BTreeSet<DocumentKey>,
     pub duplicates: &'d Duplicates,
     pub errors: &'d BTreeMap<DocumentKey, String>,
-    pub warnings: &'d BTreeMap<DocumentKey, Vec<Cow<'static, str>>>,
+    pub warnings: &'d BTreeMap<DocumentKey, Vec<Finding>>,
+    /// Findings of the baseline which are no longer present, if reported
+    pub fixed: &'d BTreeMap<DocumentKey, Vec<KnownFinding>>,
+    pub signers: &'d Signers,
+    /// Changes of the provider keys, if tracked
+    pub keys: Option<&'d KeyChanges>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -35,8 +52,77 @@ pub struct DocumentKey {
 impl DocumentKey {
     pub fn for_document(advisory: &DiscoveredAdvisory) -> Self {
         Self {
-            distribution_url: advisory.url.clone(),
+            distribution_url: advisory.context.url().clone(),
             url: advisory.possibly_relative_url(),
         }
     }
+
+    /// The full URL of the document
+    pub fn document_url(&self) -> String {
+        self.distribution_url
+            .join(&self.url)
+            .map(String::from)
+            .unwrap_or_else(|_| self.url.clone())
+    }
+}
+
+/// Example data, shared by the tests of the report formats
+#[cfg(test)]
+mod example {
+    use super::*;
+
+    /// The key of a document in the example distribution
+    pub fn key(url: &str) -> DocumentKey {
+        DocumentKey {
+            distribution_url: Url::parse("https://example.com/csaf/").expect("URL must parse"),
+            url: url.to_string(),
+        }
+    }
+
+    /// A document failing to parse (`2023/b.json`)
+    pub fn errors() -> BTreeMap<DocumentKey, String> {
+        BTreeMap::from_iter([(key("2023/b.json"), "Failed to parse".to_string())])
+    }
+
+    /// A document with findings of an error check (6.1.1) and an informative one (6.2.3)
+    /// (`2023/c.json`)
+    pub fn warnings() -> BTreeMap<DocumentKey, Vec<Finding>> {
+        BTreeMap::from_iter([(
+            key("2023/c.json"),
+            vec![
+                Finding::new("Missing product")
+                    .rule("6.1.1")
+                    .path("/vulnerabilities/0"),
+                Finding::new("Missing product").rule("6.1.1"),
+                Finding::new("Missing score")
+                    .rule("6.2.3")
+                    .severity(Severity::Info)
+                    .hint("Add a score"),
+            ],
+        )])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::discover::DistributionContext;
+    use std::{sync::Arc, time::SystemTime};
+
+    #[test]
+    fn document_url() {
+        let key = DocumentKey::for_document(&DiscoveredAdvisory {
+            context: Arc::new(DistributionContext::Directory(
+                Url::parse("https://example.com/csaf/").expect("URL must parse"),
+            )),
+            url: Url::parse("https://example.com/csaf/2023/a.json").expect("URL must parse"),
+            digest: None,
+            signature: None,
+            modified: SystemTime::now(),
+        });
+
+        assert_eq!(key.distribution_url.as_str(), "https://example.com/csaf/");
+        assert_eq!(key.url, "2023/a.json");
+        assert_eq!(key.document_url(), "https://example.com/csaf/2023/a.json");
+    }
 }
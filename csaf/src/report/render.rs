@@ -1,10 +1,13 @@
-use crate::report::{DocumentKey, ReportResult};
+use crate::report::{DocumentKey, Finding, ReportResult, Severity};
 use std::{
     fmt::{Display, Formatter},
     path::Path,
 };
 use url::Url;
-use walker_common::{locale::Formatted, report, report::Summary};
+use walker_common::{
+    locale::Formatted,
+    report::{self, FilterAttributes, ReportOptions, ReportStatistics, Summary, Trends},
+};
 
 #[derive(Clone, Debug)]
 pub struct ReportRenderOption<'a> {
@@ -12,6 +15,10 @@ pub struct ReportRenderOption<'a> {
 
     pub base_url: &'a Option<Url>,
     pub source_url: &'a Option<Url>,
+    /// Statistics of previous runs, rendered as trend charts
+    pub statistics: Option<&'a ReportStatistics>,
+    /// Options for rendering the HTML page
+    pub report_options: &'a ReportOptions,
 }
 
 pub fn render_to_html<W: std::io::Write>(
@@ -26,8 +33,9 @@ pub fn render_to_html<W: std::io::Write>(
             result: report,
             base_url: options.base_url,
             source_url: options.source_url,
+            statistics: options.statistics,
         },
-        &Default::default(),
+        options.report_options,
     )?;
 
     Ok(())
@@ -38,6 +46,7 @@ pub enum Title {
     Duplicates,
     Warnings,
     Errors,
+    Fixed,
 }
 
 impl Display for Title {
@@ -46,6 +55,7 @@ impl Display for Title {
             Self::Duplicates => f.write_str("Duplicates"),
             Self::Warnings => f.write_str("Warnings"),
             Self::Errors => f.write_str("Errors"),
+            Self::Fixed => f.write_str("Fixed"),
         }
     }
 }
@@ -56,6 +66,7 @@ struct HtmlReport<'r> {
     base_url: &'r Option<Url>,
     /// Override source URL
     source_url: &'r Option<Url>,
+    statistics: Option<&'r ReportStatistics>,
 }
 
 impl HtmlReport<'_> {
@@ -67,12 +78,13 @@ impl HtmlReport<'_> {
                 writeln!(
                     f,
                     r#"
-            <tr>
+            <tr{filter}>
                 <td><code>{label}<code></td>
                 <td class="text-right">{v}</td>
             </tr>
             "#,
                     label = html_escape::encode_text(&label),
+                    filter = Self::filter(k),
                 )?;
             }
             Ok(())
@@ -110,7 +122,7 @@ impl HtmlReport<'_> {
                 writeln!(
                     f,
                     r##"
-            <tr>
+            <tr{filter}>
                 <td id="{id}"><a href="{url}" target="_blank" style="white-space: nowrap;">{label}</a> <a class="link-secondary" href="#{id}">§</a></td>
                 <td><code>{v}</code></td>
             </tr>
@@ -118,6 +130,7 @@ impl HtmlReport<'_> {
                     url = html_escape::encode_quoted_attribute(&url),
                     label = html_escape::encode_text(&label),
                     v = html_escape::encode_text(&v),
+                    filter = Self::filter(k),
                 )?;
             }
             Ok(())
@@ -181,30 +194,98 @@ impl HtmlReport<'_> {
                 writeln!(
                     f,
                     r##"
-            <tr>
+            <tr{filter}>
                 <td id="{id}"><a href="{url}" target="_blank" style="white-space: nowrap;">{label}</a> <a class="link-secondary" href="#{id}">§</a></td>
-                <td><ul>
+                <td><details open><summary>{count} finding(s)</summary><ul>
 "##,
                     url = html_escape::encode_quoted_attribute(&url),
                     label = html_escape::encode_text(&label),
+                    count = Formatted(v.len()),
+                    filter = v.iter().fold(Self::filter(k), |filter, finding| {
+                        filter
+                            .check(Self::check(finding))
+                            .severity(finding.severity.unwrap_or_default().to_string())
+                    }),
+                )?;
+
+                for finding in v {
+                    Self::render_finding(f, finding)?;
+                }
+
+                writeln!(
+                    f,
+                    r#"
+                    </ul></details>
+                </td>
+            </tr>
+"#
                 )?;
+            }
 
-                for text in v {
-                    writeln!(
+            Ok(())
+        };
+        if total_count > 0 {
+            Self::render_table(
+                f,
+                [file_count, total_count],
+                Title::Warnings,
+                &format!(
+                    "{total_count} warning(s) in {file_count} file(s) detected",
+                    total_count = Formatted(total_count),
+                    file_count = Formatted(file_count),
+                ),
+                data,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn render_fixed(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let file_count = self.result.fixed.len();
+        let total_count = self.result.fixed.values().map(|v| v.len()).sum();
+
+        let data = |f: &mut Formatter<'_>| {
+            for (k, v) in self.result.fixed {
+                let (url, label) = self.link_document(k);
+
+                writeln!(
+                    f,
+                    r#"
+            <tr{filter}>
+                <td><a href="{url}" target="_blank" style="white-space: nowrap;">{label}</a></td>
+                <td><details><summary>{count} finding(s)</summary><ul>
+"#,
+                    url = html_escape::encode_quoted_attribute(&url),
+                    label = html_escape::encode_text(&label),
+                    count = Formatted(v.len()),
+                    filter = v.iter().fold(Self::filter(k), |filter, finding| filter
+                        .check(&finding.rule)),
+                )?;
+
+                for finding in v {
+                    write!(
                         f,
                         r#"
-            <li>
-                <code>{v}</code>
-            </li>
-            "#,
-                        v = html_escape::encode_text(&text),
+            <li data-check="{check}">
+                <span class="badge text-bg-secondary">{rule}</span> <code>{message}</code>"#,
+                        check = html_escape::encode_double_quoted_attribute(&finding.rule),
+                        rule = html_escape::encode_text(&finding.rule),
+                        message = html_escape::encode_text(&finding.message),
                     )?;
+                    if let Some(path) = &finding.path {
+                        write!(
+                            f,
+                            r#" <small class="text-body-secondary"><code>{path}</code></small>"#,
+                            path = html_escape::encode_text(path)
+                        )?;
+                    }
+                    writeln!(f, "\n            </li>")?;
                 }
 
                 writeln!(
                     f,
                     r#"
-                    </ul>
+                    </ul></details>
                 </td>
             </tr>
 "#
@@ -217,9 +298,9 @@ impl HtmlReport<'_> {
             Self::render_table(
                 f,
                 [file_count, total_count],
-                Title::Warnings,
+                Title::Fixed,
                 &format!(
-                    "{total_count} warning(s) in {file_count} file(s) detected",
+                    "{total_count} finding(s) of the baseline in {file_count} file(s) no longer present",
                     total_count = Formatted(total_count),
                     file_count = Formatted(file_count),
                 ),
@@ -229,6 +310,63 @@ impl HtmlReport<'_> {
         Ok(())
     }
 
+    fn render_finding(f: &mut Formatter<'_>, finding: &Finding) -> std::fmt::Result {
+        let severity = finding.severity.unwrap_or_default();
+        let class = match severity {
+            Severity::Error => "text-bg-danger",
+            Severity::Warning => "text-bg-warning",
+            Severity::Info => "text-bg-info",
+        };
+
+        write!(
+            f,
+            r#"
+            <li data-check="{check}" data-severity="{severity}">
+                <span class="badge {class}">{severity}</span>"#,
+            check = html_escape::encode_double_quoted_attribute(Self::check(finding)),
+        )?;
+        if !finding.rule.is_empty() {
+            write!(
+                f,
+                r#" <span class="badge text-bg-secondary">{rule}</span>"#,
+                rule = html_escape::encode_text(&finding.rule),
+            )?;
+        }
+        write!(
+            f,
+            " <code>{message}</code>",
+            message = html_escape::encode_text(&finding.message)
+        )?;
+        if let Some(path) = &finding.path {
+            write!(
+                f,
+                r#" <small class="text-body-secondary"><code>{path}</code></small>"#,
+                path = html_escape::encode_text(path)
+            )?;
+        }
+        if let Some(hint) = &finding.hint {
+            write!(
+                f,
+                r#"<br><small class="text-body-secondary">{hint}</small>"#,
+                hint = html_escape::encode_text(hint)
+            )?;
+        }
+        writeln!(f, "\n            </li>")
+    }
+
+    /// The check a finding is filtered by
+    fn check(finding: &Finding) -> &str {
+        match finding.rule.is_empty() {
+            true => "Other",
+            false => &finding.rule,
+        }
+    }
+
+    /// The filter attributes of a document, carrying its distribution
+    fn filter(key: &DocumentKey) -> FilterAttributes {
+        FilterAttributes::new().distribution(key.distribution_url.as_str())
+    }
+
     fn gen_link(&self, key: &DocumentKey) -> Option<(String, String)> {
         let label = key.url.clone();
 
@@ -263,6 +401,7 @@ impl HtmlReport<'_> {
                 (
                     match title {
                         Title::Warnings => "text-bg-warning",
+                        Title::Fixed => "text-bg-success",
                         _ => "text-bg-danger",
                     },
                     Formatted(count).to_string(),
@@ -297,9 +436,17 @@ impl HtmlReport<'_> {
 impl Display for HtmlReport<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.render_total(f)?;
+        if let Some(statistics) = self.statistics {
+            Trends(statistics).fmt(f)?;
+        }
+        self.result.signers.fmt(f)?;
+        if let Some(keys) = self.result.keys {
+            keys.fmt(f)?;
+        }
         self.render_duplicates(f)?;
         self.render_errors(f)?;
         self.render_warnings(f)?;
+        self.render_fixed(f)?;
         Ok(())
     }
 }
@@ -308,15 +455,20 @@ impl Display for HtmlReport<'_> {
 mod test {
     use super::*;
     use reqwest::Url;
-    use std::path::PathBuf;
+    use std::{collections::BTreeMap, path::PathBuf};
+    use walker_common::report::KnownFinding;
 
     #[test]
     fn test_link() {
         let details = ReportResult {
             total: 0,
+            documents: &Default::default(),
             duplicates: &Default::default(),
             errors: &Default::default(),
             warnings: &Default::default(),
+            fixed: &Default::default(),
+            signers: &Default::default(),
+            keys: None,
         };
         let _output = PathBuf::default();
         let base_url = Some(Url::parse("file:///foo/bar/").expect("example value must parse"));
@@ -324,6 +476,7 @@ mod test {
             result: &details,
             base_url: &base_url,
             source_url: &None,
+            statistics: None,
         };
 
         let (url, _label) = report.link_document(&DocumentKey {
@@ -334,4 +487,87 @@ mod test {
 
         assert_eq!(url, "distribution/2023/cve.json");
     }
+
+    #[test]
+    fn test_filter() {
+        let key = DocumentKey {
+            distribution_url: Url::parse("https://example.com/csaf/")
+                .expect("example value must parse"),
+            url: "2023/cve.json".to_string(),
+        };
+        let warnings = [(
+            key,
+            vec![
+                Finding::new("Missing title")
+                    .rule("6.1.1")
+                    .severity(Severity::Warning),
+                Finding::new("Some note").severity(Severity::Info),
+            ],
+        )]
+        .into_iter()
+        .collect();
+
+        let details = ReportResult {
+            total: 1,
+            documents: &Default::default(),
+            duplicates: &Default::default(),
+            errors: &Default::default(),
+            warnings: &warnings,
+            fixed: &Default::default(),
+            signers: &Default::default(),
+            keys: None,
+        };
+        let html = HtmlReport {
+            result: &details,
+            base_url: &None,
+            source_url: &None,
+            statistics: None,
+        }
+        .to_string();
+
+        assert!(html.contains(
+            r#"<tr data-filter data-distribution="[&quot;https://example.com/csaf/&quot;]" data-checks="[&quot;6.1.1&quot;,&quot;Other&quot;]" data-severities="[&quot;info&quot;,&quot;warning&quot;]">"#
+        ));
+        assert!(html.contains("<details open><summary>2 finding(s)</summary>"));
+        assert!(html.contains(r#"<li data-check="6.1.1" data-severity="warning">"#));
+        assert!(html.contains(r#"<li data-check="Other" data-severity="info">"#));
+    }
+
+    #[test]
+    fn test_fixed() {
+        let fixed = BTreeMap::from_iter([(
+            DocumentKey {
+                distribution_url: Url::parse("https://example.com/csaf/")
+                    .expect("example value must parse"),
+                url: "2023/cve.json".to_string(),
+            },
+            vec![KnownFinding {
+                rule: "6.1.1".to_string(),
+                message: "Missing title".to_string(),
+                path: Some("/document".to_string()),
+            }],
+        )]);
+
+        let details = ReportResult {
+            total: 1,
+            documents: &Default::default(),
+            duplicates: &Default::default(),
+            errors: &Default::default(),
+            warnings: &Default::default(),
+            fixed: &fixed,
+            signers: &Default::default(),
+            keys: None,
+        };
+        let html = HtmlReport {
+            result: &details,
+            base_url: &None,
+            source_url: &None,
+            statistics: None,
+        }
+        .to_string();
+
+        assert!(html.contains("<h2>Fixed"));
+        assert!(html.contains(r#"<li data-check="6.1.1">"#));
+        assert!(html.contains("<code>Missing title</code>"));
+    }
 }
@@ -0,0 +1,146 @@
+use crate::report::{ReportResult, Severity};
+use walker_common::report::sarif::{Level, Run, Sarif, SarifResult, ToolComponent};
+
+/// The rule of documents which could not be processed
+pub const RULE_DOCUMENT_ERROR: &str = "document-error";
+/// The rule of documents listed more than once
+pub const RULE_DUPLICATE: &str = "duplicate";
+
+impl From<Severity> for Level {
+    fn from(value: Severity) -> Self {
+        match value {
+            Severity::Error => Self::Error,
+            Severity::Warning => Self::Warning,
+            Severity::Info => Self::Note,
+        }
+    }
+}
+
+/// Convert the report into a SARIF log
+///
+/// Each check is reported as a rule, each finding as a result of the document.
+pub fn to_sarif(report: &ReportResult) -> Sarif {
+    let driver = ToolComponent::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .information_uri(env!("CARGO_PKG_REPOSITORY"))
+        .rule(RULE_DOCUMENT_ERROR, "The document could not be processed")
+        .rule(RULE_DUPLICATE, "The document is listed more than once");
+
+    let mut run = Run::new(driver);
+
+    for (key, message) in report.errors {
+        run.add_result(
+            SarifResult::new(RULE_DOCUMENT_ERROR, Level::Error, message)
+                .location(key.document_url(), None),
+        );
+    }
+
+    for (key, count) in &report.duplicates.duplicates {
+        run.add_result(
+            SarifResult::new(
+                RULE_DUPLICATE,
+                Level::Warning,
+                format!("Document is listed with {count} redundant entries"),
+            )
+            .location(key.document_url(), None),
+        );
+    }
+
+    for (key, findings) in report.warnings {
+        for finding in findings {
+            let rule = match finding.rule.is_empty() {
+                true => "unknown",
+                false => &finding.rule,
+            };
+
+            let mut result = SarifResult::new(
+                rule,
+                finding.severity.unwrap_or_default().into(),
+                finding.message.to_string(),
+            )
+            .location(key.document_url(), finding.path.clone());
+            if let Some(hint) = &finding.hint {
+                result = result.property("hint", hint.to_string());
+            }
+
+            run.add_result(result);
+        }
+    }
+
+    Sarif::new(run)
+}
+
+/// Render the report as SARIF
+pub fn render_to_sarif<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(out, &to_sarif(report))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::{
+        Duplicates,
+        example::{errors, key, warnings},
+    };
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn sarif() {
+        let errors = errors();
+        let warnings = warnings();
+
+        let sarif = to_sarif(&ReportResult {
+            total: 2,
+            documents: &BTreeSet::from_iter([key("2023/c.json")]),
+            duplicates: &Duplicates::default(),
+            errors: &errors,
+            warnings: &warnings,
+            fixed: &Default::default(),
+            signers: &Default::default(),
+            keys: None,
+        });
+
+        let run = &sarif.runs[0];
+        assert_eq!(
+            run.tool
+                .driver
+                .rules
+                .iter()
+                .map(|rule| rule.id.as_str())
+                .collect::<Vec<_>>(),
+            vec![RULE_DOCUMENT_ERROR, RULE_DUPLICATE, "6.1.1", "6.2.3"]
+        );
+        assert_eq!(run.results.len(), 4);
+        assert_eq!(
+            run.results
+                .iter()
+                .map(|result| (result.rule_index, result.level))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(0), Level::Error),
+                (Some(2), Level::Error),
+                (Some(2), Level::Error),
+                (Some(3), Level::Note),
+            ]
+        );
+
+        let json = serde_json::to_value(&sarif).expect("must serialize");
+        assert_eq!(json["version"], "2.1.0");
+        assert_eq!(
+            json["runs"][0]["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "https://example.com/csaf/2023/c.json"
+        );
+        assert_eq!(
+            json["runs"][0]["results"][1]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "/vulnerabilities/0"
+        );
+        assert_eq!(
+            json["runs"][0]["results"][3]["properties"]["hint"],
+            "Add a score"
+        );
+    }
+}
@@ -15,7 +15,10 @@ use url::Url;
 use walker_common::{
     retrieve::{RetrievalError, RetrievalMetadata, RetrievedDigest, RetrievedDocument},
     utils::{openpgp::PublicKey, url::Urlify},
-    validate::source::{KeySource, KeySourceError},
+    validate::{
+        source::{KeySource, KeySourceError},
+        trust::{self, KeyTrust},
+    },
 };
 
 /// A retrieved (but unverified) advisory
@@ -141,6 +144,7 @@ where
 pub struct RetrievingVisitor<V: RetrievedVisitor<S>, S: Source + KeySource> {
     visitor: V,
     source: S,
+    trust: Option<KeyTrust>,
 }
 
 impl<V, S> RetrievingVisitor<V, S>
@@ -149,7 +153,22 @@ where
     S: Source + KeySource,
 {
     pub fn new(source: S, visitor: V) -> Self {
-        Self { visitor, source }
+        Self {
+            visitor,
+            source,
+            trust: None,
+        }
+    }
+
+    /// Use a local trust store for loading keys, in addition to (or instead of) the provider metadata.
+    ///
+    /// The provider name must be set on the [`KeyTrust`], e.g. using [`provider_name`] on the source
+    /// provided by the user.
+    ///
+    /// [`provider_name`]: walker_common::utils::url::provider_name
+    pub fn with_trust(mut self, trust: impl Into<Option<KeyTrust>>) -> Self {
+        self.trust = trust.into();
+        self
     }
 }
 
@@ -164,6 +183,8 @@ where
     Source(SE),
     #[error("Key source error: {0}")]
     KeySource(KeySourceError<KSE>),
+    #[error("Trust store error: {0}")]
+    Trust(trust::Error),
     #[error(transparent)]
     Visitor(VE),
 }
@@ -183,13 +204,19 @@ where
     ) -> Result<Self::Context, Self::Error> {
         let mut keys = Vec::with_capacity(context.metadata.public_openpgp_keys.len());
 
-        for key in &context.metadata.public_openpgp_keys {
-            keys.push(
-                self.source
-                    .load_public_key(key.into())
-                    .await
-                    .map_err(Error::KeySource)?,
-            );
+        if self.trust.as_ref().is_none_or(KeyTrust::use_metadata_keys) {
+            for key in &context.metadata.public_openpgp_keys {
+                keys.push(
+                    self.source
+                        .load_public_key(key.into())
+                        .await
+                        .map_err(Error::KeySource)?,
+                );
+            }
+        }
+
+        if let Some(trust) = &self.trust {
+            keys.extend(trust.pinned_keys().await.map_err(Error::Trust)?);
         }
 
         log::info!(
@@ -5,6 +5,7 @@ use crate::{
     retrieve::{AsRetrieved, RetrievalContext, RetrievedAdvisory, RetrievedVisitor},
     source::Source,
 };
+use sequoia_openpgp::Fingerprint;
 use std::{
     fmt::{Debug, Display, Formatter},
     future::Future,
@@ -15,19 +16,46 @@ use url::Url;
 use walker_common::{
     retrieve::RetrievalError,
     utils::{openpgp::PublicKey, url::Urlify},
-    validate::{ValidationOptions, digest::validate_digest, openpgp},
+    validate::{
+        RequiredDigest, ValidationOptions, digest::validate_digest, openpgp,
+        openpgp::SignatureVerification,
+    },
 };
 
 /// A validated CSAF document
 ///
 /// This includes
 /// * The document could be retrieved
-/// * The digest matches or was absent
-/// * The signature was valid
+/// * The digest matches or was absent (unless required by the [`ValidationOptions`])
+/// * The signature was valid or was absent (unless required by the [`ValidationOptions`])
 #[derive(Clone, Debug)]
 pub struct ValidatedAdvisory {
     /// The retrieved advisory
     pub retrieved: RetrievedAdvisory,
+    /// The result of the signature verification, if the document was signed
+    signature_verification: Option<SignatureVerification>,
+}
+
+impl ValidatedAdvisory {
+    pub fn new(retrieved: RetrievedAdvisory) -> Self {
+        Self {
+            retrieved,
+            signature_verification: None,
+        }
+    }
+
+    pub fn with_signature_verification(
+        mut self,
+        signature_verification: impl Into<Option<SignatureVerification>>,
+    ) -> Self {
+        self.signature_verification = signature_verification.into();
+        self
+    }
+
+    /// The result of the signature verification, `None` if the document was not signed
+    pub fn signature_verification(&self) -> Option<&SignatureVerification> {
+        self.signature_verification.as_ref()
+    }
 }
 
 impl Urlify for ValidatedAdvisory {
@@ -79,6 +107,17 @@ pub enum ValidationError<S: Source> {
         error: anyhow::Error,
         retrieved: RetrievedAdvisory,
     },
+    MissingDigest {
+        required: RequiredDigest,
+        retrieved: RetrievedAdvisory,
+    },
+    MissingSignature {
+        retrieved: RetrievedAdvisory,
+    },
+    UntrustedSigner {
+        signer: Fingerprint,
+        retrieved: RetrievedAdvisory,
+    },
 }
 
 impl<S: Source + Debug> AsDiscovered for ValidationError<S> {
@@ -87,6 +126,9 @@ impl<S: Source + Debug> AsDiscovered for ValidationError<S> {
             Self::Retrieval(err) => err.discovered(),
             Self::DigestMismatch { retrieved, .. } => retrieved.as_discovered(),
             Self::Signature { retrieved, .. } => retrieved.as_discovered(),
+            Self::MissingDigest { retrieved, .. } => retrieved.as_discovered(),
+            Self::MissingSignature { retrieved } => retrieved.as_discovered(),
+            Self::UntrustedSigner { retrieved, .. } => retrieved.as_discovered(),
         }
     }
 }
@@ -97,6 +139,9 @@ impl<S: Source> Urlify for ValidationError<S> {
             Self::Retrieval(err) => err.url(),
             Self::DigestMismatch { retrieved, .. } => &retrieved.url,
             Self::Signature { retrieved, .. } => &retrieved.url,
+            Self::MissingDigest { retrieved, .. } => &retrieved.url,
+            Self::MissingSignature { retrieved } => &retrieved.url,
+            Self::UntrustedSigner { retrieved, .. } => &retrieved.url,
         }
     }
 }
@@ -119,6 +164,15 @@ impl<S: Source> Display for ValidationError<S> {
             } => {
                 write!(f, "Invalid signature: {error}",)
             }
+            Self::MissingDigest {
+                required,
+                retrieved: _,
+            } => write!(f, "Missing digest - required: {required}"),
+            Self::MissingSignature { retrieved: _ } => write!(f, "Missing signature"),
+            Self::UntrustedSigner {
+                signer,
+                retrieved: _,
+            } => write!(f, "Untrusted signer - fingerprint: {}", signer.to_hex()),
         }
     }
 }
@@ -219,8 +273,10 @@ where
         }
     }
 
+    /// Set the validation options, applying the policy overrides of the provider.
     pub fn with_options(mut self, options: impl Into<ValidationOptions>) -> Self {
-        self.options = options.into();
+        let options: ValidationOptions = options.into();
+        self.options = options.effective().into_owned();
         self
     }
 
@@ -250,21 +306,41 @@ where
                 },
             ));
         }
+        if let Err(required) = self
+            .options
+            .check_digests(retrieved.sha256.is_some(), retrieved.sha512.is_some())
+        {
+            return Err(ValidationProcessError::Proceed(
+                ValidationError::MissingDigest {
+                    required,
+                    retrieved,
+                },
+            ));
+        }
+
+        let Some(signature) = &retrieved.signature else {
+            if self.options.require_signature {
+                return Err(ValidationProcessError::Proceed(
+                    ValidationError::MissingSignature { retrieved },
+                ));
+            }
+            return Ok(ValidatedAdvisory::new(retrieved));
+        };
 
-        if let Some(signature) = &retrieved.signature {
-            match openpgp::validate_signature(
-                &self.options,
-                &context.keys,
-                signature,
-                &retrieved.data,
-            ) {
-                Ok(()) => Ok(ValidatedAdvisory { retrieved }),
-                Err(error) => Err(ValidationProcessError::Proceed(
-                    ValidationError::Signature { error, retrieved },
-                )),
+        match openpgp::validate_signature(&self.options, &context.keys, signature, &retrieved.data)
+        {
+            Ok(verification) if self.options.is_allowed_signer(&verification.fingerprint) => {
+                Ok(ValidatedAdvisory::new(retrieved).with_signature_verification(verification))
             }
-        } else {
-            Ok(ValidatedAdvisory { retrieved })
+            Ok(verification) => Err(ValidationProcessError::Proceed(
+                ValidationError::UntrustedSigner {
+                    signer: verification.fingerprint,
+                    retrieved,
+                },
+            )),
+            Err(error) => Err(ValidationProcessError::Proceed(
+                ValidationError::Signature { error, retrieved },
+            )),
         }
     }
 }
@@ -324,3 +400,121 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{discover::DistributionContext, source::FileSource};
+    use sha2::{Digest, Sha256, Sha512};
+    use std::{sync::Arc, time::SystemTime};
+    use walker_common::retrieve::RetrievedDigest;
+
+    const DATA: &[u8] = b"{}";
+
+    fn advisory(sha256: Option<&str>, sha512: Option<&str>) -> RetrievedAdvisory {
+        let url = Url::parse("https://example.com/csaf/").expect("must parse");
+        RetrievedAdvisory {
+            discovered: DiscoveredAdvisory {
+                context: Arc::new(DistributionContext::Directory(url.clone())),
+                url: url.join("2023/cve.json").expect("must parse"),
+                digest: None,
+                signature: None,
+                modified: SystemTime::now(),
+            },
+            data: DATA.into(),
+            signature: None,
+            sha256: sha256.map(|expected| RetrievedDigest {
+                expected: expected.to_string(),
+                actual: Sha256::digest(DATA),
+            }),
+            sha512: sha512.map(|expected| RetrievedDigest {
+                expected: expected.to_string(),
+                actual: Sha512::digest(DATA),
+            }),
+            metadata: Default::default(),
+        }
+    }
+
+    async fn validate(
+        options: ValidationOptions,
+        advisory: RetrievedAdvisory,
+    ) -> Result<ValidatedAdvisory, ValidationError<FileSource>> {
+        let visitor = ValidationVisitor::new(
+            |_: Result<ValidatedAdvisory, ValidationError<FileSource>>| async {
+                Ok::<_, anyhow::Error>(())
+            },
+        )
+        .with_options(options);
+
+        let context = InnerValidationContext {
+            context: (),
+            keys: vec![],
+        };
+
+        match visitor.validate(&context, advisory).await {
+            Ok(advisory) => Ok(advisory),
+            Err(ValidationProcessError::Proceed(err)) => Err(err),
+            Err(ValidationProcessError::Abort(err)) => panic!("must not abort: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn digest_mismatch() {
+        let result = validate(Default::default(), advisory(Some("0000"), None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::DigestMismatch { expected, .. }) if expected == "0000"
+        ));
+
+        let result = validate(Default::default(), advisory(None, Some("0000"))).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::DigestMismatch { expected, .. }) if expected == "0000"
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_digest() {
+        let sha256 = format!("{:x}", Sha256::digest(DATA));
+        let sha512 = format!("{:x}", Sha512::digest(DATA));
+
+        let options = ValidationOptions::new().require_digest(true);
+        let result = validate(options.clone(), advisory(None, None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingDigest {
+                required: RequiredDigest::Any,
+                ..
+            })
+        ));
+        let result = validate(options, advisory(Some(&sha256), None)).await;
+        assert!(result.is_ok());
+
+        let options = ValidationOptions::new().require_sha512(true);
+        let result = validate(options.clone(), advisory(Some(&sha256), None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingDigest {
+                required: RequiredDigest::Sha512,
+                ..
+            })
+        ));
+        let result = validate(options, advisory(Some(&sha256), Some(&sha512))).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_signature() {
+        let result = validate(Default::default(), advisory(None, None))
+            .await
+            .expect("must be valid");
+        assert!(result.signature_verification().is_none());
+
+        let options = ValidationOptions::new().require_signature(true);
+        let result = validate(options, advisory(None, None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingSignature { .. })
+        ));
+    }
+}
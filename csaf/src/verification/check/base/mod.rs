@@ -1,6 +1,11 @@
-use crate::verification::check::{Check, CheckError, Checking};
+use crate::verification::check::{Check, CheckError, Checking, Finding};
 use csaf::Csaf;
 
+/// The rule ID of [`check_current_release_date`]
+pub const RULE_CURRENT_RELEASE_DATE: &str = "current-release-date";
+/// The rule ID of [`check_initial_release_date`]
+pub const RULE_INITIAL_RELEASE_DATE: &str = "initial-release-date";
+
 pub fn check_csaf_base(csaf: &Csaf) -> Vec<CheckError> {
     let mut result = vec![];
     result.extend(
@@ -70,6 +75,40 @@ pub fn check_csaf_document_tracking_revision_history(csaf: &Csaf) -> Vec<CheckEr
     result
 }
 
+/// The current release date must be the date of the newest revision
+///
+/// The optional test 6.2.6 only reports a current release date older than the newest revision.
+pub fn check_current_release_date(csaf: &Csaf) -> Vec<Finding> {
+    let tracking = &csaf.document.tracking;
+    match tracking.revision_history.iter().map(|r| r.date).max() {
+        Some(newest) if tracking.current_release_date != newest => vec![
+            Finding::new(format!(
+                "The CSAF tracking current release date ({}) must be equal to the date of the newest revision ({newest})",
+                tracking.current_release_date
+            ))
+            .path("/document/tracking/current_release_date"),
+        ],
+        _ => vec![],
+    }
+}
+
+/// The initial release date must be the date of the first revision
+///
+/// The optional test 6.2.5 only reports an initial release date older than the first revision.
+pub fn check_initial_release_date(csaf: &Csaf) -> Vec<Finding> {
+    let tracking = &csaf.document.tracking;
+    match tracking.revision_history.iter().map(|r| r.date).min() {
+        Some(oldest) if tracking.initial_release_date != oldest => vec![
+            Finding::new(format!(
+                "The CSAF tracking initial release date ({}) must be equal to the date of the first revision ({oldest})",
+                tracking.initial_release_date
+            ))
+            .path("/document/tracking/initial_release_date"),
+        ],
+        _ => vec![],
+    }
+}
+
 pub fn init_csaf_base_verifying_visitor() -> Vec<(&'static str, Box<dyn Check>)> {
     vec![
         ("check_csaf_base", Box::new(check_csaf_base)),
@@ -77,15 +116,28 @@ pub fn init_csaf_base_verifying_visitor() -> Vec<(&'static str, Box<dyn Check>)>
             "check_csaf_document_tracking_revision_history",
             Box::new(check_csaf_document_tracking_revision_history),
         ),
+        (
+            RULE_CURRENT_RELEASE_DATE,
+            Box::new(check_current_release_date),
+        ),
+        (
+            RULE_INITIAL_RELEASE_DATE,
+            Box::new(check_initial_release_date),
+        ),
     ]
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::verification::check::base::{
-        check_csaf_base, check_csaf_document_tracking_revision_history,
+    use crate::verification::check::{
+        Finding,
+        base::{
+            check_csaf_base, check_csaf_document_tracking_revision_history,
+            check_current_release_date, check_initial_release_date,
+        },
     };
     use csaf::Csaf;
+    use serde_json::{Value, json};
 
     #[tokio::test]
     async fn test_check_csaf_base() {
@@ -105,4 +157,39 @@ mod tests {
             2
         )
     }
+
+    #[test]
+    fn test_release_dates() {
+        let mut json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+        assert!(check_current_release_date(&csaf).is_empty());
+        assert!(check_initial_release_date(&csaf).is_empty());
+
+        // newer than the newest, and older than the first revision
+        let tracking = &mut json["document"]["tracking"];
+        tracking["current_release_date"] = json!("2021-08-11T16:13:00Z");
+        tracking["initial_release_date"] = json!("2021-08-09T16:13:00Z");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("test data must parse");
+
+        assert_eq!(
+            check_current_release_date(&csaf),
+            vec![
+                Finding::new(
+                    "The CSAF tracking current release date (2021-08-11 16:13:00 UTC) must be equal to the date of the newest revision (2021-08-10 16:13:00 UTC)"
+                )
+                .path("/document/tracking/current_release_date")
+            ]
+        );
+        assert_eq!(
+            check_initial_release_date(&csaf),
+            vec![
+                Finding::new(
+                    "The CSAF tracking initial release date (2021-08-09 16:13:00 UTC) must be equal to the date of the first revision (2021-08-10 16:13:00 UTC)"
+                )
+                .path("/document/tracking/initial_release_date")
+            ]
+        );
+    }
 }
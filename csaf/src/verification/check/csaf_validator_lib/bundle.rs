@@ -0,0 +1,166 @@
+use super::InnerCheck;
+use anyhow::{Context, anyhow};
+use semver::{Version, VersionReq};
+use std::{borrow::Cow, path::Path};
+
+/// The versions of `csaf_validator_lib` an external bundle may be built from.
+///
+/// The bundle must provide the same API (the function registered with `op_register_func`) as the
+/// embedded one.
+pub const SUPPORTED_VERSIONS: &str = ">=1.3.0, <2.0.0";
+
+/// A JavaScript module
+#[derive(Clone, Debug)]
+pub struct Module {
+    pub name: String,
+    pub code: Cow<'static, str>,
+}
+
+impl Module {
+    /// Load a module from a file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let code = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read JavaScript module: {}", path.display()))?;
+
+        Ok(Self {
+            name: path.display().to_string(),
+            code: code.into(),
+        })
+    }
+}
+
+/// The JavaScript code running the validation.
+///
+/// By default, this is the bundle embedded into the binary. An external bundle can be loaded at
+/// runtime, which must register its `csaf_validator_lib` version using `op_register_version`,
+/// matching [`SUPPORTED_VERSIONS`].
+///
+/// Additional modules may provide custom tests. Each of them must register a single test function,
+/// using `op_register_func`. The function gets called with the document and must return a result
+/// like the tests of `csaf_validator_lib` (`{ isValid, errors, warnings, infos }`). The name of the
+/// function is used as the name of the test.
+#[derive(Clone, Debug)]
+pub struct Bundle {
+    pub(crate) main: Module,
+    pub(crate) external: bool,
+    pub(crate) tests: Vec<Module>,
+}
+
+impl Default for Bundle {
+    fn default() -> Self {
+        Self::embedded()
+    }
+}
+
+impl Bundle {
+    /// The bundle embedded into the binary
+    pub fn embedded() -> Self {
+        #[cfg(debug_assertions)]
+        let code = include_str!("js/bundle.debug.js");
+        #[cfg(not(debug_assertions))]
+        let code = include_str!("js/bundle.js");
+
+        Self {
+            main: Module {
+                name: "embedded".to_string(),
+                code: code.into(),
+            },
+            external: false,
+            tests: vec![],
+        }
+    }
+
+    /// Load an external bundle from a file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self {
+            main: Module::load(path)?,
+            external: true,
+            tests: vec![],
+        })
+    }
+
+    /// Add a module, providing a custom test.
+    pub fn add_test(mut self, module: Module) -> Self {
+        self.tests.push(module);
+        self
+    }
+
+    /// Add modules, providing custom tests.
+    pub fn extend_tests(mut self, modules: impl IntoIterator<Item = Module>) -> Self {
+        self.tests.extend(modules);
+        self
+    }
+
+    /// Load the bundle and its tests into a runtime, checking the version of the bundle.
+    ///
+    /// Each runtime of a [`super::CsafValidatorLib`] loads the bundle when it processes its first
+    /// document. Verifying the bundle upfront reports an unusable bundle before processing any
+    /// document. As a runtime can't be moved between threads, it is created on a dedicated one.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .context("Failed to create validator runtime")?
+                        .block_on(InnerCheck::new(self))
+                        .map(|_| ())
+                })
+                .join()
+                .map_err(|_| anyhow!("validator runtime failed"))?
+        })
+        .with_context(|| format!("Failed to load bundle '{}'", self.main.name))
+    }
+
+    /// Check the version registered by the bundle.
+    pub(crate) fn check_version(&self, version: Option<&str>) -> anyhow::Result<()> {
+        if !self.external {
+            // we know what we embedded
+            return Ok(());
+        }
+
+        let Some(version) = version else {
+            anyhow::bail!(
+                "Bundle '{}' did not register its version (using 'op_register_version')",
+                self.main.name
+            );
+        };
+
+        let version = Version::parse(version)
+            .with_context(|| format!("Invalid version of bundle '{}'", self.main.name))?;
+        let supported =
+            VersionReq::parse(SUPPORTED_VERSIONS).expect("supported versions must parse");
+
+        if !supported.matches(&version) {
+            anyhow::bail!(
+                "Version {version} of bundle '{}' is not supported (requires: {supported})",
+                self.main.name
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn version() {
+        let bundle = Bundle {
+            external: true,
+            ..Bundle::embedded()
+        };
+
+        assert!(bundle.check_version(Some("1.3.23")).is_ok());
+        assert!(bundle.check_version(Some("2.0.0")).is_err());
+        assert!(bundle.check_version(Some("1.2.0")).is_err());
+        assert!(bundle.check_version(Some("latest")).is_err());
+        assert!(bundle.check_version(None).is_err());
+
+        assert!(Bundle::embedded().check_version(None).is_ok());
+    }
+}
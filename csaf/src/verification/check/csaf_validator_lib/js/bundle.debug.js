@@ -46563,6 +46563,8 @@ var optional = /*#__PURE__*/Object.freeze({
   optionalTest_6_2_9: optionalTest_6_2_9
 });
 
+var version = "1.3.23";
+
 // main script for the JS integration.
 //
 // Whenever you make changes to this script, you will need to re-run `npm build`.
@@ -46594,4 +46596,5 @@ async function runValidation(validations, doc, ignore) {
   return validateLib(tests, doc);
 }
 
+Deno.core.ops.op_register_version(version);
 Deno.core.ops.op_register_func(runValidation);
@@ -189,4 +189,4 @@ var id,pd,rd={_:{assert:Jc,DateTimeBuilder:Pf,DateTimeParseContext:Mf,DateTimePr
 /*
  * @copyright (c) 2015-present, Philipp Thürwächter, Pattrick Hüper & js-joda contributors
  * @license BSD-3-Clause (see LICENSE in the root directory of this source tree)
- */,nativeJs:function(e,t){if(void 0===t&&(t=Nf.systemDefault()),Gc(e,"date"),Gc(t,"zone"),e instanceof Date)return Gy.ofEpochMilli(e.getTime()).atZone(t);if("function"==typeof e.toDate&&e.toDate()instanceof Date)return Gy.ofEpochMilli(e.toDate().getTime()).atZone(t);throw new zc("date must be a javascript Date or a moment instance")},ArithmeticException:Uc,DateTimeException:Wc,DateTimeParseException:Fc,IllegalArgumentException:zc,IllegalStateException:Vc,UnsupportedTemporalTypeException:jc,NullPointerException:Hc,Clock:Ky,DayOfWeek:xf,Duration:uf,Instant:Gy,LocalDate:Hy,LocalTime:By,LocalDateTime:Yy,OffsetTime:Wy,OffsetDateTime:Uy,Month:mf,MonthDay:Iy,ParsePosition:Ef,Period:vf,Year:Ty,YearConstants:lf,YearMonth:Ay,ZonedDateTime:jy,ZoneOffset:Rf,ZoneId:Nf,ZoneRegion:fy,ZoneOffsetTransition:ed,ZoneRules:Af,ZoneRulesProvider:cy,ChronoLocalDate:Of,ChronoLocalDateTime:qy,ChronoZonedDateTime:Fy,IsoChronology:Ly,ChronoField:gf,ChronoUnit:pf,IsoFields:Wf,Temporal:Cf,TemporalAccessor:ff,TemporalAdjuster:Dy,TemporalAdjusters:Ry,TemporalAmount:nf,TemporalField:rf,TemporalQueries:cf,TemporalQuery:yf,TemporalUnit:sf,ValueRange:of,DateTimeFormatter:Cy,DateTimeFormatterBuilder:my,DecimalStyle:Jf,ResolverStyle:Sf,SignStyle:Qf,TextStyle:ey},od=(id=rd,pd=[],function(e){return~pd.indexOf(e)||(e(id),pd.push(e)),id});rd.use=od;const gd=(e,t)=>{try{const a=e instanceof jy?e:jy.parse(e),n=t instanceof jy?t:jy.parse(t),s=uf.between(a,n);return s.isZero()?0:s.isNegative()?1:-1}catch(e){return 0}},{gt:cd,valid:fd}=fp;const yd=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date));const{hasTrackingRevisionHistory:dd,hasTrackingVersionField:bd,hasTrackingStatusField:xd}=bp;const{valid:hd,major:md,prerelease:_d}=fp,{hasTrackingVersionField:vd,hasTrackingStatusField:Ed}=bp;const{valid:wd,major:Sd}=fp,{hasTrackingVersionField:Cd,hasTrackingStatusField:Od,hasTrackingRevisionHistory:Id}=bp;const{valid:Nd,prerelease:Ad}=fp,{hasTrackingVersionField:kd,hasTrackingStatusField:Td,hasTrackingRevisionHistory:Dd}=bp;const{valid:Rd,prerelease:Pd}=fp,{hasTrackingVersionField:Md,hasTrackingStatusField:$d}=bp;const Ld=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date)),{hasTrackingVersionField:Wd,hasTrackingStatusField:Fd,hasTrackingRevisionHistory:jd}=bp;const Ud=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>"string"==typeof e.cve||void 0===e.cve))};const zd=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>Array.isArray(e.involvements)&&e.involvements.every((e=>!("string"!=typeof e.date&&void 0!==e.date||"string"!=typeof e.party&&void 0!==e.party||"string"!=typeof e.status&&void 0!==e.status)))))};const Vd=(e,t,a=[])=>{Array.isArray(e.branches)&&e.branches.forEach(((e,n)=>{const s=a.concat([n]);Array.isArray(e.product?.product_identification_helper?.hashes)&&e.product.product_identification_helper.hashes.forEach(((e,a)=>{Hd(e,(({fileHash:e,fileHashIndex:n})=>{t({branchIndexes:s,hashIndex:a,fileHash:e,fileHashIndex:n})}))})),Vd(e,t,s)}))},Hd=(e,t)=>{if(!Array.isArray(e.file_hashes))return;const a=new Set;e.file_hashes.forEach(((e,n)=>{null!=e.algorithm&&(a.has(e.algorithm)&&t({fileHash:e,fileHashIndex:n}),a.add(e.algorithm))}))};const qd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Yd=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,optionalProperties:{revision_history:{elements:{additionalProperties:!0,optionalProperties:{number:{type:"string"}}}},version:{type:"string"}}}}}}});function Bd(e){return parseInt(e).toString()===e?"INTEGER":"SEMANTIC"}const Xd=new Sg,Gd=Xd.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Kd=Xd.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"},branches:{elements:{additionalProperties:!0,properties:{}}}}});const Zd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Jd=(new Sg).compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}},vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});var Qd=Object.freeze({__proto__:null,mandatoryTest_6_1_1:function(e){const t=[];let a=!0;const n=xp({document:e}),s=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e].product_ids;if(n)for(let a=0;a<n.length;++a){const s=n[a];s&&t.push({id:s,instancePath:`/product_tree/product_groups/${e}/product_ids/${a}`})}}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e],s=a.product_reference;s&&t.push({id:s,instancePath:`/product_tree/relationships/${e}/product_reference`});const u=a.relates_to_product_reference;u&&t.push({id:u,instancePath:`/product_tree/relationships/${e}/relates_to_product_reference`})}const s=e.vulnerabilities;if(s)for(let e=0;e<s.length;++e){const a=s[e];hp(`/vulnerabilities/${e}/product_status`,a,t),Ep(`/vulnerabilities/${e}/remediations`,a,t),vp(`/vulnerabilities/${e}/scores`,a,t),_p(`/vulnerabilities/${e}/threats`,a,t)}return t}({document:e}),u=wp(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of product id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_10:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2;hc({vectorValues:u.vectorString.split("/"),vectorMapping:xc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${e}`,message:"value is not consistent with the vector string"})}})}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3;hc({vectorValues:u.vectorString.split("/").slice(1),vectorMapping:bc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${e}`,message:"value is not consistent with the vector string"})}})}}))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_11:function(e){const t=[];let a=!0;if(_c(e))for(let n=0;n<e.vulnerabilities.length;++n){const s=e.vulnerabilities[n];if(vc(s)){const e=mc.weaknesses.find((e=>e.id===s.cwe.id));if(!e){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/id`,message:"no weakness with this id is recognized"});continue}if(e.name!==s.cwe.name){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/name`,message:"the name does not match the weakness with the given id"});continue}}}return{isValid:a,errors:t}},mandatoryTest_6_1_12:function(e){const t=[];let a=!0;return Ac(e)&&!Cc(e.document.lang)&&(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/lang"})),kc(e)&&(Cc(e.document.source_lang)||(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/source_lang"}))),{errors:t,isValid:a}},mandatoryTest_6_1_13:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{$c(e,(()=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/purl`,message:"invalid purl"})}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{$c(e.full_product_name,(()=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/purl`,message:"invalid purl"})}))})),e.product_tree&&Mc(e.product_tree,(({branchIndexes:e})=>{a=!1;const n=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${n}/product/product_identification_helper/purl`,message:"invalid purl"})})),{errors:t,isValid:a}},mandatoryTest_6_1_14:function(e){const t=[];let a=!0;if(yd(e)){Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)||dp(t.number,e.number))).map((e=>fd(e.number)??`${e.number}.0.0`))).keys()).filter((e=>null!==fd(e))).every(((e,t,a)=>0===t||cd(e,a[t-1])))||(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"the items must be in ascending order according to the date"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_15:function(e){let t=!0;const a=[];return"translator"!==e.document?.publisher?.category||e.document.source_lang||(t=!1,a.push({instancePath:"/document/publisher/category",message:"source language attribute is missing"})),{isValid:t,errors:a}},mandatoryTest_6_1_16:function(e){let t=!0;const a=[];if(dd(e)&&bd(e)&&xd(e)&&e.document.tracking.revision_history.length>0){const n=t=>"draft"==e.document.tracking.status?t.split(/[+-]/)[0]:t.split("+")[0];n(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)||dp(e.number,t.number)))[0].number)!==n(e.document.tracking.version)&&(t=!1,a.push({message:"version does not match latest revision",instancePath:"/document/tracking/version"}))}return{errors:a,isValid:t}},mandatoryTest_6_1_17:function(e){let t=!0;const a=[];return vd(e)&&Ed(e)&&"draft"!==e.document.tracking.status&&("0"===e.document.tracking.version||hd(e.document.tracking.version)&&(0===md(e.document.tracking.version)||_d(e.document.tracking.version)))&&(t=!1,a.push({message:"the status is not compatible with the version",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_18:function(e){let t=!0;const a=[];return Cd(e)&&Od(e)&&Id(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&e.document.tracking.revision_history.some((e=>"0"===e.number||wd(e.number)&&0===Sd(e.number)))&&(t=!1,a.push({message:"some revision-history entries are not compatible with the status",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_19:function(e){let t=!0;const a=[];if(kd(e)&&Td(e)&&Dd(e))for(let n=0;n<e.document.tracking.revision_history.length;++n){const s=e.document.tracking.revision_history[n];Nd(s.number)&&Ad(s.number)&&(t=!1,a.push({message:"contains prerelease part",instancePath:`/document/tracking/revision_history/${n}/number`}))}return{errors:a,isValid:t}},mandatoryTest_6_1_2:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.full_product_names;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.product_id&&t.push({id:n.product_id,name:n.name??"",instancePath:`/product_tree/full_product_names/${e}/product_id`})}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e].full_product_name;a&&a.product_id&&t.push({id:a.product_id,name:a.name??"",instancePath:`/product_tree/relationships/${e}/full_product_name/product_id`})}const s=e.product_tree?.branches;s&&Cp(s,t,"/product_tree/branches");return t}({document:e}),s=Sp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_20:function(e){let t=!0;const a=[];return Md(e)&&$d(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&Rd(e.document.tracking.version)&&Pd(e.document.tracking.version)&&(t=!1,a.push({message:"pre-release part is not allowed for status",instancePath:"/document/tracking/version"})),{errors:a,isValid:t}},mandatoryTest_6_1_21:function(e){const t=[];let a=!0;if(Ld(e)){const n=Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date))).map((e=>parseInt(e.number)))).keys());n.length>0&&![0,1].includes(n[0])&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"revision history does not start with a version of 0 or 1 when sorted by date"}));for(let e=0;e<n.length;++e){const s=e+Number(n[0]);n[e]>s&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:`major version ${s} was omitted`}))}}return{errors:t,isValid:a}},mandatoryTest_6_1_22:function(e){let t=!0;const a=[];if(Wd(e)&&Fd(e)&&jd(e)){let n={};e.document.tracking.revision_history.forEach(((e,s)=>{n[e.number]=n[e.number]??[],n[e.number].push(s),n[e.number].length>1&&(t=!1,a.push({message:"version was already used",instancePath:`/document/tracking/revision_history/${s}/number`}))}))}return{errors:a,isValid:t}},mandatoryTest_6_1_23:function(e){const t=[];let a=!0;if(Ud(e)){const n=new Set;e.vulnerabilities.forEach(((e,s)=>{void 0!==e.cve&&(n.has(e.cve)&&(a=!1,t.push({message:"CVE identifier was already used",instancePath:`/vulnerabilities/${s}/cve`})),n.add(e.cve))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_24:function(e){const t=[];let a=!0;return zd(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.involvements.forEach(((e,u)=>{if("string"==typeof e.date&&"string"==typeof e.party){const l=s.get(e.date)??new Set;l.has(`${e.party}`)&&(a=!1,t.push({message:"status for party was already given for the same date",instancePath:`/vulnerabilities/${n}/involvements/${u}`})),l.add(`${e.party}`),s.set(e.date,l)}}))})),{errors:t,isValid:a}},mandatoryTest_6_1_25:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{Array.isArray(e.product_identification_helper?.hashes)&&e.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{Array.isArray(e.full_product_name?.product_identification_helper?.hashes)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),e.product_tree&&Vd(e.product_tree,(({branchIndexes:e,hashIndex:n,fileHashIndex:s,fileHash:u})=>{a=!1;const l=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${l}/product/product_identification_helper/hashes/${n}/file_hashes/${s}`,message:`there is already a hash with the algorithm ${u.algorithm}`})})),{errors:t,isValid:a}},mandatoryTest_6_1_26:function(e){const t=[];let a=!0;if("string"==typeof e.document?.category){const n=e.document.category,s=["securityincidentresponse","informationaladvisory","securityadvisory","vex"];if(["csaf_base","csaf_security_incident_response","csaf_informational_advisory","csaf_security_advisory","csaf_vex"].includes(n))return{errors:t,isValid:a};if(n.toLowerCase().startsWith("csaf_"))return a=!1,t.push({instancePath:"/document/category",message:"reserved prefix used"}),{errors:t,isValid:a};s.includes(n.replace(/[_-\s]+/g,"").toLowerCase())&&(a=!1,t.push({instancePath:"/document/category",message:"value prohibited"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_27_1:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["description","details","general","summary"]);return a=Array.isArray(e.document?.notes)&&e.document.notes.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/notes",message:"need at least one document note with a category of description, details, general or summary"}),{errors:t,isValid:a}},mandatoryTest_6_1_27_10:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_affected)&&u.known_affected.forEach(((u,l)=>{(Array.isArray(n.remediations)?n.remediations:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}))||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_affected/${l}`,message:"no suitable action statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_11:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.vulnerabilities),a||t.push({instancePath:"/",message:"needs vulnerabilities"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_2:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["external"]);return a=Array.isArray(e.document?.references)&&e.document.references.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/references",message:'need at least one document reference with the category "external"'}),{errors:t,isValid:a}},mandatoryTest_6_1_27_3:function(e){const t=[];let a=!0;return new Set(["csaf_informational_advisory"]).has(e.document?.category)?(a=void 0===e.vulnerabilities,a||t.push({instancePath:"/vulnerabilities",message:"must not exist"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_4:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.product_tree),a||t.push({instancePath:"/",message:"needs a product_tree"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_5:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.notes||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `notes` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_6:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.product_status||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_7:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{if(!e.product_status)return a=!1,void t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"});["fixed","known_affected","known_not_affected","under_investigation"].some((t=>Array.isArray(e.product_status[t])))||(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:"needs at least one the following attributes: `fixed`, `known_affected`, `known_not_affected`, `under_investigation`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_8:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{["ids","cve"].every((t=>void 0===e[t]))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs at least one the following attributes: `ids`, `cve`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_9:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_not_affected)&&u.known_not_affected.forEach(((u,l)=>{const i=(Array.isArray(n.threats)?n.threats:[]).some((t=>{if(!t||"impact"!==t.category)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))})),p=(Array.isArray(n.flags)?n.flags:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}));i||p||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_not_affected/${l}`,message:"no suitable impact statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_28:function(e){const t=[];let a=!0;return"string"==typeof e.document?.source_lang&&"string"==typeof e.document?.lang&&e.document.source_lang===e.document.lang&&(a=!1,t.push({instancePath:"/document/lang",message:"is the same as `/document/source_lang`"},{instancePath:"/document/source_lang",message:"is the same as `/document/lang`"})),{isValid:a,errors:t}},mandatoryTest_6_1_29:function(e){const t={errors:[],isValid:!0};return qd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.remediations.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/remediations/${n}`,message:"remediation without product reference"}))}))})),t):t},mandatoryTest_6_1_3:function(e){const t=[];let a=!0;if(!Array.isArray(e.product_tree?.relationships))return{isValid:a,errors:t};const n=e.product_tree.relationships;return n.forEach(((e,s)=>{const u=new Set;Op([],n,s,null,(({key:e})=>{"string"!=typeof e||u.has(e)||(u.add(e),a=!1,t.push({instancePath:`/product_tree/relationships/${s}/${e}`,message:"circular reference"}))}))})),{isValid:a,errors:t}},mandatoryTest_6_1_30:function(e){const t={errors:[],isValid:!0};if(!Yd(e))return t;let a=null;return e.document.tracking.revision_history?.forEach(((e,n)=>{if("string"==typeof e.number){const s=Bd(e.number);null===a&&(a=s),a!==s&&(t.isValid=!1,t.errors.push({instancePath:`/document/tracking/revisions/${n}/number`,message:"mixed integer and semantic versioning"}))}})),null!=a&&"string"==typeof e.document.tracking.version&&a!==Bd(e.document.tracking.version)&&(t.isValid=!1,t.errors.push({instancePath:"/document/tracking/version",message:"mixed integer and semantic versioning"})),t},mandatoryTest_6_1_31:function(e){const t={errors:[],isValid:!0};return Gd(e)?(function e({path:a,branches:n}){n.forEach(((n,s)=>{Kd(n)&&("product_version"===n.category&&"string"==typeof n.name&&(["<","<=",">",">="].some((e=>n.name?.toLowerCase().includes(e)))||["after","all","before","earlier","later","prior","versions"].some((e=>n.name?.toLowerCase().split(/\s/).some((t=>e===t)))))&&(t.isValid=!1,t.errors.push({instancePath:`${a}/${s}/name`,message:"version range in product version"})),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},mandatoryTest_6_1_32:function(e){const t={errors:[],isValid:!0};return Zd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.flags.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/flags/${n}`,message:"flag without product reference"}))}))})),t):t},mandatoryTest_6_1_33:function(e){const t={errors:[],isValid:!0};return Jd(e)?(e.vulnerabilities.forEach(((a,n)=>{const s=new Set;a.flags.forEach(((a,u)=>{let l=!1;function i(){l||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${n}/flags/${u}`,message:"multiple flags with vex justification codes per product"})),l=!0}a.product_ids?.forEach((e=>{s.has(e)&&i(),s.add(e)})),a.group_ids?.forEach((t=>{const a=e.product_tree.product_groups?.find((e=>e.group_id===t))?.product_ids;a?.forEach((e=>{s.has(e)&&i(),s.add(e)}))}))}))})),t):t},mandatoryTest_6_1_4:function(e){const t=[];let a=!0;const n=Np({document:e}),s=function({document:e}){const t=[],a=e.vulnerabilities;if(a)for(let e=0;e<a.length;++e){const n=a[e];Ap(`/vulnerabilities/${e}/remediations`,n,t),kp(`/vulnerabilities/${e}/threats`,n,t)}return t}({document:e}),u=Ip(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of group id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_5:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.group_id&&t.push({id:n.group_id,name:n.summary??"",instancePath:`/product_tree/product_groups/${e}/group_id`})}return t}({document:e}),s=Tp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product group id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_6:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{const s=e.product_status;if(!s)return;const u=[new Set([].concat(Array.isArray(s.first_affected)?s.first_affected:[]).concat(Array.isArray(s.known_affected)?s.known_affected:[]).concat(Array.isArray(s.last_affected)?s.last_affected:[])),new Set(Array.isArray(s.known_not_affected)?s.known_not_affected:[]),new Set([].concat(Array.isArray(s.first_fixed)?s.first_fixed:[]).concat(Array.isArray(s.fixed)?s.fixed:[])),new Set(Array.isArray(s.under_investigation)?s.under_investigation:[])];u.forEach(((e,s)=>{const l=u.slice(s+1);e.forEach((e=>{l.some((t=>t.has(e)))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:`product id "${e}" is mentioned in contradicting product status groups`}))}))}))}))}return{isValid:a,errors:t}},mandatoryTest_6_1_7:function(e){const t=[];let a=!0;return Dp(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.scores?.forEach(((e,u)=>{e.products?.forEach(((l,i)=>{const p=s.get(l)??new Set;s.set(l,p),(void 0!==e.cvss_v2?.version&&p.has(e.cvss_v2.version)||void 0!==e.cvss_v3?.version&&p.has(e.cvss_v3.version))&&(a=!1,t.push({message:`product is already included in these cvss-versions: ${Array.from(p.keys()).join(", ")}`,instancePath:`/vulnerabilities/${n}/scores/${u}/products/${i}`})),void 0!==e.cvss_v2?.version&&p.add(e.cvss_v2.version),void 0!==e.cvss_v3?.version&&p.add(e.cvss_v3.version)}))}))})),{errors:t,isValid:a}},mandatoryTest_6_1_8:function(e){const t={errors:[],isValid:!0};if(!Cg(e))return t;for(const[a,n]of e.vulnerabilities?.entries()??[])for(const[e,s]of n.scores?.entries()??[]){if(s.cvss_v2){if(!Og(s.cvss_v2)){t.isValid=!1;for(const n of Og.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v2${n.instancePath}`,message:n.message??""})}}if(s.cvss_v3){if(!Ig(s.cvss_v3)){t.isValid=!1;for(const n of Ig.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v3${n.instancePath}`,message:n.message??""})}}}return t},mandatoryTest_6_1_9:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2,l=function(e){try{return{success:!0,baseMetricScore:tc.getBaseScore(e),temporalMetricScore:tc.getTemporalScore(e),environmentalMetricScore:ac(e)}}catch(e){return{success:!1,baseMetricScore:-1,temporalMetricScore:-1,environmentalMetricScore:-1}}}(u.vectorString);if(l.success)for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${p}`,message:"invalid calculated value"}))}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3,l=("3.0"===u.version?fc:yc).calculateCVSSFromVector(u.vectorString);if(l.success){for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}));for(const{severity:e,expectedSeverity:i,name:p}of[{severity:u.baseSeverity,expectedSeverity:l.baseSeverity,name:"baseSeverity"},{severity:u.temporalSeverity,expectedSeverity:l.temporalSeverity,name:"temporalSeverity"},{severity:u.environmentalSeverity,expectedSeverity:l.environmentalSeverity,name:"environmentalSeverity"}])"string"==typeof e&&e!==i.toUpperCase()&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}))}}}))}))}return{errors:t,isValid:a}}});const eb=new Sg,tb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}},optionalProperties:{document:{additionalProperties:!0,optionalProperties:{category:{type:"string"}}}}}),ab={additionalProperties:!0,properties:{product_id:{type:"string"}}},nb=eb.compile(ab),sb={additionalProperties:!0,optionalProperties:{product:ab,branches:{elements:{additionalProperties:!0,properties:{}}}}},ub=eb.compile(sb),lb={additionalProperties:!0,properties:{full_product_name:ab}},ib=eb.compile(lb);const pb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}),rb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{relationships:{elements:{additionalProperties:!0,optionalProperties:{product_reference:{type:"string"},relates_to_product_reference:{type:"string"}}}}}}}}),ob=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}},fixed:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},known_not_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},recommended:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}}}}}}),gb=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}},threats:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}});function cb(e,t){let a=!1;return!a&&pb(e)&&(a=e.product_tree.product_groups.some((e=>e.product_ids?.includes(t)??!1))),!a&&rb(e)&&(a=e.product_tree.relationships.some((e=>e.product_reference===t||e.relates_to_product_reference===t))),!a&&ob(e)&&(a=e.vulnerabilities.some((e=>["first_affected","first_fixed","fixed","known_affected","known_not_affected","last_affected","recommended","under_investigation"].some((a=>e.product_status?.[a]?.includes(t)??!1))))),!a&&gb(e)&&(a=e.vulnerabilities.some((e=>e.remediations?.some((e=>e.product_ids?.includes(t)))||e.scores?.some((e=>e.products?.includes(t)))||e.threats?.some((e=>e.product_ids?.includes(t)))||!1))),a}const fb=(new Sg).compile({additionalProperties:!0,optionalProperties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}}},properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}},optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}},group_ids:{elements:{type:"string"}}}}}}}}}});const yb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}}}}},optionalProperties:{scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}}}}}}});const db=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{revision_history:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const bb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{initial_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const xb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{current_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const hb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{involvements:{elements:{additionalProperties:!0,properties:{}}}}}}}});const mb=new Sg,_b=mb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),vb=mb.compile({additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}),Eb=mb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}}),wb=mb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const Sb=(new Sg).compile({additionalProperties:!0,properties:{file_hashes:{elements:{additionalProperties:!0,properties:{}}}}});function Cb(e,t){const a={warnings:[]};return function(e,t){const a={warnings:[]};if(!_b(e))return a;e.product_tree.full_product_names?.forEach(((e,a)=>{vb(e)&&e.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/full_product_names/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}));const n=(e,a)=>{a.forEach(((a,s)=>{wb(a)&&(a.product?.product_identification_helper.hashes.forEach(((a,n)=>{t({path:`${e}${s}/product_identification_helper/hashes/${n}`,hash:a})})),n(`${e}${s}/branches/`,Array.isArray(a.branches)?a.branches:[]))}))};n("/product_tree/branches/",e.product_tree.branches??[]),e.product_tree.relationships?.forEach(((e,a)=>{Eb(e)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/relationships/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}))}(e,(({path:e,hash:n})=>{if(!Sb(n))return;const s=function(e){return new Set(e.file_hashes.map((e=>e.algorithm)).filter((e=>"string"==typeof e)))}(n);s.has(t)&&1===s.size&&a.warnings.push({instancePath:e,message:`use of ${t} as the only hash algorithm`})})),a}const Ob=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{distribution:{additionalProperties:!0,properties:{tlp:{additionalProperties:!0,properties:{label:{type:"string"}}}}}}}}});const Ib=new Sg,Nb=Ib.compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{references:{elements:{additionalProperties:!0,properties:{}}},tracking:{additionalProperties:!0,properties:{id:{type:"string"}}}}}}}),Ab=Ib.compile({additionalProperties:!0,properties:{category:{type:"string"},url:{type:"string"}}});const kb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{lang:{type:"string"}}}}});const Tb=new Sg,Db=Tb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),Rb=Tb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}}),Pb=Tb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}});const Mb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{ids:{elements:{additionalProperties:!0,optionalProperties:{text:{type:"string"}}}}}}}}});const $b=new Sg,Lb=$b.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Wb=$b.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"}}});const Fb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{fixed:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{cvss_v3:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},cvss_v2:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},products:{elements:{type:"string"}}}}}}}}}});const jb=rc.map((e=>[e[0],e[1],Object.fromEntries(Object.entries(e[2]).map((([e,t])=>[e,t.id])))])),Ub=dc;function zb({version:e,vectorString:t,metrics:a}){const n=new Map(t.split("/").map((e=>{const[t,a]=e.split(":");return[t,a]})).filter((([,e])=>e)));if("3.1"===e||"3.0"===e){const t=Vb({mapping:Ub,metrics:a,vector:n}).map((e=>e[1])),s=("3.1"===e?yc:fc).calculateCVSSFromMetrics(...t);return s.success?Number(s.environmentalMetricScore):null}{const e=function(e){try{return{success:!0,environmentalMetricScore:ac(e)}}catch(e){return{success:!1,environmentalMetricScore:-1}}}(Object.fromEntries(Vb({mapping:jb,metrics:a,vector:n})));return e.success?e.environmentalMetricScore:null}}function Vb({vector:e,metrics:t,mapping:a}){return a.map((a=>{const n=a[1],s=a[0],u=a[2],l=t[s];return[n,e.get(n)??u[l]]}))}const Hb=Mu.compile($u);var qb=Object.freeze({__proto__:null,optionalTest_6_2_1:function(e){const t=[],a={warnings:t};return tb(e)&&"csaf_informational_advisory"!==e.document?.category?(function a({path:n,branches:s}){s.forEach(((s,u)=>{ub(s)&&("string"!=typeof s.product?.product_id||cb(e,s.product.product_id)||t.push({instancePath:`${n}/${u}/product/product_id`,message:"is not referenced"}),Array.isArray(s.branches)&&a({path:`${n}/${u}/branches`,branches:s.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree?.branches??[]}),e.product_tree.full_product_names?.forEach(((t,n)=>{nb(t)&&(cb(e,t.product_id)||a.warnings.push({instancePath:`/product_tree/full_product_names/${n}/product_id`,message:"is not referenced"}))})),e.product_tree.relationships?.forEach(((t,n)=>{ib(t)&&(cb(e,t.full_product_name.product_id)||a.warnings.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_id`,message:"is not referenced"}))})),a):a},optionalTest_6_2_10:function(e){const t={warnings:[]};return Ob(e)||t.warnings.push({message:"missing tlp label",instancePath:"/document"}),t},optionalTest_6_2_11:function(e){const t={warnings:[]};function a(){t.warnings.push({message:"missing canonical url",instancePath:"/document/references"})}return Nb(e)?(e.document.references.some((t=>Ab(t)&&"self"===t.category&&t.url.startsWith("https://")&&t.url.endsWith(e.document.tracking.id.toLowerCase().replace(/[^+\-a-z0-9]+/g,"_")+".json")))||a(),t):(a(),t)},optionalTest_6_2_12:function(e){const t={warnings:[]};return kb(e)||t.warnings.push({message:"missing document language",instancePath:"/document"}),t},optionalTest_6_2_13:function(e){const t={warnings:[]},a=(e,n,s)=>{if("object"!=typeof s||null==s)return;Array.isArray(s)&&s.forEach(((t,s)=>{a(e,n+"/"+s,t)}));const u=Object.keys(s),l=u.slice().sort(((t,a)=>e.compare(t,a)));JSON.stringify(u)!==JSON.stringify(l)&&t.warnings.push({instancePath:n,message:"not sorted alphabetically"});for(const t of u)a(e,n+"/"+t,s[t])};return a(new Intl.Collator,"",e),t},optionalTest_6_2_14:function(e){const t={warnings:[]};return"string"==typeof e.document?.lang&&Oc(e.document.lang)&&t.warnings.push({instancePath:"/document/lang",message:"use of private language"}),"string"==typeof e.document?.source_lang&&Oc(e.document.source_lang)&&t.warnings.push({instancePath:"/document/source_lang",message:"use of private language"}),t},optionalTest_6_2_15:function(e){const t={warnings:[]},a="i-default";return e.document?.lang===a&&t.warnings.push({instancePath:"/document/lang",message:"use of default language"}),e.document?.source_lang===a&&t.warnings.push({instancePath:"/document/source_lang",message:"use of default language"}),t},optionalTest_6_2_16:function(e){const t={warnings:[]};return Db(e)?(e.product_tree.full_product_names?.forEach(((e,a)=>{e.product_identification_helper||t.warnings.push({instancePath:`/product_tree/full_product_names/${a}`,message:"missing product identification helper"})})),e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Pb(n)&&(n.product&&!n.product.product_identification_helper&&t.warnings.push({instancePath:`${a}/${s}/product`,message:"missing product identification helper"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),e.product_tree.relationships?.forEach(((e,a)=>{Rb(e)&&(e.full_product_name.product_identification_helper||t.warnings.push({instancePath:`/product_tree/relationships/${a}/full_product_name`,message:"missing product identification helper"}))})),t):t},optionalTest_6_2_17:function(e){const t={warnings:[]};return Mb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.ids?.forEach(((e,n)=>{e.text?.match(/^CVE-[0-9]{4}-[0-9]{4,}$/)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/ids/${n}`,message:"contains CVE"})}))})),t):t},optionalTest_6_2_18:function(e){const t={warnings:[]};return Lb(e)?(e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Wb(n)&&("product_version_range"!==n.category||"string"!=typeof n.name||n.name.match(new RegExp("^vers:[a-z\\.\\-\\+][a-z0-9\\.\\-\\+]*/.+"))||t.warnings.push({instancePath:`${a}/${s}`,message:"product version range without vers"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},optionalTest_6_2_19:function(e){const t={warnings:[]};return Fb(e)?(e.vulnerabilities.forEach(((e,a)=>{const n=new Set([...e.product_status?.first_fixed??[],...e.product_status?.fixed??[]]);for(const s of n)e.scores?.forEach(((e,n)=>{if(e.products?.includes(s)){if(e.cvss_v3){const u="3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version?zb({version:e.cvss_v3.version,vectorString:e.cvss_v3.vectorString??"",metrics:e.cvss_v3}):null;("number"==typeof e.cvss_v3.environmentalScore&&e.cvss_v3.environmentalScore>0||"number"==typeof u&&u>0||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v3`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}if(e.cvss_v2){const u=zb({version:"2.0",vectorString:e.cvss_v2.vectorString??"",metrics:e.cvss_v2});("number"==typeof e.cvss_v2.environmentalScore&&e.cvss_v2.environmentalScore>0||"number"==typeof u&&0!==u||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v2`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}}}))})),t):t},optionalTest_6_2_2:function(e){const t={warnings:[]};return fb(e)?(e.vulnerabilities?.forEach(((a,n)=>{const s=a.product_status;["first_affected","known_affected","last_affected","under_investigation"].forEach((u=>{const l=s[u];l?.forEach(((s,l)=>{const i=a.remediations?.some((t=>t.product_ids?.includes(s)||t.group_ids?.map((t=>e.product_tree?.product_groups?.find((e=>e.group_id===t)))).some((e=>e?.product_ids?.includes(s)))));i||t.warnings.push({message:"missing remediation",instancePath:`/vulnerabilities/${n}/product_status/${u}/${l}`})}))}))})),t):t},optionalTest_6_2_20:function(e){const t={warnings:[]};if(!Hb(e)){const e=Hb.errors?.filter((e=>"additionalProperties"===e.keyword))??[];for(const a of e)t.warnings.push({instancePath:a.instancePath,message:a.message??""})}return t},optionalTest_6_2_3:function(e){const t={warnings:[]};return yb(e)?(e.vulnerabilities?.forEach(((e,a)=>{const n=e.product_status;["first_affected","known_affected","last_affected"].forEach((s=>{const u=n[s];u?.forEach(((n,u)=>{const l=e.scores?.some((e=>e.products?.includes(n)));l||t.warnings.push({message:"missing score",instancePath:`/vulnerabilities/${a}/product_status/${s}/${u}`})}))}))})),t):t},optionalTest_6_2_4:function(e){const t=[],a={warnings:t};return db(e)?(e.document.tracking.revision_history?.forEach(((e,a)=>{const{number:n}=e;"string"==typeof n&&n.includes("+")&&t.push({message:"build metadata in revision history",instancePath:`/document/tracking/revision_history/${a}/number`})})),a):a},optionalTest_6_2_5:function(e){const t=[],a={warnings:t};if(!bb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)))[0];return n&&gd(e.document.tracking.initial_release_date,n.date)<0&&t.push({message:"older initial release date than revision history",instancePath:"/document/tracking/initial_release_date"}),a},optionalTest_6_2_6:function(e){const t=[],a={warnings:t};if(!xb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)))[0];return n&&gd(e.document.tracking.current_release_date,n.date)<0&&t.push({message:"older current release date than revision history",instancePath:"/document/tracking/current_release_date"}),a},optionalTest_6_2_7:function(e){const t={warnings:[]};return hb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.involvements?.forEach(((e,n)=>{e.date||t.warnings.push({message:"missing date",instancePath:`/vulnerabilities/${a}/involvements/${n}`})}))})),t):t},optionalTest_6_2_8:function(e){return Cb(e,"md5")},optionalTest_6_2_9:function(e){return Cb(e,"sha1")}});Deno.core.ops.op_register_func((async function(e,t,a){let n=[];for(const t of e)switch(t){case"schema":n=n.concat(Object.values(Fu));break;case"mandatory":n=n.concat(Object.values(Qd));break;case"optional":n=n.concat(Object.values(qb));break;default:throw new Error(`Unknown validation set: ${t}`)}return n=n.filter((e=>!a.has(e.name))),async function(e,t){let a=!0;const n=[];for(const s of e){const e=await s(t),u="boolean"!=typeof e.isValid||e.isValid;n.push({isValid:u,errors:e.errors??[],warnings:e.warnings??[],infos:e.infos??[],name:s.name}),a=a&&u}return{tests:n,isValid:a}}(n,t)}));
+ */,nativeJs:function(e,t){if(void 0===t&&(t=Nf.systemDefault()),Gc(e,"date"),Gc(t,"zone"),e instanceof Date)return Gy.ofEpochMilli(e.getTime()).atZone(t);if("function"==typeof e.toDate&&e.toDate()instanceof Date)return Gy.ofEpochMilli(e.toDate().getTime()).atZone(t);throw new zc("date must be a javascript Date or a moment instance")},ArithmeticException:Uc,DateTimeException:Wc,DateTimeParseException:Fc,IllegalArgumentException:zc,IllegalStateException:Vc,UnsupportedTemporalTypeException:jc,NullPointerException:Hc,Clock:Ky,DayOfWeek:xf,Duration:uf,Instant:Gy,LocalDate:Hy,LocalTime:By,LocalDateTime:Yy,OffsetTime:Wy,OffsetDateTime:Uy,Month:mf,MonthDay:Iy,ParsePosition:Ef,Period:vf,Year:Ty,YearConstants:lf,YearMonth:Ay,ZonedDateTime:jy,ZoneOffset:Rf,ZoneId:Nf,ZoneRegion:fy,ZoneOffsetTransition:ed,ZoneRules:Af,ZoneRulesProvider:cy,ChronoLocalDate:Of,ChronoLocalDateTime:qy,ChronoZonedDateTime:Fy,IsoChronology:Ly,ChronoField:gf,ChronoUnit:pf,IsoFields:Wf,Temporal:Cf,TemporalAccessor:ff,TemporalAdjuster:Dy,TemporalAdjusters:Ry,TemporalAmount:nf,TemporalField:rf,TemporalQueries:cf,TemporalQuery:yf,TemporalUnit:sf,ValueRange:of,DateTimeFormatter:Cy,DateTimeFormatterBuilder:my,DecimalStyle:Jf,ResolverStyle:Sf,SignStyle:Qf,TextStyle:ey},od=(id=rd,pd=[],function(e){return~pd.indexOf(e)||(e(id),pd.push(e)),id});rd.use=od;const gd=(e,t)=>{try{const a=e instanceof jy?e:jy.parse(e),n=t instanceof jy?t:jy.parse(t),s=uf.between(a,n);return s.isZero()?0:s.isNegative()?1:-1}catch(e){return 0}},{gt:cd,valid:fd}=fp;const yd=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date));const{hasTrackingRevisionHistory:dd,hasTrackingVersionField:bd,hasTrackingStatusField:xd}=bp;const{valid:hd,major:md,prerelease:_d}=fp,{hasTrackingVersionField:vd,hasTrackingStatusField:Ed}=bp;const{valid:wd,major:Sd}=fp,{hasTrackingVersionField:Cd,hasTrackingStatusField:Od,hasTrackingRevisionHistory:Id}=bp;const{valid:Nd,prerelease:Ad}=fp,{hasTrackingVersionField:kd,hasTrackingStatusField:Td,hasTrackingRevisionHistory:Dd}=bp;const{valid:Rd,prerelease:Pd}=fp,{hasTrackingVersionField:Md,hasTrackingStatusField:$d}=bp;const Ld=e=>Array.isArray(e?.document?.tracking?.revision_history)&&e.document.tracking.revision_history.every((e=>"string"==typeof e.number&&"string"==typeof e.date)),{hasTrackingVersionField:Wd,hasTrackingStatusField:Fd,hasTrackingRevisionHistory:jd}=bp;const Ud=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>"string"==typeof e.cve||void 0===e.cve))};const zd=e=>{if("object"!=typeof e||!e)return!1;const t=e;return Array.isArray(t.vulnerabilities)&&t.vulnerabilities.every((e=>Array.isArray(e.involvements)&&e.involvements.every((e=>!("string"!=typeof e.date&&void 0!==e.date||"string"!=typeof e.party&&void 0!==e.party||"string"!=typeof e.status&&void 0!==e.status)))))};const Vd=(e,t,a=[])=>{Array.isArray(e.branches)&&e.branches.forEach(((e,n)=>{const s=a.concat([n]);Array.isArray(e.product?.product_identification_helper?.hashes)&&e.product.product_identification_helper.hashes.forEach(((e,a)=>{Hd(e,(({fileHash:e,fileHashIndex:n})=>{t({branchIndexes:s,hashIndex:a,fileHash:e,fileHashIndex:n})}))})),Vd(e,t,s)}))},Hd=(e,t)=>{if(!Array.isArray(e.file_hashes))return;const a=new Set;e.file_hashes.forEach(((e,n)=>{null!=e.algorithm&&(a.has(e.algorithm)&&t({fileHash:e,fileHashIndex:n}),a.add(e.algorithm))}))};const qd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Yd=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,optionalProperties:{revision_history:{elements:{additionalProperties:!0,optionalProperties:{number:{type:"string"}}}},version:{type:"string"}}}}}}});function Bd(e){return parseInt(e).toString()===e?"INTEGER":"SEMANTIC"}const Xd=new Sg,Gd=Xd.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Kd=Xd.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"},branches:{elements:{additionalProperties:!0,properties:{}}}}});const Zd=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});const Jd=(new Sg).compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}},vulnerabilities:{elements:{additionalProperties:!0,properties:{flags:{elements:{additionalProperties:!0,optionalProperties:{group_ids:{elements:{type:"string"}},product_ids:{elements:{type:"string"}}}}}}}}}});var Qd=Object.freeze({__proto__:null,mandatoryTest_6_1_1:function(e){const t=[];let a=!0;const n=xp({document:e}),s=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e].product_ids;if(n)for(let a=0;a<n.length;++a){const s=n[a];s&&t.push({id:s,instancePath:`/product_tree/product_groups/${e}/product_ids/${a}`})}}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e],s=a.product_reference;s&&t.push({id:s,instancePath:`/product_tree/relationships/${e}/product_reference`});const u=a.relates_to_product_reference;u&&t.push({id:u,instancePath:`/product_tree/relationships/${e}/relates_to_product_reference`})}const s=e.vulnerabilities;if(s)for(let e=0;e<s.length;++e){const a=s[e];hp(`/vulnerabilities/${e}/product_status`,a,t),Ep(`/vulnerabilities/${e}/remediations`,a,t),vp(`/vulnerabilities/${e}/scores`,a,t),_p(`/vulnerabilities/${e}/threats`,a,t)}return t}({document:e}),u=wp(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of product id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_10:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2;hc({vectorValues:u.vectorString.split("/"),vectorMapping:xc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${e}`,message:"value is not consistent with the vector string"})}})}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3;hc({vectorValues:u.vectorString.split("/").slice(1),vectorMapping:bc,cvss:u,onError({attributeKey:e}){a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${e}`,message:"value is not consistent with the vector string"})}})}}))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_11:function(e){const t=[];let a=!0;if(_c(e))for(let n=0;n<e.vulnerabilities.length;++n){const s=e.vulnerabilities[n];if(vc(s)){const e=mc.weaknesses.find((e=>e.id===s.cwe.id));if(!e){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/id`,message:"no weakness with this id is recognized"});continue}if(e.name!==s.cwe.name){a=!1,t.push({instancePath:`/vulnerabilities/${n}/cwe/name`,message:"the name does not match the weakness with the given id"});continue}}}return{isValid:a,errors:t}},mandatoryTest_6_1_12:function(e){const t=[];let a=!0;return Ac(e)&&!Cc(e.document.lang)&&(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/lang"})),kc(e)&&(Cc(e.document.source_lang)||(a=!1,t.push({message:"is not a valid language-tag",instancePath:"/document/source_lang"}))),{errors:t,isValid:a}},mandatoryTest_6_1_13:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{$c(e,(()=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/purl`,message:"invalid purl"})}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{$c(e.full_product_name,(()=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/purl`,message:"invalid purl"})}))})),e.product_tree&&Mc(e.product_tree,(({branchIndexes:e})=>{a=!1;const n=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${n}/product/product_identification_helper/purl`,message:"invalid purl"})})),{errors:t,isValid:a}},mandatoryTest_6_1_14:function(e){const t=[];let a=!0;if(yd(e)){Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)||dp(t.number,e.number))).map((e=>fd(e.number)??`${e.number}.0.0`))).keys()).filter((e=>null!==fd(e))).every(((e,t,a)=>0===t||cd(e,a[t-1])))||(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"the items must be in ascending order according to the date"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_15:function(e){let t=!0;const a=[];return"translator"!==e.document?.publisher?.category||e.document.source_lang||(t=!1,a.push({instancePath:"/document/publisher/category",message:"source language attribute is missing"})),{isValid:t,errors:a}},mandatoryTest_6_1_16:function(e){let t=!0;const a=[];if(dd(e)&&bd(e)&&xd(e)&&e.document.tracking.revision_history.length>0){const n=t=>"draft"==e.document.tracking.status?t.split(/[+-]/)[0]:t.split("+")[0];n(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)||dp(e.number,t.number)))[0].number)!==n(e.document.tracking.version)&&(t=!1,a.push({message:"version does not match latest revision",instancePath:"/document/tracking/version"}))}return{errors:a,isValid:t}},mandatoryTest_6_1_17:function(e){let t=!0;const a=[];return vd(e)&&Ed(e)&&"draft"!==e.document.tracking.status&&("0"===e.document.tracking.version||hd(e.document.tracking.version)&&(0===md(e.document.tracking.version)||_d(e.document.tracking.version)))&&(t=!1,a.push({message:"the status is not compatible with the version",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_18:function(e){let t=!0;const a=[];return Cd(e)&&Od(e)&&Id(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&e.document.tracking.revision_history.some((e=>"0"===e.number||wd(e.number)&&0===Sd(e.number)))&&(t=!1,a.push({message:"some revision-history entries are not compatible with the status",instancePath:"/document/tracking/status"})),{errors:a,isValid:t}},mandatoryTest_6_1_19:function(e){let t=!0;const a=[];if(kd(e)&&Td(e)&&Dd(e))for(let n=0;n<e.document.tracking.revision_history.length;++n){const s=e.document.tracking.revision_history[n];Nd(s.number)&&Ad(s.number)&&(t=!1,a.push({message:"contains prerelease part",instancePath:`/document/tracking/revision_history/${n}/number`}))}return{errors:a,isValid:t}},mandatoryTest_6_1_2:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.full_product_names;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.product_id&&t.push({id:n.product_id,name:n.name??"",instancePath:`/product_tree/full_product_names/${e}/product_id`})}const n=e.product_tree?.relationships;if(n)for(let e=0;e<n.length;++e){const a=n[e].full_product_name;a&&a.product_id&&t.push({id:a.product_id,name:a.name??"",instancePath:`/product_tree/relationships/${e}/full_product_name/product_id`})}const s=e.product_tree?.branches;s&&Cp(s,t,"/product_tree/branches");return t}({document:e}),s=Sp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_20:function(e){let t=!0;const a=[];return Md(e)&&$d(e)&&("final"===e.document.tracking.status||"interim"===e.document.tracking.status)&&Rd(e.document.tracking.version)&&Pd(e.document.tracking.version)&&(t=!1,a.push({message:"pre-release part is not allowed for status",instancePath:"/document/tracking/version"})),{errors:a,isValid:t}},mandatoryTest_6_1_21:function(e){const t=[];let a=!0;if(Ld(e)){const n=Array.from(new Set(e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date))).map((e=>parseInt(e.number)))).keys());n.length>0&&![0,1].includes(n[0])&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:"revision history does not start with a version of 0 or 1 when sorted by date"}));for(let e=0;e<n.length;++e){const s=e+Number(n[0]);n[e]>s&&(a=!1,t.push({instancePath:"/document/tracking/revision_history",message:`major version ${s} was omitted`}))}}return{errors:t,isValid:a}},mandatoryTest_6_1_22:function(e){let t=!0;const a=[];if(Wd(e)&&Fd(e)&&jd(e)){let n={};e.document.tracking.revision_history.forEach(((e,s)=>{n[e.number]=n[e.number]??[],n[e.number].push(s),n[e.number].length>1&&(t=!1,a.push({message:"version was already used",instancePath:`/document/tracking/revision_history/${s}/number`}))}))}return{errors:a,isValid:t}},mandatoryTest_6_1_23:function(e){const t=[];let a=!0;if(Ud(e)){const n=new Set;e.vulnerabilities.forEach(((e,s)=>{void 0!==e.cve&&(n.has(e.cve)&&(a=!1,t.push({message:"CVE identifier was already used",instancePath:`/vulnerabilities/${s}/cve`})),n.add(e.cve))}))}return{errors:t,isValid:a}},mandatoryTest_6_1_24:function(e){const t=[];let a=!0;return zd(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.involvements.forEach(((e,u)=>{if("string"==typeof e.date&&"string"==typeof e.party){const l=s.get(e.date)??new Set;l.has(`${e.party}`)&&(a=!1,t.push({message:"status for party was already given for the same date",instancePath:`/vulnerabilities/${n}/involvements/${u}`})),l.add(`${e.party}`),s.set(e.date,l)}}))})),{errors:t,isValid:a}},mandatoryTest_6_1_25:function(e){const t=[];let a=!0;return Array.isArray(e.product_tree?.full_product_names)&&e.product_tree?.full_product_names.forEach(((e,n)=>{Array.isArray(e.product_identification_helper?.hashes)&&e.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/full_product_names/${n}/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),Array.isArray(e.product_tree?.relationships)&&e.product_tree.relationships.forEach(((e,n)=>{Array.isArray(e.full_product_name?.product_identification_helper?.hashes)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,s)=>{Hd(e,(({fileHash:e,fileHashIndex:u})=>{a=!1,t.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_identification_helper/hashes/${s}/file_hashes/${u}`,message:`there is already a hash with the algorithm ${e.algorithm}`})}))}))})),e.product_tree&&Vd(e.product_tree,(({branchIndexes:e,hashIndex:n,fileHashIndex:s,fileHash:u})=>{a=!1;const l=e.reduce(((e,t)=>`${e}/branches/${t}`),"/product_tree");t.push({instancePath:`${l}/product/product_identification_helper/hashes/${n}/file_hashes/${s}`,message:`there is already a hash with the algorithm ${u.algorithm}`})})),{errors:t,isValid:a}},mandatoryTest_6_1_26:function(e){const t=[];let a=!0;if("string"==typeof e.document?.category){const n=e.document.category,s=["securityincidentresponse","informationaladvisory","securityadvisory","vex"];if(["csaf_base","csaf_security_incident_response","csaf_informational_advisory","csaf_security_advisory","csaf_vex"].includes(n))return{errors:t,isValid:a};if(n.toLowerCase().startsWith("csaf_"))return a=!1,t.push({instancePath:"/document/category",message:"reserved prefix used"}),{errors:t,isValid:a};s.includes(n.replace(/[_-\s]+/g,"").toLowerCase())&&(a=!1,t.push({instancePath:"/document/category",message:"value prohibited"}))}return{errors:t,isValid:a}},mandatoryTest_6_1_27_1:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["description","details","general","summary"]);return a=Array.isArray(e.document?.notes)&&e.document.notes.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/notes",message:"need at least one document note with a category of description, details, general or summary"}),{errors:t,isValid:a}},mandatoryTest_6_1_27_10:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_affected)&&u.known_affected.forEach(((u,l)=>{(Array.isArray(n.remediations)?n.remediations:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}))||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_affected/${l}`,message:"no suitable action statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_11:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.vulnerabilities),a||t.push({instancePath:"/",message:"needs vulnerabilities"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_2:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_incident_response","csaf_informational_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=new Set(["external"]);return a=Array.isArray(e.document?.references)&&e.document.references.some((e=>n.has(e.category))),a||t.push({instancePath:"/document/references",message:'need at least one document reference with the category "external"'}),{errors:t,isValid:a}},mandatoryTest_6_1_27_3:function(e){const t=[];let a=!0;return new Set(["csaf_informational_advisory"]).has(e.document?.category)?(a=void 0===e.vulnerabilities,a||t.push({instancePath:"/vulnerabilities",message:"must not exist"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_4:function(e){const t=[];let a=!0;return new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category)?(a=Boolean(e.product_tree),a||t.push({instancePath:"/",message:"needs a product_tree"}),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_27_5:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory","csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.notes||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `notes` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_6:function(e){const t=[];let a=!0;if(!new Set(["csaf_security_advisory"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{e.product_status||(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_7:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{if(!e.product_status)return a=!1,void t.push({instancePath:`/vulnerabilities/${n}`,message:"needs a `product_status` attribute"});["fixed","known_affected","known_not_affected","under_investigation"].some((t=>Array.isArray(e.product_status[t])))||(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:"needs at least one the following attributes: `fixed`, `known_affected`, `known_not_affected`, `under_investigation`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_8:function(e){const t=[];let a=!0;if(!new Set(["csaf_vex"]).has(e.document?.category))return{errors:t,isValid:a};const n=e.vulnerabilities;return Array.isArray(n)&&n.forEach(((e,n)=>{["ids","cve"].every((t=>void 0===e[t]))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}`,message:"needs at least one the following attributes: `ids`, `cve`"}))})),{errors:t,isValid:a}},mandatoryTest_6_1_27_9:function(e){const t=[];let a=!0;const n=e.vulnerabilities;return"csaf_vex"===e.document?.category&&Array.isArray(n)?(n.forEach(((n,s)=>{const u=n?.product_status;n&&u&&Array.isArray(u.known_not_affected)&&u.known_not_affected.forEach(((u,l)=>{const i=(Array.isArray(n.threats)?n.threats:[]).some((t=>{if(!t||"impact"!==t.category)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))})),p=(Array.isArray(n.flags)?n.flags:[]).some((t=>{if(!t)return!1;if(Array.isArray(t.product_ids)&&t.product_ids.includes(u))return!0;const a=e.product_tree?.product_groups;return!!(Array.isArray(t.group_ids)&&Array.isArray(a)&&t.group_ids.some((e=>{const t=a.find((t=>t.group_id===e));return t&&Array.isArray(t.product_ids)&&t.product_ids.includes(u)})))}));i||p||(a=!1,t.push({instancePath:`/vulnerabilities/${s}/product_status/known_not_affected/${l}`,message:"no suitable impact statement found"}))}))})),{errors:t,isValid:a}):{errors:t,isValid:a}},mandatoryTest_6_1_28:function(e){const t=[];let a=!0;return"string"==typeof e.document?.source_lang&&"string"==typeof e.document?.lang&&e.document.source_lang===e.document.lang&&(a=!1,t.push({instancePath:"/document/lang",message:"is the same as `/document/source_lang`"},{instancePath:"/document/source_lang",message:"is the same as `/document/lang`"})),{isValid:a,errors:t}},mandatoryTest_6_1_29:function(e){const t={errors:[],isValid:!0};return qd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.remediations.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/remediations/${n}`,message:"remediation without product reference"}))}))})),t):t},mandatoryTest_6_1_3:function(e){const t=[];let a=!0;if(!Array.isArray(e.product_tree?.relationships))return{isValid:a,errors:t};const n=e.product_tree.relationships;return n.forEach(((e,s)=>{const u=new Set;Op([],n,s,null,(({key:e})=>{"string"!=typeof e||u.has(e)||(u.add(e),a=!1,t.push({instancePath:`/product_tree/relationships/${s}/${e}`,message:"circular reference"}))}))})),{isValid:a,errors:t}},mandatoryTest_6_1_30:function(e){const t={errors:[],isValid:!0};if(!Yd(e))return t;let a=null;return e.document.tracking.revision_history?.forEach(((e,n)=>{if("string"==typeof e.number){const s=Bd(e.number);null===a&&(a=s),a!==s&&(t.isValid=!1,t.errors.push({instancePath:`/document/tracking/revisions/${n}/number`,message:"mixed integer and semantic versioning"}))}})),null!=a&&"string"==typeof e.document.tracking.version&&a!==Bd(e.document.tracking.version)&&(t.isValid=!1,t.errors.push({instancePath:"/document/tracking/version",message:"mixed integer and semantic versioning"})),t},mandatoryTest_6_1_31:function(e){const t={errors:[],isValid:!0};return Gd(e)?(function e({path:a,branches:n}){n.forEach(((n,s)=>{Kd(n)&&("product_version"===n.category&&"string"==typeof n.name&&(["<","<=",">",">="].some((e=>n.name?.toLowerCase().includes(e)))||["after","all","before","earlier","later","prior","versions"].some((e=>n.name?.toLowerCase().split(/\s/).some((t=>e===t)))))&&(t.isValid=!1,t.errors.push({instancePath:`${a}/${s}/name`,message:"version range in product version"})),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},mandatoryTest_6_1_32:function(e){const t={errors:[],isValid:!0};return Zd(e)?(e.vulnerabilities.forEach(((e,a)=>{e.flags.forEach(((e,n)=>{e.group_ids||e.product_ids||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${a}/flags/${n}`,message:"flag without product reference"}))}))})),t):t},mandatoryTest_6_1_33:function(e){const t={errors:[],isValid:!0};return Jd(e)?(e.vulnerabilities.forEach(((a,n)=>{const s=new Set;a.flags.forEach(((a,u)=>{let l=!1;function i(){l||(t.isValid=!1,t.errors.push({instancePath:`/vulnerabilities/${n}/flags/${u}`,message:"multiple flags with vex justification codes per product"})),l=!0}a.product_ids?.forEach((e=>{s.has(e)&&i(),s.add(e)})),a.group_ids?.forEach((t=>{const a=e.product_tree.product_groups?.find((e=>e.group_id===t))?.product_ids;a?.forEach((e=>{s.has(e)&&i(),s.add(e)}))}))}))})),t):t},mandatoryTest_6_1_4:function(e){const t=[];let a=!0;const n=Np({document:e}),s=function({document:e}){const t=[],a=e.vulnerabilities;if(a)for(let e=0;e<a.length;++e){const n=a[e];Ap(`/vulnerabilities/${e}/remediations`,n,t),kp(`/vulnerabilities/${e}/threats`,n,t)}return t}({document:e}),u=Ip(n,s);return u.length>0&&(a=!1,u.forEach((e=>{t.push({message:"definition of group id missing",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_5:function(e){const t=[];let a=!0;const n=function({document:e}){const t=[],a=e.product_tree?.product_groups;if(a)for(let e=0;e<a.length;++e){const n=a[e];n.group_id&&t.push({id:n.group_id,name:n.summary??"",instancePath:`/product_tree/product_groups/${e}/group_id`})}return t}({document:e}),s=Tp(n);return s.length>0&&(a=!1,s.forEach((e=>{t.push({message:"duplicate definition product group id",instancePath:e.instancePath})}))),{isValid:a,errors:t}},mandatoryTest_6_1_6:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{const s=e.product_status;if(!s)return;const u=[new Set([].concat(Array.isArray(s.first_affected)?s.first_affected:[]).concat(Array.isArray(s.known_affected)?s.known_affected:[]).concat(Array.isArray(s.last_affected)?s.last_affected:[])),new Set(Array.isArray(s.known_not_affected)?s.known_not_affected:[]),new Set([].concat(Array.isArray(s.first_fixed)?s.first_fixed:[]).concat(Array.isArray(s.fixed)?s.fixed:[])),new Set(Array.isArray(s.under_investigation)?s.under_investigation:[])];u.forEach(((e,s)=>{const l=u.slice(s+1);e.forEach((e=>{l.some((t=>t.has(e)))&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/product_status`,message:`product id "${e}" is mentioned in contradicting product status groups`}))}))}))}))}return{isValid:a,errors:t}},mandatoryTest_6_1_7:function(e){const t=[];let a=!0;return Dp(e)&&e.vulnerabilities.forEach(((e,n)=>{const s=new Map;e.scores?.forEach(((e,u)=>{e.products?.forEach(((l,i)=>{const p=s.get(l)??new Set;s.set(l,p),(void 0!==e.cvss_v2?.version&&p.has(e.cvss_v2.version)||void 0!==e.cvss_v3?.version&&p.has(e.cvss_v3.version))&&(a=!1,t.push({message:`product is already included in these cvss-versions: ${Array.from(p.keys()).join(", ")}`,instancePath:`/vulnerabilities/${n}/scores/${u}/products/${i}`})),void 0!==e.cvss_v2?.version&&p.add(e.cvss_v2.version),void 0!==e.cvss_v3?.version&&p.add(e.cvss_v3.version)}))}))})),{errors:t,isValid:a}},mandatoryTest_6_1_8:function(e){const t={errors:[],isValid:!0};if(!Cg(e))return t;for(const[a,n]of e.vulnerabilities?.entries()??[])for(const[e,s]of n.scores?.entries()??[]){if(s.cvss_v2){if(!Og(s.cvss_v2)){t.isValid=!1;for(const n of Og.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v2${n.instancePath}`,message:n.message??""})}}if(s.cvss_v3){if(!Ig(s.cvss_v3)){t.isValid=!1;for(const n of Ig.errors??[])t.errors.push({instancePath:`/vulnerabilities/${a}/scores/${e}/cvss_v3${n.instancePath}`,message:n.message??""})}}}return t},mandatoryTest_6_1_9:function(e){const t=[];let a=!0;if(Array.isArray(e.vulnerabilities)){e.vulnerabilities.forEach(((e,n)=>{if(!Array.isArray(e.scores))return;e.scores.forEach(((e,s)=>{if("string"==typeof e.cvss_v2?.vectorString){const u=e.cvss_v2,l=function(e){try{return{success:!0,baseMetricScore:tc.getBaseScore(e),temporalMetricScore:tc.getTemporalScore(e),environmentalMetricScore:ac(e)}}catch(e){return{success:!1,baseMetricScore:-1,temporalMetricScore:-1,environmentalMetricScore:-1}}}(u.vectorString);if(l.success)for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v2/${p}`,message:"invalid calculated value"}))}if("string"==typeof e.cvss_v3?.vectorString&&("3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version)){const u=e.cvss_v3,l=("3.0"===u.version?fc:yc).calculateCVSSFromVector(u.vectorString);if(l.success){for(const{score:e,expectedScore:i,name:p}of[{score:u.baseScore,expectedScore:l.baseMetricScore,name:"baseScore"},{score:u.temporalScore,expectedScore:l.temporalMetricScore,name:"temporalScore"},{score:u.environmentalScore,expectedScore:l.environmentalMetricScore,name:"environmentalScore"}])"number"==typeof e&&e!==Number(i)&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}));for(const{severity:e,expectedSeverity:i,name:p}of[{severity:u.baseSeverity,expectedSeverity:l.baseSeverity,name:"baseSeverity"},{severity:u.temporalSeverity,expectedSeverity:l.temporalSeverity,name:"temporalSeverity"},{severity:u.environmentalSeverity,expectedSeverity:l.environmentalSeverity,name:"environmentalSeverity"}])"string"==typeof e&&e!==i.toUpperCase()&&(a=!1,t.push({instancePath:`/vulnerabilities/${n}/scores/${s}/cvss_v3/${p}`,message:"invalid calculated value"}))}}}))}))}return{errors:t,isValid:a}}});const eb=new Sg,tb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}},optionalProperties:{document:{additionalProperties:!0,optionalProperties:{category:{type:"string"}}}}}),ab={additionalProperties:!0,properties:{product_id:{type:"string"}}},nb=eb.compile(ab),sb={additionalProperties:!0,optionalProperties:{product:ab,branches:{elements:{additionalProperties:!0,properties:{}}}}},ub=eb.compile(sb),lb={additionalProperties:!0,properties:{full_product_name:ab}},ib=eb.compile(lb);const pb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}),rb=eb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,properties:{relationships:{elements:{additionalProperties:!0,optionalProperties:{product_reference:{type:"string"},relates_to_product_reference:{type:"string"}}}}}}}}),ob=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}},fixed:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},known_not_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},recommended:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}}}}}}),gb=eb.compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}},threats:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}}}}}}}}}});function cb(e,t){let a=!1;return!a&&pb(e)&&(a=e.product_tree.product_groups.some((e=>e.product_ids?.includes(t)??!1))),!a&&rb(e)&&(a=e.product_tree.relationships.some((e=>e.product_reference===t||e.relates_to_product_reference===t))),!a&&ob(e)&&(a=e.vulnerabilities.some((e=>["first_affected","first_fixed","fixed","known_affected","known_not_affected","last_affected","recommended","under_investigation"].some((a=>e.product_status?.[a]?.includes(t)??!1))))),!a&&gb(e)&&(a=e.vulnerabilities.some((e=>e.remediations?.some((e=>e.product_ids?.includes(t)))||e.scores?.some((e=>e.products?.includes(t)))||e.threats?.some((e=>e.product_ids?.includes(t)))||!1))),a}const fb=(new Sg).compile({additionalProperties:!0,optionalProperties:{product_tree:{additionalProperties:!0,optionalProperties:{product_groups:{elements:{additionalProperties:!0,optionalProperties:{group_id:{type:"string"},product_ids:{elements:{type:"string"}}}}}}}},properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}},under_investigation:{elements:{type:"string"}}}}},optionalProperties:{remediations:{elements:{additionalProperties:!0,optionalProperties:{product_ids:{elements:{type:"string"}},group_ids:{elements:{type:"string"}}}}}}}}}});const yb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,properties:{product_status:{additionalProperties:!0,optionalProperties:{first_affected:{elements:{type:"string"}},known_affected:{elements:{type:"string"}},last_affected:{elements:{type:"string"}}}}},optionalProperties:{scores:{elements:{additionalProperties:!0,optionalProperties:{products:{elements:{type:"string"}}}}}}}}}});const db=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{revision_history:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const bb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{initial_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const xb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{tracking:{additionalProperties:!0,properties:{current_release_date:{type:"timestamp"},revision_history:{elements:{additionalProperties:!0,properties:{date:{type:"timestamp"}}}}}}}}}});const hb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{involvements:{elements:{additionalProperties:!0,properties:{}}}}}}}});const mb=new Sg,_b=mb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),vb=mb.compile({additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}),Eb=mb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}}),wb=mb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,properties:{product_identification_helper:{additionalProperties:!0,properties:{hashes:{elements:{additionalProperties:!0,properties:{}}}}}}}}});const Sb=(new Sg).compile({additionalProperties:!0,properties:{file_hashes:{elements:{additionalProperties:!0,properties:{}}}}});function Cb(e,t){const a={warnings:[]};return function(e,t){const a={warnings:[]};if(!_b(e))return a;e.product_tree.full_product_names?.forEach(((e,a)=>{vb(e)&&e.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/full_product_names/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}));const n=(e,a)=>{a.forEach(((a,s)=>{wb(a)&&(a.product?.product_identification_helper.hashes.forEach(((a,n)=>{t({path:`${e}${s}/product_identification_helper/hashes/${n}`,hash:a})})),n(`${e}${s}/branches/`,Array.isArray(a.branches)?a.branches:[]))}))};n("/product_tree/branches/",e.product_tree.branches??[]),e.product_tree.relationships?.forEach(((e,a)=>{Eb(e)&&e.full_product_name.product_identification_helper.hashes.forEach(((e,n)=>{t({path:`/product_tree/relationships/${a}/product_identification_helper/hashes/${n}`,hash:e})}))}))}(e,(({path:e,hash:n})=>{if(!Sb(n))return;const s=function(e){return new Set(e.file_hashes.map((e=>e.algorithm)).filter((e=>"string"==typeof e)))}(n);s.has(t)&&1===s.size&&a.warnings.push({instancePath:e,message:`use of ${t} as the only hash algorithm`})})),a}const Ob=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{distribution:{additionalProperties:!0,properties:{tlp:{additionalProperties:!0,properties:{label:{type:"string"}}}}}}}}});const Ib=new Sg,Nb=Ib.compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{references:{elements:{additionalProperties:!0,properties:{}}},tracking:{additionalProperties:!0,properties:{id:{type:"string"}}}}}}}),Ab=Ib.compile({additionalProperties:!0,properties:{category:{type:"string"},url:{type:"string"}}});const kb=(new Sg).compile({additionalProperties:!0,properties:{document:{additionalProperties:!0,properties:{lang:{type:"string"}}}}});const Tb=new Sg,Db=Tb.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}},full_product_names:{elements:{additionalProperties:!0,properties:{}}},relationships:{elements:{additionalProperties:!0,properties:{}}}}}}}),Rb=Tb.compile({additionalProperties:!0,properties:{full_product_name:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}}),Pb=Tb.compile({additionalProperties:!0,optionalProperties:{product:{additionalProperties:!0,optionalProperties:{product_identification_helper:{additionalProperties:!0,properties:{}}}}}});const Mb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{ids:{elements:{additionalProperties:!0,optionalProperties:{text:{type:"string"}}}}}}}}});const $b=new Sg,Lb=$b.compile({additionalProperties:!0,properties:{product_tree:{additionalProperties:!0,optionalProperties:{branches:{elements:{additionalProperties:!0,properties:{}}}}}}}),Wb=$b.compile({additionalProperties:!0,optionalProperties:{category:{type:"string"},name:{type:"string"}}});const Fb=(new Sg).compile({additionalProperties:!0,properties:{vulnerabilities:{elements:{additionalProperties:!0,optionalProperties:{product_status:{additionalProperties:!0,optionalProperties:{fixed:{elements:{type:"string"}},first_fixed:{elements:{type:"string"}}}},scores:{elements:{additionalProperties:!0,optionalProperties:{cvss_v3:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},cvss_v2:{additionalProperties:!0,optionalProperties:{environmentalScore:{type:"float64"},vectorString:{type:"string"},version:{type:"string"}}},products:{elements:{type:"string"}}}}}}}}}});const jb=rc.map((e=>[e[0],e[1],Object.fromEntries(Object.entries(e[2]).map((([e,t])=>[e,t.id])))])),Ub=dc;function zb({version:e,vectorString:t,metrics:a}){const n=new Map(t.split("/").map((e=>{const[t,a]=e.split(":");return[t,a]})).filter((([,e])=>e)));if("3.1"===e||"3.0"===e){const t=Vb({mapping:Ub,metrics:a,vector:n}).map((e=>e[1])),s=("3.1"===e?yc:fc).calculateCVSSFromMetrics(...t);return s.success?Number(s.environmentalMetricScore):null}{const e=function(e){try{return{success:!0,environmentalMetricScore:ac(e)}}catch(e){return{success:!1,environmentalMetricScore:-1}}}(Object.fromEntries(Vb({mapping:jb,metrics:a,vector:n})));return e.success?e.environmentalMetricScore:null}}function Vb({vector:e,metrics:t,mapping:a}){return a.map((a=>{const n=a[1],s=a[0],u=a[2],l=t[s];return[n,e.get(n)??u[l]]}))}const Hb=Mu.compile($u);var qb=Object.freeze({__proto__:null,optionalTest_6_2_1:function(e){const t=[],a={warnings:t};return tb(e)&&"csaf_informational_advisory"!==e.document?.category?(function a({path:n,branches:s}){s.forEach(((s,u)=>{ub(s)&&("string"!=typeof s.product?.product_id||cb(e,s.product.product_id)||t.push({instancePath:`${n}/${u}/product/product_id`,message:"is not referenced"}),Array.isArray(s.branches)&&a({path:`${n}/${u}/branches`,branches:s.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree?.branches??[]}),e.product_tree.full_product_names?.forEach(((t,n)=>{nb(t)&&(cb(e,t.product_id)||a.warnings.push({instancePath:`/product_tree/full_product_names/${n}/product_id`,message:"is not referenced"}))})),e.product_tree.relationships?.forEach(((t,n)=>{ib(t)&&(cb(e,t.full_product_name.product_id)||a.warnings.push({instancePath:`/product_tree/relationships/${n}/full_product_name/product_id`,message:"is not referenced"}))})),a):a},optionalTest_6_2_10:function(e){const t={warnings:[]};return Ob(e)||t.warnings.push({message:"missing tlp label",instancePath:"/document"}),t},optionalTest_6_2_11:function(e){const t={warnings:[]};function a(){t.warnings.push({message:"missing canonical url",instancePath:"/document/references"})}return Nb(e)?(e.document.references.some((t=>Ab(t)&&"self"===t.category&&t.url.startsWith("https://")&&t.url.endsWith(e.document.tracking.id.toLowerCase().replace(/[^+\-a-z0-9]+/g,"_")+".json")))||a(),t):(a(),t)},optionalTest_6_2_12:function(e){const t={warnings:[]};return kb(e)||t.warnings.push({message:"missing document language",instancePath:"/document"}),t},optionalTest_6_2_13:function(e){const t={warnings:[]},a=(e,n,s)=>{if("object"!=typeof s||null==s)return;Array.isArray(s)&&s.forEach(((t,s)=>{a(e,n+"/"+s,t)}));const u=Object.keys(s),l=u.slice().sort(((t,a)=>e.compare(t,a)));JSON.stringify(u)!==JSON.stringify(l)&&t.warnings.push({instancePath:n,message:"not sorted alphabetically"});for(const t of u)a(e,n+"/"+t,s[t])};return a(new Intl.Collator,"",e),t},optionalTest_6_2_14:function(e){const t={warnings:[]};return"string"==typeof e.document?.lang&&Oc(e.document.lang)&&t.warnings.push({instancePath:"/document/lang",message:"use of private language"}),"string"==typeof e.document?.source_lang&&Oc(e.document.source_lang)&&t.warnings.push({instancePath:"/document/source_lang",message:"use of private language"}),t},optionalTest_6_2_15:function(e){const t={warnings:[]},a="i-default";return e.document?.lang===a&&t.warnings.push({instancePath:"/document/lang",message:"use of default language"}),e.document?.source_lang===a&&t.warnings.push({instancePath:"/document/source_lang",message:"use of default language"}),t},optionalTest_6_2_16:function(e){const t={warnings:[]};return Db(e)?(e.product_tree.full_product_names?.forEach(((e,a)=>{e.product_identification_helper||t.warnings.push({instancePath:`/product_tree/full_product_names/${a}`,message:"missing product identification helper"})})),e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Pb(n)&&(n.product&&!n.product.product_identification_helper&&t.warnings.push({instancePath:`${a}/${s}/product`,message:"missing product identification helper"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),e.product_tree.relationships?.forEach(((e,a)=>{Rb(e)&&(e.full_product_name.product_identification_helper||t.warnings.push({instancePath:`/product_tree/relationships/${a}/full_product_name`,message:"missing product identification helper"}))})),t):t},optionalTest_6_2_17:function(e){const t={warnings:[]};return Mb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.ids?.forEach(((e,n)=>{e.text?.match(/^CVE-[0-9]{4}-[0-9]{4,}$/)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/ids/${n}`,message:"contains CVE"})}))})),t):t},optionalTest_6_2_18:function(e){const t={warnings:[]};return Lb(e)?(e.product_tree.branches&&function e({path:a,branches:n}){n.forEach(((n,s)=>{Wb(n)&&("product_version_range"!==n.category||"string"!=typeof n.name||n.name.match(new RegExp("^vers:[a-z\\.\\-\\+][a-z0-9\\.\\-\\+]*/.+"))||t.warnings.push({instancePath:`${a}/${s}`,message:"product version range without vers"}),Array.isArray(n.branches)&&e({path:`${a}/${s}/branches`,branches:n.branches}))}))}({path:"/product_tree/branches",branches:e.product_tree.branches}),t):t},optionalTest_6_2_19:function(e){const t={warnings:[]};return Fb(e)?(e.vulnerabilities.forEach(((e,a)=>{const n=new Set([...e.product_status?.first_fixed??[],...e.product_status?.fixed??[]]);for(const s of n)e.scores?.forEach(((e,n)=>{if(e.products?.includes(s)){if(e.cvss_v3){const u="3.1"===e.cvss_v3.version||"3.0"===e.cvss_v3.version?zb({version:e.cvss_v3.version,vectorString:e.cvss_v3.vectorString??"",metrics:e.cvss_v3}):null;("number"==typeof e.cvss_v3.environmentalScore&&e.cvss_v3.environmentalScore>0||"number"==typeof u&&u>0||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v3`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}if(e.cvss_v2){const u=zb({version:"2.0",vectorString:e.cvss_v2.vectorString??"",metrics:e.cvss_v2});("number"==typeof e.cvss_v2.environmentalScore&&e.cvss_v2.environmentalScore>0||"number"==typeof u&&0!==u||null===u)&&t.warnings.push({instancePath:`/vulnerabilities/${a}/scores/${n}/cvss_v2`,message:`environmental score should be 0 since "${s}" is listed as fixed`})}}}))})),t):t},optionalTest_6_2_2:function(e){const t={warnings:[]};return fb(e)?(e.vulnerabilities?.forEach(((a,n)=>{const s=a.product_status;["first_affected","known_affected","last_affected","under_investigation"].forEach((u=>{const l=s[u];l?.forEach(((s,l)=>{const i=a.remediations?.some((t=>t.product_ids?.includes(s)||t.group_ids?.map((t=>e.product_tree?.product_groups?.find((e=>e.group_id===t)))).some((e=>e?.product_ids?.includes(s)))));i||t.warnings.push({message:"missing remediation",instancePath:`/vulnerabilities/${n}/product_status/${u}/${l}`})}))}))})),t):t},optionalTest_6_2_20:function(e){const t={warnings:[]};if(!Hb(e)){const e=Hb.errors?.filter((e=>"additionalProperties"===e.keyword))??[];for(const a of e)t.warnings.push({instancePath:a.instancePath,message:a.message??""})}return t},optionalTest_6_2_3:function(e){const t={warnings:[]};return yb(e)?(e.vulnerabilities?.forEach(((e,a)=>{const n=e.product_status;["first_affected","known_affected","last_affected"].forEach((s=>{const u=n[s];u?.forEach(((n,u)=>{const l=e.scores?.some((e=>e.products?.includes(n)));l||t.warnings.push({message:"missing score",instancePath:`/vulnerabilities/${a}/product_status/${s}/${u}`})}))}))})),t):t},optionalTest_6_2_4:function(e){const t=[],a={warnings:t};return db(e)?(e.document.tracking.revision_history?.forEach(((e,a)=>{const{number:n}=e;"string"==typeof n&&n.includes("+")&&t.push({message:"build metadata in revision history",instancePath:`/document/tracking/revision_history/${a}/number`})})),a):a},optionalTest_6_2_5:function(e){const t=[],a={warnings:t};if(!bb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(e.date,t.date)))[0];return n&&gd(e.document.tracking.initial_release_date,n.date)<0&&t.push({message:"older initial release date than revision history",instancePath:"/document/tracking/initial_release_date"}),a},optionalTest_6_2_6:function(e){const t=[],a={warnings:t};if(!xb(e))return a;const n=e.document.tracking.revision_history.slice().sort(((e,t)=>gd(t.date,e.date)))[0];return n&&gd(e.document.tracking.current_release_date,n.date)<0&&t.push({message:"older current release date than revision history",instancePath:"/document/tracking/current_release_date"}),a},optionalTest_6_2_7:function(e){const t={warnings:[]};return hb(e)?(e.vulnerabilities.forEach(((e,a)=>{e.involvements?.forEach(((e,n)=>{e.date||t.warnings.push({message:"missing date",instancePath:`/vulnerabilities/${a}/involvements/${n}`})}))})),t):t},optionalTest_6_2_8:function(e){return Cb(e,"md5")},optionalTest_6_2_9:function(e){return Cb(e,"sha1")}});Deno.core.ops.op_register_version("1.3.23"),Deno.core.ops.op_register_func((async function(e,t,a){let n=[];for(const t of e)switch(t){case"schema":n=n.concat(Object.values(Fu));break;case"mandatory":n=n.concat(Object.values(Qd));break;case"optional":n=n.concat(Object.values(qb));break;default:throw new Error(`Unknown validation set: ${t}`)}return n=n.filter((e=>!a.has(e.name))),async function(e,t){let a=!0;const n=[];for(const s of e){const e=await s(t),u="boolean"!=typeof e.isValid||e.isValid;n.push({isValid:u,errors:e.errors??[],warnings:e.warnings??[],infos:e.infos??[],name:s.name}),a=a&&u}return{tests:n,isValid:a}}(n,t)}));
@@ -3,6 +3,7 @@
 // Whenever you make changes to this script, you will need to re-run `npm build`.
 
 import validateLib from '@secvisogram/csaf-validator-lib/validate';
+import { version } from '@secvisogram/csaf-validator-lib/package.json';
 
 import * as schema from '@secvisogram/csaf-validator-lib/schemaTests';
 import * as mandatory from '@secvisogram/csaf-validator-lib/mandatoryTests';
@@ -55,4 +56,5 @@ async function runValidation(validations, doc, ignore) {
   return validateLib(tests, doc);
 }
 
+Deno.core.ops.op_register_version(version);
 Deno.core.ops.op_register_func(runValidation);
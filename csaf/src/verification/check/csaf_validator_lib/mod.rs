@@ -1,21 +1,28 @@
 //! A validator based on the `csaf_validator_lib`
 
+mod bundle;
 mod deno;
+mod pool;
 
 #[cfg(test)]
 mod test;
 
+pub use bundle::*;
+
 use crate::verification::check::{Check, CheckError};
-use anyhow::anyhow;
+use anyhow::{Context, anyhow, bail};
 use async_trait::async_trait;
 use csaf::Csaf;
 use deno_core::{
-    _ops::RustToV8NoScope, Extension, JsRuntime, OpDecl, PollEventLoopOptions, RuntimeOptions,
-    StaticModuleLoader, op2, serde_v8, v8,
+    _ops::RustToV8NoScope, Extension, JsRuntime, ModuleCodeString, OpDecl, PollEventLoopOptions,
+    RuntimeOptions, StaticModuleLoader, op2, serde_v8, v8,
 };
-use parking_lot::Mutex;
+use pool::Pool;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    borrow::Cow,
+    cell::OnceCell,
     collections::HashSet,
     fmt::Debug,
     rc::Rc,
@@ -29,9 +36,13 @@ use url::Url;
 
 const MODULE_ID: &str = "internal://bundle.js";
 
+/// The default number of runtimes, as each runtime requires a considerable amount of memory
+pub const DEFAULT_RUNTIMES: usize = 2;
+
 #[derive(Default)]
 pub struct FunctionsState {
     pub runner_func: Option<v8::Global<v8::Function>>,
+    pub version: Option<String>,
 }
 
 #[op2]
@@ -42,24 +53,60 @@ pub fn op_register_func(
     function_state.runner_func.replace(f);
 }
 
+#[op2]
+pub fn op_register_version(
+    #[state] function_state: &mut FunctionsState,
+    #[string] version: String,
+) {
+    function_state.version.replace(version);
+}
+
+/// Take the functions registered by the last evaluated module.
+fn take_functions(runtime: &mut JsRuntime) -> FunctionsState {
+    let state = runtime.op_state();
+    let mut state = state.borrow_mut();
+    std::mem::take(state.borrow_mut::<FunctionsState>())
+}
+
+/// Turn a module into code for the module loader, without copying the embedded bundle.
+fn module_code(module: &Module) -> ModuleCodeString {
+    match &module.code {
+        Cow::Borrowed(code) => ModuleCodeString::from_static(*code),
+        Cow::Owned(code) => code.clone().into(),
+    }
+}
+
+fn test_module_id(index: usize) -> String {
+    format!("internal://tests/{index}.js")
+}
+
 struct InnerCheck {
     runtime: JsRuntime,
     runner: v8::Global<v8::Function>,
+    tests: Vec<(String, v8::Global<v8::Function>)>,
 }
 
 impl InnerCheck {
-    pub async fn new() -> anyhow::Result<Self> {
+    pub async fn new(bundle: &Bundle) -> anyhow::Result<Self> {
         let specifier = Url::parse(MODULE_ID).expect("internal module ID must parse");
-        #[cfg(debug_assertions)]
-        let code = include_str!("js/bundle.debug.js");
-        #[cfg(not(debug_assertions))]
-        let code = include_str!("js/bundle.js");
+
+        let mut modules = vec![(specifier.clone(), module_code(&bundle.main))];
+        for (index, module) in bundle.tests.iter().enumerate() {
+            let specifier = Url::parse(&test_module_id(index)).expect("test module ID must parse");
+            modules.push((specifier, module_code(module)));
+        }
 
         let ext = Extension {
-            ops: std::borrow::Cow::Borrowed(&[{
-                const DECL: OpDecl = op_register_func();
-                DECL
-            }]),
+            ops: std::borrow::Cow::Borrowed(&[
+                {
+                    const DECL: OpDecl = op_register_func();
+                    DECL
+                },
+                {
+                    const DECL: OpDecl = op_register_version();
+                    DECL
+                },
+            ]),
             op_state_fn: Some(Box::new(|state| {
                 state.put(FunctionsState::default());
             })),
@@ -67,13 +114,12 @@ impl InnerCheck {
         };
 
         let mut runtime = JsRuntime::new(RuntimeOptions {
-            module_loader: Some(Rc::new(StaticModuleLoader::with(specifier, code))),
+            module_loader: Some(Rc::new(StaticModuleLoader::new(modules))),
             extensions: vec![ext],
             ..Default::default()
         });
 
-        let module = Url::parse(MODULE_ID)?;
-        let mod_id = runtime.load_main_es_module(&module).await?;
+        let mod_id = runtime.load_main_es_module(&specifier).await?;
         let result = runtime.mod_evaluate(mod_id);
         runtime
             .run_event_loop(PollEventLoopOptions::default())
@@ -81,12 +127,50 @@ impl InnerCheck {
 
         result.await?;
 
-        let state: FunctionsState = runtime.op_state().borrow_mut().take();
+        let state = take_functions(&mut runtime);
+        bundle.check_version(state.version.as_deref())?;
         let runner = state
             .runner_func
             .ok_or_else(|| anyhow!("runner function was not initialized"))?;
 
-        Ok(InnerCheck { runtime, runner })
+        let mut tests = Vec::with_capacity(bundle.tests.len());
+        for (index, module) in bundle.tests.iter().enumerate() {
+            let specifier = Url::parse(&test_module_id(index))?;
+            let mod_id = runtime.load_side_es_module(&specifier).await?;
+            let result = runtime.mod_evaluate(mod_id);
+            runtime
+                .run_event_loop(PollEventLoopOptions::default())
+                .await?;
+
+            result
+                .await
+                .with_context(|| format!("Failed to evaluate test module: {}", module.name))?;
+
+            let func = take_functions(&mut runtime).runner_func.ok_or_else(|| {
+                anyhow!("test module '{}' did not register a function", module.name)
+            })?;
+
+            let name = {
+                let scope = &mut runtime.handle_scope();
+                let func = v8::Local::new(scope, &func);
+                func.get_name(scope).to_rust_string_lossy(scope)
+            };
+
+            if name.is_empty() {
+                bail!(
+                    "test module '{}' registered an anonymous function",
+                    module.name
+                );
+            }
+
+            tests.push((name, func));
+        }
+
+        Ok(InnerCheck {
+            runtime,
+            runner,
+            tests,
+        })
     }
 
     async fn validate<S, D>(
@@ -129,6 +213,92 @@ impl InnerCheck {
             [validations, doc, ignore]
         };
 
+        let runner = self.runner.clone();
+        let Some(result) = self.call(&runner, &args, timeout).await? else {
+            return Ok(None);
+        };
+
+        log::debug!("Extract result");
+
+        let result = {
+            let scope = &mut self.runtime.handle_scope();
+            let result = v8::Local::new(scope, result);
+            let result: D = serde_v8::from_v8(scope, result)?;
+
+            result
+        };
+
+        log::trace!("Result: {result:#?}");
+
+        Ok(Some(result))
+    }
+
+    /// Run the custom tests, skipping the ignored ones.
+    async fn run_tests<S>(
+        &mut self,
+        doc: S,
+        ignore: &HashSet<String>,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<Option<Vec<Entry>>>
+    where
+        S: Serialize + Send,
+    {
+        let tests = self
+            .tests
+            .iter()
+            .filter(|(name, _)| !ignore.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if tests.is_empty() {
+            return Ok(Some(vec![]));
+        }
+
+        let doc = {
+            let scope = &mut self.runtime.handle_scope();
+            let doc = serde_v8::to_v8(scope, doc)?;
+            v8::Global::new(scope, doc)
+        };
+
+        let mut result = Vec::with_capacity(tests.len());
+
+        for (name, func) in tests {
+            log::debug!("Run custom test: {name}");
+
+            let Some(value) = self
+                .call(&func, std::slice::from_ref(&doc), timeout)
+                .await?
+            else {
+                return Ok(None);
+            };
+
+            let entry = {
+                let scope = &mut self.runtime.handle_scope();
+                let value = v8::Local::new(scope, value);
+                let entry: TestEntry = serde_v8::from_v8(scope, value)
+                    .with_context(|| format!("Invalid result of custom test: {name}"))?;
+                entry
+            };
+
+            result.push(Entry {
+                name,
+                is_valid: entry.is_valid,
+                errors: entry.errors,
+                warnings: entry.warnings,
+                infos: entry.infos,
+            });
+        }
+
+        Ok(Some(result))
+    }
+
+    /// Call a function, awaiting its result. Returns `None` if the call timed out.
+    async fn call(
+        &mut self,
+        func: &v8::Global<v8::Function>,
+        args: &[v8::Global<v8::Value>],
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<Option<v8::Global<v8::Value>>> {
         let cancelled = Arc::new(AtomicBool::new(false));
 
         let deadline = timeout.map(|duration| {
@@ -162,7 +332,7 @@ impl InnerCheck {
 
         log::debug!("Call function");
 
-        let call = self.runtime.call_with_args(&self.runner, &args);
+        let call = self.runtime.call_with_args(func, args);
 
         if cancelled.load(Ordering::Acquire) {
             // already cancelled
@@ -185,25 +355,11 @@ impl InnerCheck {
 
         // now process the result
 
-        let result = match result {
-            Err(err) if err.to_string().ends_with(": execution terminated") => return Ok(None),
-            Err(err) => return Err(err.into()),
-            Ok(result) => result,
-        };
-
-        log::debug!("Extract result");
-
-        let result = {
-            let scope = &mut self.runtime.handle_scope();
-            let result = v8::Local::new(scope, result);
-            let result: D = serde_v8::from_v8(scope, result)?;
-
-            result
-        };
-
-        log::trace!("Result: {result:#?}");
-
-        Ok(Some(result))
+        match result {
+            Err(err) if err.to_string().ends_with(": execution terminated") => Ok(None),
+            Err(err) => Err(err.into()),
+            Ok(result) => Ok(Some(result)),
+        }
     }
 }
 
@@ -232,8 +388,15 @@ pub enum Profile {
     Optional,
 }
 
+/// A check using `csaf_validator_lib`.
+///
+/// Documents are validated by a pool of runtimes, each running on a dedicated thread. By default,
+/// the pool has [`DEFAULT_RUNTIMES`] runtimes. The pool is started with the first document.
 pub struct CsafValidatorLib {
-    runtime: Rc<Mutex<Vec<InnerCheck>>>,
+    pool: OnceCell<Pool>,
+    runtimes: usize,
+    recycle_after: Option<usize>,
+    bundle: Bundle,
     validations: Vec<ValidationSet>,
     timeout: Option<Duration>,
     ignore: HashSet<String>,
@@ -241,8 +404,6 @@ pub struct CsafValidatorLib {
 
 impl CsafValidatorLib {
     pub fn new(profile: Profile) -> Self {
-        let runtime = Rc::new(Mutex::new(vec![]));
-
         let validations = match profile {
             Profile::Schema => vec![ValidationSet::Schema],
             Profile::Mandatory => vec![ValidationSet::Schema, ValidationSet::Mandatory],
@@ -254,13 +415,36 @@ impl CsafValidatorLib {
         };
 
         Self {
-            runtime,
+            pool: OnceCell::new(),
+            runtimes: DEFAULT_RUNTIMES,
+            recycle_after: None,
+            bundle: Bundle::default(),
             validations,
             ignore: Default::default(),
             timeout: None,
         }
     }
 
+    /// Use a different bundle, instead of the embedded one.
+    pub fn bundle(mut self, bundle: Bundle) -> Self {
+        self.bundle = bundle;
+        self
+    }
+
+    /// Set the number of runtimes validating documents in parallel (at least one).
+    pub fn runtimes(mut self, runtimes: usize) -> Self {
+        self.runtimes = runtimes.max(1);
+        self
+    }
+
+    /// Replace a runtime after it validated the given number of documents.
+    ///
+    /// Runtimes are always replaced after a timeout or an error.
+    pub fn recycle_after(mut self, documents: impl Into<Option<usize>>) -> Self {
+        self.recycle_after = documents.into();
+        self
+    }
+
     pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
         self.timeout = timeout.into();
         self
@@ -291,35 +475,37 @@ impl CsafValidatorLib {
             .extend(ignore.into_iter().map(|s| s.to_string()));
         self
     }
-}
 
-#[async_trait(?Send)]
-impl Check for CsafValidatorLib {
-    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
-        let mut inner = {
-            let inner = self.runtime.lock().pop();
-            match inner {
-                Some(inner) => inner,
-                None => InnerCheck::new().await?,
-            }
-        };
+    fn pool(&self) -> anyhow::Result<&Pool> {
+        if let Some(pool) = self.pool.get() {
+            return Ok(pool);
+        }
 
-        let test_result = inner
-            .validate::<_, TestResult>(csaf, &self.validations, &self.ignore, self.timeout)
-            .await?;
+        log::debug!("Starting {} validator runtime(s)", self.runtimes);
+
+        let pool = Pool::new(
+            self.runtimes,
+            pool::Settings {
+                bundle: self.bundle.clone(),
+                validations: self.validations.clone(),
+                ignore: self.ignore.clone(),
+                timeout: self.timeout,
+                recycle_after: self.recycle_after,
+            },
+        )?;
 
-        log::trace!("Result: {test_result:?}");
+        Ok(self.pool.get_or_init(|| pool))
+    }
 
-        let Some(test_result) = test_result else {
+    /// Validate a document, reporting the errors of failed tests.
+    async fn validate(&self, doc: Value) -> anyhow::Result<Vec<CheckError>> {
+        let Some(tests) = self.pool()?.validate(doc).await? else {
             return Ok(vec!["check timed out".into()]);
         };
 
-        // not timed out, not failed, we can re-use it
-        self.runtime.lock().push(inner);
-
         let mut result = vec![];
 
-        for entry in test_result.tests {
+        for entry in tests {
             // we currently only report "failed" tests
             if entry.is_valid {
                 continue;
@@ -342,6 +528,19 @@ impl Check for CsafValidatorLib {
     }
 }
 
+#[async_trait(?Send)]
+impl Check for CsafValidatorLib {
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        // lacking the original document, use the serialized model
+        self.validate(serde_json::to_value(csaf)?).await
+    }
+
+    /// Validate the original document, as the [`Csaf`] model drops unknown fields.
+    async fn check_json(&self, _csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        self.validate(json.clone()).await
+    }
+}
+
 /// Result structure, coming from the test call
 #[derive(Clone, Debug, Default, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -363,6 +562,20 @@ struct Entry {
     pub infos: Vec<TestResultEntry>,
 }
 
+/// Result of a custom test, which gets its name from the test function
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestEntry {
+    pub is_valid: bool,
+
+    #[serde(default)]
+    pub errors: Vec<TestResultEntry>,
+    #[serde(default)]
+    pub warnings: Vec<TestResultEntry>,
+    #[serde(default)]
+    pub infos: Vec<TestResultEntry>,
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TestResultEntry {
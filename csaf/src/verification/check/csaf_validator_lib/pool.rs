@@ -0,0 +1,150 @@
+//! A pool of validator runtimes, each running on a dedicated thread.
+//!
+//! A [`deno_core::JsRuntime`] can't be moved between threads, and blocks its thread while running
+//! the tests. So each runtime is owned by its own thread, picking up documents from a shared queue.
+
+use super::{Bundle, Entry, InnerCheck, TestResult, ValidationSet};
+use anyhow::{Context, anyhow};
+use parking_lot::Mutex;
+use std::{
+    collections::HashSet,
+    sync::{Arc, mpsc},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// Settings, shared by all runtimes of a pool
+#[derive(Debug)]
+pub(crate) struct Settings {
+    pub bundle: Bundle,
+    pub validations: Vec<ValidationSet>,
+    pub ignore: HashSet<String>,
+    pub timeout: Option<Duration>,
+    /// Number of documents after which a runtime gets replaced
+    pub recycle_after: Option<usize>,
+}
+
+/// The outcome of validating a document, `None` if the validation timed out
+type Outcome = anyhow::Result<Option<Vec<Entry>>>;
+
+struct Job {
+    doc: serde_json::Value,
+    reply: oneshot::Sender<Outcome>,
+}
+
+pub(crate) struct Pool {
+    sender: mpsc::Sender<Job>,
+}
+
+impl Pool {
+    /// Create a new pool, starting `size` threads.
+    ///
+    /// Runtimes are created when a thread processes its first document, and after it got recycled.
+    /// Dropping the pool stops the threads, once they processed the queued documents.
+    pub fn new(size: usize, settings: Settings) -> anyhow::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let settings = Arc::new(settings);
+
+        for index in 0..size.max(1) {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .context("Failed to create validator runtime")?;
+
+            let receiver = receiver.clone();
+            let settings = settings.clone();
+
+            std::thread::Builder::new()
+                .name(format!("csaf-validator-{index}"))
+                .spawn(move || {
+                    let mut worker = Worker {
+                        settings,
+                        check: None,
+                    };
+
+                    loop {
+                        // only hold the lock while waiting for the next job
+                        let job = receiver.lock().recv();
+                        let Ok(job) = job else {
+                            log::debug!("Validator pool closed, stopping runtime #{index}");
+                            break;
+                        };
+
+                        let outcome = runtime.block_on(worker.run(job.doc));
+                        // the caller might no longer wait for the result
+                        let _ = job.reply.send(outcome);
+                    }
+                })
+                .context("Failed to start validator thread")?;
+        }
+
+        Ok(Self { sender })
+    }
+
+    /// Validate a document, using the next available runtime.
+    pub async fn validate(&self, doc: serde_json::Value) -> Outcome {
+        let (reply, result) = oneshot::channel();
+
+        self.sender
+            .send(Job { doc, reply })
+            .map_err(|_| anyhow!("validator pool is closed"))?;
+
+        result
+            .await
+            .map_err(|_| anyhow!("validator runtime failed"))?
+    }
+}
+
+/// The current runtime of a thread
+struct Worker {
+    settings: Arc<Settings>,
+    check: Option<(InnerCheck, usize)>,
+}
+
+impl Worker {
+    async fn run(&mut self, doc: serde_json::Value) -> Outcome {
+        // on errors or timeouts, the runtime gets dropped, and re-created with the next document
+        let (mut check, documents) = match self.check.take() {
+            Some(check) => check,
+            None => (InnerCheck::new(&self.settings.bundle).await?, 0),
+        };
+
+        let settings = &self.settings;
+
+        let Some(mut result) = check
+            .validate::<_, TestResult>(
+                &doc,
+                &settings.validations,
+                &settings.ignore,
+                settings.timeout,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        log::trace!("Result: {result:?}");
+
+        let Some(custom) = check
+            .run_tests(&doc, &settings.ignore, settings.timeout)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        result.tests.extend(custom);
+
+        let documents = documents + 1;
+        if settings
+            .recycle_after
+            .is_none_or(|recycle_after| documents < recycle_after)
+        {
+            self.check = Some((check, documents));
+        } else {
+            log::debug!("Recycling validator runtime after {documents} documents");
+        }
+
+        Ok(Some(result.tests))
+    }
+}
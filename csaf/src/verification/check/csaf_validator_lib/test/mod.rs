@@ -105,6 +105,35 @@ async fn test_ok() {
     assert_eq!(result, Vec::<CheckError>::new());
 }
 
+/// validate the original document, not the model
+#[tokio::test]
+async fn test_original_json() {
+    let _ = env_logger::builder()
+        .filter_level(LevelFilter::Info)
+        .try_init();
+
+    let check = CsafValidatorLib::new(Profile::Optional);
+
+    let csaf = valid_doc();
+    let mut json = serde_json::to_value(&csaf).expect("must serialize");
+
+    let result = check.check_json(&csaf, &json).await;
+    log::info!("Result: {result:#?}");
+    let result = result.expect("must succeed");
+    assert_eq!(result, Vec::<CheckError>::new());
+
+    // only the JSON is missing the title
+    json["document"]
+        .as_object_mut()
+        .expect("must be an object")
+        .remove("title");
+
+    let result = check.check_json(&csaf, &json).await;
+    log::info!("Result: {result:#?}");
+    let result = result.expect("must succeed");
+    assert!(!result.is_empty());
+}
+
 #[tokio::test]
 async fn test_timeout() {
     let _ = env_logger::builder().try_init();
@@ -204,3 +233,97 @@ async fn test_ignore() {
     let result = result.expect("must succeed");
     assert_eq!(result, Vec::<CheckError>::new());
 }
+
+#[tokio::test]
+async fn test_custom() {
+    let _ = env_logger::builder()
+        .filter_level(LevelFilter::Info)
+        .try_init();
+
+    let bundle = Bundle::embedded().add_test(Module {
+        name: "custom".to_string(),
+        code: Cow::Borrowed(
+            r#"
+function titleNotEmpty(doc) {
+  const errors = doc.document.title ? [] : [{ message: "empty title", instancePath: "/document/title" }];
+  return { isValid: errors.length === 0, errors, warnings: [], infos: [] };
+}
+
+Deno.core.ops.op_register_func(titleNotEmpty);
+"#,
+        ),
+    });
+
+    let check = CsafValidatorLib::new(Profile::Schema).bundle(bundle);
+
+    let result = check.check(&valid_doc()).await.expect("must succeed");
+    assert!(
+        !result
+            .iter()
+            .any(|error| error.to_string().starts_with("titleNotEmpty")),
+    );
+
+    let result = check.check(&invalid_doc()).await.expect("must succeed");
+    assert!(result.contains(&"titleNotEmpty (/document/title): empty title".into()));
+
+    let check = CsafValidatorLib::new(Profile::Schema)
+        .bundle(Bundle::embedded().add_test(Module {
+            name: "broken".to_string(),
+            code: Cow::Borrowed("Deno.core.ops.op_register_func(() => ({ isValid: true }));"),
+        }))
+        .ignore(["titleNotEmpty"]);
+    assert!(check.check(&invalid_doc()).await.is_err());
+}
+
+#[test]
+fn test_verify() {
+    assert!(Bundle::embedded().verify().is_ok());
+
+    let bundle = Bundle {
+        main: Module {
+            name: "outdated".to_string(),
+            code: Cow::Borrowed(
+                r#"
+Deno.core.ops.op_register_version("1.0.0");
+Deno.core.ops.op_register_func(() => ({ tests: [] }));
+"#,
+            ),
+        },
+        external: true,
+        tests: vec![],
+    };
+    assert!(bundle.verify().is_err());
+
+    let bundle = Bundle::embedded().add_test(Module {
+        name: "broken".to_string(),
+        code: Cow::Borrowed("Deno.core.ops.op_register_func(() => ({ isValid: true }));"),
+    });
+    assert!(bundle.verify().is_err());
+}
+
+/// validate in parallel, recycling runtimes
+#[tokio::test]
+async fn test_pool() {
+    let _ = env_logger::builder()
+        .filter_level(LevelFilter::Info)
+        .try_init();
+
+    let check = CsafValidatorLib::new(Profile::Optional)
+        .runtimes(2)
+        .recycle_after(1);
+
+    let valid = valid_doc();
+    let invalid = invalid_doc();
+
+    let results = futures::future::join_all(
+        [&valid, &invalid, &valid, &invalid]
+            .into_iter()
+            .map(|doc| check.check(doc)),
+    )
+    .await;
+
+    for (index, result) in results.into_iter().enumerate() {
+        let result = result.expect("must succeed");
+        assert_eq!(result.is_empty(), index % 2 == 0);
+    }
+}
@@ -0,0 +1,410 @@
+//! Parsing and scoring of CVSS vectors.
+//!
+//! Supports CVSS v2.0, v3.0, v3.1, and v4.0 vector strings, calculating the base, temporal, and
+//! environmental scores, along with their severities.
+//!
+//! The CVSS v4 scores and the CVSS v3 base scores are calculated by the `cvss` crate. As it
+//! supports neither CVSS v2, nor the temporal and environmental metrics of CVSS v3, those equations
+//! are implemented here.
+//!
+//! ```
+//! use csaf_walker::verification::check::cvss::Vector;
+//!
+//! let vector: Vector = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:P/RL:O"
+//!     .parse()
+//!     .unwrap();
+//! let scores = vector.scores();
+//! assert_eq!(scores.base.value, 9.8);
+//! assert_eq!(scores.temporal.value, 8.8);
+//! ```
+
+mod v2;
+mod v3;
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// The version of a CVSS vector
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Version {
+    V2_0,
+    V3_0,
+    V3_1,
+    V4_0,
+}
+
+impl Version {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V2_0 => "2.0",
+            Self::V3_0 => "3.0",
+            Self::V3_1 => "3.1",
+            Self::V4_0 => "4.0",
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    #[error("unsupported version '{0}'")]
+    UnsupportedVersion(String),
+    #[error("invalid component '{0}'")]
+    InvalidComponent(String),
+    #[error("unknown metric '{0}'")]
+    UnknownMetric(String),
+    #[error("invalid value '{value}' of metric '{metric}'")]
+    InvalidValue { metric: String, value: String },
+    #[error("duplicate metric '{0}'")]
+    DuplicateMetric(String),
+    #[error("missing base metric '{0}'")]
+    MissingMetric(String),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// The severity of a score, as defined by CVSS v3 and v4
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn from_score(score: f64) -> Self {
+        if score < 0.1 {
+            Self::None
+        } else if score < 4.0 {
+            Self::Low
+        } else if score < 7.0 {
+            Self::Medium
+        } else if score < 9.0 {
+            Self::High
+        } else {
+            Self::Critical
+        }
+    }
+
+    /// The name, as used by the CVSS JSON schemas (e.g. `HIGH`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "NONE",
+            Self::Low => "LOW",
+            Self::Medium => "MEDIUM",
+            Self::High => "HIGH",
+            Self::Critical => "CRITICAL",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A calculated score
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Score {
+    pub value: f64,
+    /// The severity, not defined for CVSS v2
+    pub severity: Option<Severity>,
+}
+
+impl Score {
+    fn new(value: f64) -> Self {
+        Self {
+            value,
+            severity: None,
+        }
+    }
+
+    fn with_severity(value: f64) -> Self {
+        Self {
+            value,
+            severity: Some(Severity::from_score(value)),
+        }
+    }
+}
+
+/// The calculated scores of a vector.
+///
+/// Scores of metrics groups which are not present in the vector are calculated using the default
+/// values ("not defined"). For CVSS v4, the temporal score is the "threat" score (CVSS-BT), and the
+/// environmental score includes the threat metrics (CVSS-BTE).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scores {
+    pub base: Score,
+    pub temporal: Score,
+    pub environmental: Score,
+}
+
+/// The metrics of a vector
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Metrics(BTreeMap<String, String>);
+
+impl Metrics {
+    /// The value of a metric, empty if it is not present
+    fn get(&self, metric: &str) -> &str {
+        self.0.get(metric).map(String::as_str).unwrap_or_default()
+    }
+
+    /// Render the metrics as a CVSS v4 vector, only using the selected metrics.
+    fn v4_vector(&self, filter: impl Fn(&str) -> bool) -> String {
+        // the order of metrics is defined by the specification
+        let mut result = "CVSS:4.0".to_string();
+        for metric in V4_METRICS {
+            if !filter(metric) {
+                continue;
+            }
+            if let Some(value) = self.0.get(*metric) {
+                result.push_str(&format!("/{metric}:{value}"));
+            }
+        }
+        result
+    }
+}
+
+/// CVSS v4 metrics, in the order of the specification
+const V4_METRICS: &[&str] = &[
+    "AV", "AC", "AT", "PR", "UI", "VC", "VI", "VA", "SC", "SI", "SA", "E", "CR", "IR", "AR", "MAV",
+    "MAC", "MAT", "MPR", "MUI", "MVC", "MVI", "MVA", "MSC", "MSI", "MSA", "S", "AU", "R", "V",
+    "RE", "U",
+];
+
+const V4_BASE_METRICS: &[&str] = &[
+    "AV", "AC", "AT", "PR", "UI", "VC", "VI", "VA", "SC", "SI", "SA",
+];
+
+/// A parsed and validated CVSS vector
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vector {
+    version: Version,
+    metrics: Metrics,
+}
+
+impl Vector {
+    /// Parse a vector string.
+    ///
+    /// Vector strings without a `CVSS:` prefix are considered CVSS v2 vectors.
+    pub fn parse(vector: &str) -> Result<Self, Error> {
+        let (version, metrics) = match vector.split_once('/') {
+            Some((prefix, metrics)) if prefix.starts_with("CVSS:") => {
+                let version = match &prefix["CVSS:".len()..] {
+                    "3.0" => Version::V3_0,
+                    "3.1" => Version::V3_1,
+                    "4.0" => Version::V4_0,
+                    version => return Err(Error::UnsupportedVersion(version.to_string())),
+                };
+                (version, metrics)
+            }
+            _ => (Version::V2_0, vector),
+        };
+
+        let mut result = Metrics::default();
+        for component in metrics.split('/') {
+            let Some((metric, value)) = component.split_once(':') else {
+                return Err(Error::InvalidComponent(component.to_string()));
+            };
+            if result
+                .0
+                .insert(metric.to_string(), value.to_string())
+                .is_some()
+            {
+                return Err(Error::DuplicateMetric(metric.to_string()));
+            }
+        }
+
+        let definitions = match version {
+            Version::V2_0 => v2::METRICS,
+            Version::V3_0 | Version::V3_1 => v3::METRICS,
+            Version::V4_0 => {
+                // use the v4 implementation for validating
+                ::cvss::v4::Vector::from_str(vector)
+                    .map_err(|err| Error::Invalid(err.to_string()))?;
+                return Ok(Self {
+                    version,
+                    metrics: result,
+                });
+            }
+        };
+
+        for (metric, value) in &result.0 {
+            let Some((_, _, values)) = definitions.iter().find(|(name, _, _)| name == metric)
+            else {
+                return Err(Error::UnknownMetric(metric.clone()));
+            };
+            if !values.contains(&value.as_str()) {
+                return Err(Error::InvalidValue {
+                    metric: metric.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        if let Some((metric, _, _)) = definitions
+            .iter()
+            .find(|(metric, base, _)| *base && !result.0.contains_key(*metric))
+        {
+            return Err(Error::MissingMetric(metric.to_string()));
+        }
+
+        Ok(Self {
+            version,
+            metrics: result,
+        })
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The value of a metric, if present
+    pub fn metric(&self, metric: &str) -> Option<&str> {
+        self.metrics.0.get(metric).map(String::as_str)
+    }
+
+    /// Calculate the scores.
+    pub fn scores(&self) -> Scores {
+        match self.version {
+            Version::V2_0 => v2::scores(&self.metrics),
+            Version::V3_0 | Version::V3_1 => v3::scores(self.version, &self.metrics),
+            Version::V4_0 => {
+                let score = |filter: &dyn Fn(&str) -> bool| {
+                    let vector = self.metrics.v4_vector(filter);
+                    let value = ::cvss::v4::Vector::from_str(&vector)
+                        .map(|vector| vector.score().value())
+                        // parsing a subset of a valid vector can't fail
+                        .unwrap_or_default();
+                    Score::with_severity(value)
+                };
+
+                let base = |metric: &str| V4_BASE_METRICS.contains(&metric);
+                let threat = |metric: &str| base(metric) || metric == "E";
+                let environmental = |metric: &str| {
+                    threat(metric)
+                        || matches!(metric, "CR" | "IR" | "AR")
+                        || metric.starts_with('M')
+                };
+
+                Scores {
+                    base: score(&base),
+                    temporal: score(&threat),
+                    environmental: score(&environmental),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Vector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scores(vector: &str) -> (f64, f64, f64) {
+        let scores = Vector::parse(vector).expect("vector must parse").scores();
+        (
+            scores.base.value,
+            scores.temporal.value,
+            scores.environmental.value,
+        )
+    }
+
+    #[test]
+    fn v2() {
+        // examples of the CVSS v2 specification, section 3.3
+        assert_eq!(
+            scores("AV:N/AC:L/Au:N/C:N/I:N/A:C/E:F/RL:OF/RC:C/CDP:H/TD:H/CR:M/IR:M/AR:H"),
+            (7.8, 6.4, 9.2)
+        );
+        assert_eq!(
+            scores("AV:N/AC:L/Au:N/C:C/I:C/A:C/E:F/RL:OF/RC:C/CDP:H/TD:H/CR:M/IR:M/AR:L"),
+            (10.0, 8.3, 9.0)
+        );
+        assert_eq!(
+            scores("AV:L/AC:H/Au:N/C:C/I:C/A:C/E:POC/RL:OF/RC:C/CDP:H/TD:H/CR:M/IR:M/AR:M"),
+            (6.2, 4.9, 7.5)
+        );
+        assert_eq!(
+            Vector::parse("AV:N/AC:L/Au:N/C:N/I:N/A:N").map(|v| v.scores().base),
+            Ok(Score::new(0.0))
+        );
+    }
+
+    #[test]
+    fn v3() {
+        assert_eq!(
+            scores("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            (9.8, 9.8, 9.8)
+        );
+        assert_eq!(
+            scores("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:L/I:L/A:N/E:P/RL:T/RC:R/CR:H/MAV:A/MS:U"),
+            (6.4, 5.6, 4.6)
+        );
+        assert_eq!(
+            scores("CVSS:3.0/AV:L/AC:H/PR:H/UI:R/S:C/C:H/I:H/A:H/E:U/RL:O/RC:U/MS:C/MC:H"),
+            (7.2, 5.8, 5.8)
+        );
+
+        let scores = Vector::parse("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N")
+            .expect("vector must parse")
+            .scores();
+        assert_eq!(scores.base.value, 5.9);
+        assert_eq!(scores.base.severity, Some(Severity::Medium));
+    }
+
+    #[test]
+    fn v4() {
+        assert_eq!(
+            scores("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/E:U/CR:L"),
+            (9.3, 8.1, 8.0)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Vector::parse("CVSS:3.2/AV:N"),
+            Err(Error::UnsupportedVersion("3.2".into()))
+        );
+        assert_eq!(
+            Vector::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H"),
+            Err(Error::MissingMetric("A".into()))
+        );
+        assert_eq!(
+            Vector::parse("CVSS:3.1/AV:N/AV:L"),
+            Err(Error::DuplicateMetric("AV".into()))
+        );
+        assert_eq!(
+            Vector::parse("AV:N/AC:L/Au:N/C:N/I:N/A:X"),
+            Err(Error::InvalidValue {
+                metric: "A".into(),
+                value: "X".into()
+            })
+        );
+        assert_eq!(
+            Vector::parse("AV:N/AC:L/Au:N/C:N/I:N/A:N/XX:Y"),
+            Err(Error::UnknownMetric("XX".into()))
+        );
+        assert!(Vector::parse("CVSS:4.0/AV:N").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! CVSS v2 scoring, following section 3.2 of the specification
+//!
+//! The `cvss` crate doesn't support CVSS v2, so the equations are implemented here.
+
+use super::{Metrics, Score, Scores};
+
+/// CVSS v2 metrics, whether they are base metrics, and their values
+pub(super) const METRICS: &[(&str, bool, &[&str])] = &[
+    ("AV", true, &["L", "A", "N"]),
+    ("AC", true, &["H", "M", "L"]),
+    ("Au", true, &["M", "S", "N"]),
+    ("C", true, &["N", "P", "C"]),
+    ("I", true, &["N", "P", "C"]),
+    ("A", true, &["N", "P", "C"]),
+    ("E", false, &["U", "POC", "F", "H", "ND"]),
+    ("RL", false, &["OF", "TF", "W", "U", "ND"]),
+    ("RC", false, &["UC", "UR", "C", "ND"]),
+    ("CDP", false, &["N", "L", "LM", "MH", "H", "ND"]),
+    ("TD", false, &["N", "L", "M", "H", "ND"]),
+    ("CR", false, &["L", "M", "H", "ND"]),
+    ("IR", false, &["L", "M", "H", "ND"]),
+    ("AR", false, &["L", "M", "H", "ND"]),
+];
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn impact(metrics: &Metrics, metric: &str) -> f64 {
+    match metrics.get(metric) {
+        "P" => 0.275,
+        "C" => 0.660,
+        _ => 0.0,
+    }
+}
+
+fn requirement(metrics: &Metrics, metric: &str) -> f64 {
+    match metrics.get(metric) {
+        "L" => 0.5,
+        "H" => 1.51,
+        _ => 1.0,
+    }
+}
+
+/// The base equation, for the (possibly adjusted) impact
+fn base(metrics: &Metrics, impact: f64) -> f64 {
+    let av = match metrics.get("AV") {
+        "L" => 0.395,
+        "A" => 0.646,
+        _ => 1.0,
+    };
+    let ac = match metrics.get("AC") {
+        "H" => 0.35,
+        "M" => 0.61,
+        _ => 0.71,
+    };
+    let au = match metrics.get("Au") {
+        "M" => 0.45,
+        "S" => 0.56,
+        _ => 0.704,
+    };
+
+    if impact == 0.0 {
+        return 0.0;
+    }
+
+    let exploitability = 20.0 * av * ac * au;
+
+    round(((0.6 * impact) + (0.4 * exploitability) - 1.5) * 1.176)
+}
+
+/// The temporal equation, for a (possibly adjusted) base score
+fn temporal(metrics: &Metrics, base: f64) -> f64 {
+    let e = match metrics.get("E") {
+        "U" => 0.85,
+        "POC" => 0.9,
+        "F" => 0.95,
+        _ => 1.0,
+    };
+    let rl = match metrics.get("RL") {
+        "OF" => 0.87,
+        "TF" => 0.90,
+        "W" => 0.95,
+        _ => 1.0,
+    };
+    let rc = match metrics.get("RC") {
+        "UC" => 0.90,
+        "UR" => 0.95,
+        _ => 1.0,
+    };
+
+    round(base * e * rl * rc)
+}
+
+pub(super) fn scores(metrics: &Metrics) -> Scores {
+    let (c, i, a) = (
+        impact(metrics, "C"),
+        impact(metrics, "I"),
+        impact(metrics, "A"),
+    );
+
+    let base_score = base(metrics, 10.41 * (1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a)));
+    let temporal_score = temporal(metrics, base_score);
+
+    let adjusted_impact = f64::min(
+        10.0,
+        10.41
+            * (1.0
+                - (1.0 - c * requirement(metrics, "CR"))
+                    * (1.0 - i * requirement(metrics, "IR"))
+                    * (1.0 - a * requirement(metrics, "AR"))),
+    );
+    let adjusted_temporal = temporal(metrics, base(metrics, adjusted_impact));
+    let cdp = match metrics.get("CDP") {
+        "L" => 0.1,
+        "LM" => 0.3,
+        "MH" => 0.4,
+        "H" => 0.5,
+        _ => 0.0,
+    };
+    let td = match metrics.get("TD") {
+        "N" => 0.0,
+        "L" => 0.25,
+        "M" => 0.75,
+        _ => 1.0,
+    };
+    let environmental_score = round((adjusted_temporal + (10.0 - adjusted_temporal) * cdp) * td);
+
+    // CVSS v2 doesn't define severities
+    Scores {
+        base: Score::new(base_score),
+        temporal: Score::new(temporal_score),
+        environmental: Score::new(environmental_score),
+    }
+}
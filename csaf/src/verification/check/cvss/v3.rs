@@ -0,0 +1,189 @@
+//! CVSS v3.0 and v3.1 scoring, following section 7 of the specifications
+//!
+//! The base score is calculated by the `cvss` crate. As it doesn't support the temporal and
+//! environmental metrics, those equations are implemented here.
+
+use super::{Metrics, Score, Scores, Version};
+use std::str::FromStr;
+
+/// CVSS v3 metrics, whether they are base metrics, and their values
+pub(super) const METRICS: &[(&str, bool, &[&str])] = &[
+    ("AV", true, &["N", "A", "L", "P"]),
+    ("AC", true, &["L", "H"]),
+    ("PR", true, &["N", "L", "H"]),
+    ("UI", true, &["N", "R"]),
+    ("S", true, &["U", "C"]),
+    ("C", true, &["H", "L", "N"]),
+    ("I", true, &["H", "L", "N"]),
+    ("A", true, &["H", "L", "N"]),
+    ("E", false, &["X", "U", "P", "F", "H"]),
+    ("RL", false, &["X", "O", "T", "W", "U"]),
+    ("RC", false, &["X", "U", "R", "C"]),
+    ("CR", false, &["X", "L", "M", "H"]),
+    ("IR", false, &["X", "L", "M", "H"]),
+    ("AR", false, &["X", "L", "M", "H"]),
+    ("MAV", false, &["X", "N", "A", "L", "P"]),
+    ("MAC", false, &["X", "L", "H"]),
+    ("MPR", false, &["X", "N", "L", "H"]),
+    ("MUI", false, &["X", "N", "R"]),
+    ("MS", false, &["X", "U", "C"]),
+    ("MC", false, &["X", "N", "L", "H"]),
+    ("MI", false, &["X", "N", "L", "H"]),
+    ("MA", false, &["X", "N", "L", "H"]),
+];
+
+/// Round up to one decimal, as defined by the version
+fn roundup(version: Version, value: f64) -> f64 {
+    match version {
+        Version::V3_0 => (value * 10.0).ceil() / 10.0,
+        // v3.1 avoids floating point artifacts (Appendix A)
+        _ => {
+            let value = (value * 100_000.0).round() as i64;
+            if value % 10_000 == 0 {
+                value as f64 / 100_000.0
+            } else {
+                ((value / 10_000) + 1) as f64 / 10.0
+            }
+        }
+    }
+}
+
+/// The value of a metric, falling back to the base metric if it is not defined
+fn modified<'m>(metrics: &'m Metrics, metric: &str) -> &'m str {
+    match metrics.get(&format!("M{metric}")) {
+        "" | "X" => metrics.get(metric),
+        value => value,
+    }
+}
+
+fn attack_vector(value: &str) -> f64 {
+    match value {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        _ => 0.2,
+    }
+}
+
+fn attack_complexity(value: &str) -> f64 {
+    match value {
+        "L" => 0.77,
+        _ => 0.44,
+    }
+}
+
+fn privileges_required(value: &str, changed: bool) -> f64 {
+    match (value, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        (_, false) => 0.27,
+        (_, true) => 0.5,
+    }
+}
+
+fn user_interaction(value: &str) -> f64 {
+    match value {
+        "N" => 0.85,
+        _ => 0.62,
+    }
+}
+
+fn impact(value: &str) -> f64 {
+    match value {
+        "H" => 0.56,
+        "L" => 0.22,
+        _ => 0.0,
+    }
+}
+
+fn requirement(value: &str) -> f64 {
+    match value {
+        "H" => 1.5,
+        "L" => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Render the base metrics as a vector, as the `cvss` crate rejects other metrics.
+fn base_vector(version: Version, metrics: &Metrics) -> String {
+    let mut result = format!("CVSS:{version}");
+    for (metric, _, _) in METRICS.iter().filter(|(_, base, _)| *base) {
+        result.push_str(&format!("/{metric}:{}", metrics.get(metric)));
+    }
+    result
+}
+
+pub(super) fn scores(version: Version, metrics: &Metrics) -> Scores {
+    let roundup = |value| roundup(version, value);
+
+    // base
+
+    let base = ::cvss::v3::Base::from_str(&base_vector(version, metrics))
+        .map(|base| base.score().value())
+        // parsing the base metrics of a valid vector can't fail
+        .unwrap_or_default();
+
+    // temporal
+
+    let e = match metrics.get("E") {
+        "U" => 0.91,
+        "P" => 0.94,
+        "F" => 0.97,
+        _ => 1.0,
+    };
+    let rl = match metrics.get("RL") {
+        "O" => 0.95,
+        "T" => 0.96,
+        "W" => 0.97,
+        _ => 1.0,
+    };
+    let rc = match metrics.get("RC") {
+        "U" => 0.92,
+        "R" => 0.96,
+        _ => 1.0,
+    };
+
+    let temporal = roundup(base * e * rl * rc);
+
+    // environmental
+
+    let changed = modified(metrics, "S") == "C";
+    let miss = f64::min(
+        1.0 - (1.0 - requirement(metrics.get("CR")) * impact(modified(metrics, "C")))
+            * (1.0 - requirement(metrics.get("IR")) * impact(modified(metrics, "I")))
+            * (1.0 - requirement(metrics.get("AR")) * impact(modified(metrics, "A"))),
+        0.915,
+    );
+    let modified_impact = match (changed, version) {
+        (false, _) => 6.42 * miss,
+        (true, Version::V3_0) => 7.52 * (miss - 0.029) - 3.25 * (miss - 0.02).powi(15),
+        (true, _) => 7.52 * (miss - 0.029) - 3.25 * (miss * 0.9731 - 0.02).powi(13),
+    };
+    let modified_exploitability = 8.22
+        * attack_vector(modified(metrics, "AV"))
+        * attack_complexity(modified(metrics, "AC"))
+        * privileges_required(modified(metrics, "PR"), changed)
+        * user_interaction(modified(metrics, "UI"));
+
+    let environmental = if modified_impact <= 0.0 {
+        0.0
+    } else if changed {
+        roundup(
+            roundup(f64::min(
+                1.08 * (modified_impact + modified_exploitability),
+                10.0,
+            )) * e
+                * rl
+                * rc,
+        )
+    } else {
+        roundup(roundup(f64::min(modified_impact + modified_exploitability, 10.0)) * e * rl * rc)
+    };
+
+    Scores {
+        base: Score::with_severity(base),
+        temporal: Score::with_severity(temporal),
+        environmental: Score::with_severity(environmental),
+    }
+}
@@ -0,0 +1,34 @@
+//! The CWE catalog, as used by `csaf-validator-lib`.
+
+use serde::Deserialize;
+use std::{collections::HashMap, sync::LazyLock};
+
+/// The names of the weaknesses of the CWE catalog, by ID
+pub(crate) static CWE_NAMES: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    #[derive(Deserialize)]
+    struct Weakness {
+        id: String,
+        name: String,
+    }
+
+    serde_json::from_str::<Vec<Weakness>>(include_str!("cwe.json"))
+        .expect("embedded CWE catalog must parse")
+        .into_iter()
+        .map(|weakness| (weakness.id, weakness.name))
+        .collect()
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cwe_names() {
+        assert_eq!(
+            CWE_NAMES.get("CWE-79").map(String::as_str),
+            Some(
+                "Improper Neutralization of Input During Web Page Generation ('Cross-site Scripting')"
+            )
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Checks of the product identification helpers, which downstream tooling (like SBOM matching)
+//! relies on.
+//!
+//! * `invalid-cpe`: CPEs must be valid CPE 2.2 URIs or CPE 2.3 formatted strings, identifying at
+//!   least a vendor and a product
+//! * `duplicate-identification-helper`: the same PURL or CPE must not identify different products
+//!
+//! The checks are part of the optional profile. PURLs are validated by the mandatory test 6.1.13,
+//! missing identification helpers are reported by the optional test 6.2.16.
+
+use crate::verification::check::{
+    Check, Finding, JsonCheck, Severity, products::full_product_names, with_severity,
+};
+use cpe::{component::Component, cpe::Cpe, uri::Uri};
+use csaf::Csaf;
+use packageurl::PackageUrl;
+use serde_json::Value;
+use std::{collections::HashMap, str::FromStr};
+
+/// The attributes of a CPE 2.3 formatted string, following the `cpe:2.3` prefix
+const CPE23_ATTRIBUTES: &[&str] = &[
+    "part",
+    "vendor",
+    "product",
+    "version",
+    "update",
+    "edition",
+    "language",
+    "sw_edition",
+    "target_sw",
+    "target_hw",
+    "other",
+];
+
+/// Split a CPE 2.3 formatted string at unescaped colons.
+fn split_cpe23(cpe: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in cpe.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ':' => {
+                result.push(&cpe[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(&cpe[start..]);
+
+    result
+}
+
+/// Validate the value of a CPE 2.3 attribute (section 6.2 of NISTIR 7695).
+fn validate_cpe23_value(value: &str) -> Result<(), String> {
+    if value == "*" || value == "-" {
+        return Ok(());
+    }
+
+    // characters, along with being quoted
+    let mut chars = vec![];
+    let mut iter = value.chars();
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' => match iter.next() {
+                Some(c) if c.is_ascii_punctuation() => chars.push((c, true)),
+                _ => return Err(format!("invalid escape sequence in '{value}'")),
+            },
+            c => chars.push((c, false)),
+        }
+    }
+
+    // unquoted special characters are only allowed at the beginning and end: a single '*' or
+    // a sequence of '?'
+    let special = |chars: &[(char, bool)]| {
+        if let Some((('*', false), _)) = chars.split_first() {
+            1
+        } else {
+            chars
+                .iter()
+                .take_while(|(c, quoted)| *c == '?' && !quoted)
+                .count()
+        }
+    };
+    let chars = &chars[special(&chars)..];
+    let mut reversed = chars.to_vec();
+    reversed.reverse();
+    let chars = &chars[..chars.len() - special(&reversed)];
+
+    if chars.is_empty() {
+        return Err(format!("no value in '{value}'"));
+    }
+
+    for (c, quoted) in chars {
+        if !(*quoted || c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_')) {
+            return Err(format!("invalid character '{c}' in '{value}'"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a CPE 2.3 formatted string, returning the vendor and product.
+fn validate_cpe23(cpe: &str) -> Result<(String, String), String> {
+    let components = split_cpe23(cpe);
+    if components.len() != CPE23_ATTRIBUTES.len() + 2 {
+        return Err(format!(
+            "expected {} attributes, found {}",
+            CPE23_ATTRIBUTES.len(),
+            components.len().saturating_sub(2)
+        ));
+    }
+
+    let attributes = &components[2..];
+    for (name, value) in CPE23_ATTRIBUTES.iter().zip(attributes) {
+        match *name {
+            "part" => {
+                if !matches!(*value, "a" | "o" | "h" | "*" | "-") {
+                    return Err(format!("invalid part '{value}'"));
+                }
+            }
+            "language" => {
+                if !matches!(*value, "*" | "-")
+                    && language_tags::LanguageTag::parse(&value.replace('\\', "")).is_err()
+                {
+                    return Err(format!("invalid language '{value}'"));
+                }
+            }
+            _ => validate_cpe23_value(value).map_err(|err| format!("invalid {name}: {err}"))?,
+        }
+    }
+
+    Ok((attributes[1].to_string(), attributes[2].to_string()))
+}
+
+/// Validate a CPE, returning the vendor and product.
+fn validate_cpe(cpe: &str) -> Result<(String, String), String> {
+    if cpe.starts_with("cpe:2.3:") {
+        validate_cpe23(cpe)
+    } else if cpe.to_ascii_lowercase().starts_with("cpe:/") {
+        // the URI binding is case-insensitive
+        let cpe = cpe.to_ascii_lowercase();
+        let uri = Uri::parse(&cpe).map_err(|err| err.to_string())?;
+        let component = |value: Component| match value {
+            Component::Value(value) => value.to_string(),
+            Component::Any => "*".to_string(),
+            Component::NotApplicable => "-".to_string(),
+        };
+        Ok((component(uri.vendor()), component(uri.product())))
+    } else {
+        Err("expected a CPE 2.2 URI ('cpe:/') or a CPE 2.3 formatted string ('cpe:2.3:')".into())
+    }
+}
+
+/// The product identification helper values of a product, with their location
+fn helper_values<'a>(json: &'a Value, path: &str, name: &str) -> Option<(String, &'a str)> {
+    let path = format!("{path}/product_identification_helper/{name}");
+    json.pointer(&path)
+        .and_then(Value::as_str)
+        .map(|value| (path, value))
+}
+
+/// Invalid CPE
+pub fn check_cpe(csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, _) in full_product_names(csaf) {
+        let Some((path, cpe)) = helper_values(json, &path, "cpe") else {
+            continue;
+        };
+
+        match validate_cpe(cpe) {
+            Ok((vendor, product)) => {
+                let missing = |value: &str| matches!(value, "" | "*" | "-");
+                if missing(&vendor) || missing(&product) {
+                    result.push(
+                        Finding::new(format!("CPE '{cpe}' doesn't identify a vendor and product"))
+                            .path(path),
+                    );
+                }
+            }
+            Err(err) => result.push(Finding::new(format!("Invalid CPE '{cpe}': {err}")).path(path)),
+        }
+    }
+
+    result
+}
+
+/// Duplicate identification helper
+///
+/// PURLs are compared in their canonical form, CPE 2.2 URIs case-insensitively.
+pub fn check_duplicate_helpers(csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+    let mut seen = HashMap::<(&str, String), &str>::new();
+
+    for (path, product) in full_product_names(csaf) {
+        let product_id = product.product_id.0.as_str();
+
+        let purl = helper_values(json, &path, "purl").map(|(path, purl)| {
+            let key = PackageUrl::from_str(purl)
+                .map(|purl| purl.to_string())
+                .unwrap_or_else(|_| purl.to_string());
+            ("PURL", key, path, purl)
+        });
+        let cpe = helper_values(json, &path, "cpe").map(|(path, cpe)| {
+            let key = match cpe.starts_with("cpe:2.3:") {
+                true => cpe.to_string(),
+                false => cpe.to_ascii_lowercase(),
+            };
+            ("CPE", key, path, cpe)
+        });
+
+        for (kind, key, path, value) in purl.into_iter().chain(cpe) {
+            match seen.get(&(kind, key.clone())) {
+                Some(other) if *other != product_id => result.push(
+                    Finding::new(format!("{kind} '{value}' of product ID '{product_id}' also identifies product ID '{other}'")).path(path),
+                ),
+                Some(_) => {}
+                None => {
+                    seen.insert((kind, key), product_id);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub fn init_identification_checks() -> Vec<(&'static str, Box<dyn Check>)> {
+    let mut result: Vec<(&'static str, Box<dyn Check>)> =
+        vec![("invalid-cpe", Box::new(JsonCheck(check_cpe)))];
+
+    result.extend(with_severity(
+        vec![(
+            "duplicate-identification-helper",
+            Box::new(JsonCheck(check_duplicate_helpers)) as Box<dyn Check>,
+        )],
+        Severity::Warning,
+    ));
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn cpe() {
+        assert!(validate_cpe("cpe:/a:redhat:enterprise_linux:8::appstream").is_ok());
+        assert!(validate_cpe("cpe:/A:RedHat:Enterprise_Linux:8").is_ok());
+        assert_eq!(
+            validate_cpe("cpe:2.3:a:microsoft:internet_explorer:8.0.6001:beta:*:*:*:*:*:*"),
+            Ok(("microsoft".into(), "internet_explorer".into()))
+        );
+        assert!(
+            validate_cpe(r"cpe:2.3:a:hp:insight_diagnostics:7.4.0.1570:-:*:*:online:win2003:x64:*")
+                .is_ok()
+        );
+        assert!(validate_cpe(r"cpe:2.3:a:foo\:bar:product:1.0:*:*:*:*:*:*:*").is_ok());
+        assert!(validate_cpe("cpe:2.3:a:vendor:product:1.*:*:*:*:*:*:*:*").is_ok());
+
+        assert!(validate_cpe("cpe:2.3:a:vendor:product").is_err());
+        assert!(validate_cpe("cpe:2.3:x:vendor:product:*:*:*:*:*:*:*:*").is_err());
+        assert!(validate_cpe("cpe:2.3:a:ven dor:product:*:*:*:*:*:*:*:*").is_err());
+        assert!(validate_cpe("cpe:2.3:a:vendor:product:1*0:*:*:*:*:*:*:*").is_err());
+        assert!(validate_cpe("cpe:2.3:a:vendor:product:**:*:*:*:*:*:*:*").is_err());
+        assert!(validate_cpe("cpe:2.3:a:*:*:1:*:*:*:*:*:*:*").is_ok());
+        assert!(validate_cpe("cpe:2.3:a:vendor:product:*:*:*:not a tag:*:*:*:*").is_err());
+        assert!(validate_cpe("cpe:/x:vendor").is_err());
+        assert!(validate_cpe("pkg:rpm/redhat/openssl").is_err());
+    }
+
+    #[test]
+    fn helpers() {
+        let json = json!({
+            "document": {
+                "category": "csaf_base",
+                "csaf_version": "2.0",
+                "publisher": {
+                    "category": "vendor",
+                    "name": "Example",
+                    "namespace": "https://example.com"
+                },
+                "title": "Example",
+                "tracking": {
+                    "current_release_date": "2023-01-01T00:00:00Z",
+                    "id": "EXAMPLE-1",
+                    "initial_release_date": "2023-01-01T00:00:00Z",
+                    "revision_history": [
+                        { "date": "2023-01-01T00:00:00Z", "number": "1", "summary": "Initial" }
+                    ],
+                    "status": "final",
+                    "version": "1"
+                }
+            },
+            "product_tree": {
+                "full_product_names": [
+                    {
+                        "name": "A",
+                        "product_id": "A",
+                        "product_identification_helper": {
+                            "cpe": "cpe:/a:example:a:1",
+                            "purl": "pkg:rpm/example/a@1?arch=x86_64&distro=example"
+                        }
+                    },
+                    {
+                        "name": "B",
+                        "product_id": "B",
+                        "product_identification_helper": {
+                            "cpe": "cpe:/a:Example:A:1",
+                            "purl": "pkg:rpm/example/a@1?distro=example&arch=x86_64"
+                        }
+                    },
+                    {
+                        "name": "C",
+                        "product_id": "C",
+                        "product_identification_helper": {
+                            "cpe": "cpe:/a"
+                        }
+                    }
+                ]
+            }
+        });
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+        assert_eq!(
+            check_cpe(&csaf, &json),
+            vec![
+                Finding::new("CPE 'cpe:/a' doesn't identify a vendor and product")
+                    .path("/product_tree/full_product_names/2/product_identification_helper/cpe")
+            ]
+        );
+        assert_eq!(
+            check_duplicate_helpers(&csaf, &json),
+            vec![
+                Finding::new(
+"PURL 'pkg:rpm/example/a@1?distro=example&arch=x86_64' of product ID 'B' also identifies product ID 'A'"
+).path("/product_tree/full_product_names/1/product_identification_helper/purl"),
+                Finding::new(
+"CPE 'cpe:/a:Example:A:1' of product ID 'B' also identifies product ID 'A'"
+).path("/product_tree/full_product_names/1/product_identification_helper/cpe"),
+            ]
+        );
+    }
+
+    #[test]
+    fn example_data() {
+        for data in [
+            include_str!("../../../../test-data/rhsa-2021_3029.json"),
+            include_str!("../../../../test-data/rhsa-2023_1441.json"),
+        ] {
+            let json: Value = serde_json::from_str(data).expect("example data must parse");
+            let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+            assert_eq!(check_cpe(&csaf, &json), Vec::<Finding>::new());
+            assert_eq!(check_duplicate_helpers(&csaf, &json), Vec::<Finding>::new());
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! Native implementation of the informative tests (section 6.3) of the CSAF 2.0 specification.
+//!
+//! Checks are identified by the number of the test in the specification (e.g. `6.3.1`). In
+//! addition, the following checks are performed:
+//!
+//! * `https-references`: references using plain HTTP
+//! * `repeated-words`: texts repeating a word (like "the the"), a heuristic not requiring a
+//!   dictionary
+//!
+//! Some tests are not implemented:
+//!
+//! * 6.3.6 (Use of non-self referencing URLs Failing to Resolve) and 6.3.7 (Use of self
+//!   referencing URLs Failing to Resolve), as those require network access
+//! * 6.3.8 (Spell check), as no dictionary is available
+
+use crate::verification::check::{Check, Finding, JsonCheck, products::full_product_names};
+use csaf::{
+    Csaf,
+    definitions::{Branch, BranchCategory, BranchesT},
+};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+static VERSION_PREFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[vV][0-9].*$").expect("regex must compile"));
+
+static VERSION_IN_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(^|\s)[vV]?[0-9]+(\.[0-9]+)+(\s|$)").expect("regex must compile")
+});
+
+/// 6.3.1 Use of CVSS v2 as the only Scoring System
+pub fn check_cvss_v2_only(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, score) in vulnerability.scores.iter().flatten().enumerate() {
+            if score.cvss_v2.is_some() && score.cvss_v3.is_none() {
+                result.push(
+                    Finding::new("Use of CVSS v2 as the only scoring system")
+                        .path(format!("/vulnerabilities/{i}/scores/{j}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.3.2 Use of CVSS v3.0
+pub fn check_cvss_v30(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    let vulnerabilities = json
+        .get("vulnerabilities")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+
+    for (i, vulnerability) in vulnerabilities.enumerate() {
+        let scores = vulnerability
+            .get("scores")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+
+        for (j, score) in scores.enumerate() {
+            let Some(cvss) = score.get("cvss_v3") else {
+                continue;
+            };
+
+            let version = cvss.get("version").and_then(Value::as_str) == Some("3.0");
+            let vector = cvss
+                .get("vectorString")
+                .and_then(Value::as_str)
+                .is_some_and(|vector| vector.starts_with("CVSS:3.0/"));
+
+            if version || vector {
+                result.push(
+                    Finding::new("Use of CVSS v3.0")
+                        .path(format!("/vulnerabilities/{i}/scores/{j}/cvss_v3")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.3.3 Missing CVE
+pub fn check_missing_cve(csaf: &Csaf) -> Vec<Finding> {
+    csaf.vulnerabilities
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter(|(_, vulnerability)| vulnerability.cve.is_none())
+        .map(|(i, _)| Finding::new("Missing CVE").path(format!("/vulnerabilities/{i}/cve")))
+        .collect()
+}
+
+/// 6.3.4 Missing CWE
+pub fn check_missing_cwe(csaf: &Csaf) -> Vec<Finding> {
+    csaf.vulnerabilities
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter(|(_, vulnerability)| vulnerability.cwe.is_none())
+        .map(|(i, _)| Finding::new("Missing CWE").path(format!("/vulnerabilities/{i}/cwe")))
+        .collect()
+}
+
+/// 6.3.5 Use of Short Hash
+pub fn check_short_hash(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, product) in full_product_names(csaf) {
+        let hashes = product
+            .product_identification_helper
+            .iter()
+            .flat_map(|helper| helper.hashes.iter().flatten());
+
+        for (i, hash) in hashes.enumerate() {
+            for (j, value) in hash.file_hashes.iter().enumerate() {
+                if value.value.len() < 64 {
+                    result.push(Finding::new("Use of short hash").path(format!(
+                        "{path}/product_identification_helper/hashes/{i}/file_hashes/{j}/value"
+                    )));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Walk all branches, providing the categories of the parent branches.
+fn walk_branches<'a>(
+    path: String,
+    parents: &mut Vec<&'a BranchCategory>,
+    branches: &'a BranchesT,
+    f: &mut impl FnMut(&str, &[&'a BranchCategory], &'a Branch),
+) {
+    for (i, branch) in branches.0.iter().enumerate() {
+        let path = format!("{path}/{i}");
+        f(&path, parents, branch);
+
+        if let Some(children) = &branch.branches {
+            parents.push(&branch.category);
+            walk_branches(format!("{path}/branches"), parents, children, f);
+            parents.pop();
+        }
+    }
+}
+
+fn branches(csaf: &Csaf, mut f: impl FnMut(&str, &[&BranchCategory], &Branch)) {
+    if let Some(children) = csaf
+        .product_tree
+        .as_ref()
+        .and_then(|tree| tree.branches.as_ref())
+    {
+        walk_branches(
+            "/product_tree/branches".into(),
+            &mut vec![],
+            children,
+            &mut f,
+        );
+    }
+}
+
+/// 6.3.9 Branch Categories
+pub fn check_branch_categories(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    branches(csaf, |path, parents, branch| {
+        if branch.product.is_none() {
+            return;
+        }
+
+        let categories = || parents.iter().copied().chain([&branch.category]);
+        let vendor = categories().any(|c| matches!(c, BranchCategory::Vendor));
+        let name = categories().any(|c| matches!(c, BranchCategory::ProductName));
+        let version = categories().any(|c| {
+            matches!(
+                c,
+                BranchCategory::ProductVersion | BranchCategory::ProductVersionRange
+            )
+        });
+
+        if !(vendor && name && version) {
+            result.push(
+                Finding::new("Branch path is missing a 'vendor', 'product_name', or 'product_version' category").path(path),
+            );
+        }
+    });
+
+    result
+}
+
+/// 6.3.10 Usage of Product Version in Branches with Category `product_name`
+pub fn check_version_in_product_name(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    branches(csaf, |path, _, branch| {
+        if matches!(branch.category, BranchCategory::ProductName)
+            && VERSION_IN_NAME.is_match(&branch.name)
+        {
+            result.push(
+                Finding::new(format!(
+                    "Product name '{}' seems to contain a version",
+                    branch.name
+                ))
+                .path(format!("{path}/name")),
+            );
+        }
+    });
+
+    result
+}
+
+/// 6.3.11 Usage of V as Version Indicator
+pub fn check_version_indicator(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    branches(csaf, |path, _, branch| {
+        if matches!(branch.category, BranchCategory::ProductVersion)
+            && VERSION_PREFIX.is_match(&branch.name)
+        {
+            result.push(
+                Finding::new(format!(
+                    "Product version '{}' uses 'v' as version indicator",
+                    branch.name
+                ))
+                .path(format!("{path}/name")),
+            );
+        }
+    });
+
+    result
+}
+
+/// Use of plain HTTP in references
+pub fn check_https_references(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    let document = csaf
+        .document
+        .references
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, reference)| (format!("/document/references/{i}/url"), &reference.url));
+
+    let vulnerabilities =
+        csaf.vulnerabilities
+            .iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(i, vulnerability)| {
+                vulnerability
+                    .references
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .map(move |(j, reference)| {
+                        (
+                            format!("/vulnerabilities/{i}/references/{j}/url"),
+                            &reference.url,
+                        )
+                    })
+            });
+
+    for (path, url) in document.chain(vulnerabilities) {
+        if url.scheme() == "http" {
+            result.push(Finding::new(format!("Reference '{url}' doesn't use HTTPS")).path(path));
+        }
+    }
+
+    result
+}
+
+/// The first word which is directly repeated in a text, ignoring case
+///
+/// Words followed by punctuation (like in "a foo, foo bar") are not considered, as well as some
+/// words which may be repeated in correct English (like "had had").
+fn repeated_word(text: &str) -> Option<&str> {
+    const ALLOWED: &[&str] = &["had", "that"];
+
+    let mut previous: Option<&str> = None;
+    for word in text.split_whitespace() {
+        let trimmed = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        let current = trimmed.trim_end_matches(|c: char| !c.is_alphanumeric());
+
+        if let Some(previous) = previous {
+            if current.chars().any(char::is_alphabetic)
+                && previous.to_lowercase() == current.to_lowercase()
+                && !ALLOWED.contains(&current.to_lowercase().as_str())
+            {
+                return Some(current);
+            }
+        }
+
+        previous = (current.len() == trimmed.len()).then_some(current);
+    }
+
+    None
+}
+
+/// Repeated words in the texts of a document, like titles, notes, and remediation details
+pub fn check_repeated_words(csaf: &Csaf) -> Vec<Finding> {
+    let mut texts = vec![("/document/title".to_string(), csaf.document.title.as_str())];
+    for (i, note) in csaf.document.notes.iter().flatten().enumerate() {
+        texts.push((format!("/document/notes/{i}/text"), &note.text));
+    }
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        if let Some(title) = &vulnerability.title {
+            texts.push((format!("/vulnerabilities/{i}/title"), title));
+        }
+        for (j, note) in vulnerability.notes.iter().flatten().enumerate() {
+            texts.push((format!("/vulnerabilities/{i}/notes/{j}/text"), &note.text));
+        }
+        for (j, remediation) in vulnerability.remediations.iter().flatten().enumerate() {
+            texts.push((
+                format!("/vulnerabilities/{i}/remediations/{j}/details"),
+                &remediation.details,
+            ));
+        }
+    }
+
+    texts
+        .into_iter()
+        .filter_map(|(path, text)| {
+            repeated_word(text)
+                .map(|word| Finding::new(format!("Repeated word '{word}'")).path(path))
+        })
+        .collect()
+}
+
+pub fn init_informative_checks() -> Vec<(&'static str, Box<dyn Check>)> {
+    vec![
+        ("6.3.1", Box::new(check_cvss_v2_only)),
+        ("6.3.2", Box::new(JsonCheck(check_cvss_v30))),
+        ("6.3.3", Box::new(check_missing_cve)),
+        ("6.3.4", Box::new(check_missing_cwe)),
+        ("6.3.5", Box::new(check_short_hash)),
+        ("6.3.9", Box::new(check_branch_categories)),
+        ("6.3.10", Box::new(check_version_in_product_name)),
+        ("6.3.11", Box::new(check_version_indicator)),
+        ("https-references", Box::new(check_https_references)),
+        ("repeated-words", Box::new(check_repeated_words)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_in_name() {
+        assert!(VERSION_IN_NAME.is_match("Product 1.2"));
+        assert!(VERSION_IN_NAME.is_match("v1.2.3 Product"));
+        assert!(!VERSION_IN_NAME.is_match("Product"));
+        assert!(!VERSION_IN_NAME.is_match("Product4"));
+
+        assert!(VERSION_PREFIX.is_match("v1.0"));
+        assert!(!VERSION_PREFIX.is_match("1.0"));
+    }
+
+    #[test]
+    fn repeated_words() {
+        assert_eq!(repeated_word("Fix the the issue"), Some("the"));
+        assert_eq!(repeated_word("Fix The the issue"), Some("the"));
+        assert_eq!(repeated_word("A flaw (in in the parser)"), Some("in"));
+        assert_eq!(repeated_word("Fix the issue"), None);
+        assert_eq!(repeated_word("Update to foo, foo is fixed"), None);
+        assert_eq!(repeated_word("It had had no effect"), None);
+        assert_eq!(repeated_word("Versions 1.0 1.0"), None);
+    }
+
+    #[tokio::test]
+    async fn example_data() {
+        let json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2023_1441.json"))
+                .expect("example data must parse");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+        let mut failed = vec![];
+        for (id, check) in init_informative_checks() {
+            let result = check
+                .check_json(&csaf, &json)
+                .await
+                .expect("check must not fail");
+            if !result.is_empty() {
+                failed.push(id);
+            }
+        }
+
+        // the vulnerability has no CVE assigned, and the branches don't use 'product_name' for
+        // every product
+        assert_eq!(failed, vec!["6.3.3", "6.3.9"]);
+    }
+}
@@ -0,0 +1,452 @@
+//! CVSS related tests
+//!
+//! The [`Csaf`] model doesn't retain the declared CVSS v3 properties, so these tests work on the
+//! original JSON document.
+
+use crate::verification::check::{
+    Finding,
+    cvss::{Vector, Version},
+};
+use csaf::Csaf;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::LazyLock,
+};
+
+const V2_METRIC: &str = r"(AV:[NAL]|AC:[LMH]|Au:[MSN]|[CIA]:[NPC]|E:(U|POC|F|H|ND)|RL:(OF|TF|W|U|ND)|RC:(UC|UR|C|ND)|CDP:(N|L|LM|MH|H|ND)|TD:(N|L|M|H|ND)|[CIA]R:(L|M|H|ND))";
+const V3_METRIC: &str = r"(AV:[NALP]|AC:[LH]|PR:[NLH]|UI:[NR]|S:[UC]|[CIA]:[NLH]|E:[XUPFH]|RL:[XOTWU]|RC:[XURC]|[CIA]R:[XLMH]|MAV:[XNALP]|MAC:[XLH]|MPR:[XNLH]|MUI:[XNR]|MS:[XUC]|M[CIA]:[XNLH])";
+
+/// The vector string patterns of the CVSS JSON schemas
+static V2_VECTOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!("^({V2_METRIC}/)*{V2_METRIC}$")).expect("regex must compile")
+});
+static V3_VECTOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!("^CVSS:3[.][01]/({V3_METRIC}/)*{V3_METRIC}$")).expect("regex must compile")
+});
+
+/// Property values, with their vector value
+type Values = &'static [(&'static str, &'static str)];
+
+/// CVSS v2 properties, with their vector metric and values
+const V2_PROPERTIES: &[(&str, &str, Values)] = &[
+    (
+        "accessVector",
+        "AV",
+        &[("NETWORK", "N"), ("ADJACENT_NETWORK", "A"), ("LOCAL", "L")],
+    ),
+    (
+        "accessComplexity",
+        "AC",
+        &[("HIGH", "H"), ("MEDIUM", "M"), ("LOW", "L")],
+    ),
+    (
+        "authentication",
+        "Au",
+        &[("MULTIPLE", "M"), ("SINGLE", "S"), ("NONE", "N")],
+    ),
+    ("confidentialityImpact", "C", V2_IMPACT),
+    ("integrityImpact", "I", V2_IMPACT),
+    ("availabilityImpact", "A", V2_IMPACT),
+    (
+        "exploitability",
+        "E",
+        &[
+            ("UNPROVEN", "U"),
+            ("PROOF_OF_CONCEPT", "POC"),
+            ("FUNCTIONAL", "F"),
+            ("HIGH", "H"),
+            ("NOT_DEFINED", "ND"),
+        ],
+    ),
+    (
+        "remediationLevel",
+        "RL",
+        &[
+            ("OFFICIAL_FIX", "OF"),
+            ("TEMPORARY_FIX", "TF"),
+            ("WORKAROUND", "W"),
+            ("UNAVAILABLE", "U"),
+            ("NOT_DEFINED", "ND"),
+        ],
+    ),
+    (
+        "reportConfidence",
+        "RC",
+        &[
+            ("UNCONFIRMED", "UC"),
+            ("UNCORROBORATED", "UR"),
+            ("CONFIRMED", "C"),
+            ("NOT_DEFINED", "ND"),
+        ],
+    ),
+    (
+        "collateralDamagePotential",
+        "CDP",
+        &[
+            ("NONE", "N"),
+            ("LOW", "L"),
+            ("LOW_MEDIUM", "LM"),
+            ("MEDIUM_HIGH", "MH"),
+            ("HIGH", "H"),
+            ("NOT_DEFINED", "ND"),
+        ],
+    ),
+    (
+        "targetDistribution",
+        "TD",
+        &[
+            ("NONE", "N"),
+            ("LOW", "L"),
+            ("MEDIUM", "M"),
+            ("HIGH", "H"),
+            ("NOT_DEFINED", "ND"),
+        ],
+    ),
+    ("confidentialityRequirement", "CR", V2_REQUIREMENT),
+    ("integrityRequirement", "IR", V2_REQUIREMENT),
+    ("availabilityRequirement", "AR", V2_REQUIREMENT),
+];
+
+const V2_IMPACT: Values = &[("NONE", "N"), ("PARTIAL", "P"), ("COMPLETE", "C")];
+const V2_REQUIREMENT: Values = &[
+    ("LOW", "L"),
+    ("MEDIUM", "M"),
+    ("HIGH", "H"),
+    ("NOT_DEFINED", "ND"),
+];
+
+/// CVSS v3 properties, with their vector metric.
+///
+/// For CVSS v3, the vector value is the first letter of the property value, except for
+/// `NOT_DEFINED`, which is `X`.
+const V3_PROPERTIES: &[(&str, &str)] = &[
+    ("attackVector", "AV"),
+    ("attackComplexity", "AC"),
+    ("privilegesRequired", "PR"),
+    ("userInteraction", "UI"),
+    ("scope", "S"),
+    ("confidentialityImpact", "C"),
+    ("integrityImpact", "I"),
+    ("availabilityImpact", "A"),
+    ("exploitCodeMaturity", "E"),
+    ("remediationLevel", "RL"),
+    ("reportConfidence", "RC"),
+    ("confidentialityRequirement", "CR"),
+    ("integrityRequirement", "IR"),
+    ("availabilityRequirement", "AR"),
+    ("modifiedAttackVector", "MAV"),
+    ("modifiedAttackComplexity", "MAC"),
+    ("modifiedPrivilegesRequired", "MPR"),
+    ("modifiedUserInteraction", "MUI"),
+    ("modifiedScope", "MS"),
+    ("modifiedConfidentialityImpact", "MC"),
+    ("modifiedIntegrityImpact", "MI"),
+    ("modifiedAvailabilityImpact", "MA"),
+];
+
+const SEVERITIES: &[&str] = &["NONE", "LOW", "MEDIUM", "HIGH", "CRITICAL"];
+
+/// The CVSS properties of a score, defined by CSAF 2.0
+const CVSS_KINDS: &[&str] = &["cvss_v2", "cvss_v3"];
+
+/// All CVSS objects of the given kinds, along with their location
+fn scores<'a>(
+    json: &'a Value,
+    kinds: &[&'static str],
+) -> Vec<(String, &'static str, &'a Map<String, Value>)> {
+    let mut result = vec![];
+
+    let vulnerabilities = json
+        .get("vulnerabilities")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+
+    for (i, vulnerability) in vulnerabilities.enumerate() {
+        let scores = vulnerability
+            .get("scores")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+        for (j, score) in scores.enumerate() {
+            for kind in kinds.iter().copied() {
+                if let Some(cvss) = score.get(kind).and_then(Value::as_object) {
+                    result.push((
+                        format!("/vulnerabilities/{i}/scores/{j}/{kind}"),
+                        kind,
+                        cvss,
+                    ));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The metrics of a vector string
+fn vector_metrics(vector: &str) -> BTreeMap<&str, &str> {
+    vector
+        .split('/')
+        .filter_map(|component| component.split_once(':'))
+        .filter(|(metric, _)| *metric != "CVSS")
+        .collect()
+}
+
+fn string<'a>(cvss: &'a Map<String, Value>, name: &str) -> Option<&'a str> {
+    cvss.get(name).and_then(Value::as_str)
+}
+
+/// 6.1.7 Multiple Scores with same Version per Product
+pub fn check_multiple_scores_per_product(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    let vulnerabilities = json
+        .get("vulnerabilities")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+
+    for (i, vulnerability) in vulnerabilities.enumerate() {
+        let mut seen = HashMap::<(&str, &str), usize>::new();
+
+        let scores = vulnerability
+            .get("scores")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+        for (j, score) in scores.enumerate() {
+            let versions = ["cvss_v2", "cvss_v3"]
+                .into_iter()
+                .filter_map(|kind| score.get(kind))
+                .filter_map(|cvss| cvss.get("version").and_then(Value::as_str));
+            let products = score
+                .get("products")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>();
+
+            for version in versions {
+                for product in &products {
+                    if let Some(first) = seen.insert((product, version), j) {
+                        result.push(
+                            Finding::new(format!("Multiple CVSS {version} scores for product ID '{product}', also scored by /vulnerabilities/{i}/scores/{first}")).path(format!("/vulnerabilities/{i}/scores/{j}")),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.8 Invalid CVSS
+///
+/// Validates the required properties, the version, the vector string, scores, and severities
+/// following the CVSS JSON schemas.
+pub fn check_invalid_cvss(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, kind, cvss) in scores(json, CVSS_KINDS) {
+        let mut error = |msg: String| result.push(Finding::new(msg).path(path.clone()));
+
+        let (versions, vector, required): (&[&str], &Regex, &[&str]) = match kind {
+            "cvss_v2" => (
+                &["2.0"],
+                &V2_VECTOR,
+                &["version", "vectorString", "baseScore"],
+            ),
+            _ => (
+                &["3.0", "3.1"],
+                &V3_VECTOR,
+                &["version", "vectorString", "baseScore", "baseSeverity"],
+            ),
+        };
+
+        for property in required {
+            if !cvss.contains_key(*property) {
+                error(format!("Invalid CVSS: missing property '{property}'"));
+            }
+        }
+
+        match string(cvss, "version") {
+            Some(version) if versions.contains(&version) => {
+                if let Some(vector) = string(cvss, "vectorString") {
+                    if kind == "cvss_v3" && !vector.starts_with(&format!("CVSS:{version}/")) {
+                        error(format!(
+                            "Invalid CVSS: vector string '{vector}' doesn't match version {version}"
+                        ));
+                    }
+                }
+            }
+            Some(version) => error(format!("Invalid CVSS: unsupported version '{version}'")),
+            None => {}
+        }
+
+        if let Some(value) = string(cvss, "vectorString") {
+            if !vector.is_match(value) {
+                error(format!("Invalid CVSS: invalid vector string '{value}'"));
+            } else if let Err(err) = Vector::parse(value) {
+                error(format!(
+                    "Invalid CVSS: invalid vector string '{value}': {err}"
+                ));
+            }
+        }
+
+        for (name, value) in cvss {
+            if name.ends_with("Score") {
+                match value.as_f64() {
+                    Some(score) if (0.0..=10.0).contains(&score) => {}
+                    _ => error(format!("Invalid CVSS: invalid score '{name}': {value}")),
+                }
+            }
+            if name.ends_with("Severity") {
+                match value.as_str() {
+                    Some(severity) if SEVERITIES.contains(&severity) => {}
+                    _ => error(format!("Invalid CVSS: invalid severity '{name}': {value}")),
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The score and severity properties, for the base, temporal, and environmental scores
+fn score_properties(version: Version) -> [(&'static str, &'static str, &'static str); 3] {
+    let temporal = match version {
+        Version::V4_0 => ("threat", "threatScore", "threatSeverity"),
+        _ => ("temporal", "temporalScore", "temporalSeverity"),
+    };
+
+    [
+        ("base", "baseScore", "baseSeverity"),
+        temporal,
+        (
+            "environmental",
+            "environmentalScore",
+            "environmentalSeverity",
+        ),
+    ]
+}
+
+/// 6.1.9 Invalid CVSS computation
+///
+/// Verifies the declared scores and severities against the ones calculated from the vector
+/// string. In addition to CSAF 2.0, this also checks CVSS v4 objects (`cvss_v4`).
+pub fn check_invalid_cvss_computation(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, kind, cvss) in scores(json, &["cvss_v2", "cvss_v3", "cvss_v4"]) {
+        // invalid vectors are reported by 6.1.8
+        let Some(vector) = string(cvss, "vectorString").and_then(|v| Vector::parse(v).ok()) else {
+            continue;
+        };
+
+        let expected = match vector.version() {
+            Version::V2_0 => "cvss_v2",
+            Version::V3_0 | Version::V3_1 => "cvss_v3",
+            Version::V4_0 => "cvss_v4",
+        };
+        if kind != expected {
+            continue;
+        }
+
+        let calculated = vector.scores();
+        let calculated = [
+            calculated.base,
+            calculated.temporal,
+            calculated.environmental,
+        ];
+
+        for (score, (name, score_property, severity_property)) in calculated
+            .into_iter()
+            .zip(score_properties(vector.version()))
+        {
+            if let Some(declared) = cvss.get(score_property).and_then(Value::as_f64) {
+                if (declared - score.value).abs() > f64::EPSILON {
+                    result.push(
+                        Finding::new(format!("Invalid CVSS computation: declared {name} score {declared}, calculated {value}", value = score.value)).path(format!("{path}/{score_property}")),
+                    );
+                }
+            }
+
+            if let (Some(severity), Some(declared)) =
+                (score.severity, string(cvss, severity_property))
+            {
+                if severity.as_str() != declared {
+                    result.push(
+                        Finding::new(format!("Invalid CVSS computation: declared {name} severity {declared}, calculated {severity}")).path(format!("{path}/{severity_property}")),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.10 Inconsistent CVSS
+pub fn check_inconsistent_cvss(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, kind, cvss) in scores(json, CVSS_KINDS) {
+        let Some(vector) = string(cvss, "vectorString") else {
+            continue;
+        };
+        let metrics = vector_metrics(vector);
+
+        let mut check = |property: &str, expected: Option<&str>, actual: &str| {
+            if expected != Some(actual) {
+                result.push(
+                    Finding::new(format!(
+                        "Inconsistent CVSS: property value doesn't match vector string '{vector}'"
+                    ))
+                    .path(format!("{path}/{property}")),
+                );
+            }
+        };
+
+        match kind {
+            "cvss_v2" => {
+                for (property, metric, values) in V2_PROPERTIES {
+                    let Some(value) = string(cvss, property) else {
+                        continue;
+                    };
+                    let expected = values
+                        .iter()
+                        .find(|(name, _)| *name == value)
+                        .map(|(_, short)| *short);
+                    check(
+                        property,
+                        expected,
+                        metrics.get(metric).copied().unwrap_or("ND"),
+                    );
+                }
+            }
+            _ => {
+                for (property, metric) in V3_PROPERTIES {
+                    let Some(value) = string(cvss, property) else {
+                        continue;
+                    };
+                    let expected = match value {
+                        "NOT_DEFINED" => Some("X"),
+                        value => value.get(..1),
+                    };
+                    check(
+                        property,
+                        expected,
+                        metrics.get(metric).copied().unwrap_or("X"),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
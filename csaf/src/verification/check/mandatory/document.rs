@@ -0,0 +1,261 @@
+use crate::verification::check::{Finding, cwe::CWE_NAMES, products::full_product_names};
+use csaf::{
+    Csaf,
+    definitions::{BranchCategory, BranchesT},
+    document::PublisherCategory,
+};
+use language_tags::LanguageTag;
+use packageurl::PackageUrl;
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+static VERSION_RANGE_OPERATOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(<|<=|>|>=)").expect("regex must compile"));
+static VERSION_RANGE_WORD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(after|all|before|earlier|later|prior|versions)\b")
+        .expect("regex must compile")
+});
+
+/// The document categories of the profiles defined by the specification
+pub(crate) const PROFILES: &[&str] = &[
+    "csaf_base",
+    "csaf_security_incident_response",
+    "csaf_informational_advisory",
+    "csaf_security_advisory",
+    "csaf_vex",
+];
+
+/// 6.1.11 CWE
+///
+/// The CWE must exist in the CWE catalog, and use the name of the catalog.
+pub fn check_cwe(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let Some(cwe) = &vulnerability.cwe else {
+            continue;
+        };
+
+        match CWE_NAMES.get(&cwe.id) {
+            None => result.push(
+                Finding::new(format!("Unknown CWE '{}'", cwe.id))
+                    .path(format!("/vulnerabilities/{i}/cwe/id")),
+            ),
+            Some(name) if name != &cwe.name => result.push(
+                Finding::new(format!(
+                    "The name of {} is '{name}', not '{}'",
+                    cwe.id, cwe.name
+                ))
+                .path(format!("/vulnerabilities/{i}/cwe/name")),
+            ),
+            Some(_) => {}
+        }
+    }
+
+    result
+}
+
+/// 6.1.12 Language
+pub fn check_language(csaf: &Csaf) -> Vec<Finding> {
+    [
+        ("lang", &csaf.document.lang),
+        ("source_lang", &csaf.document.source_lang),
+    ]
+    .into_iter()
+    .filter_map(|(name, lang)| lang.as_ref().map(|lang| (name, lang)))
+    .filter(|(_, lang)| {
+        LanguageTag::parse(lang)
+            .map(|tag| tag.validate().is_err())
+            .unwrap_or(true)
+    })
+    .map(|(name, lang)| {
+        Finding::new(format!("Invalid language tag '{lang}'")).path(format!("/document/{name}"))
+    })
+    .collect()
+}
+
+/// 6.1.13 PURL
+pub fn check_purl(csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    full_product_names(csaf)
+        .into_iter()
+        .filter_map(|(path, _)| {
+            let path = format!("{path}/product_identification_helper/purl");
+            json.pointer(&path)
+                .and_then(Value::as_str)
+                .map(|purl| (path, purl))
+        })
+        .filter_map(|(path, purl)| {
+            PackageUrl::from_str(purl)
+                .err()
+                .map(|err| Finding::new(format!("Invalid PURL '{purl}': {err}")).path(path))
+        })
+        .collect()
+}
+
+/// 6.1.15 Translator
+pub fn check_translator(csaf: &Csaf) -> Vec<Finding> {
+    if matches!(
+        csaf.document.publisher.category,
+        PublisherCategory::Translator
+    ) && csaf.document.source_lang.is_none()
+    {
+        return vec![
+            Finding::new("Missing source language for a translation").path("/document/source_lang"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.23 Multiple Use of Same CVE
+pub fn check_multiple_cve(csaf: &Csaf) -> Vec<Finding> {
+    let mut seen = HashMap::<&str, usize>::new();
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let Some(cve) = &vulnerability.cve else {
+            continue;
+        };
+        if let Some(first) = seen.insert(cve, i) {
+            result.push(
+                Finding::new(format!(
+                    "Multiple use of {cve}, also used by /vulnerabilities/{first}/cve"
+                ))
+                .path(format!("/vulnerabilities/{i}/cve")),
+            );
+        }
+    }
+
+    result
+}
+
+/// 6.1.24 Multiple Definition in Involvements
+pub fn check_multiple_involvements(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let mut seen = HashSet::new();
+        for (j, involvement) in vulnerability.involvements.iter().flatten().enumerate() {
+            let key = (format!("{:?}", involvement.party), involvement.date);
+            if !seen.insert(key) {
+                result.push(
+                    Finding::new("Multiple involvements with the same party and date")
+                        .path(format!("/vulnerabilities/{i}/involvements/{j}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.25 Multiple Use of Same Hash Algorithm
+pub fn check_multiple_hash_algorithms(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, product) in full_product_names(csaf) {
+        let hashes = product
+            .product_identification_helper
+            .iter()
+            .flat_map(|helper| helper.hashes.iter().flatten());
+
+        for (i, hash) in hashes.enumerate() {
+            let mut seen = HashSet::new();
+            for (j, value) in hash.file_hashes.iter().enumerate() {
+                if !seen.insert(value.algorithm.as_str()) {
+                    result.push(
+                        Finding::new(format!("Multiple use of hash algorithm '{}'", value.algorithm)).path(format!("{path}/product_identification_helper/hashes/{i}/file_hashes/{j}/algorithm")),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.26 Prohibited Document Category Name
+pub fn check_prohibited_category_name(csaf: &Csaf) -> Vec<Finding> {
+    let category = csaf.document.category.to_string();
+    if PROFILES.contains(&category.as_str()) {
+        return vec![];
+    }
+
+    let normalize = |value: &str| {
+        value
+            .chars()
+            .filter(|c| !(c.is_whitespace() || *c == '-' || *c == '_'))
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let normalized = normalize(&category);
+
+    let prohibited = PROFILES.iter().skip(1).any(|profile| {
+        normalized == normalize(profile)
+            || normalized == normalize(profile.trim_start_matches("csaf_"))
+    });
+
+    if prohibited || category.to_lowercase().starts_with("csaf_") {
+        return vec![
+            Finding::new(format!("Prohibited document category name '{category}'"))
+                .path("/document/category"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.28 Translation
+pub fn check_translation(csaf: &Csaf) -> Vec<Finding> {
+    match (&csaf.document.lang, &csaf.document.source_lang) {
+        (Some(lang), Some(source_lang)) if lang.eq_ignore_ascii_case(source_lang) => {
+            vec![
+                Finding::new(format!(
+                    "Document language and source language must not be the same, found '{lang}'"
+                ))
+                .path("/document/source_lang"),
+            ]
+        }
+        _ => vec![],
+    }
+}
+
+/// 6.1.31 Version Range in Product Version
+pub fn check_version_range_in_product_version(csaf: &Csaf) -> Vec<Finding> {
+    fn walk(path: String, branches: &BranchesT, result: &mut Vec<Finding>) {
+        for (i, branch) in branches.0.iter().enumerate() {
+            let path = format!("{path}/{i}");
+            if matches!(branch.category, BranchCategory::ProductVersion)
+                && (VERSION_RANGE_OPERATOR.is_match(&branch.name)
+                    || VERSION_RANGE_WORD.is_match(&branch.name))
+            {
+                result.push(
+                    Finding::new(format!(
+                        "Version range in product version '{}'",
+                        branch.name
+                    ))
+                    .path(format!("{path}/name")),
+                );
+            }
+            if let Some(children) = &branch.branches {
+                walk(format!("{path}/branches"), children, result);
+            }
+        }
+    }
+
+    let mut result = vec![];
+    if let Some(children) = csaf
+        .product_tree
+        .as_ref()
+        .and_then(|tree| tree.branches.as_ref())
+    {
+        walk("/product_tree/branches".into(), children, &mut result);
+    }
+
+    result
+}
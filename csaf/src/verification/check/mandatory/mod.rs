@@ -0,0 +1,606 @@
+//! Native implementation of the mandatory tests (section 6.1) of the CSAF 2.0 specification.
+//!
+//! Checks are identified by the number of the test in the specification (e.g. `6.1.1`). Findings
+//! include the JSON pointer of the offending location.
+//!
+//! CWEs are checked against the catalog embedded in this crate (`check/cwe.json`).
+
+mod cvss;
+mod document;
+mod product;
+mod profile;
+mod revision;
+
+pub use cvss::*;
+pub use document::*;
+pub use product::*;
+pub use profile::*;
+pub use revision::*;
+
+use crate::verification::check::{Check, JsonCheck};
+
+pub fn init_mandatory_checks() -> Vec<(&'static str, Box<dyn Check>)> {
+    vec![
+        ("6.1.1", Box::new(check_missing_product_id_definition)),
+        ("6.1.2", Box::new(check_multiple_product_id_definition)),
+        ("6.1.3", Box::new(check_circular_product_id_definition)),
+        ("6.1.4", Box::new(check_missing_group_id_definition)),
+        ("6.1.5", Box::new(check_multiple_group_id_definition)),
+        ("6.1.6", Box::new(check_contradicting_product_status)),
+        (
+            "6.1.7",
+            Box::new(JsonCheck(check_multiple_scores_per_product)),
+        ),
+        ("6.1.8", Box::new(JsonCheck(check_invalid_cvss))),
+        ("6.1.9", Box::new(JsonCheck(check_invalid_cvss_computation))),
+        ("6.1.10", Box::new(JsonCheck(check_inconsistent_cvss))),
+        ("6.1.11", Box::new(check_cwe)),
+        ("6.1.12", Box::new(check_language)),
+        ("6.1.13", Box::new(JsonCheck(check_purl))),
+        ("6.1.14", Box::new(check_sorted_revision_history)),
+        ("6.1.15", Box::new(check_translator)),
+        ("6.1.16", Box::new(check_latest_document_version)),
+        ("6.1.17", Box::new(check_document_status_draft)),
+        ("6.1.18", Box::new(check_released_revision_history)),
+        ("6.1.19", Box::new(check_pre_release_revision_history)),
+        ("6.1.20", Box::new(check_non_draft_document_version)),
+        ("6.1.21", Box::new(check_missing_revision_history_item)),
+        (
+            "6.1.22",
+            Box::new(check_multiple_revision_history_definition),
+        ),
+        ("6.1.23", Box::new(check_multiple_cve)),
+        ("6.1.24", Box::new(check_multiple_involvements)),
+        ("6.1.25", Box::new(check_multiple_hash_algorithms)),
+        ("6.1.26", Box::new(check_prohibited_category_name)),
+        ("6.1.27.1", Box::new(check_document_notes)),
+        ("6.1.27.2", Box::new(check_document_references)),
+        ("6.1.27.3", Box::new(check_informational_vulnerabilities)),
+        ("6.1.27.4", Box::new(check_product_tree)),
+        ("6.1.27.5", Box::new(check_vulnerability_notes)),
+        ("6.1.27.6", Box::new(check_product_status)),
+        ("6.1.27.7", Box::new(check_vex_product_status)),
+        ("6.1.27.8", Box::new(check_vulnerability_id)),
+        ("6.1.27.9", Box::new(check_impact_statement)),
+        ("6.1.27.10", Box::new(check_action_statement)),
+        ("6.1.27.11", Box::new(check_vulnerabilities)),
+        ("6.1.28", Box::new(check_translation)),
+        (
+            "6.1.29",
+            Box::new(check_remediation_without_product_reference),
+        ),
+        ("6.1.30", Box::new(check_mixed_versioning)),
+        ("6.1.31", Box::new(check_version_range_in_product_version)),
+        ("6.1.32", Box::new(check_flag_without_product_reference)),
+        ("6.1.33", Box::new(check_multiple_flags_per_product)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use csaf::Csaf;
+    use serde::Deserialize;
+    use serde_json::{Value, json};
+    use std::collections::BTreeMap;
+
+    /// Run all mandatory checks, returning the failed ones
+    async fn failures(json: Value) -> BTreeMap<&'static str, usize> {
+        let csaf = Csaf::deserialize(&json).expect("example data must parse");
+        let mut result = BTreeMap::new();
+        for (id, check) in init_mandatory_checks() {
+            let errors = check
+                .check_json(&csaf, &json)
+                .await
+                .expect("check must not fail");
+            if !errors.is_empty() {
+                result.insert(id, errors.len());
+            }
+        }
+        result
+    }
+
+    fn document() -> Value {
+        json!({
+            "document": {
+                "category": "csaf_vex",
+                "csaf_version": "2.0",
+                "lang": "en",
+                "publisher": {
+                    "category": "vendor",
+                    "name": "Example",
+                    "namespace": "https://example.com"
+                },
+                "title": "Example",
+                "tracking": {
+                    "current_release_date": "2023-02-01T00:00:00Z",
+                    "id": "EXAMPLE-1",
+                    "initial_release_date": "2023-01-01T00:00:00Z",
+                    "revision_history": [
+                        { "date": "2023-01-01T00:00:00Z", "number": "1", "summary": "Initial" },
+                        { "date": "2023-02-01T00:00:00Z", "number": "2", "summary": "Update" }
+                    ],
+                    "status": "final",
+                    "version": "2"
+                }
+            },
+            "product_tree": {
+                "full_product_names": [
+                    { "name": "Product A", "product_id": "A" },
+                    { "name": "Product B", "product_id": "B" }
+                ]
+            },
+            "vulnerabilities": [
+                {
+                    "cve": "CVE-2023-0001",
+                    "notes": [{ "category": "description", "text": "Example" }],
+                    "product_status": {
+                        "known_affected": ["A"],
+                        "known_not_affected": ["B"]
+                    },
+                    "remediations": [
+                        { "category": "vendor_fix", "details": "Update", "product_ids": ["A"] }
+                    ],
+                    "flags": [
+                        { "label": "component_not_present", "product_ids": ["B"] }
+                    ],
+                    "scores": [{
+                        "products": ["A"],
+                        "cvss_v3": {
+                            "version": "3.1",
+                            "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                            "baseScore": 9.8,
+                            "baseSeverity": "CRITICAL",
+                            "attackVector": "NETWORK"
+                        }
+                    }]
+                }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn valid() {
+        assert_eq!(failures(document()).await, BTreeMap::new());
+    }
+
+    #[tokio::test]
+    async fn products() {
+        let mut doc = document();
+        doc["vulnerabilities"][0]["product_status"]["fixed"] = json!(["A", "C"]);
+        doc["vulnerabilities"][0]["flags"][0]["product_ids"] = json!(["A", "B"]);
+
+        assert_eq!(
+            failures(doc).await,
+            BTreeMap::from_iter([("6.1.1", 1), ("6.1.6", 1)])
+        );
+    }
+
+    #[tokio::test]
+    async fn cvss() {
+        let mut doc = document();
+        let cvss = &mut doc["vulnerabilities"][0]["scores"][0]["cvss_v3"];
+        cvss["baseScore"] = json!(7.5);
+        cvss["attackVector"] = json!("LOCAL");
+
+        assert_eq!(
+            failures(doc).await,
+            BTreeMap::from_iter([("6.1.9", 1), ("6.1.10", 1)])
+        );
+    }
+
+    #[tokio::test]
+    async fn cvss_temporal() {
+        let mut doc = document();
+        doc["vulnerabilities"][0]["scores"][0]["cvss_v2"] = json!({
+            "version": "2.0",
+            "vectorString": "AV:N/AC:L/Au:N/C:C/I:C/A:C/E:F/RL:OF",
+            "baseScore": 10.0,
+            "temporalScore": 10.0,
+            "environmentalScore": 8.3
+        });
+
+        assert_eq!(failures(doc).await, BTreeMap::from_iter([("6.1.9", 1)]));
+    }
+
+    #[tokio::test]
+    async fn cwe() {
+        let mut doc = document();
+        doc["vulnerabilities"][0]["cwe"] = json!({ "id": "CWE-79", "name": "XSS" });
+
+        assert_eq!(failures(doc).await, BTreeMap::from_iter([("6.1.11", 1)]));
+
+        let mut doc = document();
+        doc["vulnerabilities"][0]["cwe"] = json!({ "id": "CWE-999999", "name": "Unknown" });
+
+        assert_eq!(failures(doc).await, BTreeMap::from_iter([("6.1.11", 1)]));
+    }
+
+    #[tokio::test]
+    async fn revisions() {
+        let mut doc = document();
+        doc["document"]["tracking"]["version"] = json!("4");
+        doc["document"]["tracking"]["revision_history"][1]["number"] = json!("1.0.0-rc1");
+
+        assert_eq!(
+            failures(doc).await,
+            BTreeMap::from_iter([("6.1.16", 1), ("6.1.19", 1), ("6.1.30", 1)])
+        );
+
+        // without a valid document version, the oldest revision defines the scheme
+        let mut doc = document();
+        doc["document"]["tracking"]["version"] = json!("latest");
+        doc["document"]["tracking"]["revision_history"][1]["number"] = json!("2.0.0");
+
+        assert_eq!(failures(doc).await, BTreeMap::from_iter([("6.1.30", 1)]));
+    }
+
+    #[tokio::test]
+    async fn vex_statements() {
+        let mut doc = document();
+        doc["vulnerabilities"][0]
+            .as_object_mut()
+            .expect("must be an object")
+            .remove("flags");
+
+        assert_eq!(failures(doc).await, BTreeMap::from_iter([("6.1.27.9", 1)]));
+    }
+
+    /// Remove a property of an object
+    fn remove(value: &mut Value, name: &str) {
+        value
+            .as_object_mut()
+            .expect("must be an object")
+            .remove(name);
+    }
+
+    /// Append an item to an array
+    fn push(value: &mut Value, item: Value) {
+        value.as_array_mut().expect("must be an array").push(item);
+    }
+
+    /// A document of the informational advisory profile, passing all tests
+    fn informational(doc: &mut Value) {
+        doc["document"]["category"] = json!("csaf_informational_advisory");
+        doc["document"]["notes"] = json!([{ "category": "summary", "text": "Example" }]);
+        doc["document"]["references"] = json!([
+            { "category": "external", "summary": "Example", "url": "https://example.com" }
+        ]);
+        remove(doc, "vulnerabilities");
+    }
+
+    /// A named modification of the document, along with the number of findings by test
+    type Case = (
+        &'static str,
+        fn(&mut Value),
+        &'static [(&'static str, usize)],
+    );
+
+    /// A failing example for every test, with all the tests it fails
+    #[tokio::test]
+    async fn failing() {
+        let cases: Vec<Case> = vec![
+            (
+                "multiple product ID definition",
+                |doc| {
+                    push(
+                        &mut doc["product_tree"]["full_product_names"],
+                        json!({ "name": "Product A (again)", "product_id": "A" }),
+                    );
+                },
+                &[("6.1.2", 1)],
+            ),
+            (
+                "circular product ID definition",
+                |doc| {
+                    doc["product_tree"]["relationships"] = json!([{
+                        "category": "installed_on",
+                        "full_product_name": { "name": "Product C", "product_id": "C" },
+                        "product_reference": "C",
+                        "relates_to_product_reference": "A"
+                    }]);
+                },
+                &[("6.1.3", 1)],
+            ),
+            (
+                "missing group ID definition",
+                |doc| {
+                    doc["vulnerabilities"][0]["remediations"][0]["group_ids"] = json!(["G"]);
+                },
+                &[("6.1.4", 1)],
+            ),
+            (
+                "multiple group ID definition",
+                |doc| {
+                    doc["product_tree"]["product_groups"] = json!([
+                        { "group_id": "G", "product_ids": ["A", "B"] },
+                        { "group_id": "G", "product_ids": ["A", "B"] }
+                    ]);
+                },
+                &[("6.1.5", 1)],
+            ),
+            (
+                "multiple scores per product",
+                |doc| {
+                    let score = doc["vulnerabilities"][0]["scores"][0].clone();
+                    push(&mut doc["vulnerabilities"][0]["scores"], score);
+                },
+                &[("6.1.7", 1)],
+            ),
+            (
+                "invalid CVSS",
+                |doc| doc["vulnerabilities"][0]["scores"][0]["cvss_v3"]["version"] = json!("3.0"),
+                &[("6.1.8", 1)],
+            ),
+            (
+                "language",
+                |doc| doc["document"]["lang"] = json!("EZ"),
+                &[("6.1.12", 1)],
+            ),
+            (
+                "sorted revision history",
+                |doc| {
+                    let history = &mut doc["document"]["tracking"]["revision_history"];
+                    history[0]["date"] = json!("2023-03-01T00:00:00Z");
+                },
+                &[("6.1.14", 1), ("6.1.16", 1), ("6.1.21", 1)],
+            ),
+            (
+                "translator",
+                |doc| doc["document"]["publisher"]["category"] = json!("translator"),
+                &[("6.1.15", 1)],
+            ),
+            (
+                "document status draft",
+                |doc| {
+                    let tracking = &mut doc["document"]["tracking"];
+                    tracking["version"] = json!("0");
+                    tracking["revision_history"] = json!([
+                        { "date": "2023-01-01T00:00:00Z", "number": "0", "summary": "Initial" }
+                    ]);
+                },
+                &[("6.1.17", 1), ("6.1.18", 1)],
+            ),
+            (
+                "released revision history",
+                |doc| {
+                    let history = doc["document"]["tracking"]["revision_history"]
+                        .as_array_mut()
+                        .expect("must be an array");
+                    history.insert(
+                        0,
+                        json!({ "date": "2022-12-01T00:00:00Z", "number": "0", "summary": "Draft" }),
+                    );
+                },
+                &[("6.1.18", 1)],
+            ),
+            (
+                "non-draft document version",
+                |doc| {
+                    let tracking = &mut doc["document"]["tracking"];
+                    tracking["version"] = json!("2.0.0-rc1");
+                    tracking["revision_history"][0]["number"] = json!("1.0.0");
+                    tracking["revision_history"][1]["number"] = json!("2.0.0");
+                },
+                &[("6.1.16", 1), ("6.1.17", 1), ("6.1.20", 1)],
+            ),
+            (
+                "missing item in revision history",
+                |doc| {
+                    let tracking = &mut doc["document"]["tracking"];
+                    tracking["version"] = json!("3");
+                    tracking["revision_history"][1]["number"] = json!("3");
+                },
+                &[("6.1.21", 1)],
+            ),
+            (
+                "multiple definition in revision history",
+                |doc| {
+                    let tracking = &mut doc["document"]["tracking"];
+                    tracking["version"] = json!("1");
+                    tracking["revision_history"][1]["number"] = json!("1");
+                },
+                &[("6.1.22", 1)],
+            ),
+            (
+                "multiple use of same CVE",
+                |doc| {
+                    let vulnerability = doc["vulnerabilities"][0].clone();
+                    push(&mut doc["vulnerabilities"], vulnerability);
+                },
+                &[("6.1.23", 1)],
+            ),
+            (
+                "multiple definition in involvements",
+                |doc| {
+                    let involvement = json!({
+                        "party": "vendor",
+                        "status": "in_progress",
+                        "date": "2023-01-01T00:00:00Z"
+                    });
+                    doc["vulnerabilities"][0]["involvements"] = json!([involvement, involvement]);
+                },
+                &[("6.1.24", 1)],
+            ),
+            (
+                "multiple use of same hash algorithm",
+                |doc| {
+                    doc["product_tree"]["full_product_names"][0]["product_identification_helper"] = json!({
+                        "hashes": [{
+                            // the model uses `file_name`, instead of `filename` of the schema
+                            "file_name": "product_a.tar.gz",
+                            "file_hashes": [
+                                { "algorithm": "sha256", "value": "4775203615d9534a8bfca96a93dc8b461a489f69124a130d786b42204f3341cc" },
+                                { "algorithm": "sha256", "value": "9ea4c8200113d49d26505da0e02e2f49055dc078d1ad7a419b32e291c7afebbb" }
+                            ]
+                        }]
+                    });
+                },
+                &[("6.1.25", 1)],
+            ),
+            (
+                "prohibited document category name",
+                |doc| doc["document"]["category"] = json!("Security_Incident_Response"),
+                &[("6.1.26", 1)],
+            ),
+            (
+                "document notes",
+                |doc| {
+                    informational(doc);
+                    remove(&mut doc["document"], "notes");
+                },
+                &[("6.1.27.1", 1)],
+            ),
+            (
+                "document references",
+                |doc| {
+                    informational(doc);
+                    remove(&mut doc["document"], "references");
+                },
+                &[("6.1.27.2", 1)],
+            ),
+            (
+                "informational vulnerabilities",
+                |doc| {
+                    let vulnerabilities = doc["vulnerabilities"].clone();
+                    informational(doc);
+                    doc["vulnerabilities"] = vulnerabilities;
+                },
+                &[("6.1.27.3", 1)],
+            ),
+            (
+                "product tree",
+                |doc| remove(doc, "product_tree"),
+                &[("6.1.1", 5), ("6.1.27.4", 1)],
+            ),
+            (
+                "vulnerability notes",
+                |doc| remove(&mut doc["vulnerabilities"][0], "notes"),
+                &[("6.1.27.5", 1)],
+            ),
+            (
+                "product status",
+                |doc| {
+                    doc["document"]["category"] = json!("csaf_security_advisory");
+                    remove(&mut doc["vulnerabilities"][0], "product_status");
+                },
+                &[("6.1.27.6", 1)],
+            ),
+            (
+                "VEX product status",
+                |doc| {
+                    doc["vulnerabilities"][0]["product_status"] = json!({ "last_affected": ["A"] });
+                },
+                &[("6.1.27.7", 1)],
+            ),
+            (
+                "vulnerability ID",
+                |doc| remove(&mut doc["vulnerabilities"][0], "cve"),
+                &[("6.1.27.8", 1)],
+            ),
+            (
+                "action statement",
+                |doc| remove(&mut doc["vulnerabilities"][0], "remediations"),
+                &[("6.1.27.10", 1)],
+            ),
+            (
+                "vulnerabilities",
+                |doc| remove(doc, "vulnerabilities"),
+                &[("6.1.27.11", 1)],
+            ),
+            (
+                "translation",
+                |doc| doc["document"]["source_lang"] = json!("en"),
+                &[("6.1.28", 1)],
+            ),
+            (
+                "remediation without product reference",
+                |doc| {
+                    push(
+                        &mut doc["vulnerabilities"][0]["remediations"],
+                        json!({ "category": "workaround", "details": "Disable it" }),
+                    );
+                },
+                &[("6.1.29", 1)],
+            ),
+            (
+                "version range in product version",
+                |doc| {
+                    doc["product_tree"]["branches"] = json!([{
+                        "category": "vendor",
+                        "name": "Example",
+                        "branches": [{
+                            "category": "product_name",
+                            "name": "Product C",
+                            "branches": [{
+                                "category": "product_version",
+                                "name": "prior to 4.2",
+                                "product": { "name": "Product C prior to 4.2", "product_id": "C" }
+                            }]
+                        }]
+                    }]);
+                },
+                &[("6.1.31", 1)],
+            ),
+            (
+                "flag without product reference",
+                |doc| {
+                    push(
+                        &mut doc["vulnerabilities"][0]["flags"],
+                        json!({ "label": "vulnerable_code_not_present" }),
+                    );
+                },
+                &[("6.1.32", 1)],
+            ),
+            (
+                "multiple flags per product",
+                |doc| {
+                    push(
+                        &mut doc["vulnerabilities"][0]["flags"],
+                        json!({ "label": "vulnerable_code_not_present", "product_ids": ["B"] }),
+                    );
+                },
+                &[("6.1.33", 1)],
+            ),
+        ];
+
+        for (name, mutate, expected) in cases {
+            let mut doc = document();
+            mutate(&mut doc);
+
+            assert_eq!(
+                failures(doc).await,
+                expected.iter().copied().collect::<BTreeMap<_, _>>(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn purl() {
+        // the model rejects invalid PURLs, the check is performed on the original JSON
+        let csaf = Csaf::deserialize(document()).expect("example data must parse");
+        let mut json = document();
+        json["product_tree"]["full_product_names"][0]["product_identification_helper"] =
+            json!({ "purl": "pkg:maven/@1.3.4" });
+
+        assert_eq!(
+            check_purl(&csaf, &json)
+                .into_iter()
+                .map(|finding| finding.path)
+                .collect::<Vec<_>>(),
+            vec![Some(
+                "/product_tree/full_product_names/0/product_identification_helper/purl".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn example_data() {
+        let json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2023_1441.json"))
+                .expect("example data must parse");
+
+        assert_eq!(failures(json).await, BTreeMap::new());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::verification::check::{
+    Finding,
+    products::{
+        group_id_definitions, group_id_references, group_members, product_id_definitions,
+        product_id_references, resolve_products,
+    },
+};
+use csaf::Csaf;
+use std::collections::{HashMap, HashSet};
+
+/// Report every value occurring more than once, along with its first location.
+fn duplicates<'a>(
+    entries: impl IntoIterator<Item = (String, &'a str)>,
+    msg: impl Fn(&str, &str) -> String,
+) -> Vec<Finding> {
+    let mut seen = HashMap::<&str, String>::new();
+    let mut result = vec![];
+
+    for (path, id) in entries {
+        match seen.get(id) {
+            Some(first) => result.push(Finding::new(msg(id, first)).path(path)),
+            None => {
+                seen.insert(id, path);
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.1 Missing Definition of Product ID
+pub fn check_missing_product_id_definition(csaf: &Csaf) -> Vec<Finding> {
+    let defined = product_id_definitions(csaf)
+        .into_iter()
+        .map(|(_, id)| id)
+        .collect::<HashSet<_>>();
+
+    product_id_references(csaf)
+        .into_iter()
+        .filter(|(_, id)| !defined.contains(id))
+        .map(|(path, id)| {
+            Finding::new(format!("Missing definition of product ID '{id}'")).path(path)
+        })
+        .collect()
+}
+
+/// 6.1.2 Multiple Definition of Product ID
+pub fn check_multiple_product_id_definition(csaf: &Csaf) -> Vec<Finding> {
+    duplicates(product_id_definitions(csaf), |id, first| {
+        format!("Multiple definition of product ID '{id}', first defined at {first}")
+    })
+}
+
+/// 6.1.3 Circular Definition of Product ID
+pub fn check_circular_product_id_definition(csaf: &Csaf) -> Vec<Finding> {
+    let Some(relationships) = csaf
+        .product_tree
+        .as_ref()
+        .and_then(|tree| tree.relationships.as_ref())
+    else {
+        return vec![];
+    };
+
+    // the product IDs a relationship based product ID is built from
+    let mut edges = HashMap::<&str, Vec<&str>>::new();
+    for relationship in relationships {
+        edges
+            .entry(relationship.full_product_name.product_id.0.as_str())
+            .or_default()
+            .extend([
+                relationship.product_reference.0.as_str(),
+                relationship.relates_to_product_reference.0.as_str(),
+            ]);
+    }
+
+    let mut result = vec![];
+
+    for (i, relationship) in relationships.iter().enumerate() {
+        let start = relationship.full_product_name.product_id.0.as_str();
+
+        let mut visited = HashSet::new();
+        let mut pending = edges.get(start).cloned().unwrap_or_default();
+        while let Some(next) = pending.pop() {
+            if next == start {
+                result.push(
+                    Finding::new(format!("Circular definition of product ID '{start}'")).path(
+                        format!("/product_tree/relationships/{i}/full_product_name/product_id"),
+                    ),
+                );
+                break;
+            }
+            if visited.insert(next) {
+                pending.extend(edges.get(next).into_iter().flatten());
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.4 Missing Definition of Product Group ID
+pub fn check_missing_group_id_definition(csaf: &Csaf) -> Vec<Finding> {
+    let defined = group_id_definitions(csaf)
+        .into_iter()
+        .map(|(_, id)| id)
+        .collect::<HashSet<_>>();
+
+    group_id_references(csaf)
+        .into_iter()
+        .filter(|(_, id)| !defined.contains(id))
+        .map(|(path, id)| {
+            Finding::new(format!("Missing definition of product group ID '{id}'")).path(path)
+        })
+        .collect()
+}
+
+/// 6.1.5 Multiple Definition of Product Group ID
+pub fn check_multiple_group_id_definition(csaf: &Csaf) -> Vec<Finding> {
+    duplicates(group_id_definitions(csaf), |id, first| {
+        format!("Multiple definition of product group ID '{id}', first defined at {first}")
+    })
+}
+
+/// 6.1.6 Contradicting Product Status
+pub fn check_contradicting_product_status(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let Some(status) = &vulnerability.product_status else {
+            continue;
+        };
+
+        let groups = [
+            (
+                "affected",
+                vec![
+                    &status.first_affected,
+                    &status.known_affected,
+                    &status.last_affected,
+                ],
+            ),
+            ("not affected", vec![&status.known_not_affected]),
+            ("fixed", vec![&status.first_fixed, &status.fixed]),
+            ("under investigation", vec![&status.under_investigation]),
+        ];
+
+        let mut products = HashMap::<&str, Vec<&str>>::new();
+        for (name, fields) in groups {
+            let ids = fields
+                .iter()
+                .flat_map(|ids| ids.iter().flatten())
+                .map(|id| id.0.as_str())
+                .collect::<HashSet<_>>();
+            for id in ids {
+                products.entry(id).or_default().push(name);
+            }
+        }
+
+        let mut contradicting = products
+            .into_iter()
+            .filter(|(_, groups)| groups.len() > 1)
+            .collect::<Vec<_>>();
+        contradicting.sort_unstable();
+
+        for (id, groups) in contradicting {
+            result.push(
+                Finding::new(format!(
+                    "Contradicting product status for product ID '{id}': {groups}",
+                    groups = groups.join(", ")
+                ))
+                .path(format!("/vulnerabilities/{i}/product_status")),
+            );
+        }
+    }
+
+    result
+}
+
+/// 6.1.29 Remediation without Product Reference
+pub fn check_remediation_without_product_reference(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, remediation) in vulnerability.remediations.iter().flatten().enumerate() {
+            if remediation.product_ids.is_none() && remediation.group_ids.is_none() {
+                result.push(
+                    Finding::new("Remediation without product reference")
+                        .path(format!("/vulnerabilities/{i}/remediations/{j}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.32 Flag without Product Reference
+pub fn check_flag_without_product_reference(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, flag) in vulnerability.flags.iter().flatten().enumerate() {
+            if flag.product_ids.is_none() && flag.group_ids.is_none() {
+                result.push(
+                    Finding::new("Flag without product reference")
+                        .path(format!("/vulnerabilities/{i}/flags/{j}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.33 Multiple Flags with VEX Justification Codes per Product
+pub fn check_multiple_flags_per_product(csaf: &Csaf) -> Vec<Finding> {
+    let groups = group_members(csaf);
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let mut seen = HashMap::<&str, usize>::new();
+
+        for (j, flag) in vulnerability.flags.iter().flatten().enumerate() {
+            for id in resolve_products(&groups, flag.product_ids.as_ref(), flag.group_ids.as_ref())
+            {
+                if let Some(first) = seen.insert(id, j) {
+                    result.push(
+                        Finding::new(format!("Multiple flags for product ID '{id}', also flagged by /vulnerabilities/{i}/flags/{first}")).path(format!("/vulnerabilities/{i}/flags/{j}")),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,276 @@
+//! Profile specific tests (6.1.27.x)
+
+use crate::verification::check::{
+    Finding,
+    mandatory::document::PROFILES,
+    products::{group_members, resolve_products},
+};
+use csaf::{
+    Csaf,
+    definitions::{NoteCategory, ReferenceCategory},
+    document::Category,
+    vulnerability::{ThreatCategory, Vulnerability},
+};
+use std::collections::HashSet;
+
+/// The document category, if it is one of the profiles defined by the specification.
+pub(crate) fn profile(csaf: &Csaf) -> Option<&str> {
+    match &csaf.document.category {
+        Category::Base => Some("csaf_base"),
+        Category::SecurityAdvisory => Some("csaf_security_advisory"),
+        Category::Vex => Some("csaf_vex"),
+        Category::Other(other) => PROFILES.iter().copied().find(|profile| profile == other),
+    }
+}
+
+fn is_profile(csaf: &Csaf, profiles: &[&str]) -> bool {
+    profile(csaf).is_some_and(|profile| profiles.contains(&profile))
+}
+
+const INFORMATIONAL: &[&str] = &[
+    "csaf_security_incident_response",
+    "csaf_informational_advisory",
+];
+const ADVISORY: &[&str] = &["csaf_security_advisory", "csaf_vex"];
+
+/// Check every vulnerability, for documents of the given profiles.
+fn each_vulnerability(
+    csaf: &Csaf,
+    profiles: &[&str],
+    f: impl Fn(&str, &Vulnerability) -> Option<Finding>,
+) -> Vec<Finding> {
+    if !is_profile(csaf, profiles) {
+        return vec![];
+    }
+
+    csaf.vulnerabilities
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(i, vulnerability)| f(&format!("/vulnerabilities/{i}"), vulnerability))
+        .collect()
+}
+
+/// 6.1.27.1 Document Notes
+pub fn check_document_notes(csaf: &Csaf) -> Vec<Finding> {
+    if !is_profile(csaf, INFORMATIONAL) {
+        return vec![];
+    }
+
+    let ok = csaf.document.notes.iter().flatten().any(|note| {
+        matches!(
+            note.category,
+            NoteCategory::Description
+                | NoteCategory::Details
+                | NoteCategory::General
+                | NoteCategory::Summary
+        )
+    });
+
+    if !ok {
+        return vec![
+            Finding::new("Missing document note with category 'description', 'details', 'general', or 'summary'").path("/document/notes"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.27.2 Document References
+pub fn check_document_references(csaf: &Csaf) -> Vec<Finding> {
+    if !is_profile(csaf, INFORMATIONAL) {
+        return vec![];
+    }
+
+    let ok = csaf
+        .document
+        .references
+        .iter()
+        .flatten()
+        .any(|reference| matches!(reference.category, Some(ReferenceCategory::External)));
+
+    if !ok {
+        return vec![
+            Finding::new("Missing document reference with category 'external'")
+                .path("/document/references"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.27.3 Vulnerabilities
+pub fn check_informational_vulnerabilities(csaf: &Csaf) -> Vec<Finding> {
+    if is_profile(csaf, &["csaf_informational_advisory"]) && csaf.vulnerabilities.is_some() {
+        return vec![
+            Finding::new("An informational advisory must not contain vulnerabilities")
+                .path("/vulnerabilities"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.27.4 Product Tree
+pub fn check_product_tree(csaf: &Csaf) -> Vec<Finding> {
+    if is_profile(csaf, ADVISORY) && csaf.product_tree.is_none() {
+        return vec![Finding::new("Missing product tree").path("/product_tree")];
+    }
+
+    vec![]
+}
+
+/// 6.1.27.5 Vulnerability Notes
+pub fn check_vulnerability_notes(csaf: &Csaf) -> Vec<Finding> {
+    each_vulnerability(csaf, ADVISORY, |path, vulnerability| {
+        vulnerability
+            .notes
+            .is_none()
+            .then(|| Finding::new("Missing vulnerability notes").path(format!("{path}/notes")))
+    })
+}
+
+/// 6.1.27.6 Product Status
+pub fn check_product_status(csaf: &Csaf) -> Vec<Finding> {
+    each_vulnerability(csaf, &["csaf_security_advisory"], |path, vulnerability| {
+        vulnerability
+            .product_status
+            .is_none()
+            .then(|| Finding::new("Missing product status").path(format!("{path}/product_status")))
+    })
+}
+
+/// 6.1.27.7 VEX Product Status
+pub fn check_vex_product_status(csaf: &Csaf) -> Vec<Finding> {
+    each_vulnerability(csaf, &["csaf_vex"], |path, vulnerability| {
+        let ok = vulnerability.product_status.as_ref().is_some_and(|status| {
+            status.fixed.is_some()
+                || status.known_affected.is_some()
+                || status.known_not_affected.is_some()
+                || status.under_investigation.is_some()
+        });
+
+        (!ok).then(|| {
+            Finding::new("Missing one of 'fixed', 'known_affected', 'known_not_affected', or 'under_investigation'").path(format!("{path}/product_status"))
+        })
+    })
+}
+
+/// 6.1.27.8 Vulnerability ID
+pub fn check_vulnerability_id(csaf: &Csaf) -> Vec<Finding> {
+    each_vulnerability(csaf, &["csaf_vex"], |path, vulnerability| {
+        (vulnerability.cve.is_none() && vulnerability.ids.is_none())
+            .then(|| Finding::new("Missing vulnerability ID, requires 'cve' or 'ids'").path(path))
+    })
+}
+
+/// 6.1.27.9 Impact Statement
+pub fn check_impact_statement(csaf: &Csaf) -> Vec<Finding> {
+    if !is_profile(csaf, &["csaf_vex"]) {
+        return vec![];
+    }
+
+    let groups = group_members(csaf);
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let Some(not_affected) = vulnerability
+            .product_status
+            .as_ref()
+            .and_then(|status| status.known_not_affected.as_ref())
+        else {
+            continue;
+        };
+
+        let flagged = vulnerability.flags.iter().flatten().flat_map(|flag| {
+            resolve_products(&groups, flag.product_ids.as_ref(), flag.group_ids.as_ref())
+        });
+        let impact = vulnerability
+            .threats
+            .iter()
+            .flatten()
+            .filter(|threat| matches!(threat.category, ThreatCategory::Impact))
+            .flat_map(|threat| {
+                resolve_products(
+                    &groups,
+                    threat.product_ids.as_ref(),
+                    threat.group_ids.as_ref(),
+                )
+            });
+        let covered = flagged.chain(impact).collect::<HashSet<_>>();
+
+        for (j, id) in not_affected.iter().enumerate() {
+            if !covered.contains(id.0.as_str()) {
+                result.push(
+                    Finding::new(format!(
+                        "Missing impact statement for product ID '{id}'",
+                        id = id.0
+                    ))
+                    .path(format!(
+                        "/vulnerabilities/{i}/product_status/known_not_affected/{j}"
+                    )),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.27.10 Action Statement
+pub fn check_action_statement(csaf: &Csaf) -> Vec<Finding> {
+    if !is_profile(csaf, &["csaf_vex"]) {
+        return vec![];
+    }
+
+    let groups = group_members(csaf);
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let Some(affected) = vulnerability
+            .product_status
+            .as_ref()
+            .and_then(|status| status.known_affected.as_ref())
+        else {
+            continue;
+        };
+
+        let covered = vulnerability
+            .remediations
+            .iter()
+            .flatten()
+            .flat_map(|remediation| {
+                resolve_products(
+                    &groups,
+                    remediation.product_ids.as_ref(),
+                    remediation.group_ids.as_ref(),
+                )
+            })
+            .collect::<HashSet<_>>();
+
+        for (j, id) in affected.iter().enumerate() {
+            if !covered.contains(id.0.as_str()) {
+                result.push(
+                    Finding::new(format!(
+                        "Missing action statement for product ID '{id}'",
+                        id = id.0
+                    ))
+                    .path(format!(
+                        "/vulnerabilities/{i}/product_status/known_affected/{j}"
+                    )),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.1.27.11 Vulnerabilities
+pub fn check_vulnerabilities(csaf: &Csaf) -> Vec<Finding> {
+    if is_profile(csaf, ADVISORY) && csaf.vulnerabilities.is_none() {
+        return vec![Finding::new("Missing vulnerabilities").path("/vulnerabilities")];
+    }
+
+    vec![]
+}
@@ -0,0 +1,321 @@
+use crate::verification::check::Finding;
+use csaf::{
+    Csaf,
+    document::{Revision, Status},
+};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// A document version, using either integer or semantic versioning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Version {
+    Integer(u64),
+    Semantic(semver::Version),
+}
+
+impl Version {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        if value == "0" || (!value.starts_with('0') && value.bytes().all(|b| b.is_ascii_digit())) {
+            return value.parse().ok().map(Self::Integer);
+        }
+
+        semver::Version::parse(value).ok().map(Self::Semantic)
+    }
+
+    /// The integer version, or the major version in case of semantic versioning
+    pub(crate) fn major(&self) -> u64 {
+        match self {
+            Self::Integer(version) => *version,
+            Self::Semantic(version) => version.major,
+        }
+    }
+
+    pub(crate) fn is_pre_release(&self) -> bool {
+        matches!(self, Self::Semantic(version) if !version.pre.is_empty())
+    }
+
+    /// Compare two versions of the same scheme, ignoring build metadata.
+    pub(crate) fn cmp_precedence(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::Semantic(a), Self::Semantic(b)) => Some(a.cmp_precedence(b)),
+            _ => None,
+        }
+    }
+
+    /// Strip the build metadata, and the pre-release part if requested
+    fn normalize(&self, strip_pre_release: bool) -> Self {
+        match self {
+            Self::Integer(_) => self.clone(),
+            Self::Semantic(version) => {
+                let mut version = version.clone();
+                version.build = semver::BuildMetadata::EMPTY;
+                if strip_pre_release {
+                    version.pre = semver::Prerelease::EMPTY;
+                }
+                Self::Semantic(version)
+            }
+        }
+    }
+}
+
+/// The revision history, sorted ascending by date (and number), along with the original index
+pub(crate) fn sorted_revisions(csaf: &Csaf) -> Vec<(usize, &Revision, Option<Version>)> {
+    let mut revisions = csaf
+        .document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .map(|(i, revision)| (i, revision, Version::parse(&revision.number)))
+        .collect::<Vec<_>>();
+
+    revisions.sort_by(|(_, a, va), (_, b, vb)| {
+        a.date.cmp(&b.date).then_with(|| match (va, vb) {
+            (Some(va), Some(vb)) => va.cmp_precedence(vb).unwrap_or(Ordering::Equal),
+            _ => Ordering::Equal,
+        })
+    });
+
+    revisions
+}
+
+fn is_released(csaf: &Csaf) -> bool {
+    matches!(
+        csaf.document.tracking.status,
+        Status::Final | Status::Interim
+    )
+}
+
+/// 6.1.14 Sorted Revision History
+pub fn check_sorted_revision_history(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    // sort by date only, keeping the original order for revisions of the same date
+    let mut revisions = csaf
+        .document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .collect::<Vec<_>>();
+    revisions.sort_by_key(|(_, revision)| revision.date);
+
+    for pair in revisions.windows(2) {
+        let [(_, previous), (i, revision)] = pair else {
+            continue;
+        };
+        let (Some(a), Some(b)) = (
+            Version::parse(&previous.number),
+            Version::parse(&revision.number),
+        ) else {
+            continue;
+        };
+        if a.cmp_precedence(&b) == Some(Ordering::Greater) {
+            result.push(
+                Finding::new(format!(
+                    "Revision history is not sorted: version {} is dated after version {}",
+                    revision.number, previous.number
+                ))
+                .path(format!("/document/tracking/revision_history/{i}/number")),
+            );
+        }
+    }
+
+    result
+}
+
+/// 6.1.16 Latest Document Version
+pub fn check_latest_document_version(csaf: &Csaf) -> Vec<Finding> {
+    let revisions = sorted_revisions(csaf);
+    let Some((_, latest, Some(latest_version))) = revisions.last() else {
+        return vec![];
+    };
+    let Some(version) = Version::parse(&csaf.document.tracking.version) else {
+        return vec![];
+    };
+
+    let draft = matches!(csaf.document.tracking.status, Status::Draft);
+    if version.normalize(draft) != latest_version.normalize(draft) {
+        return vec![
+            Finding::new(format!(
+                "Document version ({}) doesn't match the latest revision ({})",
+                csaf.document.tracking.version, latest.number
+            ))
+            .path("/document/tracking/version"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.17 Document Status Draft
+pub fn check_document_status_draft(csaf: &Csaf) -> Vec<Finding> {
+    let Some(version) = Version::parse(&csaf.document.tracking.version) else {
+        return vec![];
+    };
+
+    if (version.major() == 0 || version.is_pre_release())
+        && !matches!(csaf.document.tracking.status, Status::Draft)
+    {
+        return vec![
+            Finding::new(format!(
+                "Document version {} requires the status 'draft'",
+                csaf.document.tracking.version
+            ))
+            .path("/document/tracking/status"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.18 Released Revision History
+pub fn check_released_revision_history(csaf: &Csaf) -> Vec<Finding> {
+    if !is_released(csaf) {
+        return vec![];
+    }
+
+    csaf.document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .filter(|(_, revision)| {
+            Version::parse(&revision.number).is_some_and(|version| version.major() == 0)
+        })
+        .map(|(i, revision)| {
+            Finding::new(format!(
+                "Released document must not contain revision {}",
+                revision.number
+            ))
+            .path(format!("/document/tracking/revision_history/{i}/number"))
+        })
+        .collect()
+}
+
+/// 6.1.19 Revision History Entries for Pre-release Versions
+pub fn check_pre_release_revision_history(csaf: &Csaf) -> Vec<Finding> {
+    csaf.document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .filter(|(_, revision)| {
+            Version::parse(&revision.number).is_some_and(|version| version.is_pre_release())
+        })
+        .map(|(i, revision)| {
+            Finding::new(format!(
+                "Revision history must not contain pre-release version {}",
+                revision.number
+            ))
+            .path(format!("/document/tracking/revision_history/{i}/number"))
+        })
+        .collect()
+}
+
+/// 6.1.20 Non-draft Document Version
+pub fn check_non_draft_document_version(csaf: &Csaf) -> Vec<Finding> {
+    if is_released(csaf)
+        && Version::parse(&csaf.document.tracking.version)
+            .is_some_and(|version| version.is_pre_release())
+    {
+        return vec![
+            Finding::new(format!(
+                "Released document must not have pre-release version {}",
+                csaf.document.tracking.version
+            ))
+            .path("/document/tracking/version"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.1.21 Missing Item in Revision History
+///
+/// In the case of semantic versioning, this only applies to the major version.
+pub fn check_missing_revision_history_item(csaf: &Csaf) -> Vec<Finding> {
+    let revisions = sorted_revisions(csaf)
+        .into_iter()
+        .filter_map(|(i, _, version)| version.map(|version| (i, version.major())))
+        .collect::<Vec<_>>();
+
+    let mut result = vec![];
+
+    if let Some((i, first)) = revisions.first() {
+        if *first > 1 {
+            result.push(
+                Finding::new(format!(
+                    "First revision must have the (major) version 0 or 1, found {first}"
+                ))
+                .path(format!("/document/tracking/revision_history/{i}/number")),
+            );
+        }
+    }
+
+    for pair in revisions.windows(2) {
+        let [(_, previous), (i, current)] = pair else {
+            continue;
+        };
+        if *current > previous + 1 {
+            result.push(
+                Finding::new(format!(
+                    "Missing revision between (major) versions {previous} and {current}"
+                ))
+                .path(format!("/document/tracking/revision_history/{i}/number")),
+            );
+        }
+    }
+
+    result
+}
+
+/// 6.1.22 Multiple Definition in Revision History
+pub fn check_multiple_revision_history_definition(csaf: &Csaf) -> Vec<Finding> {
+    let mut seen = HashMap::<&str, usize>::new();
+    let mut result = vec![];
+
+    for (i, revision) in csaf.document.tracking.revision_history.iter().enumerate() {
+        if let Some(first) = seen.insert(&revision.number, i) {
+            result.push(
+                Finding::new(format!("Multiple definition of revision {}, also defined at /document/tracking/revision_history/{first}/number", revision.number)).path(format!("/document/tracking/revision_history/{i}/number")),
+            );
+        }
+    }
+
+    result
+}
+
+/// 6.1.30 Mixed Integer and Semantic Versioning
+///
+/// The document version defines the versioning scheme. If that can't be parsed, the oldest
+/// revision with a valid version defines it.
+pub fn check_mixed_versioning(csaf: &Csaf) -> Vec<Finding> {
+    let version = Version::parse(&csaf.document.tracking.version).or_else(|| {
+        sorted_revisions(csaf)
+            .into_iter()
+            .find_map(|(_, _, version)| version)
+    });
+    let Some(version) = version else {
+        return vec![];
+    };
+    let integer = matches!(version, Version::Integer(_));
+
+    csaf.document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .filter(|(_, revision)| {
+            Version::parse(&revision.number)
+                .is_some_and(|version| matches!(version, Version::Integer(_)) != integer)
+        })
+        .map(|(i, revision)| {
+            Finding::new(format!(
+                "Revision {} uses a different versioning scheme than the document version {}",
+                revision.number, csaf.document.tracking.version
+            ))
+            .path(format!("/document/tracking/revision_history/{i}/number"))
+        })
+        .collect()
+}
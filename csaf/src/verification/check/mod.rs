@@ -1,5 +1,9 @@
 use crate::verification::check::{
-    base::{check_csaf_base, check_csaf_document_tracking_revision_history},
+    base::{
+        RULE_CURRENT_RELEASE_DATE, RULE_INITIAL_RELEASE_DATE, check_csaf_base,
+        check_csaf_document_tracking_revision_history, check_current_release_date,
+        check_initial_release_date,
+    },
     informational_advisory::check_vulnerabilities_not_exits,
     security_incident_response::{check_csaf_document_notes, check_csaf_document_references},
     vex::{
@@ -11,10 +15,20 @@ use crate::verification::check::{
 };
 use async_trait::async_trait;
 use csaf::Csaf;
+use serde_json::Value;
 use std::borrow::Cow;
 
 pub mod base;
+pub mod cvss;
+mod cwe;
+pub mod identification;
 pub mod informational_advisory;
+pub mod informative;
+pub mod mandatory;
+pub mod optional;
+pub(crate) mod products;
+pub mod rules;
+pub mod schema;
 pub mod security_advisory;
 pub mod security_incident_response;
 pub mod vex;
@@ -22,28 +36,221 @@ pub mod vex;
 #[cfg(feature = "csaf-validator-lib")]
 pub mod csaf_validator_lib;
 
+pub use crate::verification::{Finding, Severity};
+
+/// A plain check failure message
 pub type CheckError = Cow<'static, str>;
 
 #[async_trait(?Send)]
 pub trait Check {
     /// Perform a check on a CSAF document
     async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>>;
+
+    /// Perform a check on a CSAF document, having access to its original JSON representation
+    ///
+    /// The [`Csaf`] model doesn't retain all information of the original document (like declared
+    /// CVSS scores). The default implementation delegates to [`Check::check`].
+    async fn check_json(&self, csaf: &Csaf, _json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        self.check(csaf).await
+    }
+
+    /// The severity of findings reported by this check
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Perform a check on a CSAF document, reporting structured findings
+    ///
+    /// The default implementation turns the messages of [`Check::check_json`] into findings.
+    /// Findings without a severity get the one of [`Check::severity`], findings without a rule ID
+    /// get the ID the check was registered with.
+    async fn findings(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        Ok(self
+            .check_json(csaf, json)
+            .await?
+            .into_iter()
+            .map(Finding::new)
+            .collect())
+    }
+
+    /// Perform a check on a CSAF document, having access to its original data
+    ///
+    /// Some information (like the order of keys) is only available from the original data. The
+    /// default implementation delegates to [`Check::findings`].
+    async fn findings_data(
+        &self,
+        csaf: &Csaf,
+        json: &Value,
+        _data: &[u8],
+    ) -> anyhow::Result<Vec<Finding>> {
+        self.findings(csaf, json).await
+    }
+}
+
+/// The outcome of a function style check, either plain messages or structured findings
+pub trait CheckOutcome {
+    fn into_findings(self) -> Vec<Finding>;
+}
+
+impl CheckOutcome for Vec<CheckError> {
+    fn into_findings(self) -> Vec<Finding> {
+        self.into_iter().map(Finding::new).collect()
+    }
+}
+
+impl CheckOutcome for Vec<Finding> {
+    fn into_findings(self) -> Vec<Finding> {
+        self
+    }
+}
+
+/// Plain messages of findings, for [`Check::check`]
+fn messages(outcome: impl CheckOutcome) -> Vec<CheckError> {
+    outcome
+        .into_findings()
+        .into_iter()
+        .map(|finding| finding.to_string().into())
+        .collect()
 }
 
 /// Implementation to allow a simple function style check
 #[async_trait(?Send)]
-impl<F> Check for F
+impl<F, O> Check for F
 where
-    F: Fn(&Csaf) -> Vec<CheckError>,
+    F: Fn(&Csaf) -> O,
+    O: CheckOutcome,
 {
     async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
-        Ok((self)(csaf))
+        Ok(messages((self)(csaf)))
+    }
+
+    async fn findings(&self, csaf: &Csaf, _json: &Value) -> anyhow::Result<Vec<Finding>> {
+        Ok((self)(csaf).into_findings())
     }
 }
 
+/// A function style check, requiring the original JSON representation of the document
+pub struct JsonCheck<F>(pub F);
+
+#[async_trait(?Send)]
+impl<F, O> Check for JsonCheck<F>
+where
+    F: Fn(&Csaf, &Value) -> O,
+    O: CheckOutcome,
+{
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        // lacking the original document, use the serialized model
+        let json = serde_json::to_value(csaf)?;
+        Ok(messages((self.0)(csaf, &json)))
+    }
+
+    async fn check_json(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        Ok(messages((self.0)(csaf, json)))
+    }
+
+    async fn findings(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        Ok((self.0)(csaf, json).into_findings())
+    }
+}
+
+/// A function style check, requiring the original data of the document
+pub struct DataCheck<F>(pub F);
+
+#[async_trait(?Send)]
+impl<F, O> Check for DataCheck<F>
+where
+    F: Fn(&Csaf, &[u8]) -> O,
+    O: CheckOutcome,
+{
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        self.check_json(csaf, &serde_json::to_value(csaf)?).await
+    }
+
+    async fn check_json(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        // lacking the original data, use the serialized JSON representation
+        Ok(messages((self.0)(csaf, &serde_json::to_vec(json)?)))
+    }
+
+    async fn findings(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        self.findings_data(csaf, json, &serde_json::to_vec(json)?)
+            .await
+    }
+
+    async fn findings_data(
+        &self,
+        csaf: &Csaf,
+        _json: &Value,
+        data: &[u8],
+    ) -> anyhow::Result<Vec<Finding>> {
+        Ok((self.0)(csaf, data).into_findings())
+    }
+}
+
+/// A check, setting the severity of its findings which don't have one
+struct WithSeverity {
+    check: Box<dyn Check>,
+    severity: Severity,
+}
+
+#[async_trait(?Send)]
+impl Check for WithSeverity {
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        self.check.check(csaf).await
+    }
+
+    async fn check_json(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        self.check.check_json(csaf, json).await
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    async fn findings(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        Ok(self
+            .check
+            .findings(csaf, json)
+            .await?
+            .into_iter()
+            .map(|finding| finding.default_severity(self.severity))
+            .collect())
+    }
+
+    async fn findings_data(
+        &self,
+        csaf: &Csaf,
+        json: &Value,
+        data: &[u8],
+    ) -> anyhow::Result<Vec<Finding>> {
+        Ok(self
+            .check
+            .findings_data(csaf, json, data)
+            .await?
+            .into_iter()
+            .map(|finding| finding.default_severity(self.severity))
+            .collect())
+    }
+}
+
+/// Set the severity of the findings of a set of checks, unless a finding sets its own
+pub fn with_severity<I>(
+    checks: Vec<(I, Box<dyn Check>)>,
+    severity: Severity,
+) -> Vec<(I, Box<dyn Check>)> {
+    checks
+        .into_iter()
+        .map(|(id, check)| {
+            (
+                id,
+                Box::new(WithSeverity { check, severity }) as Box<dyn Check>,
+            )
+        })
+        .collect()
+}
+
 #[derive(Debug, Default)]
 pub struct Checking {
-    results: Vec<CheckError>,
+    results: Vec<Finding>,
 }
 
 impl Checking {
@@ -51,14 +258,32 @@ impl Checking {
         Default::default()
     }
 
-    pub fn require(mut self, msg: impl Into<CheckError>, ok: bool) -> Self {
+    pub fn require(self, msg: impl Into<CheckError>, ok: bool) -> Self {
+        self.require_finding(Finding::new(msg), ok)
+    }
+
+    /// Require a condition, reporting the JSON pointer of the location if it isn't met
+    pub fn require_at(self, path: impl Into<String>, msg: impl Into<CheckError>, ok: bool) -> Self {
+        self.require_finding(Finding::new(msg).path(path), ok)
+    }
+
+    pub fn require_finding(mut self, finding: Finding, ok: bool) -> Self {
         if !ok {
-            self.results.push(msg.into());
+            self.results.push(finding);
         }
         self
     }
 
+    /// The findings, as plain messages
     pub fn done(self) -> Vec<CheckError> {
+        self.results
+            .into_iter()
+            .map(|finding| finding.to_string().into())
+            .collect()
+    }
+
+    /// The structured findings
+    pub fn findings(self) -> Vec<Finding> {
         self.results
     }
 }
@@ -82,6 +307,14 @@ pub fn init_verifying_visitor() -> Vec<(&'static str, Box<dyn Check>)> {
             "check_csaf_document_tracking_revision_history",
             Box::new(check_csaf_document_tracking_revision_history),
         ),
+        (
+            RULE_CURRENT_RELEASE_DATE,
+            Box::new(check_current_release_date),
+        ),
+        (
+            RULE_INITIAL_RELEASE_DATE,
+            Box::new(check_initial_release_date),
+        ),
         (
             "check_vulnerabilities_size",
             Box::new(check_vulnerabilities_size),
@@ -106,3 +339,102 @@ pub fn init_verifying_visitor() -> Vec<(&'static str, Box<dyn Check>)> {
         ),
     ]
 }
+
+/// Native profile of the tests of the CSAF 2.0 specification
+///
+/// Each profile includes the tests of the previous ones.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// JSON schema validation
+    Schema,
+    /// JSON schema validation and mandatory tests (section 6.1)
+    #[default]
+    Mandatory,
+    /// JSON schema validation, mandatory, and optional tests (section 6.2), as well as the checks
+    /// of product identification helpers
+    Optional,
+    /// JSON schema validation, mandatory, optional, and informative tests (section 6.3)
+    Informative,
+}
+
+/// Create the native checks of the CSAF 2.0 specification for a profile
+pub fn init_profile_checks(profile: Profile) -> Vec<(&'static str, Box<dyn Check>)> {
+    let mut result = schema::init_schema_checks();
+
+    if !matches!(profile, Profile::Schema) {
+        result.extend(mandatory::init_mandatory_checks());
+    }
+    if matches!(profile, Profile::Optional | Profile::Informative) {
+        // not part of the specification, but relied upon by downstream tooling
+        result.extend(identification::init_identification_checks());
+        result.extend(with_severity(
+            optional::init_optional_checks(),
+            Severity::Warning,
+        ));
+    }
+    if matches!(profile, Profile::Informative) {
+        result.extend(with_severity(
+            informative::init_informative_checks(),
+            Severity::Info,
+        ));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[tokio::test]
+    async fn findings() {
+        let json: Value =
+            serde_json::from_str(include_str!("../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        let csaf = Csaf::deserialize(&json).expect("example data must parse");
+
+        let check = |_: &Csaf| {
+            let mut findings = Checking::new()
+                .require("Always failing", false)
+                .require_at("/document/title", "Bad title", false)
+                .findings();
+            findings.push(Finding::new("Just a note").severity(Severity::Info));
+            findings
+        };
+
+        let checks = with_severity(
+            vec![("example", Box::new(check) as Box<dyn Check>)],
+            Severity::Warning,
+        );
+        let findings = checks[0]
+            .1
+            .findings(&csaf, &json)
+            .await
+            .expect("check must not fail");
+
+        assert_eq!(
+            findings,
+            vec![
+                Finding::new("Always failing").severity(Severity::Warning),
+                Finding::new("Bad title")
+                    .severity(Severity::Warning)
+                    .path("/document/title"),
+                Finding::new("Just a note").severity(Severity::Info),
+            ]
+        );
+    }
+}
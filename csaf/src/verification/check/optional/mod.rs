@@ -0,0 +1,695 @@
+//! Native implementation of the optional tests (section 6.2) of the CSAF 2.0 specification.
+//!
+//! Checks are identified by the number of the test in the specification (e.g. `6.2.1`).
+//!
+//! The test 6.2.13 (Sorting) requires the original data of the document, and only gives
+//! meaningful results when performed using [`Check::findings_data`]. The test 6.2.20 (Additional
+//! Properties) requires the original JSON document, see [`Check::check_json`].
+
+use crate::verification::check::{
+    Check, DataCheck, Finding, JsonCheck,
+    mandatory::Version,
+    products::{
+        full_product_names, group_members, product_id_definitions, product_id_references,
+        resolve_products,
+    },
+    schema,
+};
+use csaf::{
+    Csaf,
+    definitions::{BranchCategory, BranchesT, ProductIdT, ReferenceCategory},
+    vulnerability::{ProductStatus, Vulnerability},
+};
+use language_tags::LanguageTag;
+use regex::Regex;
+use serde::{
+    Deserializer,
+    de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
+};
+use serde_json::Value;
+use std::{collections::HashSet, fmt::Formatter, sync::LazyLock};
+
+static CVE_ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^CVE-[0-9]{4}-[0-9]{4,}$").expect("regex must compile"));
+
+static FILENAME_REPLACE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[^+\-a-z0-9]+").expect("regex must compile"));
+
+/// The filename of a document, derived from its tracking ID
+pub fn document_filename(tracking_id: &str) -> String {
+    format!(
+        "{}.json",
+        FILENAME_REPLACE.replace_all(&tracking_id.to_lowercase(), "_")
+    )
+}
+
+/// The products of a vulnerability, having one of the product status
+fn products_with_status<'a>(
+    vulnerability: &'a Vulnerability,
+    status: impl Fn(&'a ProductStatus) -> Vec<&'a Option<Vec<ProductIdT>>>,
+) -> Vec<&'a str> {
+    let mut result = vulnerability
+        .product_status
+        .iter()
+        .flat_map(status)
+        .flat_map(|ids| ids.iter().flatten())
+        .map(|id| id.0.as_str())
+        .collect::<Vec<_>>();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+/// 6.2.1 Unused Definition of Product ID
+pub fn check_unused_product_id(csaf: &Csaf) -> Vec<Finding> {
+    let referenced = product_id_references(csaf)
+        .into_iter()
+        .map(|(_, id)| id)
+        .collect::<HashSet<_>>();
+
+    product_id_definitions(csaf)
+        .into_iter()
+        .filter(|(_, id)| !referenced.contains(id))
+        .map(|(path, id)| {
+            Finding::new(format!("Unused definition of product ID '{id}'")).path(path)
+        })
+        .collect()
+}
+
+/// 6.2.2 Missing Remediation
+pub fn check_missing_remediation(csaf: &Csaf) -> Vec<Finding> {
+    let groups = group_members(csaf);
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let covered = vulnerability
+            .remediations
+            .iter()
+            .flatten()
+            .flat_map(|remediation| {
+                resolve_products(
+                    &groups,
+                    remediation.product_ids.as_ref(),
+                    remediation.group_ids.as_ref(),
+                )
+            })
+            .collect::<HashSet<_>>();
+
+        let products = products_with_status(vulnerability, |status| {
+            vec![
+                &status.first_affected,
+                &status.known_affected,
+                &status.last_affected,
+                &status.under_investigation,
+            ]
+        });
+
+        for id in products {
+            if !covered.contains(id) {
+                result.push(
+                    Finding::new(format!("Missing remediation for product ID '{id}'"))
+                        .path(format!("/vulnerabilities/{i}/remediations")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.2.3 Missing Score
+pub fn check_missing_score(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let covered = vulnerability
+            .scores
+            .iter()
+            .flatten()
+            .flat_map(|score| score.products.iter().map(|id| id.0.as_str()))
+            .collect::<HashSet<_>>();
+
+        let products = products_with_status(vulnerability, |status| {
+            vec![
+                &status.first_affected,
+                &status.known_affected,
+                &status.last_affected,
+            ]
+        });
+
+        for id in products {
+            if !covered.contains(id) {
+                result.push(
+                    Finding::new(format!("Missing score for product ID '{id}'"))
+                        .path(format!("/vulnerabilities/{i}/scores")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.2.4 Build Metadata in Revision History
+pub fn check_build_metadata_in_revision_history(csaf: &Csaf) -> Vec<Finding> {
+    csaf.document
+        .tracking
+        .revision_history
+        .iter()
+        .enumerate()
+        .filter(|(_, revision)| {
+            matches!(Version::parse(&revision.number), Some(Version::Semantic(version)) if !version.build.is_empty())
+        })
+        .map(|(i, revision)| {
+            Finding::new(format!("Build metadata in revision {}", revision.number)).path(format!("/document/tracking/revision_history/{i}/number"))
+        })
+        .collect()
+}
+
+/// 6.2.5 Older Initial Release Date than Revision History
+pub fn check_older_initial_release_date(csaf: &Csaf) -> Vec<Finding> {
+    let tracking = &csaf.document.tracking;
+    match tracking.revision_history.iter().map(|r| r.date).min() {
+        Some(oldest) if tracking.initial_release_date < oldest => vec![
+            Finding::new(format!(
+                "Initial release date ({}) is older than the oldest revision ({oldest})",
+                tracking.initial_release_date
+            ))
+            .path("/document/tracking/initial_release_date"),
+        ],
+        _ => vec![],
+    }
+}
+
+/// 6.2.6 Older Current Release Date than Revision History
+pub fn check_older_current_release_date(csaf: &Csaf) -> Vec<Finding> {
+    let tracking = &csaf.document.tracking;
+    match tracking.revision_history.iter().map(|r| r.date).max() {
+        Some(newest) if tracking.current_release_date < newest => vec![
+            Finding::new(format!(
+                "Current release date ({}) is older than the newest revision ({newest})",
+                tracking.current_release_date
+            ))
+            .path("/document/tracking/current_release_date"),
+        ],
+        _ => vec![],
+    }
+}
+
+/// 6.2.7 Missing Date in Involvements
+pub fn check_missing_involvement_date(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, involvement) in vulnerability.involvements.iter().flatten().enumerate() {
+            if involvement.date.is_none() {
+                result.push(
+                    Finding::new("Missing date in involvement")
+                        .path(format!("/vulnerabilities/{i}/involvements/{j}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// Find hashes only using a weak algorithm.
+fn weak_hashes(csaf: &Csaf, algorithm: &str) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (path, product) in full_product_names(csaf) {
+        let hashes = product
+            .product_identification_helper
+            .iter()
+            .flat_map(|helper| helper.hashes.iter().flatten());
+
+        for (i, hash) in hashes.enumerate() {
+            if !hash.file_hashes.is_empty()
+                && hash
+                    .file_hashes
+                    .iter()
+                    .all(|value| value.algorithm.eq_ignore_ascii_case(algorithm))
+            {
+                result.push(
+                    Finding::new(format!("Use of {algorithm} as the only hash algorithm"))
+                        .path(format!("{path}/product_identification_helper/hashes/{i}")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.2.8 Use of MD5 as the only Hash Algorithm
+pub fn check_md5_only(csaf: &Csaf) -> Vec<Finding> {
+    weak_hashes(csaf, "md5")
+}
+
+/// 6.2.9 Use of SHA-1 as the only Hash Algorithm
+pub fn check_sha1_only(csaf: &Csaf) -> Vec<Finding> {
+    weak_hashes(csaf, "sha1")
+}
+
+/// 6.2.10 Missing TLP label
+pub fn check_missing_tlp_label(csaf: &Csaf) -> Vec<Finding> {
+    let ok = csaf
+        .document
+        .distribution
+        .as_ref()
+        .is_some_and(|distribution| distribution.tlp.is_some());
+
+    if !ok {
+        return vec![Finding::new("Missing TLP label").path("/document/distribution/tlp/label")];
+    }
+
+    vec![]
+}
+
+/// 6.2.11 Missing Canonical URL
+pub fn check_missing_canonical_url(csaf: &Csaf) -> Vec<Finding> {
+    let filename = document_filename(&csaf.document.tracking.id);
+
+    let ok = csaf
+        .document
+        .references
+        .iter()
+        .flatten()
+        .filter(|reference| matches!(reference.category, Some(ReferenceCategory::RefSelf)))
+        .any(|reference| {
+            reference.url.scheme() == "https"
+                && reference
+                    .url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    == Some(filename.as_str())
+        });
+
+    if !ok {
+        return vec![
+            Finding::new(format!("Missing canonical URL, expected a 'self' reference to an HTTPS URL ending with '{filename}'")).path("/document/references"),
+        ];
+    }
+
+    vec![]
+}
+
+/// 6.2.12 Missing Document Language
+pub fn check_missing_document_language(csaf: &Csaf) -> Vec<Finding> {
+    if csaf.document.lang.is_none() {
+        return vec![Finding::new("Missing document language").path("/document/lang")];
+    }
+
+    vec![]
+}
+
+/// Check if a language tag uses private use subtags.
+fn is_private_language(tag: &LanguageTag) -> bool {
+    let in_range = |value: Option<&str>, from: &str, to: &str| {
+        value.is_some_and(|value| {
+            let value = value.to_ascii_lowercase();
+            value.len() == from.len() && value.as_str() >= from && value.as_str() <= to
+        })
+    };
+
+    tag.private_use().is_some()
+        || in_range(Some(tag.primary_language()), "qaa", "qtz")
+        || in_range(tag.script(), "qaaa", "qabx")
+        || in_range(tag.region(), "qm", "qz")
+        || in_range(tag.region(), "xa", "xz")
+}
+
+/// 6.2.14 Use of Private Language
+pub fn check_private_language(csaf: &Csaf) -> Vec<Finding> {
+    [
+        ("lang", &csaf.document.lang),
+        ("source_lang", &csaf.document.source_lang),
+    ]
+    .into_iter()
+    .filter_map(|(name, lang)| lang.as_ref().map(|lang| (name, lang)))
+    .filter(|(_, lang)| LanguageTag::parse(lang).is_ok_and(|tag| is_private_language(&tag)))
+    .map(|(name, lang)| {
+        Finding::new(format!("Use of private language '{lang}'")).path(format!("/document/{name}"))
+    })
+    .collect()
+}
+
+/// 6.2.15 Use of Default Language
+pub fn check_default_language(csaf: &Csaf) -> Vec<Finding> {
+    [
+        ("lang", &csaf.document.lang),
+        ("source_lang", &csaf.document.source_lang),
+    ]
+    .into_iter()
+    .filter_map(|(name, lang)| lang.as_ref().map(|lang| (name, lang)))
+    .filter(|(_, lang)| lang.eq_ignore_ascii_case("i-default"))
+    .map(|(name, lang)| {
+        Finding::new(format!("Use of default language '{lang}'")).path(format!("/document/{name}"))
+    })
+    .collect()
+}
+
+/// 6.2.16 Missing Product Identification Helper
+pub fn check_missing_product_identification_helper(csaf: &Csaf) -> Vec<Finding> {
+    full_product_names(csaf)
+        .into_iter()
+        .filter(|(_, product)| product.product_identification_helper.is_none())
+        .map(|(path, product)| {
+            Finding::new(format!(
+                "Missing product identification helper for product ID '{}'",
+                product.product_id.0
+            ))
+            .path(path)
+        })
+        .collect()
+}
+
+/// 6.2.17 CVE in field IDs
+pub fn check_cve_in_ids(csaf: &Csaf) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, id) in vulnerability.ids.iter().flatten().enumerate() {
+            if CVE_ID.is_match(&id.text) {
+                result.push(
+                    Finding::new(format!(
+                        "CVE '{}' should be provided using the 'cve' field",
+                        id.text
+                    ))
+                    .path(format!("/vulnerabilities/{i}/ids/{j}/text")),
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.2.18 Product Version Range without vers
+pub fn check_version_range_without_vers(csaf: &Csaf) -> Vec<Finding> {
+    fn walk(path: String, branches: &BranchesT, result: &mut Vec<Finding>) {
+        for (i, branch) in branches.0.iter().enumerate() {
+            let path = format!("{path}/{i}");
+            if matches!(branch.category, BranchCategory::ProductVersionRange)
+                && !branch.name.starts_with("vers:")
+            {
+                result.push(
+                    Finding::new(format!(
+                        "Product version range '{}' doesn't use vers",
+                        branch.name
+                    ))
+                    .path(format!("{path}/name")),
+                );
+            }
+            if let Some(children) = &branch.branches {
+                walk(format!("{path}/branches"), children, result);
+            }
+        }
+    }
+
+    let mut result = vec![];
+    if let Some(children) = csaf
+        .product_tree
+        .as_ref()
+        .and_then(|tree| tree.branches.as_ref())
+    {
+        walk("/product_tree/branches".into(), children, &mut result);
+    }
+
+    result
+}
+
+/// 6.2.19 CVSS for Fixed Products
+pub fn check_cvss_for_fixed_products(csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        let fixed = products_with_status(vulnerability, |status| {
+            vec![&status.first_fixed, &status.fixed]
+        })
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+        for (j, score) in vulnerability.scores.iter().flatten().enumerate() {
+            if !score
+                .products
+                .iter()
+                .any(|id| fixed.contains(id.0.as_str()))
+            {
+                continue;
+            }
+
+            for kind in ["cvss_v2", "cvss_v3"] {
+                let path = format!("/vulnerabilities/{i}/scores/{j}/{kind}");
+                let Some(cvss) = json.pointer(&path) else {
+                    continue;
+                };
+                if cvss.get("environmentalScore").and_then(Value::as_f64) != Some(0.0) {
+                    result.push(
+                        Finding::new(
+                            "CVSS for a fixed product must have an environmental score of 0",
+                        )
+                        .path(format!("{path}/environmentalScore")),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 6.2.13 Sorting
+///
+/// The order of keys is not retained by [`Value`], so this test scans the original data of the
+/// document. Documents which can't be parsed are not reported.
+pub fn check_sorting(_csaf: &Csaf, data: &[u8]) -> Vec<Finding> {
+    let mut result = vec![];
+    let _ = SortedKeys {
+        path: String::new(),
+        result: &mut result,
+    }
+    .deserialize(&mut serde_json::Deserializer::from_slice(data));
+    result
+}
+
+/// Walks a JSON value, reporting objects with keys which are not sorted alphabetically
+struct SortedKeys<'r> {
+    /// The JSON pointer of the value
+    path: String,
+    result: &'r mut Vec<Finding>,
+}
+
+impl<'de> DeserializeSeed<'de> for SortedKeys<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SortedKeys<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut i = 0;
+        while seq
+            .next_element_seed(SortedKeys {
+                path: format!("{}/{i}", self.path),
+                result: self.result,
+            })?
+            .is_some()
+        {
+            i += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut previous: Option<String> = None;
+        let mut sorted = true;
+        let mut nested = vec![];
+
+        while let Some(key) = map.next_key::<String>()? {
+            if previous.as_ref().is_some_and(|previous| previous > &key) {
+                sorted = false;
+            }
+            let path = format!(
+                "{}/{}",
+                self.path,
+                key.replace('~', "~0").replace('/', "~1")
+            );
+            map.next_value_seed(SortedKeys {
+                path,
+                result: &mut nested,
+            })?;
+            previous = Some(key);
+        }
+
+        if !sorted {
+            let path = if self.path.is_empty() {
+                "/".to_string()
+            } else {
+                self.path
+            };
+            self.result
+                .push(Finding::new("Keys are not sorted alphabetically").path(path));
+        }
+        self.result.append(&mut nested);
+
+        Ok(())
+    }
+}
+
+/// 6.2.20 Additional Properties
+///
+/// Reports the properties which are not defined by the JSON schema. Unlike the (strict) schema
+/// check, other violations of the schema are not reported.
+pub fn check_additional_properties(_csaf: &Csaf, json: &Value) -> Vec<Finding> {
+    schema::validate(json)
+        .into_iter()
+        .filter(|violation| violation.keyword == "additionalProperties")
+        .map(|violation| Finding::new(violation.message).path(violation.path))
+        .collect()
+}
+
+pub fn init_optional_checks() -> Vec<(&'static str, Box<dyn Check>)> {
+    vec![
+        ("6.2.1", Box::new(check_unused_product_id)),
+        ("6.2.2", Box::new(check_missing_remediation)),
+        ("6.2.3", Box::new(check_missing_score)),
+        ("6.2.4", Box::new(check_build_metadata_in_revision_history)),
+        ("6.2.5", Box::new(check_older_initial_release_date)),
+        ("6.2.6", Box::new(check_older_current_release_date)),
+        ("6.2.7", Box::new(check_missing_involvement_date)),
+        ("6.2.8", Box::new(check_md5_only)),
+        ("6.2.9", Box::new(check_sha1_only)),
+        ("6.2.10", Box::new(check_missing_tlp_label)),
+        ("6.2.11", Box::new(check_missing_canonical_url)),
+        ("6.2.12", Box::new(check_missing_document_language)),
+        ("6.2.13", Box::new(DataCheck(check_sorting))),
+        ("6.2.14", Box::new(check_private_language)),
+        ("6.2.15", Box::new(check_default_language)),
+        (
+            "6.2.16",
+            Box::new(check_missing_product_identification_helper),
+        ),
+        ("6.2.17", Box::new(check_cve_in_ids)),
+        ("6.2.18", Box::new(check_version_range_without_vers)),
+        ("6.2.19", Box::new(JsonCheck(check_cvss_for_fixed_products))),
+        ("6.2.20", Box::new(JsonCheck(check_additional_properties))),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filename() {
+        assert_eq!(document_filename("RHSA-2023:1441"), "rhsa-2023_1441.json");
+        assert_eq!(document_filename("Example  ID/1"), "example_id_1.json");
+    }
+
+    #[tokio::test]
+    async fn example_data() {
+        let data = include_bytes!("../../../../test-data/rhsa-2023_1441.json");
+        let json: Value = serde_json::from_slice(data).expect("example data must parse");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+        let mut failed = vec![];
+        for (id, check) in init_optional_checks() {
+            let result = check
+                .findings_data(&csaf, &json, data)
+                .await
+                .expect("check must not fail");
+            if !result.is_empty() {
+                failed.push(id);
+            }
+        }
+
+        // Red Hat lists the known affected products before the fixed ones, and only provides purls
+        // or CPEs for some products
+        assert_eq!(failed, vec!["6.2.13", "6.2.16"]);
+    }
+
+    #[test]
+    fn sorting_and_additional_properties() {
+        let data = include_bytes!("../../../../test-data/rhsa-2023_1441.json");
+        let mut json: Value = serde_json::from_slice(data).expect("example data must parse");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+        assert_eq!(
+            check_sorting(&csaf, data),
+            vec![
+                Finding::new("Keys are not sorted alphabetically")
+                    .path("/vulnerabilities/0/product_status")
+            ]
+        );
+        assert!(check_additional_properties(&csaf, &json).is_empty());
+
+        json["document"]["tracking"]["unknown"] = Value::Bool(true);
+        json["document"]["title"] = Value::Null;
+
+        // the invalid title is only reported by the schema check
+        assert_eq!(
+            check_additional_properties(&csaf, &json),
+            vec![
+                Finding::new("Additional property 'unknown' is not allowed")
+                    .path("/document/tracking/unknown")
+            ]
+        );
+    }
+
+    #[test]
+    fn sorting() {
+        let csaf: Csaf =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2023_1441.json"))
+                .expect("example data must parse");
+
+        assert!(
+            check_sorting(&csaf, br#"{"a": [{"b": 1, "c/d": {"e": null, "f": "x"}}]}"#).is_empty()
+        );
+        assert_eq!(
+            check_sorting(
+                &csaf,
+                br#"{"b": [{"a": 1, "c/d": {"f": true, "e": 1.5}}], "a": {}}"#
+            ),
+            vec![
+                Finding::new("Keys are not sorted alphabetically").path("/"),
+                Finding::new("Keys are not sorted alphabetically").path("/b/0/c~1d"),
+            ]
+        );
+        // documents which can't be parsed are not reported
+        assert!(check_sorting(&csaf, b"{\"b\": 1, \"a\":").is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+//! Helpers for locating product (group) definitions and references in a CSAF document.
+//!
+//! Locations are reported as JSON pointers into the document.
+
+use csaf::{
+    Csaf,
+    definitions::{BranchesT, FullProductName, ProductIdT},
+    vulnerability::{ProductStatus, Vulnerability},
+};
+use std::collections::HashMap;
+
+/// All full product names defined in the product tree, along with their location.
+pub(crate) fn full_product_names(csaf: &Csaf) -> Vec<(String, &FullProductName)> {
+    fn walk<'a>(
+        path: String,
+        branches: &'a BranchesT,
+        result: &mut Vec<(String, &'a FullProductName)>,
+    ) {
+        for (i, branch) in branches.0.iter().enumerate() {
+            let path = format!("{path}/{i}");
+            if let Some(product) = &branch.product {
+                result.push((format!("{path}/product"), product));
+            }
+            if let Some(children) = &branch.branches {
+                walk(format!("{path}/branches"), children, result);
+            }
+        }
+    }
+
+    let mut result = vec![];
+
+    let Some(tree) = &csaf.product_tree else {
+        return result;
+    };
+
+    if let Some(children) = &tree.branches {
+        walk("/product_tree/branches".into(), children, &mut result);
+    }
+
+    for (i, product) in tree.full_product_names.iter().flatten().enumerate() {
+        result.push((format!("/product_tree/full_product_names/{i}"), product));
+    }
+
+    for (i, relationship) in tree.relationships.iter().flatten().enumerate() {
+        result.push((
+            format!("/product_tree/relationships/{i}/full_product_name"),
+            &relationship.full_product_name,
+        ));
+    }
+
+    result
+}
+
+/// All product ID definitions, along with the location of the `product_id` field.
+pub(crate) fn product_id_definitions(csaf: &Csaf) -> Vec<(String, &str)> {
+    full_product_names(csaf)
+        .into_iter()
+        .map(|(path, product)| (format!("{path}/product_id"), product.product_id.0.as_str()))
+        .collect()
+}
+
+/// The fields of the product status, by name.
+pub(crate) fn product_status_fields(
+    status: &ProductStatus,
+) -> [(&'static str, Option<&Vec<ProductIdT>>); 8] {
+    [
+        ("first_affected", status.first_affected.as_ref()),
+        ("first_fixed", status.first_fixed.as_ref()),
+        ("fixed", status.fixed.as_ref()),
+        ("known_affected", status.known_affected.as_ref()),
+        ("known_not_affected", status.known_not_affected.as_ref()),
+        ("last_affected", status.last_affected.as_ref()),
+        ("recommended", status.recommended.as_ref()),
+        ("under_investigation", status.under_investigation.as_ref()),
+    ]
+}
+
+fn push_ids<'a>(
+    result: &mut Vec<(String, &'a str)>,
+    path: String,
+    ids: Option<&'a Vec<ProductIdT>>,
+) {
+    for (i, id) in ids.into_iter().flatten().enumerate() {
+        result.push((format!("{path}/{i}"), id.0.as_str()));
+    }
+}
+
+/// Product IDs referenced by a vulnerability, along with their location.
+pub(crate) fn vulnerability_product_id_references<'a>(
+    path: &str,
+    vulnerability: &'a Vulnerability,
+) -> Vec<(String, &'a str)> {
+    let mut result = vec![];
+
+    if let Some(status) = &vulnerability.product_status {
+        for (name, ids) in product_status_fields(status) {
+            push_ids(&mut result, format!("{path}/product_status/{name}"), ids);
+        }
+    }
+
+    for (i, remediation) in vulnerability.remediations.iter().flatten().enumerate() {
+        push_ids(
+            &mut result,
+            format!("{path}/remediations/{i}/product_ids"),
+            remediation.product_ids.as_ref(),
+        );
+    }
+
+    for (i, score) in vulnerability.scores.iter().flatten().enumerate() {
+        push_ids(
+            &mut result,
+            format!("{path}/scores/{i}/products"),
+            Some(&score.products),
+        );
+    }
+
+    for (i, threat) in vulnerability.threats.iter().flatten().enumerate() {
+        push_ids(
+            &mut result,
+            format!("{path}/threats/{i}/product_ids"),
+            threat.product_ids.as_ref(),
+        );
+    }
+
+    for (i, flag) in vulnerability.flags.iter().flatten().enumerate() {
+        push_ids(
+            &mut result,
+            format!("{path}/flags/{i}/product_ids"),
+            flag.product_ids.as_ref(),
+        );
+    }
+
+    result
+}
+
+/// All product IDs referenced in the document, along with their location.
+pub(crate) fn product_id_references(csaf: &Csaf) -> Vec<(String, &str)> {
+    let mut result = vec![];
+
+    if let Some(tree) = &csaf.product_tree {
+        for (i, group) in tree.product_groups.iter().flatten().enumerate() {
+            push_ids(
+                &mut result,
+                format!("/product_tree/product_groups/{i}/product_ids"),
+                Some(&group.product_ids),
+            );
+        }
+
+        for (i, relationship) in tree.relationships.iter().flatten().enumerate() {
+            result.push((
+                format!("/product_tree/relationships/{i}/product_reference"),
+                relationship.product_reference.0.as_str(),
+            ));
+            result.push((
+                format!("/product_tree/relationships/{i}/relates_to_product_reference"),
+                relationship.relates_to_product_reference.0.as_str(),
+            ));
+        }
+    }
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        result.extend(vulnerability_product_id_references(
+            &format!("/vulnerabilities/{i}"),
+            vulnerability,
+        ));
+    }
+
+    result
+}
+
+/// All product group ID definitions, along with the location of the `group_id` field.
+pub(crate) fn group_id_definitions(csaf: &Csaf) -> Vec<(String, &str)> {
+    csaf.product_tree
+        .iter()
+        .flat_map(|tree| tree.product_groups.iter().flatten())
+        .enumerate()
+        .map(|(i, group)| {
+            (
+                format!("/product_tree/product_groups/{i}/group_id"),
+                group.group_id.as_str(),
+            )
+        })
+        .collect()
+}
+
+/// All product group IDs referenced in the document, along with their location.
+pub(crate) fn group_id_references(csaf: &Csaf) -> Vec<(String, &str)> {
+    fn push<'a>(result: &mut Vec<(String, &'a str)>, path: String, ids: Option<&'a Vec<String>>) {
+        for (i, id) in ids.into_iter().flatten().enumerate() {
+            result.push((format!("{path}/{i}"), id.as_str()));
+        }
+    }
+
+    let mut result = vec![];
+
+    for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+        for (j, remediation) in vulnerability.remediations.iter().flatten().enumerate() {
+            push(
+                &mut result,
+                format!("/vulnerabilities/{i}/remediations/{j}/group_ids"),
+                remediation.group_ids.as_ref(),
+            );
+        }
+        for (j, threat) in vulnerability.threats.iter().flatten().enumerate() {
+            push(
+                &mut result,
+                format!("/vulnerabilities/{i}/threats/{j}/group_ids"),
+                threat.group_ids.as_ref(),
+            );
+        }
+        for (j, flag) in vulnerability.flags.iter().flatten().enumerate() {
+            push(
+                &mut result,
+                format!("/vulnerabilities/{i}/flags/{j}/group_ids"),
+                flag.group_ids.as_ref(),
+            );
+        }
+    }
+
+    result
+}
+
+/// The members of each product group, by group ID.
+pub(crate) fn group_members(csaf: &Csaf) -> HashMap<&str, Vec<&str>> {
+    csaf.product_tree
+        .iter()
+        .flat_map(|tree| tree.product_groups.iter().flatten())
+        .map(|group| {
+            (
+                group.group_id.as_str(),
+                group.product_ids.iter().map(|id| id.0.as_str()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Resolve product IDs and product group IDs into a list of product IDs.
+pub(crate) fn resolve_products<'a>(
+    groups: &HashMap<&str, Vec<&'a str>>,
+    product_ids: Option<&'a Vec<ProductIdT>>,
+    group_ids: Option<&'a Vec<String>>,
+) -> Vec<&'a str> {
+    let mut result: Vec<&str> = product_ids
+        .into_iter()
+        .flatten()
+        .map(|id| id.0.as_str())
+        .collect();
+
+    for group in group_ids.into_iter().flatten() {
+        if let Some(members) = groups.get(group.as_str()) {
+            result.extend(members);
+        }
+    }
+
+    result.sort_unstable();
+    result.dedup();
+    result
+}
@@ -0,0 +1,423 @@
+//! Declarative checks, using JSONPath expressions.
+//!
+//! A rule selects values of the document using a JSONPath expression (`path`, defaulting to the
+//! document root), and requires a condition for them:
+//!
+//! * `exists`: a JSONPath expression, evaluated against each selected value, which must match
+//! * `matches`: a regular expression, which each selected value must be a matching string of
+//! * `count`: the number of selected values must be within `min` and `max`
+//!
+//! Rules are loaded from JSON, YAML, or TOML files. Each rule is run as a check on its own,
+//! registered using the ID of the rule, so that it can be configured like any other check.
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     {
+//!       "id": "product-purl",
+//!       "message": "Product without a PURL",
+//!       "severity": "warning",
+//!       "path": "$.product_tree..product",
+//!       "exists": "$.product_identification_helper.purl",
+//!       "hint": "Add a PURL to the product identification helper"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::verification::check::{Check, CheckError, Finding, Severity};
+use anyhow::{Context, bail};
+use async_trait::async_trait;
+use csaf::Csaf;
+use jsonpath_rust::{
+    JsonPath,
+    parser::{
+        model::{Segment, Selector},
+        parse_json_path,
+    },
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{borrow::Cow, path::Path};
+
+fn default_path() -> String {
+    "$".to_string()
+}
+
+/// A condition, required by a rule
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Condition {
+    /// A JSONPath expression, which must match for each selected value
+    Exists(String),
+    /// A regular expression, which each selected value must match
+    Matches(String),
+    /// The number of selected values
+    Count {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<usize>,
+    },
+}
+
+/// A declarative rule
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    /// The ID of the rule, reported with its findings
+    pub id: String,
+    /// The message of findings
+    pub message: String,
+    #[serde(default)]
+    pub severity: Severity,
+    /// A JSONPath expression, selecting the values to check
+    #[serde(default = "default_path")]
+    pub path: String,
+    #[serde(flatten)]
+    pub condition: Condition,
+    /// A hint on how to fix findings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// A set of rules
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSet {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Load a rule set from a file.
+    ///
+    /// The format is chosen by the extension of the file: `.yaml` or `.yml` for YAML, `.toml` for
+    /// TOML, and JSON otherwise.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to open rules file: {}", path.display()))?;
+
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("yaml" | "yml") => serde_yaml::from_str(&data).map_err(anyhow::Error::from),
+            Some("toml") => toml::from_str(&data).map_err(anyhow::Error::from),
+            _ => serde_json::from_str(&data).map_err(anyhow::Error::from),
+        }
+        .with_context(|| format!("Failed to parse rules file: {}", path.display()))
+    }
+
+    /// Compile the rules into checks, one per rule, failing on invalid expressions.
+    pub fn compile(self) -> anyhow::Result<Vec<RuleCheck>> {
+        self.rules
+            .into_iter()
+            .map(|rule| {
+                let id = rule.id.clone();
+                RuleCheck::new(rule).with_context(|| format!("Invalid rule: {id}"))
+            })
+            .collect()
+    }
+}
+
+/// A check, evaluating a single rule
+#[derive(Debug)]
+pub struct RuleCheck {
+    rule: Rule,
+    pattern: Option<Regex>,
+}
+
+impl RuleCheck {
+    /// Compile a rule, failing on invalid expressions.
+    pub fn new(rule: Rule) -> anyhow::Result<Self> {
+        parse_json_path(&rule.path)
+            .with_context(|| format!("Invalid JSONPath expression: {}", rule.path))?;
+
+        let pattern = match &rule.condition {
+            Condition::Exists(path) => {
+                parse_json_path(path)
+                    .with_context(|| format!("Invalid JSONPath expression: {path}"))?;
+                None
+            }
+            Condition::Matches(pattern) => Some(
+                Regex::new(pattern)
+                    .with_context(|| format!("Invalid regular expression: {pattern}"))?,
+            ),
+            Condition::Count { min, max } => {
+                if let (Some(min), Some(max)) = (min, max) {
+                    if min > max {
+                        bail!("Minimum count ({min}) is greater than maximum count ({max})");
+                    }
+                }
+                None
+            }
+        };
+
+        Ok(Self { rule, pattern })
+    }
+
+    /// The ID of the rule
+    pub fn id(&self) -> &str {
+        &self.rule.id
+    }
+
+    fn finding(&self, path: Option<String>, detail: Option<String>) -> Finding {
+        let message: Cow<'static, str> = match detail {
+            Some(detail) => format!("{}: {detail}", self.rule.message).into(),
+            None => self.rule.message.clone().into(),
+        };
+
+        let mut finding = Finding::new(message)
+            .rule(self.rule.id.clone())
+            .severity(self.rule.severity);
+        finding.path = path;
+        finding.hint = self.rule.hint.clone().map(Into::into);
+        finding
+    }
+
+    fn evaluate(&self, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        let selected = json
+            .query_with_path(&self.rule.path)
+            .with_context(|| format!("Failed to evaluate rule: {}", self.rule.id))?;
+
+        let mut result = vec![];
+
+        match &self.rule.condition {
+            Condition::Exists(path) => {
+                for selected in selected {
+                    let location = to_pointer(&selected.clone().path());
+                    if selected.val().query(path)?.is_empty() {
+                        result.push(self.finding(location, None));
+                    }
+                }
+            }
+            Condition::Matches(_) => {
+                let pattern = self.pattern.as_ref().expect("compiled with pattern");
+                for selected in selected {
+                    let location = to_pointer(&selected.clone().path());
+                    let ok = selected
+                        .val()
+                        .as_str()
+                        .is_some_and(|value| pattern.is_match(value));
+                    if !ok {
+                        result.push(self.finding(location, None));
+                    }
+                }
+            }
+            Condition::Count { min, max } => {
+                let count = selected.len();
+                if min.is_some_and(|min| count < min) || max.is_some_and(|max| count > max) {
+                    result.push(self.finding(None, Some(format!("found {count} value(s)"))));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Convert a normalized JSONPath expression (like `$['a'][0]`) into a JSON pointer.
+fn to_pointer(path: &str) -> Option<String> {
+    let query = parse_json_path(path).ok()?;
+
+    let mut result = String::new();
+    for segment in query.segments {
+        match segment {
+            Segment::Selector(Selector::Name(name)) => {
+                let name = name
+                    .strip_prefix('\'')
+                    .and_then(|name| name.strip_suffix('\''))
+                    .unwrap_or(&name);
+                result.push('/');
+                result.push_str(&name.replace('~', "~0").replace('/', "~1"));
+            }
+            Segment::Selector(Selector::Index(index)) => {
+                result.push_str(&format!("/{index}"));
+            }
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
+#[async_trait(?Send)]
+impl Check for RuleCheck {
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        self.check_json(csaf, &serde_json::to_value(csaf)?).await
+    }
+
+    async fn check_json(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        Ok(self
+            .findings(csaf, json)
+            .await?
+            .into_iter()
+            .map(|finding| finding.to_string().into())
+            .collect())
+    }
+
+    async fn findings(&self, _csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        self.evaluate(json)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pointer() {
+        assert_eq!(
+            to_pointer("$['document']['references'][1]").as_deref(),
+            Some("/document/references/1")
+        );
+        assert_eq!(to_pointer("$['a/b']").as_deref(), Some("/a~1b"));
+        assert_eq!(to_pointer("$").as_deref(), Some(""));
+    }
+
+    #[tokio::test]
+    async fn rules() {
+        let json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("example data must parse");
+
+        let rules: RuleSet = serde_json::from_value(json!({
+            "rules": [
+                {
+                    "id": "remediation-url",
+                    "message": "Remediation without URL",
+                    "path": "$.vulnerabilities[*].remediations[*]",
+                    "exists": "$.url"
+                },
+                {
+                    "id": "tracking-id",
+                    "message": "Unexpected tracking ID",
+                    "severity": "warning",
+                    "path": "$.document.tracking.id",
+                    "matches": "^EXAMPLE-",
+                    "hint": "Use the EXAMPLE prefix"
+                },
+                {
+                    "id": "single-vulnerability",
+                    "message": "Expected a single vulnerability",
+                    "path": "$.vulnerabilities[*]",
+                    "count": { "max": 1 }
+                }
+            ]
+        }))
+        .expect("example rules must parse");
+
+        let checks = rules.compile().expect("example rules must compile");
+        assert_eq!(
+            checks.iter().map(RuleCheck::id).collect::<Vec<_>>(),
+            ["remediation-url", "tracking-id", "single-vulnerability"]
+        );
+
+        let mut findings = vec![];
+        for check in checks {
+            findings.extend(
+                check
+                    .findings(&csaf, &json)
+                    .await
+                    .expect("rules must evaluate"),
+            );
+        }
+
+        assert_eq!(
+            findings,
+            vec![
+                Finding::new("Unexpected tracking ID")
+                    .rule("tracking-id")
+                    .severity(Severity::Warning)
+                    .path("/document/tracking/id")
+                    .hint("Use the EXAMPLE prefix"),
+                Finding::new("Expected a single vulnerability: found 9 value(s)")
+                    .rule("single-vulnerability")
+                    .severity(Severity::Error),
+            ]
+        );
+    }
+
+    /// The rule of the example files
+    fn product_purl() -> RuleSet {
+        RuleSet {
+            rules: vec![Rule {
+                id: "product-purl".into(),
+                message: "Product without a PURL".into(),
+                severity: Severity::Warning,
+                path: "$.product_tree..product".into(),
+                condition: Condition::Exists("$.product_identification_helper.purl".into()),
+                hint: None,
+            }],
+        }
+    }
+
+    /// Load a rule set from a temporary file
+    fn load(name: &str, content: &str) -> RuleSet {
+        let base = std::env::temp_dir().join(format!("walker-rules-{}", std::process::id()));
+        std::fs::create_dir_all(&base).expect("must create directory");
+        let path = base.join(name);
+        std::fs::write(&path, content).expect("must write rules file");
+
+        let result = RuleSet::load(&path);
+        let _ = std::fs::remove_file(&path);
+        result.expect("rules file must load")
+    }
+
+    #[test]
+    fn load_json() {
+        let rules = load(
+            "rules.json",
+            r#"{"rules": [{"id": "product-purl", "message": "Product without a PURL", "severity": "warning", "path": "$.product_tree..product", "exists": "$.product_identification_helper.purl"}]}"#,
+        );
+        assert_eq!(rules, product_purl());
+    }
+
+    #[test]
+    fn load_yaml() {
+        let rules = load(
+            "rules.yml",
+            r#"
+rules:
+  - id: product-purl
+    message: Product without a PURL
+    severity: warning
+    path: "$.product_tree..product"
+    exists: "$.product_identification_helper.purl"
+"#,
+        );
+        assert_eq!(rules, product_purl());
+    }
+
+    #[test]
+    fn load_toml() {
+        let rules = load(
+            "rules.toml",
+            r#"
+[[rules]]
+id = "product-purl"
+message = "Product without a PURL"
+severity = "warning"
+path = "$.product_tree..product"
+exists = "$.product_identification_helper.purl"
+"#,
+        );
+        assert_eq!(rules, product_purl());
+    }
+
+    #[test]
+    fn invalid() {
+        let rules: RuleSet = serde_json::from_value(json!({
+            "rules": [{ "id": "broken", "message": "Broken", "path": "$[", "count": {} }]
+        }))
+        .expect("example rules must parse");
+
+        assert!(rules.compile().is_err());
+    }
+}
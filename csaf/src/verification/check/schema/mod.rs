@@ -0,0 +1,530 @@
+//! Native validation against the CSAF 2.0 JSON schema.
+//!
+//! The official (strict) CSAF 2.0 schema, as well as the referenced CVSS v2.0, v3.0, and v3.1
+//! schemas, are embedded (taken from `csaf-validator-lib`). Only the subset of JSON schema (draft 2020-12) used by those schemas is
+//! supported. Unknown keywords are ignored.
+
+use crate::verification::check::{Check, CheckError, Finding};
+use async_trait::async_trait;
+use csaf::Csaf;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    sync::LazyLock,
+};
+
+const CSAF_SCHEMA_ID: &str = "https://docs.oasis-open.org/csaf/csaf/v2.0/csaf_json_schema.json";
+
+const SCHEMAS: &[&str] = &[
+    include_str!("csaf_json_schema.json"),
+    include_str!("cvss-v2.0.json"),
+    include_str!("cvss-v3.0.json"),
+    include_str!("cvss-v3.1.json"),
+];
+
+/// The ID of the schema check, and the rule of its findings
+pub const RULE_SCHEMA: &str = "schema";
+
+static VALIDATOR: LazyLock<SchemaValidator> = LazyLock::new(|| SchemaValidator::new(SCHEMAS));
+
+/// A violation of the JSON schema
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// The JSON pointer of the offending value
+    pub path: String,
+    /// The keyword of the schema which was violated (e.g. `required`)
+    pub keyword: &'static str,
+    /// The description of the violation
+    pub message: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = match self.path.is_empty() {
+            true => "/",
+            false => &self.path,
+        };
+        write!(f, "{} ({path})", self.message)
+    }
+}
+
+/// Validate a document against the CSAF 2.0 JSON schema
+pub fn validate(json: &Value) -> Vec<Violation> {
+    VALIDATOR.validate(CSAF_SCHEMA_ID, json)
+}
+
+/// Validate a value against another (self-contained) JSON schema, like the one of the JSON report
+#[cfg(test)]
+pub(crate) fn validate_with(schema: &str, json: &Value) -> Vec<Violation> {
+    let validator = SchemaValidator::new(&[schema]);
+    let id = validator
+        .schemas
+        .keys()
+        .next()
+        .cloned()
+        .expect("schema must be present");
+    validator.validate(&id, json)
+}
+
+/// Check a document against the CSAF 2.0 JSON schema
+pub fn check_schema(_csaf: &Csaf, json: &Value) -> Vec<CheckError> {
+    validate(json)
+        .into_iter()
+        .map(|violation| violation.to_string().into())
+        .collect()
+}
+
+/// Check a document against the CSAF 2.0 JSON schema, reporting the JSON pointer of violations
+pub struct SchemaCheck;
+
+#[async_trait(?Send)]
+impl Check for SchemaCheck {
+    async fn check(&self, csaf: &Csaf) -> anyhow::Result<Vec<CheckError>> {
+        Ok(check_schema(csaf, &serde_json::to_value(csaf)?))
+    }
+
+    async fn check_json(&self, csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<CheckError>> {
+        Ok(check_schema(csaf, json))
+    }
+
+    async fn findings(&self, _csaf: &Csaf, json: &Value) -> anyhow::Result<Vec<Finding>> {
+        Ok(validate(json)
+            .into_iter()
+            .map(|violation| Finding::new(violation.message).path(violation.path))
+            .collect())
+    }
+}
+
+pub fn init_schema_checks() -> Vec<(&'static str, Box<dyn Check>)> {
+    vec![(RULE_SCHEMA, Box::new(SchemaCheck))]
+}
+
+/// Escape a property name for use in a JSON pointer
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Strip the query and fragment from a schema ID
+fn base_id(id: &str) -> &str {
+    id.split(['?', '#']).next().unwrap_or(id)
+}
+
+struct SchemaValidator {
+    /// schemas, by their ID (without the query part)
+    schemas: HashMap<String, Value>,
+    /// compiled patterns, by their source
+    patterns: HashMap<String, Regex>,
+}
+
+impl SchemaValidator {
+    fn new(sources: &[&str]) -> Self {
+        let mut schemas = HashMap::new();
+        let mut patterns = HashMap::new();
+
+        for schema in sources {
+            let schema: Value = serde_json::from_str(schema).expect("embedded schema must parse");
+            collect_patterns(&schema, &mut patterns);
+            let id = schema["$id"]
+                .as_str()
+                .expect("embedded schema must have an ID");
+            schemas.insert(base_id(id).to_string(), schema);
+        }
+
+        Self { schemas, patterns }
+    }
+
+    /// Validate a value against the schema with the ID
+    fn validate(&self, id: &str, json: &Value) -> Vec<Violation> {
+        let mut context = Context {
+            validator: self,
+            violations: vec![],
+        };
+        context.validate(id, &self.schemas[id], json, "");
+        context.violations
+    }
+
+    /// Resolve a reference, in the context of the schema document `base`
+    fn resolve<'a>(&'a self, base: &'a str, reference: &'a str) -> Option<(&'a str, &'a Value)> {
+        let (document, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let document = match document {
+            "" => base,
+            document => base_id(document),
+        };
+
+        let (id, schema) = self.schemas.get_key_value(document)?;
+        Some((id.as_str(), schema.pointer(fragment)?))
+    }
+}
+
+/// Compile all patterns of a schema
+fn collect_patterns(schema: &Value, patterns: &mut HashMap<String, Regex>) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("pattern", Value::String(pattern)) => {
+                        patterns.entry(pattern.clone()).or_insert_with(|| {
+                            Regex::new(pattern).expect("embedded pattern must compile")
+                        });
+                    }
+                    ("enum" | "const" | "examples" | "default", _) => {}
+                    _ => collect_patterns(value, patterns),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_patterns(value, patterns);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value
+            .as_f64()
+            .is_some_and(|value| value.fract() == 0.0 && value.is_finite()),
+        name => type_name(value) == name,
+    }
+}
+
+fn is_format(value: &str, format: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        "uri" => url::Url::parse(value).is_ok(),
+        // unknown formats are annotations only
+        _ => true,
+    }
+}
+
+struct Context<'v> {
+    validator: &'v SchemaValidator,
+    violations: Vec<Violation>,
+}
+
+impl Context<'_> {
+    fn report(&mut self, path: &str, keyword: &'static str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            path: path.to_string(),
+            keyword,
+            message: message.into(),
+        });
+    }
+
+    /// Validate a value against a schema, `base` being the ID of the schema document
+    fn validate(&mut self, base: &str, schema: &Value, value: &Value, path: &str) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                self.report(path, "false", "Value is not allowed");
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match self.validator.resolve(base, reference) {
+                Some((base, schema)) => self.validate(base, schema, value, path),
+                None => self.report(
+                    path,
+                    "$ref",
+                    format!("Unresolvable schema reference '{reference}'"),
+                ),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let ok = match expected {
+                Value::String(name) => is_type(value, name),
+                Value::Array(names) => names
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|name| is_type(value, name)),
+                _ => true,
+            };
+            if !ok {
+                self.report(
+                    path,
+                    "type",
+                    format!(
+                        "Invalid type '{}', expected '{}'",
+                        type_name(value),
+                        match expected {
+                            Value::String(name) => name.clone(),
+                            other => other.to_string(),
+                        }
+                    ),
+                );
+                // further keywords would only produce follow-up errors
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                self.report(
+                    path,
+                    "enum",
+                    format!("Value {value} is not one of the allowed values"),
+                );
+            }
+        }
+
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                self.report(path, "const", format!("Value {value} must be {expected}"));
+            }
+        }
+
+        if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+            self.one_of(base, schemas, value, path);
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(base, schema, object, path),
+            Value::Array(items) => self.validate_array(base, schema, items, path),
+            Value::String(string) => self.validate_string(schema, string, path),
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                    if number < minimum {
+                        self.report(
+                            path,
+                            "minimum",
+                            format!("Value {number} is lower than {minimum}"),
+                        );
+                    }
+                }
+                if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                    if number > maximum {
+                        self.report(
+                            path,
+                            "maximum",
+                            format!("Value {number} is greater than {maximum}"),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn one_of(&mut self, base: &str, schemas: &[Value], value: &Value, path: &str) {
+        let results = schemas
+            .iter()
+            .map(|schema| {
+                let mut context = Context {
+                    validator: self.validator,
+                    violations: vec![],
+                };
+                context.validate(base, schema, value, path);
+                context.violations
+            })
+            .collect::<Vec<_>>();
+
+        match results
+            .iter()
+            .filter(|violations| violations.is_empty())
+            .count()
+        {
+            1 => {}
+            0 => {
+                // report the violations of the closest match
+                if let Some(closest) = results.into_iter().min_by_key(Vec::len) {
+                    self.violations.extend(closest);
+                }
+            }
+            n => self.report(
+                path,
+                "oneOf",
+                format!("Value must match exactly one schema, but matches {n}"),
+            ),
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        base: &str,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+    ) {
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(required) {
+                self.report(
+                    path,
+                    "required",
+                    format!("Missing required property '{required}'"),
+                );
+            }
+        }
+
+        if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+            if (object.len() as u64) < min {
+                self.report(
+                    path,
+                    "minProperties",
+                    format!("Object must have at least {min} properties"),
+                );
+            }
+        }
+        if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+            if (object.len() as u64) > max {
+                self.report(
+                    path,
+                    "maxProperties",
+                    format!("Object must have at most {max} properties"),
+                );
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+
+        for (name, value) in object {
+            let path = format!("{path}/{}", escape(name));
+            match (properties.and_then(|p| p.get(name)), additional) {
+                (Some(property), _) => self.validate(base, property, value, &path),
+                (None, Some(Value::Bool(false))) => self.report(
+                    &path,
+                    "additionalProperties",
+                    format!("Additional property '{name}' is not allowed"),
+                ),
+                (None, Some(additional)) => self.validate(base, additional, value, &path),
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn validate_array(
+        &mut self,
+        base: &str,
+        schema: &Map<String, Value>,
+        items: &[Value],
+        path: &str,
+    ) {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                self.report(
+                    path,
+                    "minItems",
+                    format!("Array must have at least {min} items"),
+                );
+            }
+        }
+
+        if schema.get("uniqueItems").and_then(Value::as_bool) == Some(true) {
+            for (i, item) in items.iter().enumerate() {
+                if let Some(first) = items[..i].iter().position(|other| other == item) {
+                    self.report(
+                        &format!("{path}/{i}"),
+                        "uniqueItems",
+                        format!("Duplicate item, equal to item {first}"),
+                    );
+                }
+            }
+        }
+
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                self.validate(base, item_schema, item, &format!("{path}/{i}"));
+            }
+        }
+    }
+
+    fn validate_string(&mut self, schema: &Map<String, Value>, string: &str, path: &str) {
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if (string.chars().count() as u64) < min {
+                self.report(
+                    path,
+                    "minLength",
+                    format!("String must have at least {min} characters"),
+                );
+            }
+        }
+
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            if let Some(regex) = self.validator.patterns.get(pattern) {
+                if !regex.is_match(string) {
+                    self.report(path, "pattern", "String doesn't match the required pattern");
+                }
+            }
+        }
+
+        if let Some(format) = schema.get("format").and_then(Value::as_str) {
+            if !is_format(string, format) {
+                self.report(path, "format", format!("String is not a valid '{format}'"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn example_data() {
+        let json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+
+        assert_eq!(validate(&json), vec![]);
+    }
+
+    #[test]
+    fn violations() {
+        let mut json: Value =
+            serde_json::from_str(include_str!("../../../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+
+        json["document"]["unknown"] = json!(true);
+        json["document"]["tracking"]["current_release_date"] = json!("yesterday");
+        json["document"]["tracking"]["revision_history"] = json!([]);
+        json["vulnerabilities"][0]["scores"][0]["cvss_v3"]["baseScore"] = json!(11);
+        json["document"]
+            .as_object_mut()
+            .expect("must be an object")
+            .remove("title");
+
+        let mut paths = validate(&json)
+            .into_iter()
+            .map(|violation| violation.path)
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                "/document",
+                "/document/tracking/current_release_date",
+                "/document/tracking/revision_history",
+                "/document/unknown",
+                "/vulnerabilities/0/scores/0/cvss_v3/baseScore",
+            ]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Configuration of the checks run by the [`super::VerifyingVisitor`].
+//!
+//! A configuration selects the native [`Profile`], enables or disables individual checks, and
+//! overrides the severity of their findings. Check IDs may end with a `*`, matching all checks
+//! starting with the prefix (e.g. `6.3.*`).
+//!
+//! The default configuration can be refined per document category (e.g. `csaf_vex`) and per
+//! provider (domain). Those are applied in that order, later ones winning.
+//!
+//! The provider is the name of the provider of the walked source (see
+//! [`walker_common::utils::url::provider_name`]), like `redhat.com`. This is the same key the
+//! trust store and the OpenPGP policy overrides use.
+
+use crate::verification::check::{
+    Profile, Severity, identification::init_identification_checks,
+    informative::init_informative_checks, mandatory::init_mandatory_checks,
+    optional::init_optional_checks, schema::init_schema_checks,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::LazyLock,
+};
+
+/// The profile the native checks are part of, by check ID
+static PROFILES: LazyLock<HashMap<&'static str, Profile>> = LazyLock::new(|| {
+    [
+        (Profile::Schema, init_schema_checks()),
+        (Profile::Mandatory, init_mandatory_checks()),
+        (Profile::Optional, init_identification_checks()),
+        (Profile::Optional, init_optional_checks()),
+        (Profile::Informative, init_informative_checks()),
+    ]
+    .into_iter()
+    .flat_map(|(profile, checks)| checks.into_iter().map(move |(id, _)| (id, profile)))
+    .collect()
+});
+
+/// Check if a check ID matches a pattern (an ID, or a prefix ending with `*`).
+fn matches(pattern: &str, id: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => id.starts_with(prefix),
+        None => pattern == id,
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckConfig {
+    /// The profile of native checks to run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+
+    /// Checks to run, in addition to the profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enable: Vec<String>,
+
+    /// Checks to skip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+
+    /// Severity overrides, by check ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, Severity>,
+}
+
+/// A check configuration file, with overrides per document category and provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckConfigFile {
+    /// The default configuration
+    #[serde(flatten)]
+    pub default: CheckConfig,
+
+    /// Overrides, by document category (e.g. `csaf_vex`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, CheckConfig>,
+
+    /// Overrides, by provider name (e.g. `redhat.com`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, CheckConfig>,
+}
+
+impl CheckConfigFile {
+    /// Load a configuration from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open check configuration: {}", path.display()))?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse check configuration: {}", path.display()))
+    }
+
+    /// Get the effective configuration for a document.
+    pub fn resolve(&self, provider: Option<&str>, category: &str) -> EffectiveCheckConfig<'_> {
+        let layers = [Some(&self.default)]
+            .into_iter()
+            .chain([self.categories.get(category)])
+            .chain([provider.and_then(|provider| self.providers.get(provider))])
+            .flatten()
+            .collect();
+
+        EffectiveCheckConfig { layers }
+    }
+}
+
+/// The configuration for a single document.
+#[derive(Clone, Debug)]
+pub struct EffectiveCheckConfig<'c> {
+    layers: Vec<&'c CheckConfig>,
+}
+
+impl EffectiveCheckConfig<'_> {
+    /// The profile of native checks, if selected
+    pub fn profile(&self) -> Option<Profile> {
+        self.layers.iter().rev().find_map(|layer| layer.profile)
+    }
+
+    /// Check if a check should run.
+    ///
+    /// Explicitly enabled or disabled checks take precedence over the profile. Later configurations
+    /// win, and within a configuration, disabling wins. Checks which are not part of a native
+    /// profile are enabled by default.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        let explicit = self
+            .layers
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .enable
+                    .iter()
+                    .map(|pattern| (pattern, true))
+                    .chain(layer.disable.iter().map(|pattern| (pattern, false)))
+            })
+            .rev()
+            .find(|(pattern, _)| matches(pattern, id))
+            .map(|(_, enabled)| enabled);
+
+        if let Some(enabled) = explicit {
+            return enabled;
+        }
+
+        match (self.profile(), PROFILES.get(id)) {
+            (Some(selected), Some(profile)) => *profile <= selected,
+            _ => true,
+        }
+    }
+
+    /// The severity override for a check, an exact ID winning over the longest prefix.
+    pub fn severity(&self, id: &str) -> Option<Severity> {
+        self.layers.iter().rev().find_map(|layer| {
+            layer.severity.get(id).copied().or_else(|| {
+                layer
+                    .severity
+                    .iter()
+                    .filter(|(pattern, _)| pattern.ends_with('*') && matches(pattern, id))
+                    .max_by_key(|(pattern, _)| pattern.len())
+                    .map(|(_, severity)| *severity)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolve() {
+        let config: CheckConfigFile = serde_json::from_value(json!({
+            "profile": "optional",
+            "disable": ["6.2.16"],
+            "severity": { "6.2.*": "info", "6.2.1": "error" },
+            "categories": {
+                "csaf_vex": { "enable": ["6.3.*"], "disable": ["6.3.3"] }
+            },
+            "providers": {
+                "example.com": { "profile": "mandatory", "enable": ["6.2.16"] }
+            }
+        }))
+        .expect("example config must parse");
+
+        let default = config.resolve(None, "csaf_security_advisory");
+        assert!(default.is_enabled("6.1.1"));
+        assert!(default.is_enabled("6.2.1"));
+        assert!(!default.is_enabled("6.2.16"));
+        assert!(!default.is_enabled("6.3.1"));
+        assert!(default.is_enabled("check_csaf_base"));
+        assert!(default.is_enabled("invalid-cpe"));
+        assert_eq!(default.severity("6.2.1"), Some(Severity::Error));
+        assert_eq!(default.severity("6.2.2"), Some(Severity::Info));
+        assert_eq!(default.severity("6.1.1"), None);
+
+        let vex = config.resolve(None, "csaf_vex");
+        assert!(vex.is_enabled("6.3.1"));
+        assert!(!vex.is_enabled("6.3.3"));
+
+        let provider = config.resolve(Some("example.com"), "csaf_security_advisory");
+        assert!(!provider.is_enabled("6.2.1"));
+        assert!(!provider.is_enabled("duplicate-identification-helper"));
+        assert!(provider.is_enabled("6.2.16"));
+    }
+}
@@ -0,0 +1,511 @@
+//! Checks across documents
+//!
+//! A [`Check`](super::check::Check) only sees a single document. Some problems of a provider only
+//! show up when looking at all of its documents. The [`Corpus`] indexes documents (e.g. after
+//! walking a provider, or a stored directory) and reports findings across them:
+//!
+//! * `corpus-duplicate-id`: the same tracking ID is used by documents with different content
+//! * `corpus-conflicting-status`: documents with different tracking IDs report a conflicting
+//!   product status for the same CVE and product. As product IDs are only unique within a
+//!   document, products are identified by their PURL or CPE, falling back to their name.
+//! * `corpus-filename`: the filename doesn't match the tracking ID
+//! * `corpus-fixed-regression`: a newer revision of a document lists fewer fixed products
+//!
+//! The rules can be selected using a [`CheckConfigFile`], like the checks of a single document.
+
+use crate::verification::{
+    Finding, Severity,
+    check::{mandatory::Version, optional::document_filename, products::full_product_names},
+    config::CheckConfigFile,
+};
+use chrono::{DateTime, Utc};
+use csaf::{Csaf, definitions::FullProductName};
+use sha2::{Digest, Sha256};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap},
+};
+use url::Url;
+
+/// The indexed information of a single document
+#[derive(Debug)]
+struct Entry<K> {
+    key: K,
+    url: Url,
+    digest: digest::Output<Sha256>,
+    category: String,
+    id: String,
+    version: String,
+    current_release_date: DateTime<Utc>,
+    /// The status group of products (by their identity), by CVE, along with the index of the
+    /// vulnerability
+    status: BTreeMap<String, (usize, BTreeMap<String, &'static str>)>,
+    /// IDs of products fixed by any vulnerability
+    fixed: BTreeSet<String>,
+}
+
+/// The identity of a product across documents: its PURL or CPE, falling back to its name
+fn product_identity(product: &FullProductName) -> String {
+    let helper = product.product_identification_helper.as_ref();
+    if let Some(purl) = helper.and_then(|helper| helper.purl.as_ref()) {
+        format!("PURL '{purl}'")
+    } else if let Some(cpe) = helper.and_then(|helper| helper.cpe.as_ref()) {
+        format!("CPE '{cpe}'")
+    } else {
+        format!("Product '{}'", product.name)
+    }
+}
+
+impl<K> Entry<K> {
+    fn new(key: K, url: Url, data: &[u8], csaf: &Csaf) -> Self {
+        let identities = full_product_names(csaf)
+            .into_iter()
+            .map(|(_, product)| (product.product_id.0.as_str(), product_identity(product)))
+            .collect::<HashMap<_, _>>();
+
+        let mut status = BTreeMap::new();
+        let mut fixed = BTreeSet::new();
+
+        for (i, vulnerability) in csaf.vulnerabilities.iter().flatten().enumerate() {
+            let Some(product_status) = &vulnerability.product_status else {
+                continue;
+            };
+
+            let groups = [
+                (
+                    "affected",
+                    vec![
+                        &product_status.first_affected,
+                        &product_status.known_affected,
+                        &product_status.last_affected,
+                    ],
+                ),
+                ("not affected", vec![&product_status.known_not_affected]),
+                (
+                    "fixed",
+                    vec![&product_status.first_fixed, &product_status.fixed],
+                ),
+                (
+                    "under investigation",
+                    vec![&product_status.under_investigation],
+                ),
+            ];
+
+            let mut products = BTreeMap::new();
+            for (group, fields) in groups {
+                for id in fields.iter().flat_map(|ids| ids.iter().flatten()) {
+                    // the revisions of a document share their product IDs
+                    if group == "fixed" {
+                        fixed.insert(id.0.clone());
+                    }
+                    // undefined products are covered by 6.1.1
+                    let Some(identity) = identities.get(id.0.as_str()) else {
+                        continue;
+                    };
+                    // contradictions within a document are covered by 6.1.6
+                    products.entry(identity.clone()).or_insert(group);
+                }
+            }
+
+            if let Some(cve) = &vulnerability.cve {
+                status.entry(cve.clone()).or_insert((i, products));
+            }
+        }
+
+        Self {
+            key,
+            url,
+            digest: Sha256::digest(data),
+            category: csaf.document.category.to_string(),
+            id: csaf.document.tracking.id.clone(),
+            version: csaf.document.tracking.version.clone(),
+            current_release_date: csaf.document.tracking.current_release_date,
+            status,
+            fixed,
+        }
+    }
+
+    /// Compare the revisions of documents, by version, or by date if that isn't possible
+    fn cmp_revision(&self, other: &Self) -> Ordering {
+        match (
+            Version::parse(&self.version),
+            Version::parse(&other.version),
+        ) {
+            (Some(a), Some(b)) => a.cmp_precedence(&b),
+            _ => None,
+        }
+        .unwrap_or_else(|| self.current_release_date.cmp(&other.current_release_date))
+    }
+}
+
+/// An index of documents, running checks across them
+///
+/// Documents are identified by a key (e.g. a [`DocumentKey`](crate::report::DocumentKey)), which
+/// is used to report findings.
+#[derive(Debug)]
+pub struct Corpus<K> {
+    entries: Vec<Entry<K>>,
+}
+
+impl<K> Default for Corpus<K> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+
+impl<K> Corpus<K>
+where
+    K: Clone + Ord,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a document, located at the URL, with its original data
+    pub fn add(&mut self, key: K, url: Url, data: &[u8], csaf: &Csaf) {
+        self.entries.push(Entry::new(key, url, data, csaf));
+    }
+
+    /// The number of documents
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Run all checks, reporting the findings by document
+    pub fn check(&self) -> BTreeMap<K, Vec<Finding>> {
+        self.check_with_config(None, None)
+    }
+
+    /// Run the checks enabled by the configuration, reporting the findings by document
+    ///
+    /// The `provider` selects the overrides of [`CheckConfigFile::providers`], see
+    /// [`walker_common::utils::url::provider_name`].
+    pub fn check_with_config(
+        &self,
+        config: Option<&CheckConfigFile>,
+        provider: Option<&str>,
+    ) -> BTreeMap<K, Vec<Finding>> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        // the order of documents from a walk is random
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+
+        let mut findings = Vec::new();
+        check_duplicate_ids(&entries, &mut findings);
+        check_conflicting_status(&entries, &mut findings);
+        check_filenames(&entries, &mut findings);
+        check_fixed_regressions(&entries, &mut findings);
+
+        let mut result = BTreeMap::<K, Vec<Finding>>::new();
+        for (entry, mut finding) in findings {
+            if let Some(config) = config {
+                let config = config.resolve(provider, &entry.category);
+                if !config.is_enabled(&finding.rule) {
+                    continue;
+                }
+                if let Some(severity) = config.severity(&finding.rule) {
+                    finding.severity = Some(severity);
+                }
+            }
+            result.entry(entry.key.clone()).or_default().push(finding);
+        }
+
+        result
+    }
+}
+
+type Findings<'a, K> = Vec<(&'a Entry<K>, Finding)>;
+
+/// Group entries by their tracking ID
+fn by_id<'a, K>(entries: &[&'a Entry<K>]) -> Vec<Vec<&'a Entry<K>>> {
+    let mut result = BTreeMap::<&str, Vec<&Entry<K>>>::new();
+    for entry in entries {
+        result.entry(&entry.id).or_default().push(entry);
+    }
+    result.into_values().filter(|v| v.len() > 1).collect()
+}
+
+fn check_duplicate_ids<'a, K>(entries: &[&'a Entry<K>], findings: &mut Findings<'a, K>) {
+    for entries in by_id(entries) {
+        for entry in &entries {
+            let others = entries
+                .iter()
+                .filter(|other| other.digest != entry.digest)
+                .map(|other| other.url.as_str())
+                .collect::<Vec<_>>();
+
+            if !others.is_empty() {
+                findings.push((
+                    entry,
+                    Finding::new(format!(
+                        "Tracking ID '{}' is also used by documents with different content: {}",
+                        entry.id,
+                        others.join(", ")
+                    ))
+                    .rule("corpus-duplicate-id")
+                    .path("/document/tracking/id")
+                    .hint("Publish each revision of a document at a single URL"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_conflicting_status<'a, K>(entries: &[&'a Entry<K>], findings: &mut Findings<'a, K>) {
+    // documents reporting a status group, by CVE and product. Keeping two documents with
+    // different tracking IDs per group is sufficient to find a conflict with any other document.
+    let mut seen = HashMap::<(&str, &str), BTreeMap<&str, Vec<&Entry<K>>>>::new();
+
+    for entry in entries {
+        for (cve, (i, products)) in &entry.status {
+            for (product, group) in products {
+                let groups = seen.entry((cve, product)).or_default();
+
+                if let Some((other_group, other)) = groups
+                    .iter()
+                    .filter(|(other_group, _)| *other_group != group)
+                    .flat_map(|(other_group, others)| {
+                        others.iter().map(move |other| (other_group, other))
+                    })
+                    .find(|(_, other)| other.id != entry.id)
+                {
+                    findings.push((
+                        entry,
+                        Finding::new(format!(
+                            "{product} is {group} by {cve}, but {other_group} in '{}' ({})",
+                            other.id, other.url
+                        ))
+                        .rule("corpus-conflicting-status")
+                        .severity(Severity::Warning)
+                        .path(format!("/vulnerabilities/{i}/product_status")),
+                    ));
+                }
+
+                let others = groups.entry(group).or_default();
+                if others.len() < 2 && others.iter().all(|other| other.id != entry.id) {
+                    others.push(entry);
+                }
+            }
+        }
+    }
+}
+
+fn check_filenames<'a, K>(entries: &[&'a Entry<K>], findings: &mut Findings<'a, K>) {
+    for entry in entries {
+        let expected = document_filename(&entry.id);
+        let Some(filename) = entry
+            .url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+        else {
+            continue;
+        };
+
+        if filename != expected {
+            findings.push((
+                entry,
+                Finding::new(format!(
+                    "Filename '{filename}' doesn't match the tracking ID '{}', expected '{expected}'",
+                    entry.id
+                ))
+                .rule("corpus-filename")
+                .path("/document/tracking/id"),
+            ));
+        }
+    }
+}
+
+fn check_fixed_regressions<'a, K>(entries: &[&'a Entry<K>], findings: &mut Findings<'a, K>) {
+    for mut entries in by_id(entries) {
+        entries.sort_by(|a, b| a.cmp_revision(b));
+
+        for pair in entries.windows(2) {
+            let [previous, entry] = pair else {
+                continue;
+            };
+            if previous.cmp_revision(entry) != Ordering::Less {
+                continue;
+            }
+
+            let missing = previous
+                .fixed
+                .difference(&entry.fixed)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                findings.push((
+                    entry,
+                    Finding::new(format!(
+                        "Revision {} no longer lists products as fixed, which revision {} ({}) did: {}",
+                        entry.version,
+                        previous.version,
+                        previous.url,
+                        missing.join(", ")
+                    ))
+                    .rule("corpus-fixed-regression")
+                    .severity(Severity::Warning)
+                    .path("/vulnerabilities"),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::{Value, json};
+
+    /// A document, defining products by their ID, name, and optional PURL
+    fn document(
+        id: &str,
+        version: &str,
+        products: &[(&str, &str, Option<&str>)],
+        fixed: &[&str],
+        affected: &[&str],
+    ) -> Value {
+        let mut json: Value =
+            serde_json::from_str(include_str!("../../test-data/rhsa-2021_3029.json"))
+                .expect("example data must parse");
+        json["document"]["tracking"]["id"] = json!(id);
+        json["document"]["tracking"]["version"] = json!(version);
+        json["product_tree"] = json!({
+            "full_product_names": products
+                .iter()
+                .map(|(id, name, purl)| match purl {
+                    Some(purl) => json!({
+                        "product_id": id,
+                        "name": name,
+                        "product_identification_helper": { "purl": purl },
+                    }),
+                    None => json!({ "product_id": id, "name": name }),
+                })
+                .collect::<Vec<_>>(),
+        });
+        json["vulnerabilities"] = json!([{
+            "cve": "CVE-2021-0001",
+            "product_status": {
+                "fixed": fixed,
+                "known_affected": affected,
+            }
+        }]);
+        json
+    }
+
+    fn add(corpus: &mut Corpus<&'static str>, key: &'static str, url: &str, json: &Value) {
+        let csaf: Csaf = serde_json::from_value(json.clone()).expect("test data must parse");
+        let data = serde_json::to_vec(json).expect("test data must serialize");
+        corpus.add(
+            key,
+            Url::parse(url).expect("test URL must parse"),
+            &data,
+            &csaf,
+        );
+    }
+
+    fn rules(findings: &BTreeMap<&str, Vec<Finding>>, key: &str) -> Vec<String> {
+        findings
+            .get(key)
+            .into_iter()
+            .flatten()
+            .map(|finding| finding.rule.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn corpus() {
+        let mut corpus = Corpus::new();
+
+        let products = [
+            ("P1", "Example 1", Some("pkg:rpm/example/p1@1.0.1")),
+            ("P2", "Example 2", Some("pkg:rpm/example/p2@1.0.1")),
+        ];
+        add(
+            &mut corpus,
+            "a1",
+            "https://example.com/2021/example-a.json",
+            &document("EXAMPLE-A", "1", &products, &["P1", "P2"], &[]),
+        );
+        add(
+            &mut corpus,
+            "a2",
+            "https://example.com/2022/example-a.json",
+            &document("EXAMPLE-A", "2", &products, &["P1"], &["P2"]),
+        );
+        // the same product, using a different ID
+        add(
+            &mut corpus,
+            "b",
+            "https://example.com/2021/example-b.json",
+            &document(
+                "EXAMPLE-B",
+                "1",
+                &[("B-1", "Example 2", Some("pkg:rpm/example/p2@1.0.1"))],
+                &["B-1"],
+                &[],
+            ),
+        );
+        // different products, using the same IDs
+        add(
+            &mut corpus,
+            "c",
+            "https://example.com/2021/c.json",
+            &document(
+                "EXAMPLE-C",
+                "1",
+                &[("P1", "Other 1", None), ("P2", "Other 2", None)],
+                &["P2"],
+                &["P1"],
+            ),
+        );
+        // without an identification helper, the same name
+        add(
+            &mut corpus,
+            "d",
+            "https://example.com/2021/example-d.json",
+            &document("EXAMPLE-D", "1", &[("D-2", "Other 2", None)], &[], &["D-2"]),
+        );
+        assert_eq!(corpus.len(), 5);
+
+        let findings = corpus.check();
+
+        assert_eq!(rules(&findings, "a1"), vec!["corpus-duplicate-id"]);
+        assert_eq!(
+            rules(&findings, "a2"),
+            vec![
+                "corpus-duplicate-id",
+                "corpus-conflicting-status",
+                "corpus-fixed-regression"
+            ]
+        );
+        assert_eq!(rules(&findings, "b"), Vec::<String>::new());
+        assert_eq!(rules(&findings, "c"), vec!["corpus-filename"]);
+        assert_eq!(rules(&findings, "d"), vec!["corpus-conflicting-status"]);
+
+        assert_eq!(
+            findings["a2"][1].message,
+            "PURL 'pkg:rpm/example/p2@1.0.1' is affected by CVE-2021-0001, but fixed in 'EXAMPLE-B' (https://example.com/2021/example-b.json)"
+        );
+        assert_eq!(
+            findings["d"][0].message,
+            "Product 'Other 2' is affected by CVE-2021-0001, but fixed in 'EXAMPLE-C' (https://example.com/2021/c.json)"
+        );
+        assert_eq!(
+            findings["a2"][2].message,
+            "Revision 2 no longer lists products as fixed, which revision 1 (https://example.com/2021/example-a.json) did: P2"
+        );
+
+        let config: CheckConfigFile = serde_json::from_value(json!({
+            "disable": ["corpus-duplicate-id"],
+            "severity": { "corpus-filename": "info" },
+        }))
+        .expect("configuration must parse");
+        let findings = corpus.check_with_config(Some(&config), None);
+
+        assert_eq!(rules(&findings, "a1"), Vec::<String>::new());
+        assert_eq!(findings["c"][0].severity, Some(Severity::Info));
+    }
+}
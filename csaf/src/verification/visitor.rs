@@ -0,0 +1,518 @@
+use crate::{
+    discover::{AsDiscovered, DiscoveredAdvisory},
+    retrieve::{AsRetrieved, RetrievalContext, RetrievedAdvisory, RetrievedVisitor},
+    source::Source,
+    validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
+    verification::{
+        Finding,
+        check::{Check, schema},
+        config::CheckConfigFile,
+    },
+};
+use csaf::Csaf;
+use serde::{Deserialize, de::Error as _};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Display},
+    future::Future,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use url::Url;
+use walker_common::retrieve::RetrievalError;
+use walker_common::utils::url::Urlify;
+
+#[derive(Debug)]
+pub struct VerifiedAdvisory<A, I>
+where
+    A: AsRetrieved,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    pub advisory: A,
+    pub csaf: Csaf,
+    pub failures: HashMap<I, Vec<Finding>>,
+    pub successes: HashSet<I>,
+}
+
+impl<A, I> Deref for VerifiedAdvisory<A, I>
+where
+    A: AsRetrieved,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    type Target = A;
+
+    fn deref(&self) -> &Self::Target {
+        &self.advisory
+    }
+}
+
+impl<A, I> DerefMut for VerifiedAdvisory<A, I>
+where
+    A: AsRetrieved,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.advisory
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError<UE, A>
+where
+    A: Debug,
+    UE: Display + Debug,
+{
+    #[error(transparent)]
+    Upstream(UE),
+    #[error("document parsing error: {error}")]
+    Parsing {
+        advisory: A,
+        error: serde_json::Error,
+    },
+    /// The document is valid JSON, but doesn't conform to the schema
+    #[error("document doesn't conform to the schema ({} violations): {error}", findings.len())]
+    Invalid {
+        advisory: A,
+        error: serde_json::Error,
+        /// The violations of the schema
+        findings: Vec<Finding>,
+    },
+    #[error("check runtime error: {error}")]
+    Check { advisory: A, error: anyhow::Error },
+}
+
+impl<A, UE> AsDiscovered for VerificationError<UE, A>
+where
+    A: AsDiscovered + Debug,
+    UE: AsDiscovered + Display + Debug,
+{
+    fn as_discovered(&self) -> &DiscoveredAdvisory {
+        match self {
+            Self::Upstream(err) => err.as_discovered(),
+            Self::Parsing { advisory, .. } => advisory.as_discovered(),
+            Self::Invalid { advisory, .. } => advisory.as_discovered(),
+            Self::Check { advisory, .. } => advisory.as_discovered(),
+        }
+    }
+}
+
+impl<UE, A> Urlify for VerificationError<UE, A>
+where
+    A: AsRetrieved + Debug,
+    UE: Urlify + Display + Debug,
+{
+    fn url(&self) -> &Url {
+        match self {
+            Self::Upstream(err) => err.url(),
+            Self::Parsing { advisory, .. } => advisory.as_retrieved().url(),
+            Self::Invalid { advisory, .. } => advisory.as_retrieved().url(),
+            Self::Check { advisory, .. } => advisory.as_retrieved().url(),
+        }
+    }
+}
+
+pub struct VerificationContext {}
+
+/// A visitor accepting a verified advisory
+pub trait VerifiedVisitor<A, E, I>
+where
+    A: AsRetrieved,
+    E: Display + Debug,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    type Error: Display + Debug;
+    type Context;
+
+    fn visit_context(
+        &self,
+        context: &VerificationContext,
+    ) -> impl Future<Output = Result<Self::Context, Self::Error>>;
+
+    fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<VerifiedAdvisory<A, I>, VerificationError<E, A>>,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error<VE>
+where
+    VE: Display + Debug,
+{
+    #[error(transparent)]
+    Visitor(VE),
+}
+
+/// A visitor implementing the verification of a CSAF document
+pub struct VerifyingVisitor<A, E, V, I>
+where
+    A: AsRetrieved,
+    V: VerifiedVisitor<A, E, I>,
+    E: Display + Debug,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    visitor: V,
+    checks: Vec<(I, Box<dyn Check>)>,
+    config: Option<CheckConfigFile>,
+    provider: Option<String>,
+    rule_id: Option<fn(&I) -> Option<String>>,
+    _marker: PhantomData<(A, E)>,
+}
+
+impl<A, E, V, I> VerifyingVisitor<A, E, V, I>
+where
+    A: AsRetrieved,
+    V: VerifiedVisitor<A, E, I>,
+    E: Display + Debug,
+    I: Clone + PartialEq + Eq + Hash,
+{
+    pub fn new(visitor: V) -> Self {
+        Self {
+            visitor,
+            checks: vec![],
+            config: None,
+            provider: None,
+            rule_id: None,
+            _marker: Default::default(),
+        }
+    }
+
+    pub fn with_checks(visitor: V, checks: Vec<(I, Box<dyn Check>)>) -> Self {
+        Self {
+            visitor,
+            checks,
+            config: None,
+            provider: None,
+            rule_id: None,
+            _marker: Default::default(),
+        }
+    }
+
+    pub fn add<F: Check + 'static>(mut self, index: I, check: F) -> Self {
+        self.checks.push((index, Box::new(check)));
+        self
+    }
+
+    /// Set the configuration, selecting the checks to run and the severity of their findings
+    ///
+    /// The `provider` selects the overrides of [`CheckConfigFile::providers`], see
+    /// [`walker_common::utils::url::provider_name`].
+    ///
+    /// Unless set using [`Self::with_rule_ids`], the index a check was registered with is used as
+    /// its rule ID.
+    pub fn with_config(
+        mut self,
+        config: impl Into<Option<CheckConfigFile>>,
+        provider: Option<String>,
+    ) -> Self
+    where
+        I: Display,
+    {
+        self.config = config.into();
+        self.provider = provider;
+        self.rule_id.get_or_insert(|id| Some(id.to_string()));
+        self
+    }
+
+    /// Set how to derive the rule ID from the index of a check.
+    ///
+    /// The rule ID is assigned to findings which don't carry a rule of their own, and is used to
+    /// look up the check in the configuration.
+    pub fn with_rule_ids(mut self, rule_id: fn(&I) -> Option<String>) -> Self {
+        self.rule_id = Some(rule_id);
+        self
+    }
+
+    async fn verify(&self, advisory: A) -> Result<VerifiedAdvisory<A, I>, VerificationError<E, A>> {
+        let data = advisory.as_retrieved().data.clone();
+
+        let (json, csaf) = match tokio::task::spawn_blocking({
+            let data = data.clone();
+            move || {
+                let json = serde_json::from_slice::<Value>(&data)?;
+                // the typed model only reports the first error, the schema all violations
+                let csaf = Csaf::deserialize(&json).map_err(|err| (err, schema::validate(&json)));
+                Ok::<_, serde_json::Error>((json, csaf))
+            }
+        })
+        .await
+        {
+            Ok(Ok(result)) => result,
+            Ok(Err(error)) => return Err(VerificationError::Parsing { error, advisory }),
+            Err(_) => {
+                return Err(VerificationError::Parsing {
+                    error: serde_json::error::Error::custom("failed to wait for deserialization"),
+                    advisory,
+                });
+            }
+        };
+
+        let csaf = match csaf {
+            Ok(csaf) => csaf,
+            Err((error, violations)) if violations.is_empty() => {
+                return Err(VerificationError::Parsing { error, advisory });
+            }
+            Err((error, violations)) => {
+                let config = self.config.as_ref().map(|config| {
+                    let category = json["document"]["category"].as_str().unwrap_or_default();
+                    config.resolve(self.provider.as_deref(), category)
+                });
+                let findings = violations
+                    .into_iter()
+                    .map(|violation| {
+                        let severity = config
+                            .as_ref()
+                            .and_then(|config| config.severity(schema::RULE_SCHEMA))
+                            .unwrap_or(schema::SchemaCheck.severity());
+                        Finding::new(violation.message)
+                            .rule(schema::RULE_SCHEMA)
+                            .severity(severity)
+                            .path(violation.path)
+                    })
+                    .collect();
+                return Err(VerificationError::Invalid {
+                    advisory,
+                    error,
+                    findings,
+                });
+            }
+        };
+
+        let mut failures = HashMap::new();
+        let mut successes = HashSet::new();
+
+        let category = csaf.document.category.to_string();
+        let config = self
+            .config
+            .as_ref()
+            .map(|config| config.resolve(self.provider.as_deref(), &category));
+
+        for (index, check) in &self.checks {
+            let id = self.rule_id.and_then(|rule_id| rule_id(index));
+            if config
+                .as_ref()
+                .zip(id.as_deref())
+                .is_some_and(|(config, id)| !config.is_enabled(id))
+            {
+                continue;
+            }
+
+            let mut result = match check.as_ref().findings_data(&csaf, &json, &data).await {
+                Ok(result) => result,
+                Err(error) => return Err(VerificationError::Check { error, advisory }),
+            };
+            for finding in &mut result {
+                if finding.rule.is_empty() {
+                    if let Some(id) = &id {
+                        finding.rule = id.clone().into();
+                    }
+                }
+                // the configuration takes precedence over the severity of the finding and the check
+                let severity = config
+                    .as_ref()
+                    .and_then(|config| config.severity(&finding.rule));
+                finding.severity = severity.or(finding.severity).or(Some(check.severity()));
+            }
+            if !result.is_empty() {
+                failures.insert(index.clone(), result);
+            } else {
+                successes.insert(index.clone());
+            }
+        }
+
+        Ok(VerifiedAdvisory {
+            advisory,
+            csaf,
+            failures,
+            successes,
+        })
+    }
+}
+
+impl<V, I, S> RetrievedVisitor<S>
+    for VerifyingVisitor<RetrievedAdvisory, RetrievalError<DiscoveredAdvisory, S>, V, I>
+where
+    V: VerifiedVisitor<RetrievedAdvisory, RetrievalError<DiscoveredAdvisory, S>, I>,
+    I: Clone + PartialEq + Eq + Hash,
+    S: Source,
+{
+    type Error = Error<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        _context: &RetrievalContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor
+            .visit_context(&VerificationContext {})
+            .await
+            .map_err(Error::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError<DiscoveredAdvisory, S>>,
+    ) -> Result<(), Self::Error> {
+        let result = match result {
+            Ok(doc) => self.verify(doc).await,
+            Err(err) => Err(VerificationError::Upstream(err)),
+        };
+
+        self.visitor
+            .visit_advisory(context, result)
+            .await
+            .map_err(Error::Visitor)?;
+
+        Ok(())
+    }
+}
+
+impl<V, I, S> ValidatedVisitor<S> for VerifyingVisitor<ValidatedAdvisory, ValidationError<S>, V, I>
+where
+    V: VerifiedVisitor<ValidatedAdvisory, ValidationError<S>, I>,
+    I: Clone + PartialEq + Eq + Hash,
+    S: Source,
+{
+    type Error = Error<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        _context: &ValidationContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        self.visitor
+            .visit_context(&VerificationContext {})
+            .await
+            .map_err(Error::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<ValidatedAdvisory, ValidationError<S>>,
+    ) -> Result<(), Self::Error> {
+        let result = match result {
+            Ok(doc) => self.verify(doc).await,
+            Err(err) => Err(VerificationError::Upstream(err)),
+        };
+
+        self.visitor
+            .visit_advisory(context, result)
+            .await
+            .map_err(Error::Visitor)?;
+
+        Ok(())
+    }
+}
+
+impl<F, E, Fut, A, I, UE> VerifiedVisitor<A, UE, I> for F
+where
+    UE: Debug + Display + 'static,
+    F: Fn(Result<VerifiedAdvisory<A, I>, VerificationError<UE, A>>) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display + Debug + 'static,
+    A: AsRetrieved + 'static,
+    I: Clone + PartialEq + Eq + Hash + 'static,
+{
+    type Error = E;
+    type Context = ();
+
+    async fn visit_context(
+        &self,
+        _context: &VerificationContext,
+    ) -> Result<Self::Context, Self::Error> {
+        Ok(())
+    }
+
+    async fn visit_advisory(
+        &self,
+        _ctx: &Self::Context,
+        outcome: Result<VerifiedAdvisory<A, I>, VerificationError<UE, A>>,
+    ) -> Result<(), Self::Error> {
+        self(outcome).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{discover::DistributionContext, verification::check::init_verifying_visitor};
+    use std::{sync::Arc, time::SystemTime};
+    use walker_common::retrieve::RetrievalMetadata;
+
+    fn advisory(data: Vec<u8>) -> RetrievedAdvisory {
+        let url = Url::parse("https://example.com/csaf/2023/example.json").expect("URL must parse");
+        RetrievedAdvisory {
+            discovered: DiscoveredAdvisory {
+                context: Arc::new(DistributionContext::Directory(
+                    Url::parse("https://example.com/csaf/").expect("URL must parse"),
+                )),
+                url,
+                digest: None,
+                signature: None,
+                modified: SystemTime::now(),
+            },
+            data: data.into(),
+            signature: None,
+            sha256: None,
+            sha512: None,
+            metadata: RetrievalMetadata::default(),
+        }
+    }
+
+    fn visitor() -> VerifyingVisitor<
+        RetrievedAdvisory,
+        anyhow::Error,
+        impl VerifiedVisitor<RetrievedAdvisory, anyhow::Error, &'static str>,
+        &'static str,
+    > {
+        VerifyingVisitor::with_checks(
+            async |_: Result<_, VerificationError<anyhow::Error, _>>| Ok::<_, anyhow::Error>(()),
+            init_verifying_visitor(),
+        )
+    }
+
+    #[tokio::test]
+    async fn schema_violations() {
+        let mut json: Value =
+            serde_json::from_str(include_str!("../../test-data/rhsa-2023_1441.json"))
+                .expect("example data must parse");
+        json["document"]
+            .as_object_mut()
+            .expect("document must be an object")
+            .remove("title");
+        json["document"]["tracking"]["status"] = "unknown".into();
+
+        let data = serde_json::to_vec(&json).expect("document must serialize");
+        let Err(VerificationError::Invalid { findings, .. }) =
+            visitor().verify(advisory(data)).await
+        else {
+            panic!("document must be reported as invalid");
+        };
+
+        // the strict schema also reports some issues of the example data
+        assert!(findings.iter().all(|finding| finding.rule == "schema"));
+        for path in ["/document", "/document/tracking/status"] {
+            assert!(
+                findings
+                    .iter()
+                    .any(|finding| finding.path.as_deref() == Some(path)),
+                "missing violation at {path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn parsing() {
+        let result = visitor().verify(advisory(b"{\"document\":".to_vec())).await;
+        assert!(matches!(result, Err(VerificationError::Parsing { .. })));
+
+        let data = include_bytes!("../../test-data/rhsa-2023_1441.json").to_vec();
+        let result = visitor().verify(advisory(data)).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    discover::DiscoveredAdvisory,
+    retrieve::{RetrievalContext, RetrievedAdvisory, RetrievedVisitor},
+    source::Source,
+};
+use std::{fmt::Debug, sync::Arc};
+use tokio::sync::Mutex;
+use walker_common::{
+    report::KeyChanges,
+    retrieve::RetrievalError,
+    validate::rotation::{self, KeyTracking},
+};
+
+/// An intercepting visitor, tracking changes of the provider keys between runs
+pub struct KeyTrackingVisitor<V> {
+    pub visitor: V,
+    /// The key tracking configuration, `None` to disable tracking
+    pub tracking: Option<KeyTracking>,
+    /// The detected changes, if tracking is enabled
+    pub changes: Arc<Mutex<Option<KeyChanges>>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyTrackingError<VE: std::fmt::Display + Debug> {
+    #[error(transparent)]
+    Keys(#[from] rotation::Error),
+    #[error(transparent)]
+    Visitor(VE),
+}
+
+impl<V: RetrievedVisitor<S>, S: Source> RetrievedVisitor<S> for KeyTrackingVisitor<V> {
+    type Error = KeyTrackingError<V::Error>;
+    type Context = V::Context;
+
+    async fn visit_context(
+        &self,
+        context: &RetrievalContext<'_>,
+    ) -> Result<Self::Context, Self::Error> {
+        if let Some(tracking) = &self.tracking {
+            *self.changes.lock().await = Some(tracking.track(context.keys).await?);
+        }
+
+        self.visitor
+            .visit_context(context)
+            .await
+            .map_err(KeyTrackingError::Visitor)
+    }
+
+    async fn visit_advisory(
+        &self,
+        context: &Self::Context,
+        result: Result<RetrievedAdvisory, RetrievalError<DiscoveredAdvisory, S>>,
+    ) -> Result<(), Self::Error> {
+        self.visitor
+            .visit_advisory(context, result)
+            .await
+            .map_err(KeyTrackingError::Visitor)
+    }
+}
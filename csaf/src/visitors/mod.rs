@@ -2,5 +2,6 @@
 
 pub mod duplicates;
 pub mod filter;
+pub mod keys;
 pub mod skip;
 pub mod store;
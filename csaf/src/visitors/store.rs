@@ -1,45 +1,50 @@
 use crate::{
     discover::DiscoveredAdvisory,
-    model::{metadata::ProviderMetadata, store::distribution_base},
+    model::{metadata::ProviderMetadata, store::distribution_name},
     retrieve::{RetrievalContext, RetrievedAdvisory, RetrievedVisitor},
     source::Source,
     validation::{ValidatedAdvisory, ValidatedVisitor, ValidationContext, ValidationError},
 };
 use anyhow::Context;
 use sequoia_openpgp::{Cert, armor::Kind, serialize::SerializeInto};
-use std::{
-    fmt::Debug,
-    io::{ErrorKind, Write},
-    path::{Path, PathBuf},
-    rc::Rc,
-};
-use tokio::fs;
+use std::{fmt::Debug, io::Write, path::PathBuf, rc::Rc};
 use walker_common::{
     retrieve::RetrievalError,
-    store::{Document, StoreError, store_document},
+    store::{Document, FileStore, StoreBackend, StoreError},
     utils::openpgp::PublicKey,
 };
 
 pub const DIR_METADATA: &str = "metadata";
 
 /// Stores all data so that it can be used as a [`crate::source::Source`] later.
+///
+/// By default, data is stored in the local filesystem. Other backends can be used with
+/// [`StoreVisitor::with_backend`].
 #[non_exhaustive]
-pub struct StoreVisitor {
-    /// the output base
-    pub base: PathBuf,
+pub struct StoreVisitor<B: StoreBackend = FileStore> {
+    /// the backend to store the data in
+    pub backend: B,
 
     /// whether to set the file modification timestamps
     pub no_timestamps: bool,
 
     /// whether to store additional metadata (like the etag) using extended attributes
+    ///
+    /// This is ignored by backends which don't use extended attributes, like S3.
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     pub no_xattrs: bool,
 }
 
 impl StoreVisitor {
     pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self::with_backend(FileStore::new(base))
+    }
+}
+
+impl<B: StoreBackend> StoreVisitor<B> {
+    pub fn with_backend(backend: B) -> Self {
         Self {
-            base: base.into(),
+            backend,
             no_timestamps: false,
             #[cfg(any(target_os = "linux", target_os = "macos"))]
             no_xattrs: false,
@@ -75,7 +80,7 @@ pub enum StoreValidatedError<S: Source> {
     Validation(#[from] ValidationError<S>),
 }
 
-impl<S: Source + Debug> RetrievedVisitor<S> for StoreVisitor {
+impl<S: Source + Debug, B: StoreBackend> RetrievedVisitor<S> for StoreVisitor<B> {
     type Error = StoreRetrievedError<S>;
     type Context = Rc<ProviderMetadata>;
 
@@ -100,7 +105,7 @@ impl<S: Source + Debug> RetrievedVisitor<S> for StoreVisitor {
     }
 }
 
-impl<S: Source> ValidatedVisitor<S> for StoreVisitor {
+impl<S: Source, B: StoreBackend> ValidatedVisitor<S> for StoreVisitor<B> {
     type Error = StoreValidatedError<S>;
     type Context = ();
 
@@ -124,35 +129,19 @@ impl<S: Source> ValidatedVisitor<S> for StoreVisitor {
     }
 }
 
-impl StoreVisitor {
+impl<B: StoreBackend> StoreVisitor<B> {
     async fn prepare_distributions(&self, metadata: &ProviderMetadata) -> Result<(), StoreError> {
         for dist in &metadata.distributions {
             if let Some(directory_url) = &dist.directory_url {
-                let base = distribution_base(&self.base, directory_url.as_str());
-                log::debug!("Creating base distribution directory: {}", base.display());
-
-                fs::create_dir_all(&base)
-                    .await
-                    .with_context(|| {
-                        format!(
-                            "Unable to create distribution directory: {}",
-                            base.display()
-                        )
-                    })
-                    .map_err(StoreError::Io)?;
+                let base = distribution_name(directory_url.as_str());
+                log::debug!("Creating base distribution directory: {base}");
+
+                self.backend.create_dir(&base).await?;
             }
             if let Some(rolie) = &dist.rolie {
                 for feed in &rolie.feeds {
-                    let base = distribution_base(&self.base, feed.url.as_str());
-                    fs::create_dir_all(&base)
-                        .await
-                        .with_context(|| {
-                            format!(
-                                "Unable to create distribution directory: {}",
-                                base.display()
-                            )
-                        })
-                        .map_err(StoreError::Io)?;
+                    let base = distribution_name(feed.url.as_str());
+                    self.backend.create_dir(&base).await?;
                 }
             }
         }
@@ -161,47 +150,18 @@ impl StoreVisitor {
     }
 
     async fn store_provider_metadata(&self, metadata: &ProviderMetadata) -> Result<(), StoreError> {
-        let metadir = self.base.join(DIR_METADATA);
-
-        fs::create_dir(&metadir)
-            .await
-            .or_else(|err| match err.kind() {
-                ErrorKind::AlreadyExists => Ok(()),
-                _ => Err(err),
-            })
-            .with_context(|| format!("Failed to create metadata directory: {}", metadir.display()))
-            .map_err(StoreError::Io)?;
-
-        let file = metadir.join("provider-metadata.json");
-        let mut out = std::fs::File::create(&file)
-            .with_context(|| {
-                format!(
-                    "Unable to open provider metadata file for writing: {}",
-                    file.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
-        serde_json::to_writer_pretty(&mut out, metadata)
+        let data = serde_json::to_vec_pretty(metadata)
             .context("Failed serializing provider metadata")
             .map_err(StoreError::Io)?;
-        Ok(())
+
+        self.backend
+            .write(&format!("{DIR_METADATA}/provider-metadata.json"), &data)
+            .await
     }
 
     async fn store_keys(&self, keys: &[PublicKey]) -> Result<(), StoreError> {
-        let metadata = self.base.join(DIR_METADATA).join("keys");
-        std::fs::create_dir(&metadata)
-            // ignore if the directory already exists
-            .or_else(|err| match err.kind() {
-                ErrorKind::AlreadyExists => Ok(()),
-                _ => Err(err),
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to create metadata directory: {}",
-                    metadata.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
+        let metadata = format!("{DIR_METADATA}/keys");
+        self.backend.create_dir(&metadata).await?;
 
         for cert in keys.iter().flat_map(|k| &k.certs) {
             log::info!("Storing key: {}", cert.fingerprint());
@@ -211,16 +171,12 @@ impl StoreVisitor {
         Ok(())
     }
 
-    async fn store_cert(&self, cert: &Cert, path: &Path) -> Result<(), StoreError> {
-        let name = path.join(format!("{}.txt", cert.fingerprint().to_hex()));
+    async fn store_cert(&self, cert: &Cert, path: &str) -> Result<(), StoreError> {
+        let name = format!("{path}/{}.txt", cert.fingerprint().to_hex());
 
         let data = Self::serialize_key(cert).map_err(StoreError::SerializeKey)?;
 
-        fs::write(&name, data)
-            .await
-            .with_context(|| format!("Failed to store key: {}", name.display()))
-            .map_err(StoreError::Io)?;
-        Ok(())
+        self.backend.write(&name, &data).await
     }
 
     fn serialize_key(cert: &Cert) -> Result<Vec<u8>, anyhow::Error> {
@@ -242,27 +198,28 @@ impl StoreVisitor {
             None => return Err(StoreError::Filename(advisory.url.to_string())),
         };
 
-        // create a distribution base
-        let distribution_base = distribution_base(&self.base, advisory.context.url().as_str());
-
-        // put the file there
-        let file = distribution_base.join(name);
-
-        store_document(
-            &file,
-            Document {
-                data: &advisory.data,
-                changed: advisory.modified,
-                metadata: &advisory.metadata,
-                sha256: &advisory.sha256,
-                sha512: &advisory.sha512,
-                signature: &advisory.signature,
-                no_timestamps: self.no_timestamps,
-                #[cfg(any(target_os = "linux", target_os = "macos"))]
-                no_xattrs: self.no_xattrs,
-            },
-        )
-        .await?;
+        // put the file into its distribution base
+        let file = format!(
+            "{}/{name}",
+            distribution_name(advisory.context.url().as_str())
+        );
+
+        self.backend
+            .store_document(
+                &file,
+                Document {
+                    data: &advisory.data,
+                    changed: advisory.modified,
+                    metadata: &advisory.metadata,
+                    sha256: &advisory.sha256,
+                    sha512: &advisory.sha512,
+                    signature: &advisory.signature,
+                    no_timestamps: self.no_timestamps,
+                    #[cfg(any(target_os = "linux", target_os = "macos"))]
+                    no_xattrs: self.no_xattrs,
+                },
+            )
+            .await?;
 
         Ok(())
     }
@@ -3,13 +3,14 @@ use crate::{
     common::walk_visitor,
 };
 use sbom_walker::{
-    discover::DiscoverConfig, retrieve::RetrievingVisitor, visitors::skip::SkipExistingVisitor,
-    visitors::store::StoreVisitor,
+    discover::DiscoverConfig, retrieve::RetrievingVisitor, source::DispatchSource,
+    visitors::skip::SkipExistingVisitor,
 };
 use walker_common::{
     cli::{CommandDefaults, client::ClientArguments, runner::RunnerArguments},
     progress::Progress,
     since::Since,
+    store::DispatchStore,
 };
 
 /// Like `sync`, but doesn't validate.
@@ -35,8 +36,12 @@ impl CommandDefaults for Download {}
 
 impl Download {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let store: StoreVisitor = self.store.try_into()?;
-        let base = store.base.clone();
+        let store = self.store.into_visitor().await?;
+        // existing documents can only be skipped when storing to the local filesystem
+        let base = match &store.backend {
+            DispatchStore::File(backend) => Some(backend.base.clone()),
+            DispatchStore::S3(_) => None,
+        };
 
         let since = Since::new(
             self.skip.since,
@@ -47,22 +52,37 @@ impl Download {
                 .unwrap_or_default(),
         )?;
 
-        walk_visitor(
-            progress,
-            self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.runner,
-            async move |source| {
-                let visitor = RetrievingVisitor::new(source.clone(), store);
+        let discover = DiscoverConfig::from(self.discover).with_since(since.since);
+        let visitor = |source: DispatchSource| RetrievingVisitor::new(source, store);
 
-                Ok(SkipExistingVisitor {
-                    visitor,
-                    output: base,
-                    since: since.since,
-                })
-            },
-        )
-        .await?;
+        match base {
+            Some(output) => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.runner,
+                    async move |source| {
+                        Ok(SkipExistingVisitor {
+                            visitor: visitor(source),
+                            output,
+                            since: since.since,
+                        })
+                    },
+                )
+                .await?
+            }
+            None => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.runner,
+                    async move |source| Ok(visitor(source)),
+                )
+                .await?
+            }
+        }
 
         since.store()?;
 
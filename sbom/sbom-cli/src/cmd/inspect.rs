@@ -82,19 +82,17 @@ impl Inspect {
 
         inspect_validated(
             &(source, messages),
-            ValidatedSbom {
-                retrieved: RetrievedSbom {
-                    discovered: DiscoveredSbom {
-                        url,
-                        modified: SystemTime::now(),
-                    },
-                    data,
-                    signature: None,
-                    sha256: None,
-                    sha512: None,
-                    metadata: Default::default(),
+            ValidatedSbom::new(RetrievedSbom {
+                discovered: DiscoveredSbom {
+                    url,
+                    modified: SystemTime::now(),
                 },
-            },
+                data,
+                signature: None,
+                sha256: None,
+                sha512: None,
+                metadata: Default::default(),
+            }),
         );
 
         Ok(())
@@ -3,6 +3,7 @@ use flexible_time::timestamp::StartTimestamp;
 use reqwest::Url;
 use sbom_walker::visitors::store::StoreVisitor;
 use std::path::PathBuf;
+use walker_common::store::{DispatchStore, FileStore, S3Options, S3Store};
 
 pub mod discover;
 pub mod download;
@@ -33,18 +34,43 @@ pub struct StoreArguments {
     /// Output path, defaults to the local directory.
     #[arg(short, long)]
     pub data: Option<PathBuf>,
-}
 
-impl TryFrom<StoreArguments> for StoreVisitor {
-    type Error = anyhow::Error;
+    /// Store to an S3 compatible object store instead, using a URL like `s3://[<key>:<secret>@]<region>/<bucket>[/<prefix>]`.
+    #[arg(long, value_name = "URL", conflicts_with = "data")]
+    pub s3: Option<String>,
+
+    /// A custom endpoint of the object store, e.g. `http://localhost:9000` for a local MinIO instance.
+    #[arg(long, value_name = "URL", requires = "s3")]
+    pub s3_endpoint: Option<String>,
+
+    /// Address buckets by path instead of by virtual host, as required by MinIO.
+    #[arg(long, requires = "s3")]
+    pub s3_path_style: bool,
+}
 
-    fn try_from(value: StoreArguments) -> Result<Self, Self::Error> {
-        let base = match value.data {
-            Some(base) => base,
-            None => std::env::current_dir().context("Get the current working directory")?,
+impl StoreArguments {
+    /// Create the store visitor, storing to the object store if an S3 URL is set, or to the local
+    /// filesystem otherwise.
+    pub async fn into_visitor(self) -> anyhow::Result<StoreVisitor<DispatchStore>> {
+        let backend: DispatchStore = match self.s3 {
+            Some(url) => {
+                let mut options = S3Options::default().force_path_style(self.s3_path_style);
+                options.endpoint = self.s3_endpoint;
+                S3Store::from_url(&url, options)
+                    .await
+                    .context("Create S3 store")?
+                    .into()
+            }
+            None => {
+                let base = match self.data {
+                    Some(base) => base,
+                    None => std::env::current_dir().context("Get the current working directory")?,
+                };
+                FileStore::new(base).into()
+            }
         };
 
-        Ok(Self::new(base).no_timestamps(value.no_timestamps))
+        Ok(StoreVisitor::with_backend(backend).no_timestamps(self.no_timestamps))
     }
 }
 
@@ -5,13 +5,15 @@ use parking_lot::Mutex;
 use reqwest::Url;
 use sbom_walker::{
     model::sbom::ParseAnyError,
-    report::ReportResult,
+    report::{
+        JsonReport, RULE_SBOM, ReportResult, render_to_json, render_to_junit, render_to_sarif,
+    },
     retrieve::RetrievingVisitor,
     source::{DispatchSource, Source},
     validation::{ValidatedSbom, ValidationVisitor},
 };
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     path::PathBuf,
     sync::{
         Arc,
@@ -21,13 +23,17 @@ use std::{
 use tokio::task;
 use walker_common::{
     cli::{
-        CommandDefaults, client::ClientArguments, runner::RunnerArguments,
+        CommandDefaults,
+        baseline::BaselineArguments,
+        client::ClientArguments,
+        report::{Diff, ReportFormat},
+        runner::RunnerArguments,
         validation::ValidationArguments,
     },
     progress::Progress,
-    report::{self, Statistics},
+    report::{self, KnownFinding, ReportOptions, ReportStatistics, Signers, Statistics},
     utils::url::Urlify,
-    validate::{ValidationError, ValidationOptions},
+    validate::ValidationError,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -40,7 +46,11 @@ pub enum SbomError<S: Source> {
 
 /// Analyze (and report) the state of the data.
 #[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Report {
+    #[command(subcommand)]
+    command: Option<ReportCommand>,
+
     #[command(flatten)]
     client: ClientArguments,
 
@@ -48,22 +58,38 @@ pub struct Report {
     runner: RunnerArguments,
 
     #[command(flatten)]
-    discover: DiscoverArguments,
+    discover: Option<DiscoverArguments>,
 
     #[command(flatten)]
     validation: ValidationArguments,
 
+    #[command(flatten)]
+    baseline: BaselineArguments,
+
     #[command(flatten)]
     render: RenderOptions,
 }
 
-impl CommandDefaults for Report {}
+impl CommandDefaults for Report {
+    fn progress(&self) -> bool {
+        self.command.is_none()
+    }
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ReportCommand {
+    Diff(Diff),
+}
 
 #[derive(clap::Args, Debug)]
 pub struct RenderOptions {
-    /// Path of the HTML output file
-    #[arg(long, default_value = "report.html")]
-    output: PathBuf,
+    /// Path of the output file [default: report.<extension of the format>]
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// The format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Html)]
+    format: ReportFormat,
 
     /// Make links relative to this URL.
     #[arg(short = 'B', long)]
@@ -73,25 +99,60 @@ pub struct RenderOptions {
     #[arg(long)]
     source_url: Option<Url>,
 
-    /// Statistics file to append to
+    /// Also write the JSON report to this file, e.g. for comparing runs using `report diff`
+    #[arg(long, value_name = "FILE")]
+    json_output: Option<PathBuf>,
+
+    /// Statistics file to append to, the HTML report shows the trends of the recorded runs
     #[arg(long)]
     statistics_file: Option<PathBuf>,
+
+    /// Create a self-contained HTML report, not loading any external resources
+    #[arg(long)]
+    self_contained: bool,
+}
+
+impl RenderOptions {
+    /// The path of the output file
+    fn output(&self) -> PathBuf {
+        self.output
+            .clone()
+            .unwrap_or_else(|| format!("report.{}", self.format.extension()).into())
+    }
+
+    /// The options for rendering the HTML report
+    fn report_options(&self) -> ReportOptions {
+        report::report_options(self.self_contained)
+    }
 }
 
 impl Report {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        if let Some(ReportCommand::Diff(diff)) = self.command {
+            return diff.run::<JsonReport>("SBOM Report Diff").await;
+        }
+        // required, unless running a subcommand
+        let Some(discover) = self.discover else {
+            anyhow::bail!("Missing the source to analyze");
+        };
+
+        let trust = discover.key_trust(&self.validation);
+        let options = self.validation.options(&discover.source);
 
         let total: Arc<AtomicUsize> = Default::default();
         let errors: Arc<Mutex<BTreeMap<String, Vec<String>>>> = Default::default();
+        let documents: Arc<Mutex<BTreeSet<String>>> = Default::default();
+        let signers: Arc<Mutex<Signers>> = Default::default();
 
         {
             let total = total.clone();
             let errors = errors.clone();
+            let documents = documents.clone();
+            let signers = signers.clone();
             walk_visitor(
                 progress,
                 self.client,
-                self.discover,
+                discover,
                 self.runner,
                 async |source| {
                     Ok(RetrievingVisitor::new(
@@ -99,12 +160,17 @@ impl Report {
                         ValidationVisitor::new(
                             move |sbom: Result<ValidatedSbom, ValidationError<DispatchSource>>| {
                                 let errors = errors.clone();
+                                let documents = documents.clone();
                                 total.fetch_add(1, Ordering::SeqCst);
+                                if let Ok(sbom) = &sbom {
+                                    signers.lock().record(sbom.signature_verification());
+                                }
                                 async move {
                                     let name = match &sbom {
                                         Ok(sbom) => sbom.url.to_string(),
                                         Err(sbom) => sbom.url().to_string(),
                                     };
+                                    documents.lock().insert(name.clone());
 
                                     task::spawn_blocking(move || {
                                         inspect(&(name, errors), sbom);
@@ -117,22 +183,18 @@ impl Report {
                             },
                         )
                         .with_options(options),
-                    ))
+                    )
+                    .with_trust(trust))
                 },
             )
             .await?;
         }
 
         let total = total.load(Ordering::SeqCst);
-        let errors = errors.lock();
+        let mut errors = errors.lock();
+        let documents = documents.lock();
 
-        Self::render(
-            &self.render,
-            &ReportResult {
-                errors: &errors,
-                total,
-            },
-        )?;
+        // statistics, before applying the baseline
 
         report::record_now(
             self.render.statistics_file.as_deref(),
@@ -142,15 +204,72 @@ impl Report {
                 total_errors: errors.iter().map(|(_, v)| v.len()).sum(),
                 warnings: 0,
                 total_warnings: 0,
+                checks: Default::default(),
+            },
+        )?;
+
+        // baseline
+
+        let fixed =
+            self.baseline
+                .process(&mut errors, &documents, Clone::clone, |message: &String| {
+                    KnownFinding {
+                        rule: RULE_SBOM.to_string(),
+                        message: message.clone(),
+                        path: None,
+                    }
+                })?;
+
+        let signers = signers.lock();
+
+        // including the current run
+        let statistics = self
+            .render
+            .statistics_file
+            .as_deref()
+            .map(ReportStatistics::load)
+            .transpose()?;
+
+        Self::render(
+            &self.render,
+            &ReportResult {
+                errors: &errors,
+                documents: &documents,
+                fixed: &fixed,
+                total,
+                signers: &signers,
             },
+            statistics.as_ref(),
         )?;
 
         Ok(())
     }
 
-    fn render(render: &RenderOptions, report: &ReportResult) -> anyhow::Result<()> {
-        let mut out = std::fs::File::create(&render.output)?;
-        render::render_to_html(&mut out, report, render)?;
+    fn render(
+        render: &RenderOptions,
+        report: &ReportResult,
+        statistics: Option<&ReportStatistics>,
+    ) -> anyhow::Result<()> {
+        let mut out = std::fs::File::create(render.output())?;
+
+        if let Some(path) = &render.json_output {
+            render_to_json(
+                &mut std::fs::File::create(path)?,
+                report,
+                render.source_url.as_ref().or(render.base_url.as_ref()),
+            )?;
+        }
+
+        match render.format {
+            ReportFormat::Html => render::render_to_html(&mut out, report, render, statistics)?,
+            ReportFormat::Json => render_to_json(
+                &mut out,
+                report,
+                render.source_url.as_ref().or(render.base_url.as_ref()),
+            )?,
+            ReportFormat::Sarif => render_to_sarif(&mut out, report)?,
+            ReportFormat::Junit => render_to_junit(&mut out, report)?,
+        }
 
         Ok(())
     }
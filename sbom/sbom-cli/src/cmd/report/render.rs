@@ -1,33 +1,42 @@
 use super::ReportResult;
 use crate::cmd::report::RenderOptions;
 use reqwest::Url;
+use sbom_walker::report::RULE_SBOM;
 use std::{
     borrow::Cow,
     fmt::{Display, Formatter},
 };
-use walker_common::{locale::Formatted, report, report::Summary};
+use walker_common::{
+    locale::Formatted,
+    report::{self, FilterAttributes, ReportStatistics, Summary, Trends},
+};
 
 pub fn render_to_html<W: std::io::Write>(
     out: &mut W,
     report: &ReportResult,
     render: &RenderOptions,
+    statistics: Option<&ReportStatistics>,
 ) -> anyhow::Result<()> {
     report::render(
         out,
         "SBOM Report",
-        HtmlReport(report, render),
-        &Default::default(),
+        HtmlReport(report, render, statistics),
+        &render.report_options(),
     )?;
 
     Ok(())
 }
 
-struct HtmlReport<'r>(&'r ReportResult<'r>, &'r RenderOptions);
+struct HtmlReport<'r>(
+    &'r ReportResult<'r>,
+    &'r RenderOptions,
+    Option<&'r ReportStatistics>,
+);
 
 impl HtmlReport<'_> {
     fn render_errors(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let total = self.0.errors.iter().map(|(_, v)| v.len()).sum();
-        Self::title(f, "Errors", &[self.0.errors.len(), total])?;
+        Self::title(f, "Errors", &[self.0.errors.len(), total], "text-bg-danger")?;
 
         if !self.0.errors.is_empty() {
             writeln!(
@@ -46,26 +55,21 @@ impl HtmlReport<'_> {
             )?;
 
             for (k, v) in self.0.errors {
-                let k: Cow<str> = match (&self.1.base_url, Url::parse(k)) {
-                    (Some(base_url), Ok(url)) => match base_url.make_relative(&url) {
-                        Some(url) => Cow::Owned(url),
-                        None => Cow::Borrowed(k),
-                    },
-                    _ => Cow::Borrowed(k),
-                };
-
-                let k = k.rsplit_once('/').map(|r| r.1).unwrap_or(&k);
+                let filter = self.filter(k);
+                let k = self.link(k);
+                let k = k.as_ref();
                 let id = format!("error-{k}");
                 let id = html_escape::encode_quoted_attribute(&id);
 
                 writeln!(
                     f,
                     r##"
-            <tr>
+            <tr{filter}>
                 <td id="{id}"><a href="{k}" target="_blank" style="white-space: nowrap;">{k}</a> <a class="link-secondary" href="#{id}">§</a></td>
-                <td><ul>
+                <td><details open><summary>{count} error(s)</summary><ul>
             "##,
                     k = html_escape::encode_quoted_attribute(&k),
+                    count = Formatted(v.len()),
                 )?;
 
                 for msg in v {
@@ -83,7 +87,7 @@ impl HtmlReport<'_> {
                 writeln!(
                     f,
                     r#"
-                </ul></td>
+                </ul></details></td>
             </tr>
             "#,
                 )?;
@@ -101,6 +105,116 @@ impl HtmlReport<'_> {
         Ok(())
     }
 
+    fn render_fixed(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.fixed.is_empty() {
+            return Ok(());
+        }
+
+        let total = self.0.fixed.values().map(Vec::len).sum();
+        Self::title(f, "Fixed", &[self.0.fixed.len(), total], "text-bg-success")?;
+
+        writeln!(
+            f,
+            r#"
+    <p>Errors of the baseline which are no longer present</p>
+    <table class="table">
+        <thead>
+            <tr>
+                <th scope="col">File</th>
+                <th scope="col">Fixed</th>
+            </tr>
+        </thead>
+
+        <tbody>
+"#
+        )?;
+
+        for (k, v) in self.0.fixed {
+            let filter = self.filter(k);
+            let k = self.link(k);
+
+            writeln!(
+                f,
+                r#"
+            <tr{filter}>
+                <td><a href="{k}" target="_blank" style="white-space: nowrap;">{k}</a></td>
+                <td><details><summary>{count} error(s)</summary><ul>
+            "#,
+                k = html_escape::encode_quoted_attribute(&k),
+                count = Formatted(v.len()),
+            )?;
+
+            for finding in v {
+                writeln!(
+                    f,
+                    r#"
+                            <li>
+                              <code>{msg}</code>
+                            </li>
+                        "#,
+                    msg = html_escape::encode_text(&finding.message),
+                )?;
+            }
+
+            writeln!(
+                f,
+                r#"
+                </ul></details></td>
+            </tr>
+            "#,
+            )?;
+        }
+
+        writeln!(
+            f,
+            r#"
+        <tbody>
+    </table>
+"#
+        )?;
+
+        Ok(())
+    }
+
+    /// The link to a document, relative to the base URL
+    fn link<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        let key: Cow<str> = match (&self.1.base_url, Url::parse(key)) {
+            (Some(base_url), Ok(url)) => match base_url.make_relative(&url) {
+                Some(url) => Cow::Owned(url),
+                None => Cow::Borrowed(key),
+            },
+            _ => Cow::Borrowed(key),
+        };
+
+        match key {
+            Cow::Borrowed(key) => Cow::Borrowed(key.rsplit_once('/').map(|r| r.1).unwrap_or(key)),
+            Cow::Owned(key) => match key.rsplit_once('/') {
+                Some((_, name)) => Cow::Owned(name.to_string()),
+                None => Cow::Owned(key),
+            },
+        }
+    }
+
+    /// The filter attributes of a document, carrying its distribution and the SBOM check
+    fn filter(&self, key: &str) -> FilterAttributes {
+        let distribution = match key.rsplit_once('/') {
+            Some((parent, _)) if Url::parse(key).is_ok() => Some(format!("{parent}/")),
+            _ => self
+                .1
+                .source_url
+                .as_ref()
+                .or(self.1.base_url.as_ref())
+                .map(|url| url.to_string()),
+        };
+
+        let filter = FilterAttributes::new().check(RULE_SBOM).severity("error");
+
+        match distribution {
+            Some(distribution) => filter.distribution(distribution),
+            None => filter,
+        }
+    }
+
     fn render_total(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut summary = Vec::new();
 
@@ -112,16 +226,12 @@ impl HtmlReport<'_> {
         Summary(summary).fmt(f)
     }
 
-    fn title(f: &mut Formatter<'_>, title: &str, count: &[usize]) -> std::fmt::Result {
+    fn title(f: &mut Formatter<'_>, title: &str, count: &[usize], class: &str) -> std::fmt::Result {
         write!(f, "<h2>{title}")?;
 
         let total: usize = count.iter().sum();
 
-        let class = if total > 0 {
-            "text-bg-danger"
-        } else {
-            "text-bg-light"
-        };
+        let class = if total > 0 { class } else { "text-bg-light" };
 
         for v in count {
             let v: Cow<'static, str> = match v {
@@ -140,7 +250,12 @@ impl HtmlReport<'_> {
 impl Display for HtmlReport<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.render_total(f)?;
+        if let Some(statistics) = self.2 {
+            Trends(statistics).fmt(f)?;
+        }
+        self.0.signers.fmt(f)?;
         self.render_errors(f)?;
+        self.render_fixed(f)?;
 
         Ok(())
     }
@@ -55,6 +55,7 @@ impl Scan {
                                     discovered: DiscoveredSbom { url, .. },
                                     ..
                                 },
+                            ..
                         } = sbom;
 
                         let data =
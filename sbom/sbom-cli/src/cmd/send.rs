@@ -13,7 +13,6 @@ use walker_common::{
     },
     progress::Progress,
     since::Since,
-    validate::ValidationOptions,
 };
 use walker_extras::visitors::{SendArguments, SendVisitor};
 
@@ -47,7 +46,8 @@ impl CommandDefaults for Send {}
 
 impl Send {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
+        let trust = self.discover.key_trust(&self.validation);
+        let options = self.validation.options(&self.discover.source);
         let send: SendVisitor = self.send.into_visitor().await?;
 
         let since = Since::new(
@@ -75,6 +75,7 @@ impl Send {
                         })
                         .with_options(options)
                     })
+                    .with_trust(trust)
                 };
 
                 Ok(visitor)
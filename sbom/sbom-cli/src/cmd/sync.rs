@@ -3,10 +3,8 @@ use crate::{
     common::walk_visitor,
 };
 use sbom_walker::{
-    discover::DiscoverConfig,
-    retrieve::RetrievingVisitor,
-    validation::ValidationVisitor,
-    visitors::{skip::SkipExistingVisitor, store::StoreVisitor},
+    discover::DiscoverConfig, retrieve::RetrievingVisitor, source::DispatchSource,
+    validation::ValidationVisitor, visitors::skip::SkipExistingVisitor,
 };
 use walker_common::{
     cli::{
@@ -15,7 +13,7 @@ use walker_common::{
     },
     progress::Progress,
     since::Since,
-    validate::ValidationOptions,
+    store::DispatchStore,
 };
 
 /// Sync only what changed.
@@ -44,9 +42,14 @@ impl CommandDefaults for Sync {}
 
 impl Sync {
     pub async fn run<P: Progress>(self, progress: P) -> anyhow::Result<()> {
-        let options: ValidationOptions = self.validation.into();
-        let store: StoreVisitor = self.store.try_into()?;
-        let base = store.base.clone();
+        let trust = self.discover.key_trust(&self.validation);
+        let options = self.validation.options(&self.discover.source);
+        let store = self.store.into_visitor().await?;
+        // existing documents can only be skipped when storing to the local filesystem
+        let base = match &store.backend {
+            DispatchStore::File(backend) => Some(backend.base.clone()),
+            DispatchStore::S3(_) => None,
+        };
 
         let since = Since::new(
             self.skip.since,
@@ -57,27 +60,40 @@ impl Sync {
                 .unwrap_or_default(),
         )?;
 
-        walk_visitor(
-            progress,
-            self.client,
-            DiscoverConfig::from(self.discover).with_since(since.since),
-            self.runner,
-            async move |source| {
-                let visitor = {
-                    RetrievingVisitor::new(
-                        source,
-                        ValidationVisitor::new(store).with_options(options),
-                    )
-                };
+        let discover = DiscoverConfig::from(self.discover).with_since(since.since);
+        let visitor = |source: DispatchSource| {
+            RetrievingVisitor::new(source, ValidationVisitor::new(store).with_options(options))
+                .with_trust(trust)
+        };
 
-                Ok(SkipExistingVisitor {
-                    visitor,
-                    output: base,
-                    since: since.since,
-                })
-            },
-        )
-        .await?;
+        match base {
+            Some(output) => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.runner,
+                    async move |source| {
+                        Ok(SkipExistingVisitor {
+                            visitor: visitor(source),
+                            output,
+                            since: since.since,
+                        })
+                    },
+                )
+                .await?
+            }
+            None => {
+                walk_visitor(
+                    progress,
+                    self.client,
+                    discover,
+                    self.runner,
+                    async move |source| Ok(visitor(source)),
+                )
+                .await?
+            }
+        }
 
         since.store()?;
 
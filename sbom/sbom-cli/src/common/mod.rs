@@ -16,7 +16,7 @@ use std::future::Future;
 use walker_common::{
     cli::{client::ClientArguments, runner::RunnerArguments, validation::ValidationArguments},
     progress::Progress,
-    validate::ValidationOptions,
+    validate::trust::KeyTrust,
 };
 
 pub async fn walk_standard<V, P>(
@@ -32,17 +32,26 @@ where
     V::Error: Send + Sync + 'static,
     P: Progress,
 {
-    let options: ValidationOptions = validation.into();
+    let discover = discover.into();
+    let trust = validation.key_trust(&discover.source);
+    let options = validation.options(&discover.source);
 
     walk_visitor(progress, client, discover, runner, async move |source| {
         Ok(RetrievingVisitor::new(
             source,
             ValidationVisitor::new(visitor).with_options(options),
-        ))
+        )
+        .with_trust(trust))
     })
     .await
 }
 
+impl DiscoverArguments {
+    pub fn key_trust(&self, validation: &ValidationArguments) -> Option<KeyTrust> {
+        validation.key_trust(&self.source)
+    }
+}
+
 impl From<DiscoverArguments> for DiscoverConfig {
     fn from(value: DiscoverArguments) -> Self {
         Self {
@@ -17,6 +17,7 @@ pub fn inspect_validated(report: &dyn ReportSink, sbom: ValidatedSbom) {
                 discovered: DiscoveredSbom { url, .. },
                 ..
             },
+        ..
     } = sbom;
 
     let data = decompress(data, url.path());
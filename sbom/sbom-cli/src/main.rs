@@ -12,7 +12,7 @@ use crate::cmd::{
 use clap::Parser;
 use std::{ops::Deref, process::ExitCode};
 use walker_common::{
-    cli::{CommandDefaults, log::Logging},
+    cli::{CommandDefaults, log::Logging, trust::Keys},
     progress::Progress,
     utils::measure::MeasureTime,
 };
@@ -38,6 +38,7 @@ enum Command {
     Send(Send),
     Scoop(Scoop),
     Inspect(Inspect),
+    Keys(Keys),
 }
 
 impl Deref for Command {
@@ -53,6 +54,7 @@ impl Deref for Command {
             Self::Send(cmd) => cmd,
             Self::Scoop(cmd) => cmd,
             Self::Inspect(cmd) => cmd,
+            Self::Keys(cmd) => cmd,
         }
     }
 }
@@ -68,6 +70,7 @@ impl Command {
             Self::Send(cmd) => cmd.run(progress).await,
             Self::Scoop(cmd) => cmd.run(progress).await,
             Self::Inspect(cmd) => cmd.run(progress).await,
+            Self::Keys(cmd) => cmd.run().await,
         }
     }
 }
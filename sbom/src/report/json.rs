@@ -0,0 +1,194 @@
+use super::{RULE_SBOM, ReportResult};
+use time::OffsetDateTime;
+use url::Url;
+use walker_common::report::{FixedFindings, KnownFinding, RunFindings, RunReport, Signers};
+
+/// The version of the JSON report format
+///
+/// The version is increased for changes which are not backwards compatible.
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+/// The JSON schema of the JSON report
+pub const JSON_REPORT_SCHEMA: &str = include_str!("report.schema.json");
+
+/// The report, in a machine-readable format
+///
+/// See [`JSON_REPORT_SCHEMA`] for the schema of the serialized form.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct JsonReport {
+    /// The version of the format, see [`JSON_REPORT_VERSION`]
+    pub version: u32,
+    /// The time the report was created
+    #[serde(with = "time::serde::rfc3339")]
+    pub generated: OffsetDateTime,
+    /// The source of the documents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Url>,
+    pub summary: ReportSummary,
+    /// Documents which were processed, with or without errors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<String>,
+    /// Errors, by document
+    pub errors: Vec<DocumentErrors>,
+    /// Findings of the baseline which are no longer present, by document
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed: Vec<FixedFindings>,
+    pub signers: Signers,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReportSummary {
+    /// The total number of documents
+    pub total: usize,
+    /// The number of documents with errors
+    pub errors: usize,
+    /// The total number of errors
+    pub total_errors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentErrors {
+    /// The URL of the document
+    pub document: String,
+    pub messages: Vec<String>,
+}
+
+impl JsonReport {
+    pub fn new(report: &ReportResult, source: Option<&Url>) -> Self {
+        Self {
+            version: JSON_REPORT_VERSION,
+            generated: OffsetDateTime::now_utc(),
+            source: source.cloned(),
+            summary: ReportSummary {
+                total: report.total,
+                errors: report.errors.len(),
+                total_errors: report.errors.values().map(Vec::len).sum(),
+            },
+            documents: report.documents.iter().cloned().collect(),
+            errors: report
+                .errors
+                .iter()
+                .map(|(document, messages)| DocumentErrors {
+                    document: document.clone(),
+                    messages: messages.clone(),
+                })
+                .collect(),
+            fixed: report
+                .fixed
+                .iter()
+                .map(|(document, findings)| FixedFindings {
+                    document: document.clone(),
+                    findings: findings.clone(),
+                })
+                .collect(),
+            signers: report.signers.clone(),
+        }
+    }
+}
+
+impl RunReport for JsonReport {
+    /// Get the errors by document, as findings of [`RULE_SBOM`].
+    fn run_findings(&self) -> RunFindings {
+        RunFindings {
+            findings: self
+                .errors
+                .iter()
+                .map(|errors| {
+                    (
+                        errors.document.clone(),
+                        errors
+                            .messages
+                            .iter()
+                            .map(|message| KnownFinding {
+                                rule: RULE_SBOM.to_string(),
+                                message: message.clone(),
+                                path: None,
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+            documents: (!self.documents.is_empty())
+                .then(|| self.documents.iter().cloned().collect()),
+        }
+    }
+}
+
+/// Render the report as JSON
+pub fn render_to_json<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+    source: Option<&Url>,
+) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(out, &JsonReport::new(report, source))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn json() {
+        let errors = BTreeMap::from_iter([(
+            "https://example.com/sbom/a.json".to_string(),
+            vec![
+                "Faulty SPDX license expression".to_string(),
+                "Failed".to_string(),
+            ],
+        )]);
+        let signers = Signers {
+            unsigned: 3,
+            ..Default::default()
+        };
+        let fixed = BTreeMap::from_iter([(
+            "https://example.com/sbom/b.json".to_string(),
+            vec![KnownFinding {
+                rule: RULE_SBOM.to_string(),
+                message: "Failed".to_string(),
+                path: None,
+            }],
+        )]);
+        let source = Url::parse("https://example.com/sbom/").expect("URL must parse");
+
+        let report = JsonReport::new(
+            &ReportResult {
+                errors: &errors,
+                documents: &Default::default(),
+                fixed: &fixed,
+                total: 3,
+                signers: &signers,
+            },
+            Some(&source),
+        );
+
+        assert_eq!(
+            report.summary,
+            ReportSummary {
+                total: 3,
+                errors: 1,
+                total_errors: 2,
+            }
+        );
+
+        let json = serde_json::to_value(&report).expect("report must serialize");
+        assert_eq!(
+            json["errors"][0]["document"],
+            "https://example.com/sbom/a.json"
+        );
+        assert_eq!(json["signers"]["unsigned"], 3);
+        assert_eq!(
+            json["fixed"][0]["document"],
+            "https://example.com/sbom/b.json"
+        );
+        assert_eq!(
+            report.run_findings().findings["https://example.com/sbom/a.json"].len(),
+            2
+        );
+        assert_eq!(
+            serde_json::from_value::<JsonReport>(json).expect("report must deserialize"),
+            report
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use super::ReportResult;
+use std::collections::BTreeSet;
+use walker_common::report::junit::{Failure, TestCase, TestSuite, TestSuites};
+
+/// Convert the report into JUnit test suites
+///
+/// Every SBOM is a test case, every error a failure of it.
+pub fn to_junit(report: &ReportResult) -> TestSuites {
+    let documents = report
+        .documents
+        .iter()
+        .chain(report.errors.keys())
+        .collect::<BTreeSet<_>>();
+
+    let cases = documents
+        .into_iter()
+        .map(|document| TestCase {
+            failures: report
+                .errors
+                .get(document)
+                .into_iter()
+                .flatten()
+                .map(|message| Failure::new("sbom", message, ""))
+                .collect(),
+            ..TestCase::new("sbom", document)
+        })
+        .collect();
+
+    TestSuites {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        suites: vec![TestSuite {
+            name: "sbom".to_string(),
+            cases,
+        }],
+    }
+}
+
+/// Render the report as JUnit XML
+pub fn render_to_junit<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+) -> anyhow::Result<()> {
+    to_junit(report).write(out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn junit() {
+        let errors = BTreeMap::from_iter([(
+            "https://example.com/sbom/b.json".to_string(),
+            vec!["Duplicate bom-ref 'a', occurred 2 times.".to_string()],
+        )]);
+        let documents = BTreeSet::from_iter([
+            "https://example.com/sbom/a.json".to_string(),
+            "https://example.com/sbom/b.json".to_string(),
+        ]);
+
+        let suites = to_junit(&ReportResult {
+            errors: &errors,
+            documents: &documents,
+            fixed: &Default::default(),
+            total: 2,
+            signers: &Default::default(),
+        });
+
+        let cases = &suites.suites[0].cases;
+        assert_eq!(cases.len(), 2);
+        assert!(cases[0].failures.is_empty());
+        assert_eq!(
+            cases[1].failures,
+            vec![Failure::new(
+                "sbom",
+                "Duplicate bom-ref 'a', occurred 2 times.",
+                ""
+            )]
+        );
+    }
+}
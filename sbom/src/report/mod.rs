@@ -1,12 +1,28 @@
 pub mod check;
+mod json;
+mod junit;
+mod sarif;
+
+pub use json::*;
+pub use junit::*;
+pub use sarif::*;
 
 use parking_lot::Mutex;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use walker_common::report::{KnownFinding, Signers};
 
 #[derive(Clone, Debug)]
 pub struct ReportResult<'d> {
     pub errors: &'d BTreeMap<String, Vec<String>>,
+    /// Documents which were processed, with or without errors
+    pub documents: &'d BTreeSet<String>,
+    /// Findings of the baseline which are no longer present, if reported
+    pub fixed: &'d BTreeMap<String, Vec<KnownFinding>>,
     pub total: usize,
+    pub signers: &'d Signers,
 }
 
 pub trait ReportSink {
@@ -0,0 +1,39 @@
+use super::ReportResult;
+use walker_common::report::sarif::{Level, Run, Sarif, SarifResult, ToolComponent};
+
+/// The rule of all errors reported for an SBOM
+///
+/// The SBOM checks don't carry an identifier, so all errors are reported by this one rule.
+pub const RULE_SBOM: &str = "sbom";
+
+/// Convert the report into a SARIF log
+pub fn to_sarif(report: &ReportResult) -> Sarif {
+    let driver = ToolComponent::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .information_uri(env!("CARGO_PKG_REPOSITORY"))
+        .rule(
+            RULE_SBOM,
+            "The SBOM could not be processed, or failed a check",
+        );
+
+    let mut run = Run::new(driver);
+
+    for (document, messages) in report.errors {
+        for message in messages {
+            run.add_result(
+                SarifResult::new(RULE_SBOM, Level::Error, message).location(document, None),
+            );
+        }
+    }
+
+    Sarif::new(run)
+}
+
+/// Render the report as SARIF
+pub fn render_to_sarif<W: std::io::Write>(
+    out: &mut W,
+    report: &ReportResult,
+) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(out, &to_sarif(report))?;
+    Ok(())
+}
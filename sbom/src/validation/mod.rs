@@ -15,13 +15,40 @@ use url::Url;
 use walker_common::{
     retrieve::RetrievalError,
     utils::{openpgp::PublicKey, url::Urlify},
-    validate::{ValidationError, ValidationOptions, digest::validate_digest, openpgp},
+    validate::{
+        ValidationError, ValidationOptions, digest::validate_digest, openpgp,
+        openpgp::SignatureVerification,
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct ValidatedSbom {
     /// The discovered advisory
     pub retrieved: RetrievedSbom,
+    /// The result of the signature verification, if the document was signed
+    signature_verification: Option<SignatureVerification>,
+}
+
+impl ValidatedSbom {
+    pub fn new(retrieved: RetrievedSbom) -> Self {
+        Self {
+            retrieved,
+            signature_verification: None,
+        }
+    }
+
+    pub fn with_signature_verification(
+        mut self,
+        signature_verification: impl Into<Option<SignatureVerification>>,
+    ) -> Self {
+        self.signature_verification = signature_verification.into();
+        self
+    }
+
+    /// The result of the signature verification, `None` if the document was not signed
+    pub fn signature_verification(&self) -> Option<&SignatureVerification> {
+        self.signature_verification.as_ref()
+    }
 }
 
 impl Urlify for ValidatedSbom {
@@ -144,8 +171,10 @@ where
         }
     }
 
+    /// Set the validation options, applying the policy overrides of the provider.
     pub fn with_options(mut self, options: impl Into<ValidationOptions>) -> Self {
-        self.options = options.into();
+        let options: ValidationOptions = options.into();
+        self.options = options.effective().into_owned();
         self
     }
 
@@ -175,21 +204,41 @@ where
                 },
             ));
         }
+        if let Err(required) = self
+            .options
+            .check_digests(retrieved.sha256.is_some(), retrieved.sha512.is_some())
+        {
+            return Err(ValidationProcessError::Proceed(
+                ValidationError::MissingDigest {
+                    required,
+                    retrieved,
+                },
+            ));
+        }
 
-        if let Some(signature) = &retrieved.signature {
-            match openpgp::validate_signature(
-                &self.options,
-                &context.keys,
-                signature,
-                &retrieved.data,
-            ) {
-                Ok(()) => Ok(ValidatedSbom { retrieved }),
-                Err(error) => Err(ValidationProcessError::Proceed(
-                    ValidationError::Signature { error, retrieved },
-                )),
+        let Some(signature) = &retrieved.signature else {
+            if self.options.require_signature {
+                return Err(ValidationProcessError::Proceed(
+                    ValidationError::MissingSignature { retrieved },
+                ));
             }
-        } else {
-            Ok(ValidatedSbom { retrieved })
+            return Ok(ValidatedSbom::new(retrieved));
+        };
+
+        match openpgp::validate_signature(&self.options, &context.keys, signature, &retrieved.data)
+        {
+            Ok(verification) if self.options.is_allowed_signer(&verification.fingerprint) => {
+                Ok(ValidatedSbom::new(retrieved).with_signature_verification(verification))
+            }
+            Ok(verification) => Err(ValidationProcessError::Proceed(
+                ValidationError::UntrustedSigner {
+                    signer: verification.fingerprint,
+                    retrieved,
+                },
+            )),
+            Err(error) => Err(ValidationProcessError::Proceed(
+                ValidationError::Signature { error, retrieved },
+            )),
         }
     }
 }
@@ -249,3 +298,202 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::source::FileSource;
+    use sequoia_openpgp::{Cert, types::HashAlgorithm};
+    use sha2::{Digest, Sha256};
+    use std::time::SystemTime;
+    use walker_common::{
+        retrieve::RetrievedDigest,
+        validate::{
+            RequiredDigest,
+            policy::{AlgorithmPolicy, PolicyConfig},
+            testing,
+        },
+    };
+
+    const DATA: &[u8] = b"{}";
+
+    fn cert() -> Cert {
+        testing::cert("signer@example.com", None)
+    }
+
+    fn sign(cert: &Cert, data: &[u8]) -> String {
+        testing::sign(cert, HashAlgorithm::SHA256, data)
+    }
+
+    fn sbom(sha256: Option<&str>, signature: Option<String>) -> RetrievedSbom {
+        RetrievedSbom {
+            discovered: DiscoveredSbom {
+                url: Url::parse("https://example.com/sbom.json").expect("must parse"),
+                modified: SystemTime::now(),
+            },
+            data: DATA.into(),
+            signature,
+            sha256: sha256.map(|expected| RetrievedDigest {
+                expected: expected.to_string(),
+                actual: Sha256::digest(DATA),
+            }),
+            sha512: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn validate(
+        options: ValidationOptions,
+        keys: &[&Cert],
+        sbom: RetrievedSbom,
+    ) -> Result<ValidatedSbom, ValidationError<FileSource>> {
+        let visitor = ValidationVisitor::new(
+            |_: Result<ValidatedSbom, ValidationError<FileSource>>| async {
+                Ok::<_, anyhow::Error>(())
+            },
+        )
+        .with_options(options);
+
+        let context = InnerValidationContext {
+            context: (),
+            keys: keys
+                .iter()
+                .map(|cert| PublicKey {
+                    certs: vec![(*cert).clone()],
+                    raw: Default::default(),
+                })
+                .collect(),
+        };
+
+        match visitor.validate(&context, sbom).await {
+            Ok(sbom) => Ok(sbom),
+            Err(ValidationProcessError::Proceed(err)) => Err(err),
+            Err(ValidationProcessError::Abort(err)) => panic!("must not abort: {err}"),
+        }
+    }
+
+    fn sha256() -> String {
+        format!("{:x}", Sha256::digest(DATA))
+    }
+
+    #[tokio::test]
+    async fn digest_mismatch() {
+        let result = validate(Default::default(), &[], sbom(Some("0000"), None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::DigestMismatch { expected, .. }) if expected == "0000"
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_digest() {
+        let options = ValidationOptions::new().require_digest(true);
+        let result = validate(options.clone(), &[], sbom(None, None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingDigest {
+                required: RequiredDigest::Any,
+                ..
+            })
+        ));
+
+        let result = validate(options, &[], sbom(Some(&sha256()), None)).await;
+        assert!(result.is_ok());
+
+        let options = ValidationOptions::new().require_sha512(true);
+        let result = validate(options, &[], sbom(Some(&sha256()), None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingDigest {
+                required: RequiredDigest::Sha512,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_signature() {
+        let result = validate(Default::default(), &[], sbom(None, None))
+            .await
+            .expect("must be valid");
+        assert!(result.signature_verification().is_none());
+
+        let options = ValidationOptions::new().require_signature(true);
+        let result = validate(options, &[], sbom(None, None)).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::MissingSignature { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn allowed_signers() {
+        let signer = cert();
+        let other = cert();
+        let signature = sign(&signer, DATA);
+
+        let result = validate(
+            Default::default(),
+            &[&signer],
+            sbom(None, Some(signature.clone())),
+        )
+        .await
+        .expect("must be valid");
+        assert_eq!(
+            result
+                .signature_verification()
+                .map(|v| v.fingerprint.clone()),
+            Some(signer.fingerprint())
+        );
+
+        let options = ValidationOptions::new().allowed_signers([other.fingerprint()]);
+        let result = validate(options, &[&signer], sbom(None, Some(signature.clone()))).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::UntrustedSigner { signer: fingerprint, .. }) if fingerprint == signer.fingerprint()
+        ));
+
+        // signed, but the key is unknown
+        let result = validate(Default::default(), &[&other], sbom(None, Some(signature))).await;
+        assert!(matches!(result, Err(ValidationError::Signature { .. })));
+    }
+
+    #[tokio::test]
+    async fn provider_policy() {
+        let signer = cert();
+        let signature = sign(&signer, DATA);
+
+        let reject = PolicyConfig {
+            hash_algorithms: [
+                HashAlgorithm::SHA224,
+                HashAlgorithm::SHA256,
+                HashAlgorithm::SHA384,
+                HashAlgorithm::SHA512,
+            ]
+            .into_iter()
+            .map(|algorithm| (algorithm, AlgorithmPolicy::Reject))
+            .collect(),
+            ..Default::default()
+        };
+        let options = ValidationOptions::new()
+            .provider_policies([("example.com".to_string(), reject)].into());
+
+        // the overrides only apply to the selected provider
+        let result = validate(
+            options.clone().provider("other.com".to_string()),
+            &[&signer],
+            sbom(None, Some(signature.clone())),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let result = validate(
+            options.provider("example.com".to_string()),
+            &[&signer],
+            sbom(None, Some(signature)),
+        )
+        .await;
+        assert!(matches!(result, Err(ValidationError::Signature { .. })));
+    }
+}
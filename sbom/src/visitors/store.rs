@@ -7,14 +7,10 @@ use crate::{
 };
 use anyhow::Context;
 use sequoia_openpgp::{Cert, armor::Kind, serialize::SerializeInto};
-use std::{
-    io::{ErrorKind, Write},
-    path::{Path, PathBuf},
-};
-use tokio::fs;
+use std::{io::Write, path::PathBuf};
 use walker_common::{
     retrieve::RetrievalError,
-    store::{Document, StoreError, store_document},
+    store::{Document, FileStore, StoreBackend, StoreError},
     utils::openpgp::PublicKey,
     validate::ValidationError,
 };
@@ -22,23 +18,34 @@ use walker_common::{
 pub const DIR_METADATA: &str = "metadata";
 
 /// Stores all data so that it can be used as a [`crate::source::Source`] later.
+///
+/// By default, data is stored in the local filesystem. Other backends can be used with
+/// [`StoreVisitor::with_backend`].
 #[non_exhaustive]
-pub struct StoreVisitor {
-    /// the output base
-    pub base: PathBuf,
+pub struct StoreVisitor<B: StoreBackend = FileStore> {
+    /// the backend to store the data in
+    pub backend: B,
 
     /// whether to set the file modification timestamps
     pub no_timestamps: bool,
 
     /// whether to store additional metadata (like the etag) using extended attributes
+    ///
+    /// This is ignored by backends which don't use extended attributes, like S3.
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     pub no_xattrs: bool,
 }
 
 impl StoreVisitor {
     pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self::with_backend(FileStore::new(base))
+    }
+}
+
+impl<B: StoreBackend> StoreVisitor<B> {
+    pub fn with_backend(backend: B) -> Self {
         Self {
-            base: base.into(),
+            backend,
             no_timestamps: false,
             #[cfg(any(target_os = "linux", target_os = "macos"))]
             no_xattrs: false,
@@ -73,7 +80,7 @@ pub enum StoreValidatedError<S: Source> {
     Validation(#[from] ValidationError<S>),
 }
 
-impl<S: Source> RetrievedVisitor<S> for StoreVisitor {
+impl<S: Source, B: StoreBackend> RetrievedVisitor<S> for StoreVisitor<B> {
     type Error = StoreRetrievedError<S>;
     type Context = ();
 
@@ -96,7 +103,7 @@ impl<S: Source> RetrievedVisitor<S> for StoreVisitor {
     }
 }
 
-impl<S: Source> ValidatedVisitor<S> for StoreVisitor {
+impl<S: Source, B: StoreBackend> ValidatedVisitor<S> for StoreVisitor<B> {
     type Error = StoreValidatedError<S>;
     type Context = ();
 
@@ -119,50 +126,20 @@ impl<S: Source> ValidatedVisitor<S> for StoreVisitor {
     }
 }
 
-impl StoreVisitor {
+impl<B: StoreBackend> StoreVisitor<B> {
     async fn store_provider_metadata(&self, metadata: &SourceMetadata) -> Result<(), StoreError> {
-        let metadir = self.base.join(DIR_METADATA);
-
-        fs::create_dir(&metadir)
-            .await
-            .or_else(|err| match err.kind() {
-                ErrorKind::AlreadyExists => Ok(()),
-                _ => Err(err),
-            })
-            .with_context(|| format!("Failed to create metadata directory: {}", metadir.display()))
-            .map_err(StoreError::Io)?;
-
-        let file = metadir.join("metadata.json");
-        let mut out = std::fs::File::create(&file)
-            .with_context(|| {
-                format!(
-                    "Unable to open provider metadata file for writing: {}",
-                    file.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
-        serde_json::to_writer_pretty(&mut out, metadata)
+        let data = serde_json::to_vec_pretty(metadata)
             .context("Failed serializing provider metadata")
             .map_err(StoreError::Io)?;
-        Ok(())
+
+        self.backend
+            .write(&format!("{DIR_METADATA}/metadata.json"), &data)
+            .await
     }
 
     async fn store_keys(&self, keys: &[PublicKey]) -> Result<(), StoreError> {
-        let metadata = self.base.join(DIR_METADATA).join("keys");
-        fs::create_dir(&metadata)
-            .await
-            // ignore if the directory already exists
-            .or_else(|err| match err.kind() {
-                ErrorKind::AlreadyExists => Ok(()),
-                _ => Err(err),
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to create metadata directory: {}",
-                    metadata.display()
-                )
-            })
-            .map_err(StoreError::Io)?;
+        let metadata = format!("{DIR_METADATA}/keys");
+        self.backend.create_dir(&metadata).await?;
 
         for cert in keys.iter().flat_map(|k| &k.certs) {
             log::info!("Storing key: {}", cert.fingerprint());
@@ -172,16 +149,12 @@ impl StoreVisitor {
         Ok(())
     }
 
-    async fn store_cert(&self, cert: &Cert, path: &Path) -> Result<(), StoreError> {
-        let name = path.join(format!("{}.txt", cert.fingerprint().to_hex()));
+    async fn store_cert(&self, cert: &Cert, path: &str) -> Result<(), StoreError> {
+        let name = format!("{path}/{}.txt", cert.fingerprint().to_hex());
 
         let data = Self::serialize_key(cert).map_err(StoreError::SerializeKey)?;
 
-        fs::write(&name, data)
-            .await
-            .with_context(|| format!("Failed to store key: {}", name.display()))
-            .map_err(StoreError::Io)?;
-        Ok(())
+        self.backend.write(&name, &data).await
     }
 
     fn serialize_key(cert: &Cert) -> Result<Vec<u8>, anyhow::Error> {
@@ -199,26 +172,26 @@ impl StoreVisitor {
 
         let file = PathBuf::from(sbom.url.path())
             .file_name()
-            .map(|file| self.base.join(file))
+            .and_then(|file| file.to_str())
+            .map(ToString::to_string)
             .ok_or_else(|| StoreError::Filename(sbom.url.to_string()))?;
 
-        log::debug!("Writing {}", file.display());
-
-        store_document(
-            &file,
-            Document {
-                data: &sbom.data,
-                changed: sbom.modified,
-                metadata: &sbom.metadata,
-                sha256: &sbom.sha256,
-                sha512: &sbom.sha512,
-                signature: &sbom.signature,
-                no_timestamps: self.no_timestamps,
-                #[cfg(any(target_os = "linux", target_os = "macos"))]
-                no_xattrs: self.no_xattrs,
-            },
-        )
-        .await?;
+        self.backend
+            .store_document(
+                &file,
+                Document {
+                    data: &sbom.data,
+                    changed: sbom.modified,
+                    metadata: &sbom.metadata,
+                    sha256: &sbom.sha256,
+                    sha512: &sbom.sha512,
+                    signature: &sbom.signature,
+                    no_timestamps: self.no_timestamps,
+                    #[cfg(any(target_os = "linux", target_os = "macos"))]
+                    no_xattrs: self.no_xattrs,
+                },
+            )
+            .await?;
 
         Ok(())
     }